@@ -1,7 +1,9 @@
-use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys};
+use anyhow::{Result, Context, bail};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SigningCapabilities, SignedTransaction};
+use crate::blockchain::algorand_tx::{self, AlgorandTransactionGroup};
 use crate::crypto::ed25519_utils::{derive_ed25519_key_from_mnemonic, private_key_to_public_key_ed25519};
 use algo_rust_sdk::crypto::Address as AlgorandAddress;
+use ed25519_dalek::SigningKey;
 
 pub struct AlgorandHandler;
 
@@ -32,6 +34,25 @@ impl AlgorandHandler {
 
         Ok(address_string)
     }
+
+    fn signing_key(&self, private_key: &[u8], address: &str) -> Result<SigningKey> {
+        let seed: [u8; 32] = private_key.try_into()
+            .map_err(|_| anyhow::anyhow!("Algorand private key must be 32 bytes"))?;
+        let signing_key = SigningKey::from_bytes(&seed);
+        if algorand_tx::signing_key_address(&signing_key) != address {
+            bail!("Private key does not belong to {}", address);
+        }
+        Ok(signing_key)
+    }
+}
+
+/// The native 25-word Algorand mnemonic for a stored private key (what Pera and goal export)
+pub fn export_mnemonic(private_key_hex: &str) -> Result<String> {
+    let seed: [u8; 32] = hex::decode(private_key_hex)
+        .context("Invalid hex private key")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Algorand private key must be 32 bytes"))?;
+    Ok(algorand_tx::key_to_mnemonic(&seed))
 }
 
 impl BlockchainHandler for AlgorandHandler {
@@ -70,9 +91,12 @@ impl BlockchainHandler for AlgorandHandler {
     }
 
     fn derive_from_private_key(&self, private_key_hex: &str) -> Result<WalletKeys> {
-        // Parse private key from hex
-        let private_key_bytes = hex::decode(private_key_hex)
-            .context("Invalid hex private key")?;
+        // Algorand wallets export a 25-word mnemonic that encodes the ed25519 seed directly
+        let private_key_bytes = if algorand_tx::looks_like_mnemonic(private_key_hex) {
+            algorand_tx::mnemonic_to_key(private_key_hex)?.to_vec()
+        } else {
+            hex::decode(private_key_hex).context("Invalid hex private key")?
+        };
 
         if private_key_bytes.len() != 32 {
             anyhow::bail!("Algorand private key must be 32 bytes");
@@ -101,6 +125,19 @@ impl BlockchainHandler for AlgorandHandler {
     fn get_blockchain_name(&self) -> &'static str {
         "Algorand"
     }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities { sign_transaction: true, ..SigningCapabilities::default() }
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        AlgorandTransactionGroup::parse(transaction)?.details()
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        let signing_key = self.signing_key(private_key, address)?;
+        AlgorandTransactionGroup::parse(transaction)?.sign(&signing_key, address)
+    }
 }
 
 #[cfg(test)]
@@ -151,4 +188,34 @@ mod tests {
         assert_eq!(keys.address.len(), 58);
         assert!(keys.address.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_algorand_mnemonic_import_export() {
+        let handler = AlgorandHandler::new();
+        let private_key = "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+
+        let mnemonic = export_mnemonic(private_key).unwrap();
+        assert_eq!(mnemonic.split(' ').count(), 25);
+
+        let keys = handler.derive_from_private_key(&mnemonic).unwrap();
+        assert_eq!(keys.private_key, private_key);
+        assert_eq!(keys.address, "LTHKVWOCALBDNTMMS5ZJBKCE4PZPBIK2TMYYSIQNXYHZCIPLBTJHXYIUEQ");
+    }
+
+    #[test]
+    fn test_algorand_transaction_signing() {
+        let handler = AlgorandHandler::new();
+        let private_key = hex::decode("1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef").unwrap();
+        let address = "LTHKVWOCALBDNTMMS5ZJBKCE4PZPBIK2TMYYSIQNXYHZCIPLBTJHXYIUEQ";
+        // Application OptIn call to app 123456 sent by the address above
+        let transaction = b"89a46170616192c405636c61696dc4020001a46170616e01a461706964ce0001e240a3666565cd07d0a2667601a26768c420c061c4d8fc1dbdded2d7604be4568e3f6d041987ac37bde4b620b5ab39248adfa26c76cd03e9a3736e64c4205cceaad9c202c236cd8c977290a844e3f2f0a15a9b3189220dbe0f9121eb0cd2a474797065a46170706c";
+
+        assert!(handler.signing_capabilities().sign_transaction);
+        let details = handler.describe_transaction(transaction).unwrap();
+        assert!(details.contains(&("Fee".to_string(), "0.002 ALGO".to_string())));
+
+        let signed = handler.sign_transaction(&private_key, address, transaction).unwrap();
+        assert!(signed.hash.is_some());
+        assert!(handler.sign_transaction(&private_key, "AAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYP7MUPJQE", transaction).is_err());
+    }
+}
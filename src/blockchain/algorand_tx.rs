@@ -0,0 +1,722 @@
+use anyhow::{Result, Context, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use algo_rust_sdk::{mnemonic, Address};
+use data_encoding::BASE32_NOPAD;
+use ed25519_dalek::{Signer, SigningKey};
+use rmp::{decode, encode, Marker};
+use sha2::{Digest, Sha512_256};
+use crate::blockchain::SignedTransaction;
+
+const MNEMONIC_WORDS: usize = 25;
+
+// Domain separation prefixes hashed in front of transactions and group lists
+const TRANSACTION_PREFIX: &[u8] = b"TX";
+const GROUP_PREFIX: &[u8] = b"TG";
+const MAX_GROUP_SIZE: usize = 16;
+// A SignedTxn nests a few levels at most (txn -> apar, msig -> subsig -> entry)
+const MAX_DEPTH: usize = 16;
+
+const MICROALGOS_PER_ALGO: u64 = 1_000_000;
+
+// ========== MNEMONICS ==========
+
+/// Algorand's native 25-word mnemonic for a 32-byte ed25519 seed: the seed as 24 little-endian
+/// 11-bit words from the BIP-39 English list, followed by a checksum word
+pub fn key_to_mnemonic(seed: &[u8; 32]) -> String {
+    mnemonic::from_key(seed).expect("a 32-byte seed always has a mnemonic")
+}
+
+pub fn mnemonic_to_key(mnemonic: &str) -> Result<[u8; 32]> {
+    let words: Vec<String> = mnemonic.split_whitespace().map(str::to_lowercase).collect();
+    if words.len() != MNEMONIC_WORDS {
+        bail!("Algorand mnemonics have {} words, got {}", MNEMONIC_WORDS, words.len());
+    }
+    mnemonic::to_key(&words.join(" ")).map_err(|e| anyhow::anyhow!("Invalid Algorand mnemonic: {}", e))
+}
+
+/// True for 25-word input, which can only be an Algorand mnemonic (BIP-39 stops at 24)
+pub fn looks_like_mnemonic(value: &str) -> bool {
+    value.split_whitespace().count() == MNEMONIC_WORDS
+}
+
+// ========== MSGPACK ==========
+
+/// A decoded msgpack value. Algorand encodes transactions canonically (sorted keys, shortest
+/// forms, zero values omitted), so re-encoding a decoded transaction reproduces its bytes.
+/// algo_rust_sdk's `Transaction` only models payments and key registrations and drops fields such
+/// as `grp` and `rekey`, so transactions are kept as this generic map and signed byte for byte
+#[derive(Debug, Clone, PartialEq)]
+enum MsgValue {
+    Nil,
+    Bool(bool),
+    Uint(u64),
+    Int(i64),
+    Bin(Vec<u8>),
+    Str(String),
+    Array(Vec<MsgValue>),
+    Map(Vec<(MsgValue, MsgValue)>),
+}
+
+impl MsgValue {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode_into(&mut out).expect("msgpack writes to a Vec cannot fail");
+        out
+    }
+
+    // rmp always picks the shortest form, which is what canonical encoding requires
+    fn encode_into(&self, out: &mut Vec<u8>) -> Result<()> {
+        match self {
+            MsgValue::Nil => encode::write_nil(out)?,
+            MsgValue::Bool(value) => encode::write_bool(out, *value)?,
+            MsgValue::Uint(value) => {
+                encode::write_uint(out, *value)?;
+            }
+            MsgValue::Int(value) => {
+                encode::write_sint(out, *value)?;
+            }
+            MsgValue::Bin(bytes) => encode::write_bin(out, bytes)?,
+            MsgValue::Str(text) => encode::write_str(out, text)?,
+            MsgValue::Array(items) => {
+                encode::write_array_len(out, items.len() as u32)?;
+                for item in items {
+                    item.encode_into(out)?;
+                }
+            }
+            MsgValue::Map(entries) => {
+                encode::write_map_len(out, entries.len() as u32)?;
+                for (key, value) in entries {
+                    key.encode_into(out)?;
+                    value.encode_into(out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Value under a string key of a map
+    fn get(&self, key: &str) -> Option<&MsgValue> {
+        match self {
+            MsgValue::Map(entries) => entries.iter()
+                .find(|(k, _)| matches!(k, MsgValue::Str(name) if name == key))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Map without the entry for `key`
+    fn without(&self, key: &str) -> MsgValue {
+        match self {
+            MsgValue::Map(entries) => MsgValue::Map(
+                entries.iter()
+                    .filter(|(k, _)| !matches!(k, MsgValue::Str(name) if name == key))
+                    .cloned()
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+
+    /// Map with `key` set, keeping the canonical key order
+    fn with(&self, key: &str, value: MsgValue) -> MsgValue {
+        let mut entries = match self.without(key) {
+            MsgValue::Map(entries) => entries,
+            _ => Vec::new(),
+        };
+        entries.push((MsgValue::Str(key.to_string()), value));
+        // Canonical order compares the key strings, not their encodings (which lead with the length)
+        entries.sort_by(|(a, _), (b, _)| match (a, b) {
+            (MsgValue::Str(a), MsgValue::Str(b)) => a.cmp(b),
+            _ => a.encode().cmp(&b.encode()),
+        });
+        MsgValue::Map(entries)
+    }
+}
+
+fn take(data: &[u8], pos: usize, length: u32) -> Result<(&[u8], usize)> {
+    let end = pos.checked_add(length as usize)
+        .filter(|end| *end <= data.len())
+        .context("Truncated msgpack")?;
+    Ok((&data[pos..end], end))
+}
+
+/// Decodes the value at `pos` and returns it with the position after it. `depth` counts the
+/// enclosing arrays and maps, so hostile nesting fails instead of overflowing the stack
+fn decode_value(data: &[u8], pos: usize, depth: usize) -> Result<(MsgValue, usize)> {
+    if depth > MAX_DEPTH {
+        bail!("msgpack nesting exceeds {} levels", MAX_DEPTH);
+    }
+    let marker = Marker::from_u8(*data.get(pos).context("Truncated msgpack")?);
+    let mut reader = &data[pos..];
+    let value = match marker {
+        Marker::FixPos(_) | Marker::U8 | Marker::U16 | Marker::U32 | Marker::U64 => {
+            MsgValue::Uint(decode::read_int(&mut reader)?)
+        }
+        Marker::FixNeg(_) | Marker::I8 | Marker::I16 | Marker::I32 | Marker::I64 => {
+            MsgValue::Int(decode::read_int(&mut reader)?)
+        }
+        Marker::Null => {
+            decode::read_nil(&mut reader)?;
+            MsgValue::Nil
+        }
+        Marker::True | Marker::False => MsgValue::Bool(decode::read_bool(&mut reader)?),
+        Marker::Bin8 | Marker::Bin16 | Marker::Bin32 => {
+            let length = decode::read_bin_len(&mut reader)?;
+            let (bytes, end) = take(data, data.len() - reader.len(), length)?;
+            return Ok((MsgValue::Bin(bytes.to_vec()), end));
+        }
+        Marker::FixStr(_) | Marker::Str8 | Marker::Str16 | Marker::Str32 => {
+            let length = decode::read_str_len(&mut reader)?;
+            let (bytes, end) = take(data, data.len() - reader.len(), length)?;
+            let text = String::from_utf8(bytes.to_vec()).context("msgpack string is not UTF-8")?;
+            return Ok((MsgValue::Str(text), end));
+        }
+        Marker::FixArray(_) | Marker::Array16 | Marker::Array32 => {
+            let count = decode::read_array_len(&mut reader)?;
+            return decode_array(data, data.len() - reader.len(), count, depth + 1);
+        }
+        Marker::FixMap(_) | Marker::Map16 | Marker::Map32 => {
+            let count = decode::read_map_len(&mut reader)?;
+            return decode_map(data, data.len() - reader.len(), count, depth + 1);
+        }
+        other => bail!("Unsupported msgpack type {:?}", other),
+    };
+    Ok((value, data.len() - reader.len()))
+}
+
+fn decode_array(data: &[u8], mut pos: usize, count: u32, depth: usize) -> Result<(MsgValue, usize)> {
+    let mut items = Vec::new();
+    for _ in 0..count {
+        let (item, next) = decode_value(data, pos, depth)?;
+        items.push(item);
+        pos = next;
+    }
+    Ok((MsgValue::Array(items), pos))
+}
+
+fn decode_map(data: &[u8], mut pos: usize, count: u32, depth: usize) -> Result<(MsgValue, usize)> {
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let (key, next) = decode_value(data, pos, depth)?;
+        let (value, next) = decode_value(data, next, depth)?;
+        entries.push((key, value));
+        pos = next;
+    }
+    Ok((MsgValue::Map(entries), pos))
+}
+
+// ========== TRANSACTIONS ==========
+
+/// One transaction, unsigned or wrapped in a SignedTxn
+pub struct AlgorandTransaction {
+    // The `txn` map
+    fields: MsgValue,
+    // Other SignedTxn entries (sig, msig, lsig, sgnr) when a signed transaction was given
+    envelope: Vec<(MsgValue, MsgValue)>,
+}
+
+impl AlgorandTransaction {
+    fn from_value(value: MsgValue) -> Result<Self> {
+        let MsgValue::Map(entries) = &value else {
+            bail!("An Algorand transaction is a msgpack map");
+        };
+        if let Some(fields) = value.get("txn") {
+            let envelope = entries.iter()
+                .filter(|(key, _)| !matches!(key, MsgValue::Str(name) if name == "txn"))
+                .cloned()
+                .collect();
+            Ok(Self { fields: fields.clone(), envelope })
+        } else if value.get("type").is_some() {
+            Ok(Self { fields: value, envelope: Vec::new() })
+        } else {
+            bail!("Expected a transaction or SignedTxn (a map with 'type' or 'txn')");
+        }
+    }
+
+    /// Transaction ID: base32 of SHA-512/256("TX" || msgpack)
+    pub fn id(&self) -> String {
+        BASE32_NOPAD.encode(&self.raw_id())
+    }
+
+    fn raw_id(&self) -> [u8; 32] {
+        Sha512_256::digest([TRANSACTION_PREFIX, &self.fields.encode()].concat()).into()
+    }
+
+    pub fn sender(&self) -> Result<String> {
+        self.address("snd")?.context("Transaction has no sender (snd)")
+    }
+
+    fn is_signed(&self) -> bool {
+        self.envelope.iter().any(|(key, _)| {
+            matches!(key, MsgValue::Str(name) if name == "sig" || name == "msig" || name == "lsig")
+        })
+    }
+
+    /// SignedTxn encoding: the envelope with `sig` added when a signature is given
+    fn signed_txn(&self, signature: Option<&[u8]>) -> Vec<u8> {
+        let mut signed = MsgValue::Map(self.envelope.clone()).with("txn", self.fields.clone());
+        if let Some(signature) = signature {
+            signed = signed.with("sig", MsgValue::Bin(signature.to_vec()));
+        }
+        signed.encode()
+    }
+
+    fn summary(&self) -> Result<String> {
+        let kind = match self.fields.get("type") {
+            Some(MsgValue::Str(kind)) => kind.as_str(),
+            _ => bail!("Transaction has no type"),
+        };
+        let sender = self.sender()?;
+        Ok(match kind {
+            "pay" => format!(
+                "Payment of {} to {}",
+                format_microalgos(self.uint("amt")),
+                self.address("rcv")?.unwrap_or_else(|| sender.clone())
+            ),
+            "axfer" => {
+                let asset = self.uint("xaid");
+                let receiver = self.address("arcv")?.unwrap_or_else(|| sender.clone());
+                if self.uint("aamt") == 0 && receiver == sender && self.address("aclose")?.is_none() {
+                    format!("Opt-in to asset {}", asset)
+                } else {
+                    let mut summary = format!("Transfer of {} units of asset {} to {}", self.uint("aamt"), asset, receiver);
+                    if let Some(revoked) = self.address("asnd")? {
+                        summary.push_str(&format!(" (clawback from {})", revoked));
+                    }
+                    if let Some(close) = self.address("aclose")? {
+                        summary.push_str(&format!(", closing the holding out to {}", close));
+                    }
+                    summary
+                }
+            }
+            "appl" => {
+                let args = match self.fields.get("apaa") {
+                    Some(MsgValue::Array(args)) => args.len(),
+                    _ => 0,
+                };
+                let on_complete = on_complete_name(self.uint("apan"));
+                match self.uint("apid") {
+                    0 => format!("Application create ({}, {} args)", on_complete, args),
+                    app => format!("Application call to app {} ({}, {} args)", app, on_complete, args),
+                }
+            }
+            "acfg" => match (self.uint("caid"), self.fields.get("apar")) {
+                (0, params) => {
+                    let name = match params.and_then(|params| params.get("an")) {
+                        Some(MsgValue::Str(name)) => name.clone(),
+                        _ => "unnamed".to_string(),
+                    };
+                    format!("Asset creation: {} (total {})", name, params.and_then(|p| p.get("t")).map(msg_uint).unwrap_or(0))
+                }
+                (asset, None) => format!("Destroy asset {}", asset),
+                (asset, Some(_)) => format!("Reconfigure asset {}", asset),
+            },
+            "afrz" => format!(
+                "{} asset {} for {}",
+                if matches!(self.fields.get("afrz"), Some(MsgValue::Bool(true))) { "Freeze" } else { "Unfreeze" },
+                self.uint("faid"),
+                self.address("fadd")?.unwrap_or_default()
+            ),
+            "keyreg" if self.fields.get("nonpart").is_some() => "Key registration (non-participating)".to_string(),
+            "keyreg" if self.fields.get("votekey").is_some() => "Key registration (online)".to_string(),
+            "keyreg" => "Key registration (offline)".to_string(),
+            other => bail!("Unsupported Algorand transaction type '{}'", other),
+        })
+    }
+
+    /// Integer field; Algorand omits zero values from the encoding
+    fn uint(&self, key: &str) -> u64 {
+        self.fields.get(key).map(msg_uint).unwrap_or(0)
+    }
+
+    fn address(&self, key: &str) -> Result<Option<String>> {
+        match self.fields.get(key) {
+            None => Ok(None),
+            Some(MsgValue::Bin(bytes)) => {
+                let public_key: [u8; 32] = bytes.as_slice().try_into()
+                    .with_context(|| format!("Field '{}' is not a 32-byte address", key))?;
+                Ok(Some(encode_address(&public_key)))
+            }
+            Some(_) => bail!("Field '{}' is not an address", key),
+        }
+    }
+}
+
+/// A single transaction or an atomic group, as produced by goal clerk, algosdk or wallet tooling
+pub struct AlgorandTransactionGroup {
+    pub transactions: Vec<AlgorandTransaction>,
+    // True when the group ID was assigned here rather than present in the input
+    assigned_group: bool,
+}
+
+impl AlgorandTransactionGroup {
+    /// Parses msgpack transactions given as raw bytes, or as base64 / hex text (several blobs may be
+    /// separated by whitespace or given as a JSON array); concatenated transactions form a group
+    pub fn parse(input: &[u8]) -> Result<Self> {
+        let mut transactions = Vec::new();
+        for chunk in transaction_chunks(input)? {
+            let mut pos = 0;
+            while pos < chunk.len() {
+                let (value, end) = decode_value(&chunk, pos, 0).context("Transaction is not valid msgpack")?;
+                if value.encode() != chunk[pos..end] {
+                    bail!("Transaction is not canonically encoded msgpack");
+                }
+                transactions.push(AlgorandTransaction::from_value(value)?);
+                pos = end;
+            }
+        }
+
+        if transactions.is_empty() {
+            bail!("No transactions found");
+        }
+        if transactions.len() > MAX_GROUP_SIZE {
+            bail!("Atomic groups hold at most {} transactions, got {}", MAX_GROUP_SIZE, transactions.len());
+        }
+
+        let mut group = Self { transactions, assigned_group: false };
+        group.check_group()?;
+        Ok(group)
+    }
+
+    pub fn group_id(&self) -> Option<Vec<u8>> {
+        match self.transactions[0].fields.get("grp") {
+            Some(MsgValue::Bin(group_id)) => Some(group_id.clone()),
+            _ => None,
+        }
+    }
+
+    /// Requires the whole group, so every leg of an atomic transfer is shown before signing.
+    /// Several ungrouped transactions are grouped here, since that is the only reason to sign them together
+    fn check_group(&mut self) -> Result<()> {
+        let group_ids: Vec<Option<MsgValue>> = self.transactions.iter().map(|tx| tx.fields.get("grp").cloned()).collect();
+        if group_ids.iter().all(Option::is_none) {
+            if self.transactions.len() == 1 {
+                return Ok(());
+            }
+            if self.transactions.iter().any(AlgorandTransaction::is_signed) {
+                bail!("Cannot group transactions that are already signed");
+            }
+            let group_id = MsgValue::Bin(self.compute_group_id().to_vec());
+            for transaction in &mut self.transactions {
+                transaction.fields = transaction.fields.with("grp", group_id.clone());
+            }
+            self.assigned_group = true;
+            return Ok(());
+        }
+
+        if group_ids.iter().any(|id| *id != group_ids[0]) {
+            bail!("The transactions belong to different groups (or some have no group ID)");
+        }
+        if group_ids[0] != Some(MsgValue::Bin(self.compute_group_id().to_vec())) {
+            bail!("The group ID does not match these {} transactions; pass the complete group", self.transactions.len());
+        }
+        Ok(())
+    }
+
+    /// SHA-512/256("TG" || msgpack({txlist: [IDs of the transactions without their group ID]}))
+    fn compute_group_id(&self) -> [u8; 32] {
+        let ids = self.transactions.iter()
+            .map(|tx| {
+                let ungrouped = AlgorandTransaction { fields: tx.fields.without("grp"), envelope: Vec::new() };
+                MsgValue::Bin(ungrouped.raw_id().to_vec())
+            })
+            .collect();
+        let tx_group = MsgValue::Map(vec![(MsgValue::Str("txlist".to_string()), MsgValue::Array(ids))]);
+        Sha512_256::digest([GROUP_PREFIX, &tx_group.encode()].concat()).into()
+    }
+
+    pub fn details(&self) -> Result<Vec<(String, String)>> {
+        let mut details = Vec::new();
+        if let Some(group_id) = self.group_id() {
+            let note = if self.assigned_group { " (assigned now)" } else { "" };
+            details.push(("Group ID".to_string(), format!("{}{}", BASE64.encode(group_id), note)));
+            details.push(("Group Size".to_string(), self.transactions.len().to_string()));
+        }
+
+        let grouped = self.transactions.len() > 1;
+        for (index, transaction) in self.transactions.iter().enumerate() {
+            let label = |name: &str| if grouped { format!("#{} {}", index + 1, name) } else { name.to_string() };
+            let fields = &transaction.fields;
+
+            details.push((label("Transaction"), transaction.summary()?));
+            details.push((label("Transaction ID"), transaction.id()));
+            details.push((label("Sender"), transaction.sender()?));
+            details.push((label("Fee"), format_microalgos(transaction.uint("fee"))));
+            details.push((label("Valid Rounds"), format!("{} - {}", transaction.uint("fv"), transaction.uint("lv"))));
+            if let Some(MsgValue::Str(genesis_id)) = fields.get("gen") {
+                details.push((label("Network"), genesis_id.clone()));
+            }
+            if let Some(MsgValue::Bin(note)) = fields.get("note") {
+                let note = String::from_utf8(note.clone()).unwrap_or_else(|_| format!("0x{}", hex::encode(note)));
+                details.push((label("Note"), note));
+            }
+            if let Some(close) = transaction.address("close")? {
+                details.push((label("Close Remainder To"), format!("{} (empties and closes the sender account)", close)));
+            }
+            if let Some(rekey) = transaction.address("rekey")? {
+                details.push((label("Rekey To"), format!("{} (hands signing authority to that account)", rekey)));
+            }
+            if transaction.is_signed() {
+                details.push((label("Signed"), "yes".to_string()));
+            }
+        }
+        Ok(details)
+    }
+
+    /// Signs every transaction sent by `address` and returns the whole group as concatenated SignedTxns
+    /// (base64), ready for algod's POST /v2/transactions
+    pub fn sign(&self, signing_key: &SigningKey, address: &str) -> Result<SignedTransaction> {
+        let mut encoded = Vec::new();
+        let mut signed_ids = Vec::new();
+        for transaction in &self.transactions {
+            if transaction.sender()? == address {
+                let message = [TRANSACTION_PREFIX, &transaction.fields.encode()].concat();
+                let signature = signing_key.sign(&message).to_bytes();
+                encoded.extend(transaction.signed_txn(Some(&signature)));
+                signed_ids.push(transaction.id());
+            } else {
+                encoded.extend(transaction.signed_txn(None));
+            }
+        }
+        if signed_ids.is_empty() {
+            bail!("None of the transactions is sent by {}", address);
+        }
+
+        Ok(SignedTransaction {
+            encoded: BASE64.encode(encoded),
+            hash: Some(signed_ids.join(", ")),
+        })
+    }
+}
+
+fn transaction_chunks(input: &[u8]) -> Result<Vec<Vec<u8>>> {
+    // Binary msgpack maps start with a byte that is never valid UTF-8
+    let text = match std::str::from_utf8(input) {
+        Ok(text) => text.replace(['"', ',', '[', ']'], " "),
+        Err(_) => return Ok(vec![input.to_vec()]),
+    };
+    text.split_whitespace()
+        .map(|token| {
+            hex::decode(token.trim_start_matches("0x"))
+                .or_else(|_| BASE64.decode(token))
+                .context("Transactions are expected as msgpack, base64 or hex")
+        })
+        .collect()
+}
+
+fn on_complete_name(on_complete: u64) -> &'static str {
+    match on_complete {
+        0 => "NoOp",
+        1 => "OptIn",
+        2 => "CloseOut",
+        3 => "ClearState",
+        4 => "UpdateApplication",
+        5 => "DeleteApplication",
+        _ => "unknown on-completion",
+    }
+}
+
+// ========== ADDRESSES ==========
+
+/// Base32 of the public key followed by the last 4 bytes of its SHA-512/256 hash
+pub fn encode_address(public_key: &[u8; 32]) -> String {
+    Address::new(*public_key).encode_string()
+}
+
+pub fn signing_key_address(signing_key: &SigningKey) -> String {
+    encode_address(&signing_key.verifying_key().to_bytes())
+}
+
+// ========== HELPERS ==========
+
+fn msg_uint(value: &MsgValue) -> u64 {
+    match value {
+        MsgValue::Uint(value) => *value,
+        _ => 0,
+    }
+}
+
+fn format_microalgos(microalgos: u64) -> String {
+    let algo = format!("{}.{:06}", microalgos / MICROALGOS_PER_ALGO, microalgos % MICROALGOS_PER_ALGO);
+    format!("{} ALGO", algo.trim_end_matches('0').trim_end_matches('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bip39::Language;
+
+    const PRIVATE_KEY: &str = "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+    const ADDRESS: &str = "LTHKVWOCALBDNTMMS5ZJBKCE4PZPBIK2TMYYSIQNXYHZCIPLBTJHXYIUEQ";
+    // Public key 0x00..0x1f
+    const OTHER: &str = "AAAQEAYEAUDAOCAJBIFQYDIOB4IBCEQTCQKRMFYYDENBWHA5DYP7MUPJQE";
+
+    // 1.5 ALGO to OTHER on mainnet with the note "rent"
+    const PAYMENT: &str = "iqNhbXTOABbjYKNmZWXNA+iiZnbOAmJaAKNnZW6sbWFpbm5ldC12MS4womdoxCDAYcTY/B293tLXYEvkVo4/bQQZh6w3veS2ILWrOSSK36Jsds4CYl3opG5vdGXEBHJlbnSjcmN2xCAAAQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eH6NzbmTEIFzOqtnCAsI2zYyXcpCoROPy8KFamzGJIg2+D5Eh6wzSpHR5cGWjcGF5";
+    const PAYMENT_ID: &str = "3RG3D2JG2Q6GXCRQHLZHUMC3XNQDXBNXIJ675K6ANC5IPP3PYITQ";
+    const PAYMENT_SIGNATURE: &str = "c2d62e9afe9669aa5f9c46e2d4f92dfadeac9c8a0be1a6c8189992e7179e910cc5529e49f0ce72bc0ceeb9dc887266f7982ed3a2b62d201a073a69559dd95004";
+
+    // Atomic swap: 2 ALGO from ADDRESS to OTHER, 250 units of asset 31566704 back from OTHER
+    const SWAP_PAYMENT: &str = "88a3616d74ce001e8480a3666565cd03e8a26676ce02625a00a26768c420c061c4d8fc1dbdded2d7604be4568e3f6d041987ac37bde4b620b5ab39248adfa26c76ce02625de8a3726376c420000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1fa3736e64c4205cceaad9c202c236cd8c977290a844e3f2f0a15a9b3189220dbe0f9121eb0cd2a474797065a3706179";
+    const SWAP_ASSET: &str = "89a461616d74ccfaa461726376c4205cceaad9c202c236cd8c977290a844e3f2f0a15a9b3189220dbe0f9121eb0cd2a3666565cd03e8a26676ce02625a00a26768c420c061c4d8fc1dbdded2d7604be4568e3f6d041987ac37bde4b620b5ab39248adfa26c76ce02625de8a3736e64c420000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1fa474797065a56178666572a478616964ce01e1ab70";
+    const SWAP_GROUP_ID: &str = "+SY2hfJP5q3S6f/Az/uLw9tbkkzTO1+rtpQVEAN1c8c=";
+    const SWAP_PAYMENT_GROUPED: &str = "89a3616d74ce001e8480a3666565cd03e8a26676ce02625a00a26768c420c061c4d8fc1dbdded2d7604be4568e3f6d041987ac37bde4b620b5ab39248adfa3677270c420f9263685f24fe6add2e9ffc0cffb8bc3db5b924cd33b5fabb6941510037573c7a26c76ce02625de8a3726376c420000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1fa3736e64c4205cceaad9c202c236cd8c977290a844e3f2f0a15a9b3189220dbe0f9121eb0cd2a474797065a3706179";
+    const SWAP_ASSET_GROUPED: &str = "8aa461616d74ccfaa461726376c4205cceaad9c202c236cd8c977290a844e3f2f0a15a9b3189220dbe0f9121eb0cd2a3666565cd03e8a26676ce02625a00a26768c420c061c4d8fc1dbdded2d7604be4568e3f6d041987ac37bde4b620b5ab39248adfa3677270c420f9263685f24fe6add2e9ffc0cffb8bc3db5b924cd33b5fabb6941510037573c7a26c76ce02625de8a3736e64c420000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1fa474797065a56178666572a478616964ce01e1ab70";
+
+    // OptIn call to app 123456 with two arguments
+    const APP_CALL: &str = "89a46170616192c405636c61696dc4020001a46170616e01a461706964ce0001e240a3666565cd07d0a2667601a26768c420c061c4d8fc1dbdded2d7604be4568e3f6d041987ac37bde4b620b5ab39248adfa26c76cd03e9a3736e64c4205cceaad9c202c236cd8c977290a844e3f2f0a15a9b3189220dbe0f9121eb0cd2a474797065a46170706c";
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&hex::decode(PRIVATE_KEY).unwrap().try_into().unwrap())
+    }
+
+    fn decode_all(data: &[u8]) -> Vec<MsgValue> {
+        let mut values = Vec::new();
+        let mut pos = 0;
+        while pos < data.len() {
+            let (value, end) = decode_value(data, pos, 0).unwrap();
+            values.push(value);
+            pos = end;
+        }
+        values
+    }
+
+    #[test]
+    fn test_mnemonic_vectors() {
+        let words = Language::English.word_list();
+
+        // The zero seed is 24 x "abandon" plus the checksum word (index 943)
+        let zero = key_to_mnemonic(&[0u8; 32]);
+        let expected = format!("{} {}", vec!["abandon"; 24].join(" "), words[943]);
+        assert_eq!(zero, expected);
+        assert_eq!(mnemonic_to_key(&zero).unwrap(), [0u8; 32]);
+
+        let seed: [u8; 32] = hex::decode(PRIVATE_KEY).unwrap().try_into().unwrap();
+        let indices = [
+            1042, 710, 481, 1480, 1242, 1503, 1284, 689, 120, 1394, 1846, 375, 833, 172, 1054, 1372, 1997, 605,
+            208, 1067, 263, 855, 1011, 7, 173,
+        ];
+        let expected: Vec<&str> = indices.iter().map(|index| words[*index]).collect();
+        let mnemonic = key_to_mnemonic(&seed);
+        assert_eq!(mnemonic, expected.join(" "));
+        assert_eq!(mnemonic_to_key(&mnemonic.to_uppercase()).unwrap(), seed);
+        assert!(looks_like_mnemonic(&mnemonic));
+    }
+
+    #[test]
+    fn test_mnemonic_rejects_bad_input() {
+        let words = Language::English.word_list();
+        let mnemonic = key_to_mnemonic(&[7u8; 32]);
+        let mut tampered: Vec<&str> = mnemonic.split(' ').collect();
+        tampered[3] = if tampered[3] == words[0] { words[1] } else { words[0] };
+        assert!(mnemonic_to_key(&tampered.join(" ")).is_err());
+
+        let short: Vec<&str> = mnemonic.split(' ').take(24).collect();
+        assert!(mnemonic_to_key(&short.join(" ")).is_err());
+        let mut unknown: Vec<&str> = mnemonic.split(' ').collect();
+        unknown[0] = "notaword";
+        assert!(mnemonic_to_key(&unknown.join(" ")).is_err());
+    }
+
+    #[test]
+    fn test_msgpack_round_trip() {
+        let value = MsgValue::Map(vec![
+            (MsgValue::Str("a".to_string()), MsgValue::Int(-200)),
+            (MsgValue::Str("b".to_string()), MsgValue::Array(vec![MsgValue::Uint(300), MsgValue::Bool(true), MsgValue::Nil])),
+            (MsgValue::Str("c".to_string()), MsgValue::Bin(vec![0xab; 40])),
+            (MsgValue::Str("d".to_string()), MsgValue::Str("x".repeat(40))),
+            (MsgValue::Str("e".to_string()), MsgValue::Uint(u64::MAX)),
+        ]);
+        let encoded = value.encode();
+        assert_eq!(decode_value(&encoded, 0, 0).unwrap(), (value, encoded.len()));
+
+        assert_eq!(MsgValue::Int(-5).encode(), vec![0xfb]);
+        assert_eq!(MsgValue::Uint(200).encode(), vec![0xcc, 0xc8]);
+        assert!(decode_value(&[0xc4, 0x05, 0x01], 0, 0).is_err());
+
+        // A non-minimal integer is rejected as non-canonical
+        assert!(AlgorandTransactionGroup::parse(b"81a474797065cd0001").is_err());
+    }
+
+    #[test]
+    fn test_msgpack_depth_limit() {
+        // 100k nested one-element arrays must fail cleanly rather than overflow the stack
+        let mut nested = vec![0x91; 100_000];
+        nested.push(0xc0);
+        assert!(decode_value(&nested, 0, 0).is_err());
+        assert!(AlgorandTransactionGroup::parse(&nested).is_err());
+
+        let mut shallow = vec![0x91; MAX_DEPTH];
+        shallow.push(0xc0);
+        assert!(decode_value(&shallow, 0, 0).is_ok());
+    }
+
+    #[test]
+    fn test_payment_signing() {
+        let group = AlgorandTransactionGroup::parse(PAYMENT.as_bytes()).unwrap();
+        let transaction = &group.transactions[0];
+        assert_eq!(transaction.id(), PAYMENT_ID);
+        assert_eq!(transaction.sender().unwrap(), ADDRESS);
+        assert_eq!(signing_key_address(&signing_key()), ADDRESS);
+
+        let details = group.details().unwrap();
+        assert!(details.contains(&("Transaction".to_string(), format!("Payment of 1.5 ALGO to {}", OTHER))));
+        assert!(details.contains(&("Fee".to_string(), "0.001 ALGO".to_string())));
+        assert!(details.contains(&("Network".to_string(), "mainnet-v1.0".to_string())));
+        assert!(details.contains(&("Note".to_string(), "rent".to_string())));
+
+        let signed = group.sign(&signing_key(), ADDRESS).unwrap();
+        assert_eq!(signed.hash.as_deref(), Some(PAYMENT_ID));
+        let mut expected = hex::decode("82a3736967c440").unwrap();
+        expected.extend(hex::decode(PAYMENT_SIGNATURE).unwrap());
+        expected.extend(hex::decode("a374786e").unwrap());
+        expected.extend(BASE64.decode(PAYMENT).unwrap());
+        assert_eq!(BASE64.decode(&signed.encoded).unwrap(), expected);
+
+        // The signed transaction parses again with the same ID
+        let resigned = AlgorandTransactionGroup::parse(signed.encoded.as_bytes()).unwrap();
+        assert_eq!(resigned.transactions[0].id(), PAYMENT_ID);
+        assert!(resigned.transactions[0].is_signed());
+
+        assert!(group.sign(&signing_key(), OTHER).is_err());
+    }
+
+    #[test]
+    fn test_group_assignment_and_signing() {
+        // Two ungrouped transactions get the group ID algosdk's assignGroupID would give them
+        let input = format!("{}\n{}", SWAP_PAYMENT, SWAP_ASSET);
+        let group = AlgorandTransactionGroup::parse(input.as_bytes()).unwrap();
+        assert_eq!(BASE64.encode(group.group_id().unwrap()), SWAP_GROUP_ID);
+        assert_eq!(hex::encode(group.transactions[0].fields.encode()), SWAP_PAYMENT_GROUPED);
+        assert_eq!(hex::encode(group.transactions[1].fields.encode()), SWAP_ASSET_GROUPED);
+
+        let details = group.details().unwrap();
+        assert!(details.contains(&("Group ID".to_string(), format!("{} (assigned now)", SWAP_GROUP_ID))));
+        assert!(details.contains(&("#2 Transaction".to_string(), format!("Transfer of 250 units of asset 31566704 to {}", ADDRESS))));
+
+        // Only the leg sent by the wallet is signed; the other stays an unsigned SignedTxn
+        let signed = group.sign(&signing_key(), ADDRESS).unwrap();
+        let values = decode_all(&BASE64.decode(&signed.encoded).unwrap());
+        assert_eq!(values.len(), 2);
+        assert!(values[0].get("sig").is_some());
+        assert!(values[1].get("sig").is_none());
+        assert_eq!(values[1].get("txn").unwrap().encode(), hex::decode(SWAP_ASSET_GROUPED).unwrap());
+
+        // A pre-grouped file is accepted as is, but only complete
+        let grouped = format!("[\"{}\", \"{}\"]", SWAP_PAYMENT_GROUPED, SWAP_ASSET_GROUPED);
+        let group = AlgorandTransactionGroup::parse(grouped.as_bytes()).unwrap();
+        assert!(!group.assigned_group);
+        assert!(AlgorandTransactionGroup::parse(SWAP_PAYMENT_GROUPED.as_bytes()).is_err());
+        let mixed = format!("{} {}", SWAP_PAYMENT_GROUPED, SWAP_ASSET);
+        assert!(AlgorandTransactionGroup::parse(mixed.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_app_call_summary() {
+        let group = AlgorandTransactionGroup::parse(APP_CALL.as_bytes()).unwrap();
+        let details = group.details().unwrap();
+        assert!(details.contains(&("Transaction".to_string(), "Application call to app 123456 (OptIn, 2 args)".to_string())));
+        assert!(details.contains(&("Valid Rounds".to_string(), "1 - 1001".to_string())));
+    }
+}
@@ -1,5 +1,6 @@
 use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain, SigningCapabilities, SignedTransaction};
+use crate::blockchain::evm::{self, EvmSigner};
 use crate::crypto::bip32::{derive_secp256k1_key_from_mnemonic, private_key_to_public_key_secp256k1};
 use alloy_primitives::{Address, keccak256};
 use k256::ecdsa::VerifyingKey;
@@ -112,9 +113,39 @@ impl BlockchainHandler for BinanceHandler {
         Address::parse_checksummed(address, None).is_ok()
     }
 
+    fn derive_from_public_key(&self, public_key: &[u8], derivation_path: &str) -> Result<WalletKeys> {
+        WalletKeys::watch_only(public_key, derivation_path, |key| self.public_key_to_address(key))
+    }
+
     fn get_blockchain_name(&self) -> &'static str {
         "BinanceBNB"
     }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities::ALL
+    }
+
+    fn sign_message(&self, private_key: &[u8], address: &str, message: &[u8], format: Option<&str>) -> Result<String> {
+        evm::sign_handler_message(self, private_key, address, message, format)
+    }
+
+    fn verify_message(&self, address: &str, message: &[u8], signature: &str, format: Option<&str>) -> Result<String> {
+        evm::verify_handler_message(self, address, message, signature, format)
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        evm::describe_handler_transaction(self, transaction)
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        evm::sign_handler_transaction(self, private_key, address, transaction)
+    }
+}
+
+impl EvmSigner for BinanceHandler {
+    fn evm_blockchain(&self) -> SupportedBlockchain {
+        SupportedBlockchain::BinanceBNB
+    }
 }
 
 #[cfg(test)]
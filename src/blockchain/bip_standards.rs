@@ -15,6 +15,10 @@ pub enum BipStandard {
     /// Purpose: 84' - Native SegWit addresses (start with "bc1")
     Bip84,
 
+    /// BIP-86: Key Derivation for Single Key P2TR Outputs
+    /// Purpose: 86' - Taproot addresses (start with "bc1p")
+    Bip86,
+
     /// BIP-141: Segregated Witness (SegWit) specification
     /// Purpose: Defines SegWit transaction format (not a derivation path)
     Bip141,
@@ -30,9 +34,10 @@ impl BipStandard {
             "44" | "bip44" | "bip-44" => Ok(Self::Bip44),
             "49" | "bip49" | "bip-49" => Ok(Self::Bip49),
             "84" | "bip84" | "bip-84" => Ok(Self::Bip84),
+            "86" | "bip86" | "bip-86" => Ok(Self::Bip86),
             "141" | "bip141" | "bip-141" => Ok(Self::Bip141),
             "85" | "bip85" | "bip-85" => Ok(Self::Bip85),
-            _ => bail!("Unsupported BIP standard: {}. Supported: 44, 49, 84, 85, 141", s),
+            _ => bail!("Unsupported BIP standard: {}. Supported: 44, 49, 84, 85, 86, 141", s),
         }
     }
 
@@ -41,6 +46,7 @@ impl BipStandard {
             Self::Bip44 => 44,
             Self::Bip49 => 49,
             Self::Bip84 => 84,
+            Self::Bip86 => 86,
             Self::Bip141 => 141, // Note: BIP-141 is not typically used in derivation paths
             Self::Bip85 => 85,
         }
@@ -51,6 +57,7 @@ impl BipStandard {
             Self::Bip44 => "Multi-Account Hierarchy (Legacy addresses)",
             Self::Bip49 => "P2SH-wrapped SegWit (addresses start with '3')",
             Self::Bip84 => "Native SegWit (addresses start with 'bc1')",
+            Self::Bip86 => "Taproot (addresses start with 'bc1p')",
             Self::Bip141 => "Segregated Witness specification",
             Self::Bip85 => "Deterministic Entropy Generation",
         }
@@ -58,7 +65,7 @@ impl BipStandard {
 
     pub fn supports_derivation_path(&self) -> bool {
         match self {
-            Self::Bip44 | Self::Bip49 | Self::Bip84 | Self::Bip85 => true,
+            Self::Bip44 | Self::Bip49 | Self::Bip84 | Self::Bip85 | Self::Bip86 => true,
             Self::Bip141 => false, // BIP-141 is a transaction format, not derivation
         }
     }
@@ -77,8 +84,17 @@ impl BipStandard {
         )
     }
 
+    /// Address-type BIP (44/49/84/86) from the purpose field of a derivation path
+    pub fn from_derivation_path(path: &str) -> Option<Self> {
+        let purpose = path.split('/').nth(1)?.trim_end_matches('\'');
+        match Self::from_str(purpose).ok()? {
+            bip @ (Self::Bip44 | Self::Bip49 | Self::Bip84 | Self::Bip86) => Some(bip),
+            _ => None,
+        }
+    }
+
     pub fn get_all_supported() -> Vec<Self> {
-        vec![Self::Bip44, Self::Bip49, Self::Bip84, Self::Bip85]
+        vec![Self::Bip44, Self::Bip49, Self::Bip84, Self::Bip85, Self::Bip86]
     }
 }
 
@@ -95,7 +111,7 @@ impl SupportedBlockchain {
     pub fn get_supported_bips(&self) -> Vec<BipStandard> {
         match self {
             // Bitcoin supports all major BIPs
-            Self::Bitcoin => vec![BipStandard::Bip44, BipStandard::Bip49, BipStandard::Bip84],
+            Self::Bitcoin => vec![BipStandard::Bip44, BipStandard::Bip49, BipStandard::Bip84, BipStandard::Bip86],
 
             // Litecoin supports the same BIPs as Bitcoin
             Self::Litecoin => vec![BipStandard::Bip44, BipStandard::Bip49, BipStandard::Bip84],
@@ -164,6 +180,8 @@ mod tests {
         assert!(bitcoin.supports_bip(BipStandard::Bip44));
         assert!(bitcoin.supports_bip(BipStandard::Bip49));
         assert!(bitcoin.supports_bip(BipStandard::Bip84));
+        assert!(bitcoin.supports_bip(BipStandard::Bip86));
+        assert!(!SupportedBlockchain::Litecoin.supports_bip(BipStandard::Bip86));
         assert_eq!(bitcoin.get_default_bip(), BipStandard::Bip84);
     }
 
@@ -1,8 +1,12 @@
 use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain, BipStandard};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain, BipStandard, SigningCapabilities, SignedTransaction};
+use crate::blockchain::bitcoin_message::{BitcoinMessageFormat, BitcoinMessageSigner};
+use crate::crypto::psbt::{describe_psbt, sign_psbt_with_key};
 use crate::crypto::bip32::{derive_secp256k1_key_from_mnemonic, private_key_to_public_key_secp256k1};
+use crate::crypto::master_key::MasterKey;
 use bitcoin::address::Address;
-use bitcoin::key::{PublicKey, CompressedPublicKey};
+use bitcoin::key::{PublicKey, CompressedPublicKey, TapTweak, XOnlyPublicKey};
+use bitcoin::secp256k1::Secp256k1;
 use bitcoin::Network;
 use std::str::FromStr;
 
@@ -92,6 +96,41 @@ impl BlockchainHandler for BitcoinHandler {
     fn get_blockchain_name(&self) -> &'static str {
         "bitcoin"
     }
+
+    fn derive_from_public_key(&self, public_key: &[u8], derivation_path: &str) -> Result<WalletKeys> {
+        // Address type follows the purpose of the watched account (44/49/84/86)
+        let bip = BipStandard::from_derivation_path(derivation_path).unwrap_or(BipStandard::Bip84);
+        let mut keys = WalletKeys::watch_only(public_key, derivation_path, |key| self.public_key_to_address_with_bip(key, bip))?;
+        if bip == BipStandard::Bip86 {
+            self.add_taproot_details(&mut keys, public_key)?;
+        }
+        Ok(keys)
+    }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities::ALL
+    }
+
+    fn sign_message(&self, private_key: &[u8], address: &str, message: &[u8], format: Option<&str>) -> Result<String> {
+        let format = match format {
+            Some(format) => BitcoinMessageFormat::parse(format)?,
+            None => BitcoinMessageFormat::default_for(address),
+        };
+        self.sign_bitcoin_message(private_key, address, message, format)
+    }
+
+    fn verify_message(&self, address: &str, message: &[u8], signature: &str, _format: Option<&str>) -> Result<String> {
+        // The format is recognised from the signature itself
+        self.verify_bitcoin_message(address, message, signature)
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        describe_psbt(&SupportedBlockchain::Bitcoin, transaction)
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], _address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        sign_psbt_with_key(private_key, transaction)
+    }
 }
 
 impl BitcoinHandler {
@@ -106,6 +145,7 @@ impl BitcoinHandler {
             BipStandard::Bip44 => self.public_key_to_legacy_address(public_key_bytes),
             BipStandard::Bip49 => self.public_key_to_nested_segwit_address(public_key_bytes),
             BipStandard::Bip84 => self.public_key_to_native_segwit_address(public_key_bytes),
+            BipStandard::Bip86 => self.public_key_to_taproot_address(public_key_bytes),
             _ => Err(anyhow::anyhow!("Bitcoin does not support {} for address generation", bip)),
         }
     }
@@ -127,6 +167,34 @@ impl BitcoinHandler {
         Ok(address.to_string())
     }
 
+    /// Generate Taproot address (starts with bc1p) - BIP-86
+    /// Key-path only: the internal key is tweaked with an empty script tree
+    fn public_key_to_taproot_address(&self, public_key_bytes: &[u8]) -> Result<String> {
+        let secp = Secp256k1::verification_only();
+        let internal_key = taproot_internal_key(public_key_bytes)?;
+
+        let address = Address::p2tr(&secp, internal_key, None, self.network);
+
+        Ok(address.to_string())
+    }
+
+    /// Records the x-only internal/output keys of a BIP-86 wallet and, for comparison,
+    /// the legacy, nested and native SegWit addresses of the same public key
+    pub fn add_taproot_details(&self, keys: &mut WalletKeys, public_key_bytes: &[u8]) -> Result<()> {
+        let secp = Secp256k1::verification_only();
+        let internal_key = taproot_internal_key(public_key_bytes)?;
+        let (output_key, _parity) = internal_key.tap_tweak(&secp, None);
+
+        keys.add_data("taproot_internal_key".to_string(), hex::encode(internal_key.serialize()));
+        keys.add_data("taproot_output_key".to_string(), hex::encode(output_key.to_inner().serialize()));
+
+        keys.add_secondary_address("legacy".to_string(), self.public_key_to_legacy_address(public_key_bytes)?);
+        keys.add_secondary_address("nested_segwit".to_string(), self.public_key_to_nested_segwit_address(public_key_bytes)?);
+        keys.add_secondary_address("native_segwit".to_string(), self.public_key_to_native_segwit_address(public_key_bytes)?);
+
+        Ok(())
+    }
+
     /// Derive wallet with specific BIP standard
     pub fn derive_with_bip(
         &self,
@@ -156,12 +224,43 @@ impl BitcoinHandler {
         // Generate Bitcoin address based on BIP standard
         let address = self.public_key_to_address_with_bip(&public_key_bytes, bip)?;
 
-        Ok(WalletKeys::new_simple(
+        let mut keys = WalletKeys::new_simple(
             hex::encode(&private_key_bytes),
             hex::encode(&public_key_bytes),
             address,
             derivation_path,
-        ))
+        );
+        if bip == BipStandard::Bip86 {
+            self.add_taproot_details(&mut keys, &public_key_bytes)?;
+        }
+        Ok(keys)
+    }
+
+    /// Same as `derive_with_bip`, for accounts backed by an xprv instead of a mnemonic
+    pub fn derive_from_master_key_with_bip(
+        &self,
+        master_key: &MasterKey,
+        account: u32,
+        address_index: u32,
+        bip: BipStandard,
+    ) -> Result<WalletKeys> {
+        let blockchain = SupportedBlockchain::Bitcoin;
+
+        if !blockchain.supports_bip(bip) {
+            return Err(anyhow::anyhow!("Bitcoin does not support {}", bip));
+        }
+
+        let derivation_path = blockchain.get_bip_derivation_path(bip, account, address_index)?;
+        let mut keys = self.derive_from_master_key(master_key, account, address_index, Some(&derivation_path))?;
+
+        let public_key_bytes = hex::decode(&keys.public_key)
+            .context("Invalid public key encoding")?;
+        keys.address = self.public_key_to_address_with_bip(&public_key_bytes, bip)?;
+        if bip == BipStandard::Bip86 {
+            self.add_taproot_details(&mut keys, &public_key_bytes)?;
+        }
+
+        Ok(keys)
     }
     
     /// Generate legacy P2PKH address (starts with 1)
@@ -190,6 +289,20 @@ impl BitcoinHandler {
     }
 }
 
+// BIP-340 x-only form of a compressed secp256k1 public key
+fn taproot_internal_key(public_key_bytes: &[u8]) -> Result<XOnlyPublicKey> {
+    let secp_pubkey = bitcoin::secp256k1::PublicKey::from_slice(public_key_bytes)
+        .context("Invalid secp256k1 public key format")?;
+    let (x_only, _parity) = secp_pubkey.x_only_public_key();
+    Ok(x_only)
+}
+
+impl BitcoinMessageSigner for BitcoinHandler {
+    fn message_blockchain(&self) -> SupportedBlockchain {
+        SupportedBlockchain::Bitcoin
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,4 +363,43 @@ mod tests {
         assert!(legacy.starts_with("1"));
         assert!(nested_segwit.starts_with("3"));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_watch_only_from_zpub() {
+        use crate::crypto::watch_only::WatchOnlyKey;
+
+        // BIP-84 test vector account 0 for "abandon ... about"
+        let zpub = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+        let key = WatchOnlyKey::new(SupportedBlockchain::Bitcoin, zpub, None).unwrap();
+
+        let handler = BitcoinHandler::new();
+        let public_key = key.derive_public_key(0, 0).unwrap();
+        let keys = handler.derive_from_public_key(&public_key, &key.derivation_path(0, 0)).unwrap();
+
+        assert!(keys.private_key.is_empty());
+        assert_eq!(keys.address, "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu");
+        assert_eq!(keys.derivation_path, "m/84'/0'/0'/0/0");
+    }
+
+    #[test]
+    fn test_bip86_taproot_vector() {
+        // BIP-86 test vector: m/86'/0'/0'/0/0 of "abandon ... about"
+        let handler = BitcoinHandler::new();
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        let keys = handler.derive_with_bip(mnemonic, None, 0, 0, BipStandard::Bip86).unwrap();
+        assert_eq!(keys.derivation_path, "m/86'/0'/0'/0/0");
+        assert_eq!(keys.address, "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr");
+        assert_eq!(
+            keys.additional_data["taproot_internal_key"],
+            "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115"
+        );
+        assert_eq!(
+            keys.additional_data["taproot_output_key"],
+            "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c"
+        );
+        assert!(keys.secondary_addresses["legacy"].starts_with('1'));
+        assert!(keys.secondary_addresses["nested_segwit"].starts_with('3'));
+        assert!(keys.secondary_addresses["native_segwit"].starts_with("bc1q"));
+    }
+}
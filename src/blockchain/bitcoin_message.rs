@@ -0,0 +1,675 @@
+use anyhow::{Result, Context, bail, anyhow};
+use alloy_primitives::B256;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use bitcoin::hashes::Hash;
+use bitcoin::key::{CompressedPublicKey, Keypair, PublicKey, TapTweak, XOnlyPublicKey};
+use bitcoin::opcodes;
+use bitcoin::script::{Builder, Instruction, PushBytes};
+use bitcoin::secp256k1::{Message, Secp256k1, SecretKey};
+use bitcoin::sighash::{EcdsaSighashType, Prevouts, SighashCache, TapSighashType};
+use bitcoin::{absolute, consensus, transaction, Address, Amount, Network, OutPoint, PubkeyHash, Script, ScriptBuf, ScriptHash, Sequence, Transaction, TxIn, TxOut, Txid, Witness};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+use crate::blockchain::SupportedBlockchain;
+use crate::blockchain::evm::sign_hash;
+
+/// Signature formats for arbitrary messages on Bitcoin-style chains
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitcoinMessageFormat {
+    /// `signmessage` compact signature, with a BIP-137 header for segwit addresses
+    Legacy,
+    /// BIP-322 simple: the witness stack of the virtual `to_sign` transaction
+    Bip322Simple,
+    /// BIP-322 full: the whole virtual `to_sign` transaction
+    Bip322Full,
+}
+
+impl BitcoinMessageFormat {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "legacy" | "bip137" => Ok(Self::Legacy),
+            "bip322" | "bip322-simple" | "simple" => Ok(Self::Bip322Simple),
+            "bip322-full" | "full" => Ok(Self::Bip322Full),
+            other => bail!("Unknown message signature format '{}'. Use legacy, bip322-simple or bip322-full", other),
+        }
+    }
+
+    /// Legacy, except for Taproot addresses that compact signatures cannot commit to
+    pub fn default_for(address: &str) -> Self {
+        if address.to_lowercase().starts_with("bc1p") {
+            Self::Bip322Simple
+        } else {
+            Self::Legacy
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Legacy => "legacy signmessage / BIP-137",
+            Self::Bip322Simple => "BIP-322 simple",
+            Self::Bip322Full => "BIP-322 full",
+        }
+    }
+}
+
+/// Address types a compact (legacy / BIP-137) signature can commit to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompactAddressType {
+    P2pkh,
+    P2shP2wpkh,
+    P2wpkh,
+}
+
+impl CompactAddressType {
+    const ALL: [Self; 3] = [Self::P2pkh, Self::P2shP2wpkh, Self::P2wpkh];
+
+    /// Header byte for recovery id 0 with a compressed key
+    /// Reference: https://github.com/bitcoin/bips/blob/master/bip-0137.mediawiki
+    fn header_base(self) -> u8 {
+        match self {
+            Self::P2pkh => 31,
+            Self::P2shP2wpkh => 35,
+            Self::P2wpkh => 39,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Self::P2pkh => "legacy signmessage (P2PKH)",
+            Self::P2shP2wpkh => "BIP-137 (P2SH-P2WPKH)",
+            Self::P2wpkh => "BIP-137 (P2WPKH)",
+        }
+    }
+
+    fn script(self, public_key: &PublicKey) -> Result<ScriptBuf> {
+        match self {
+            Self::P2pkh => Ok(ScriptBuf::new_p2pkh(&public_key.pubkey_hash())),
+            Self::P2shP2wpkh => {
+                let redeem_script = ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash()?);
+                Ok(ScriptBuf::new_p2sh(&redeem_script.script_hash()))
+            }
+            Self::P2wpkh => Ok(ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash()?)),
+        }
+    }
+}
+
+/// Prefix hashed in front of every signed message
+pub fn message_magic(blockchain: &SupportedBlockchain) -> &'static str {
+    match blockchain {
+        SupportedBlockchain::Litecoin => "Litecoin Signed Message:\n",
+        _ => "Bitcoin Signed Message:\n",
+    }
+}
+
+/// Double SHA-256 of `varint(len) || magic || varint(len) || message`, as used by `signmessage`
+pub fn signed_message_hash(blockchain: &SupportedBlockchain, message: &[u8]) -> [u8; 32] {
+    let magic = message_magic(blockchain).as_bytes();
+    let mut data = compact_size(magic.len());
+    data.extend_from_slice(magic);
+    data.extend_from_slice(&compact_size(message.len()));
+    data.extend_from_slice(message);
+
+    Sha256::digest(Sha256::digest(&data)).into()
+}
+
+/// BIP-340 tagged hash of the message, committed to by the BIP-322 `to_spend` transaction
+pub fn bip322_message_hash(message: &[u8]) -> [u8; 32] {
+    let tag = Sha256::digest(b"BIP0322-signed-message");
+    Sha256::new()
+        .chain_update(tag)
+        .chain_update(tag)
+        .chain_update(message)
+        .finalize()
+        .into()
+}
+
+/// scriptPubKey of a mainnet Bitcoin or Litecoin address
+pub fn address_script(blockchain: &SupportedBlockchain, address: &str) -> Result<ScriptBuf> {
+    let address = address.trim();
+    match blockchain {
+        SupportedBlockchain::Bitcoin => {
+            let parsed = Address::from_str(address)
+                .with_context(|| format!("Invalid Bitcoin address: {}", address))?
+                .require_network(Network::Bitcoin)
+                .with_context(|| format!("{} is not a Bitcoin mainnet address", address))?;
+            Ok(parsed.script_pubkey())
+        }
+        SupportedBlockchain::Litecoin => litecoin_address_script(address),
+        other => bail!("{} does not use Bitcoin-style addresses", other),
+    }
+}
+
+fn litecoin_address_script(address: &str) -> Result<ScriptBuf> {
+    if address.to_lowercase().starts_with("ltc1") {
+        let (hrp, version, program) = bech32::segwit::decode(address)
+            .with_context(|| format!("Invalid Litecoin address: {}", address))?;
+        if !hrp.as_str().eq_ignore_ascii_case("ltc") {
+            bail!("{} is not a Litecoin mainnet address", address);
+        }
+        let version = version.to_u8();
+        let mut bytes = vec![if version == 0 { 0x00 } else { 0x50 + version }, program.len() as u8];
+        bytes.extend_from_slice(&program);
+        return Ok(ScriptBuf::from_bytes(bytes));
+    }
+
+    let payload = bitcoin::base58::decode_check(address)
+        .with_context(|| format!("Invalid Litecoin address: {}", address))?;
+    if payload.len() != 21 {
+        bail!("Invalid Litecoin address length: {}", address);
+    }
+    let mut hash = [0u8; 20];
+    hash.copy_from_slice(&payload[1..]);
+    match payload[0] {
+        0x30 => Ok(ScriptBuf::new_p2pkh(&PubkeyHash::from_byte_array(hash))),
+        // 0x05 is the deprecated '3' prefix Litecoin used for P2SH before switching to 'M'
+        0x32 | 0x05 => Ok(ScriptBuf::new_p2sh(&ScriptHash::from_byte_array(hash))),
+        version => bail!("Unknown Litecoin address version byte 0x{:02x}", version),
+    }
+}
+
+// ========== LEGACY / BIP-137 ==========
+
+fn sign_compact(blockchain: &SupportedBlockchain, private_key: &[u8], script_pubkey: &Script, message: &[u8]) -> Result<[u8; 65]> {
+    let signing_key = SigningKey::from_slice(private_key)
+        .context("Invalid secp256k1 private key")?;
+    let public_key = PublicKey::from_slice(signing_key.verifying_key().to_encoded_point(true).as_bytes())
+        .context("Invalid secp256k1 public key")?;
+
+    let address_type = CompactAddressType::ALL
+        .into_iter()
+        .find(|address_type| address_type.script(&public_key).map_or(false, |script| script.as_script() == script_pubkey))
+        .with_context(|| if script_pubkey.is_p2tr() {
+            "Legacy message signatures cannot commit to Taproot addresses; use a BIP-322 format"
+        } else {
+            "The private key does not belong to this address"
+        })?;
+
+    let digest = signed_message_hash(blockchain, message);
+    let (signature, recovery_id) = sign_hash(&signing_key, &B256::from(digest))?;
+
+    let mut bytes = [0u8; 65];
+    bytes[0] = address_type.header_base() + recovery_id;
+    bytes[1..].copy_from_slice(&signature);
+    Ok(bytes)
+}
+
+fn verify_compact(blockchain: &SupportedBlockchain, script_pubkey: &Script, message: &[u8], signature: &[u8]) -> Result<String> {
+    let header = signature[0];
+    let (address_type, compressed, recovery_id) = match header {
+        27..=30 => (CompactAddressType::P2pkh, false, header - 27),
+        31..=34 => (CompactAddressType::P2pkh, true, header - 31),
+        35..=38 => (CompactAddressType::P2shP2wpkh, true, header - 35),
+        39..=42 => (CompactAddressType::P2wpkh, true, header - 39),
+        _ => bail!("Invalid signature header byte {}", header),
+    };
+
+    let digest = signed_message_hash(blockchain, message);
+    let signature = Signature::from_slice(&signature[1..]).context("Invalid signature encoding")?;
+    let recovery_id = RecoveryId::from_byte(recovery_id).context("Invalid recovery id")?;
+    let recovered = VerifyingKey::recover_from_prehash(&digest, &signature, recovery_id)
+        .map_err(|_| anyhow!("No public key can be recovered from this signature"))?;
+    let public_key = PublicKey::from_slice(recovered.to_encoded_point(compressed).as_bytes())
+        .context("Invalid recovered public key")?;
+
+    if address_type.script(&public_key)?.as_script() == script_pubkey {
+        return Ok(address_type.describe().to_string());
+    }
+
+    // Electrum and several hardware wallets write the P2PKH header for every address type
+    if compressed && address_type == CompactAddressType::P2pkh {
+        for other in [CompactAddressType::P2shP2wpkh, CompactAddressType::P2wpkh] {
+            if other.script(&public_key)?.as_script() == script_pubkey {
+                return Ok(format!("{} with a P2PKH header", other.describe()));
+            }
+        }
+    }
+
+    bail!("The signature was not made by this address")
+}
+
+// ========== BIP-322 ==========
+// Reference: https://github.com/bitcoin/bips/blob/master/bip-0322.mediawiki
+
+fn bip322_to_spend(script_pubkey: &Script, message: &[u8]) -> Transaction {
+    let script_sig = Builder::new()
+        .push_opcode(opcodes::OP_0)
+        .push_slice(bip322_message_hash(message))
+        .into_script();
+
+    Transaction {
+        version: transaction::Version(0),
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint { txid: Txid::all_zeros(), vout: 0xFFFF_FFFF },
+            script_sig,
+            sequence: Sequence::ZERO,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut { value: Amount::ZERO, script_pubkey: script_pubkey.to_owned() }],
+    }
+}
+
+fn bip322_to_sign(to_spend: &Transaction, witness: Witness) -> Transaction {
+    Transaction {
+        version: transaction::Version(0),
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint { txid: to_spend.compute_txid(), vout: 0 },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ZERO,
+            witness,
+        }],
+        output: vec![TxOut {
+            value: Amount::ZERO,
+            script_pubkey: Builder::new().push_opcode(opcodes::all::OP_RETURN).into_script(),
+        }],
+    }
+}
+
+/// scriptSig of a P2SH-P2WPKH spend: a single push of the P2WPKH redeem script
+fn p2sh_p2wpkh_script_sig(wpkh_script: &Script) -> Result<ScriptBuf> {
+    let redeem_script = <&PushBytes>::try_from(wpkh_script.as_bytes())
+        .map_err(|_| anyhow!("Redeem script is too long to push"))?;
+    Ok(Builder::new().push_slice(redeem_script).into_script())
+}
+
+/// `to_sign` for a BIP-322 simple signature; P2SH-P2WPKH also needs the redeem script, rebuilt from the witness key
+fn bip322_simple_to_sign(script_pubkey: &Script, to_spend: &Transaction, witness: Witness) -> Result<Transaction> {
+    let mut to_sign = bip322_to_sign(to_spend, witness);
+    if script_pubkey.is_p2sh() {
+        let witness = &to_sign.input[0].witness;
+        let public_key = witness.nth(1)
+            .filter(|_| witness.len() == 2)
+            .context("A P2SH-P2WPKH witness holds a signature and a public key")?;
+        let public_key = CompressedPublicKey::from_slice(public_key).context("Invalid public key in witness")?;
+        to_sign.input[0].script_sig = p2sh_p2wpkh_script_sig(&ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash()))?;
+    }
+    Ok(to_sign)
+}
+
+/// Signs the virtual `to_sign` transaction for a P2PKH, P2SH-P2WPKH, P2WPKH or key-path P2TR address
+fn sign_bip322(private_key: &[u8], script_pubkey: &Script, message: &[u8]) -> Result<Transaction> {
+    let secp = Secp256k1::new();
+    let secret_key = SecretKey::from_slice(private_key).context("Invalid secp256k1 private key")?;
+    let to_spend = bip322_to_spend(script_pubkey, message);
+    let mut to_sign = bip322_to_sign(&to_spend, Witness::new());
+    let public_key = CompressedPublicKey(secret_key.public_key(&secp));
+
+    if script_pubkey.is_p2wpkh() || script_pubkey.is_p2sh() {
+        let wpkh_script = ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash());
+        if script_pubkey.is_p2sh() {
+            if ScriptBuf::new_p2sh(&wpkh_script.script_hash()).as_script() != script_pubkey {
+                bail!("The private key does not belong to this address (only P2SH-P2WPKH is supported for P2SH)");
+            }
+            to_sign.input[0].script_sig = p2sh_p2wpkh_script_sig(&wpkh_script)?;
+        } else if wpkh_script.as_script() != script_pubkey {
+            bail!("The private key does not belong to this address");
+        }
+
+        let sighash = SighashCache::new(&to_sign)
+            .p2wpkh_signature_hash(0, &wpkh_script, Amount::ZERO, EcdsaSighashType::All)?;
+        // Low-R grinding keeps signatures byte-identical to Bitcoin Core's
+        let signature = secp.sign_ecdsa_low_r(&Message::from_digest(sighash.to_byte_array()), &secret_key);
+        to_sign.input[0].witness = Witness::p2wpkh(&bitcoin::ecdsa::Signature::sighash_all(signature), &public_key.0);
+    } else if script_pubkey.is_p2pkh() {
+        let public_key = PublicKey::from(public_key);
+        if ScriptBuf::new_p2pkh(&public_key.pubkey_hash()).as_script() != script_pubkey {
+            bail!("The private key does not belong to this address");
+        }
+
+        let sighash = SighashCache::new(&to_sign)
+            .legacy_signature_hash(0, script_pubkey, EcdsaSighashType::All.to_u32())?;
+        let signature = secp.sign_ecdsa_low_r(&Message::from_digest(sighash.to_byte_array()), &secret_key);
+        to_sign.input[0].script_sig = Builder::new()
+            .push_slice(bitcoin::ecdsa::Signature::sighash_all(signature).serialize())
+            .push_key(&public_key)
+            .into_script();
+    } else if script_pubkey.is_p2tr() {
+        let keypair = Keypair::from_secret_key(&secp, &secret_key).tap_tweak(&secp, None).to_inner();
+        let (output_key, _parity) = keypair.x_only_public_key();
+        if script_pubkey.as_bytes()[2..] != output_key.serialize() {
+            bail!("The private key does not belong to this address");
+        }
+
+        let prevouts = [to_spend.output[0].clone()];
+        let sighash = SighashCache::new(&to_sign)
+            .taproot_key_spend_signature_hash(0, &Prevouts::All(&prevouts), TapSighashType::Default)?;
+        let signature = secp.sign_schnorr_with_aux_rand(
+            &Message::from_digest(sighash.to_byte_array()),
+            &keypair,
+            &rand::random::<[u8; 32]>(),
+        );
+        to_sign.input[0].witness = Witness::p2tr_key_spend(&bitcoin::taproot::Signature { signature, sighash_type: TapSighashType::Default });
+    } else {
+        bail!("BIP-322 signing supports P2PKH, P2SH-P2WPKH, P2WPKH and Taproot addresses");
+    }
+
+    Ok(to_sign)
+}
+
+fn verify_bip322(script_pubkey: &Script, to_spend: &Transaction, to_sign: &Transaction) -> Result<()> {
+    let secp = Secp256k1::verification_only();
+    let witness = &to_sign.input[0].witness;
+    let script_sig = &to_sign.input[0].script_sig;
+    let mut cache = SighashCache::new(to_sign);
+
+    if script_pubkey.is_p2wpkh() || script_pubkey.is_p2sh() {
+        if witness.len() != 2 {
+            bail!("A P2WPKH witness holds a signature and a public key, found {} item(s)", witness.len());
+        }
+        let signature = bitcoin::ecdsa::Signature::from_slice(&witness[0])
+            .context("Invalid ECDSA signature in witness")?;
+        let public_key = CompressedPublicKey::from_slice(&witness[1])
+            .context("Invalid public key in witness")?;
+        let wpkh_script = ScriptBuf::new_p2wpkh(&public_key.wpubkey_hash());
+        if script_pubkey.is_p2sh() {
+            if ScriptBuf::new_p2sh(&wpkh_script.script_hash()).as_script() != script_pubkey {
+                bail!("The witness public key does not belong to this address (only P2SH-P2WPKH is supported for P2SH)");
+            }
+            if *script_sig != p2sh_p2wpkh_script_sig(&wpkh_script)? {
+                bail!("The scriptSig does not push the P2WPKH redeem script");
+            }
+        } else if wpkh_script.as_script() != script_pubkey {
+            bail!("The witness public key does not belong to this address");
+        }
+
+        let sighash = cache.p2wpkh_signature_hash(0, &wpkh_script, Amount::ZERO, signature.sighash_type)?;
+        secp.verify_ecdsa(&Message::from_digest(sighash.to_byte_array()), &signature.signature, &public_key.0)
+            .map_err(|_| anyhow!("The signature was not made by this address"))?;
+    } else if script_pubkey.is_p2pkh() {
+        // Legacy spends carry the signature and key in the scriptSig, so only the full format applies
+        if !witness.is_empty() {
+            bail!("P2PKH BIP-322 signatures use the full format with an empty witness");
+        }
+        let pushes = script_sig.instructions()
+            .map(|instruction| match instruction {
+                Ok(Instruction::PushBytes(bytes)) => Ok(bytes.as_bytes().to_vec()),
+                _ => Err(anyhow!("The scriptSig must only push a signature and a public key")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if pushes.len() != 2 {
+            bail!("A P2PKH scriptSig holds a signature and a public key, found {} item(s)", pushes.len());
+        }
+        let signature = bitcoin::ecdsa::Signature::from_slice(&pushes[0])
+            .context("Invalid ECDSA signature in scriptSig")?;
+        let public_key = PublicKey::from_slice(&pushes[1]).context("Invalid public key in scriptSig")?;
+        if ScriptBuf::new_p2pkh(&public_key.pubkey_hash()).as_script() != script_pubkey {
+            bail!("The scriptSig public key does not belong to this address");
+        }
+
+        let sighash = cache.legacy_signature_hash(0, script_pubkey, signature.sighash_type.to_u32())?;
+        secp.verify_ecdsa(&Message::from_digest(sighash.to_byte_array()), &signature.signature, &public_key.inner)
+            .map_err(|_| anyhow!("The signature was not made by this address"))?;
+    } else if script_pubkey.is_p2tr() {
+        if witness.len() != 1 {
+            bail!("Only Taproot key-path signatures are supported, found {} witness item(s)", witness.len());
+        }
+        let signature = bitcoin::taproot::Signature::from_slice(&witness[0])
+            .context("Invalid Schnorr signature in witness")?;
+        let output_key = XOnlyPublicKey::from_slice(&script_pubkey.as_bytes()[2..])
+            .context("Invalid Taproot output key")?;
+
+        let prevouts = [to_spend.output[0].clone()];
+        let sighash = cache.taproot_key_spend_signature_hash(0, &Prevouts::All(&prevouts), signature.sighash_type)?;
+        secp.verify_schnorr(&signature.signature, &Message::from_digest(sighash.to_byte_array()), &output_key)
+            .map_err(|_| anyhow!("The signature was not made by this address"))?;
+    } else {
+        bail!("BIP-322 verification supports P2PKH, P2SH-P2WPKH, P2WPKH and Taproot addresses");
+    }
+
+    Ok(())
+}
+
+// ========== HANDLER INTEGRATION ==========
+
+/// Message signing shared by the Bitcoin-style handlers (Bitcoin, Litecoin).
+/// Implementors only name their chain, which decides the magic prefix and address encoding.
+pub trait BitcoinMessageSigner {
+    fn message_blockchain(&self) -> SupportedBlockchain;
+
+    /// Signs `message` for `address` and returns the base64 signature
+    fn sign_bitcoin_message(&self, private_key: &[u8], address: &str, message: &[u8], format: BitcoinMessageFormat) -> Result<String> {
+        let blockchain = self.message_blockchain();
+        let script_pubkey = address_script(&blockchain, address)?;
+
+        match format {
+            BitcoinMessageFormat::Legacy => {
+                Ok(BASE64.encode(sign_compact(&blockchain, private_key, &script_pubkey, message)?))
+            }
+            BitcoinMessageFormat::Bip322Simple | BitcoinMessageFormat::Bip322Full => {
+                if blockchain != SupportedBlockchain::Bitcoin {
+                    bail!("BIP-322 signatures are only defined for Bitcoin addresses; use the legacy format for {}", blockchain);
+                }
+                if format == BitcoinMessageFormat::Bip322Simple && script_pubkey.is_p2pkh() {
+                    bail!("P2PKH addresses have no BIP-322 simple form; use bip322-full or legacy");
+                }
+                let to_sign = sign_bip322(private_key, &script_pubkey, message)?;
+                let bytes = if format == BitcoinMessageFormat::Bip322Simple {
+                    consensus::serialize(&to_sign.input[0].witness)
+                } else {
+                    consensus::serialize(&to_sign)
+                };
+                Ok(BASE64.encode(bytes))
+            }
+        }
+    }
+
+    /// Checks a base64 signature (legacy, BIP-137 or BIP-322) against any address of the chain.
+    /// Returns the scheme that verified; any failure is an error explaining why.
+    fn verify_bitcoin_message(&self, address: &str, message: &[u8], signature: &str) -> Result<String> {
+        let blockchain = self.message_blockchain();
+        let script_pubkey = address_script(&blockchain, address)?;
+        let bytes = BASE64.decode(signature.trim()).context("Signature is not valid base64")?;
+
+        if bytes.len() == 65 && (27..=42).contains(&bytes[0]) {
+            return verify_compact(&blockchain, &script_pubkey, message, &bytes);
+        }
+        if blockchain != SupportedBlockchain::Bitcoin {
+            bail!("Expected a 65-byte compact signature for a {} address", blockchain);
+        }
+
+        let to_spend = bip322_to_spend(&script_pubkey, message);
+        if let Ok(witness) = consensus::deserialize::<Witness>(&bytes) {
+            verify_bip322(&script_pubkey, &to_spend, &bip322_simple_to_sign(&script_pubkey, &to_spend, witness)?)?;
+            return Ok(BitcoinMessageFormat::Bip322Simple.describe().to_string());
+        }
+
+        let to_sign: Transaction = consensus::deserialize(&bytes)
+            .context("Signature is neither a compact signature nor a BIP-322 witness or transaction")?;
+        let spends_to_spend = to_sign.input.len() == 1
+            && to_sign.input[0].previous_output == OutPoint { txid: to_spend.compute_txid(), vout: 0 };
+        let burns_to_op_return = to_sign.output.len() == 1 && to_sign.output[0].script_pubkey.is_op_return();
+        if !spends_to_spend || !burns_to_op_return {
+            bail!("The transaction is not a BIP-322 to_sign transaction for this message and address");
+        }
+        verify_bip322(&script_pubkey, &to_spend, &to_sign)?;
+        Ok(BitcoinMessageFormat::Bip322Full.describe().to_string())
+    }
+}
+
+pub fn get_bitcoin_message_signer(blockchain: &SupportedBlockchain) -> Option<Box<dyn BitcoinMessageSigner>> {
+    Some(match blockchain {
+        SupportedBlockchain::Bitcoin => Box::new(crate::blockchain::bitcoin::BitcoinHandler::new()),
+        SupportedBlockchain::Litecoin => Box::new(crate::blockchain::litecoin::LitecoinHandler::new()),
+        _ => return None,
+    })
+}
+
+/// Bitcoin CompactSize length prefix
+fn compact_size(length: usize) -> Vec<u8> {
+    match length {
+        0..=0xfc => vec![length as u8],
+        0xfd..=0xffff => {
+            let mut bytes = vec![0xfd];
+            bytes.extend_from_slice(&(length as u16).to_le_bytes());
+            bytes
+        }
+        _ => {
+            let mut bytes = vec![0xfe];
+            bytes.extend_from_slice(&(length as u32).to_le_bytes());
+            bytes
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::psbt::script_address;
+    use bitcoin::PrivateKey;
+
+    // Test key from https://github.com/bitcoin/bips/blob/master/bip-0322.mediawiki
+    const WIF: &str = "L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k";
+    const SEGWIT_ADDRESS: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+
+    fn test_key() -> (Vec<u8>, PublicKey) {
+        let private_key = PrivateKey::from_wif(WIF).unwrap();
+        let public_key = private_key.public_key(&Secp256k1::new());
+        (private_key.inner.secret_bytes().to_vec(), public_key)
+    }
+
+    fn signer(blockchain: SupportedBlockchain) -> Box<dyn BitcoinMessageSigner> {
+        get_bitcoin_message_signer(&blockchain).unwrap()
+    }
+
+    #[test]
+    fn test_bip322_vectors() {
+        assert_eq!(
+            hex::encode(bip322_message_hash(b"")),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            hex::encode(bip322_message_hash(b"Hello World")),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+
+        let script_pubkey = address_script(&SupportedBlockchain::Bitcoin, SEGWIT_ADDRESS).unwrap();
+        assert_eq!(
+            bip322_to_spend(&script_pubkey, b"").compute_txid().to_string(),
+            "c5680aa69bb8d860bf82d4e9cd3504b55dde018de765a91bb566283c545a99a7"
+        );
+        assert_eq!(
+            bip322_to_spend(&script_pubkey, b"Hello World").compute_txid().to_string(),
+            "b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b"
+        );
+
+        let bitcoin = signer(SupportedBlockchain::Bitcoin);
+        let empty = "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+        let hello = "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+        assert_eq!(bitcoin.verify_bitcoin_message(SEGWIT_ADDRESS, b"", empty).unwrap(), "BIP-322 simple");
+        assert_eq!(bitcoin.verify_bitcoin_message(SEGWIT_ADDRESS, b"Hello World", hello).unwrap(), "BIP-322 simple");
+        assert!(bitcoin.verify_bitcoin_message(SEGWIT_ADDRESS, b"Hello World", empty).is_err());
+    }
+
+    #[test]
+    fn test_bip322_round_trip() {
+        let (private_key, public_key) = test_key();
+        let secp = Secp256k1::new();
+        let (internal_key, _parity) = public_key.inner.x_only_public_key();
+        let taproot_address = Address::p2tr(&secp, internal_key, None, Network::Bitcoin).to_string();
+        let bitcoin = signer(SupportedBlockchain::Bitcoin);
+
+        for address in [SEGWIT_ADDRESS, taproot_address.as_str()] {
+            for format in [BitcoinMessageFormat::Bip322Simple, BitcoinMessageFormat::Bip322Full] {
+                let signature = bitcoin.sign_bitcoin_message(&private_key, address, b"Hello World", format).unwrap();
+                assert_eq!(bitcoin.verify_bitcoin_message(address, b"Hello World", &signature).unwrap(), format.describe());
+                assert!(bitcoin.verify_bitcoin_message(address, b"Hello World!", &signature).is_err());
+            }
+        }
+
+        // P2SH-P2WPKH supports both forms; P2PKH only the full transaction
+        let compressed = CompressedPublicKey::try_from(public_key).unwrap();
+        let nested = Address::p2shwpkh(&compressed, Network::Bitcoin).to_string();
+        let legacy = Address::p2pkh(&public_key, Network::Bitcoin).to_string();
+        let cases = [
+            (nested.as_str(), BitcoinMessageFormat::Bip322Simple),
+            (nested.as_str(), BitcoinMessageFormat::Bip322Full),
+            (legacy.as_str(), BitcoinMessageFormat::Bip322Full),
+        ];
+        for (address, format) in cases {
+            let signature = bitcoin.sign_bitcoin_message(&private_key, address, b"Hello World", format).unwrap();
+            assert_eq!(bitcoin.verify_bitcoin_message(address, b"Hello World", &signature).unwrap(), format.describe());
+            assert!(bitcoin.verify_bitcoin_message(address, b"Hello World!", &signature).is_err());
+        }
+        assert!(bitcoin.sign_bitcoin_message(&private_key, &legacy, b"x", BitcoinMessageFormat::Bip322Simple).is_err());
+
+        // Another key cannot sign for the address
+        let other_key = [0x11u8; 32];
+        assert!(bitcoin.sign_bitcoin_message(&other_key, SEGWIT_ADDRESS, b"x", BitcoinMessageFormat::Bip322Simple).is_err());
+        assert!(bitcoin.sign_bitcoin_message(&other_key, &legacy, b"x", BitcoinMessageFormat::Bip322Full).is_err());
+    }
+
+    #[test]
+    fn test_legacy_and_bip137_round_trip() {
+        let (private_key, public_key) = test_key();
+        let compressed = CompressedPublicKey::try_from(public_key).unwrap();
+        let bitcoin = signer(SupportedBlockchain::Bitcoin);
+
+        let cases = [
+            (Address::p2pkh(&public_key, Network::Bitcoin).to_string(), 31..=34, "legacy signmessage (P2PKH)"),
+            (Address::p2shwpkh(&compressed, Network::Bitcoin).to_string(), 35..=38, "BIP-137 (P2SH-P2WPKH)"),
+            (Address::p2wpkh(&compressed, Network::Bitcoin).to_string(), 39..=42, "BIP-137 (P2WPKH)"),
+        ];
+        for (address, headers, scheme) in cases {
+            let signature = bitcoin.sign_bitcoin_message(&private_key, &address, b"Hello World", BitcoinMessageFormat::Legacy).unwrap();
+            let bytes = BASE64.decode(&signature).unwrap();
+            assert_eq!(bytes.len(), 65);
+            assert!(headers.contains(&bytes[0]));
+            assert_eq!(bitcoin.verify_bitcoin_message(&address, b"Hello World", &signature).unwrap(), scheme);
+            assert!(bitcoin.verify_bitcoin_message(&address, b"Hello", &signature).is_err());
+        }
+
+        // Electrum-style: P2PKH header on a segwit address
+        let mut bytes = BASE64.decode(
+            bitcoin.sign_bitcoin_message(&private_key, SEGWIT_ADDRESS, b"Hello World", BitcoinMessageFormat::Legacy).unwrap()
+        ).unwrap();
+        bytes[0] -= 39 - 31;
+        let scheme = bitcoin.verify_bitcoin_message(SEGWIT_ADDRESS, b"Hello World", &BASE64.encode(&bytes)).unwrap();
+        assert_eq!(scheme, "BIP-137 (P2WPKH) with a P2PKH header");
+    }
+
+    #[test]
+    fn test_litecoin_messages() {
+        let (private_key, public_key) = test_key();
+        let script = ScriptBuf::new_p2pkh(&public_key.pubkey_hash());
+        let address = script_address(&script, &SupportedBlockchain::Litecoin).unwrap();
+        assert!(address.starts_with('L'));
+        assert_eq!(address_script(&SupportedBlockchain::Litecoin, &address).unwrap(), script);
+
+        let litecoin = signer(SupportedBlockchain::Litecoin);
+        let signature = litecoin.sign_bitcoin_message(&private_key, &address, b"Hello World", BitcoinMessageFormat::Legacy).unwrap();
+        assert!(litecoin.verify_bitcoin_message(&address, b"Hello World", &signature).is_ok());
+        assert!(litecoin.sign_bitcoin_message(&private_key, &address, b"x", BitcoinMessageFormat::Bip322Simple).is_err());
+
+        // The magic prefix differs, so a Bitcoin signature does not verify as Litecoin
+        assert_ne!(
+            signed_message_hash(&SupportedBlockchain::Bitcoin, b"Hello World"),
+            signed_message_hash(&SupportedBlockchain::Litecoin, b"Hello World")
+        );
+        let bitcoin_address = Address::p2pkh(&public_key, Network::Bitcoin).to_string();
+        let bitcoin_signature = signer(SupportedBlockchain::Bitcoin)
+            .sign_bitcoin_message(&private_key, &bitcoin_address, b"Hello World", BitcoinMessageFormat::Legacy)
+            .unwrap();
+        assert!(litecoin.verify_bitcoin_message(&address, b"Hello World", &bitcoin_signature).is_err());
+
+        let segwit = CompressedPublicKey::try_from(public_key).unwrap();
+        let segwit_script = ScriptBuf::new_p2wpkh(&segwit.wpubkey_hash());
+        let segwit_address = script_address(&segwit_script, &SupportedBlockchain::Litecoin).unwrap();
+        assert!(segwit_address.starts_with("ltc1q"));
+        assert_eq!(address_script(&SupportedBlockchain::Litecoin, &segwit_address).unwrap(), segwit_script);
+    }
+
+    #[test]
+    fn test_format_and_compact_size() {
+        assert_eq!(BitcoinMessageFormat::parse("BIP322").unwrap(), BitcoinMessageFormat::Bip322Simple);
+        assert_eq!(BitcoinMessageFormat::parse("bip137").unwrap(), BitcoinMessageFormat::Legacy);
+        assert!(BitcoinMessageFormat::parse("electrum").is_err());
+        assert_eq!(compact_size(0xfc), vec![0xfc]);
+        assert_eq!(compact_size(0xfd), vec![0xfd, 0xfd, 0x00]);
+        assert!(get_bitcoin_message_signer(&SupportedBlockchain::Ethereum).is_none());
+    }
+}
@@ -1,15 +1,59 @@
-use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain};
+use anyhow::{Result, Context, bail};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain, SigningCapabilities, SignedTransaction};
+use crate::blockchain::cardano_tx::{
+    address_credentials, key_hash, sign_cip8_message, signing_key, verify_cip8_message, CardanoTransaction,
+    DataSignature,
+};
 use crate::crypto::ed25519_utils::{derive_cardano_key_from_mnemonic, private_key_to_public_key_ed25519};
 use cardano_serialization_lib::{
-    BaseAddress, EnterpriseAddress, Credential, PublicKey as CardanoPublicKey
+    BaseAddress, EnterpriseAddress, Credential, PrivateKey, PublicKey as CardanoPublicKey, Vkeywitnesses
 };
 
-pub struct CardanoHandler;
+pub struct CardanoHandler {
+    /// Stake key (role 2) for transactions that need its witness; mnemonic wallets only store the payment key
+    stake_key: Option<Vec<u8>>,
+}
 
 impl CardanoHandler {
     pub fn new() -> Self {
-        Self
+        Self { stake_key: None }
+    }
+
+    pub fn with_stake_key(stake_key: Vec<u8>) -> Self {
+        Self { stake_key: Some(stake_key) }
+    }
+
+    /// Vkey witnesses of the payment key and, when certificates, withdrawals or required
+    /// signers name the address's stake credential, of the stake key
+    pub fn witness_transaction(&self, private_key: &[u8], address: &str, transaction: &CardanoTransaction) -> Result<Vkeywitnesses> {
+        let credentials = address_credentials(address)?;
+        let mut keys = vec![self.payment_key(private_key, address)?];
+
+        if let Some(stake_hash) = credentials.stake.filter(|hash| transaction.requires_stake_witness(hash)) {
+            // Standalone imports use one key for both credentials, so the payment witness covers it
+            if credentials.payment.as_ref() != Some(&stake_hash) {
+                let stake_key = match &self.stake_key {
+                    Some(stake_key) => signing_key(stake_key)?,
+                    None => bail!(
+                        "This transaction also needs the stake key's witness; use 'wallet-backup sign-cardano-tx', which re-derives it from the account mnemonic"
+                    ),
+                };
+                if key_hash(&stake_key.to_public().as_bytes()) != stake_hash {
+                    bail!("Stake key does not belong to {}", address);
+                }
+                keys.push(stake_key);
+            }
+        }
+
+        Ok(transaction.witness(&keys))
+    }
+
+    fn payment_key(&self, private_key: &[u8], address: &str) -> Result<PrivateKey> {
+        let key = signing_key(private_key)?;
+        if address_credentials(address)?.payment != Some(key_hash(&key.to_public().as_bytes())) {
+            bail!("Private key does not belong to {}", address);
+        }
+        Ok(key)
     }
 
     fn generate_base_address(&self, payment_public_key: &[u8], stake_public_key: &[u8]) -> Result<String> {
@@ -161,6 +205,54 @@ impl BlockchainHandler for CardanoHandler {
     fn get_blockchain_name(&self) -> &'static str {
         "Cardano"
     }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities::ALL
+    }
+
+    fn sign_message(&self, private_key: &[u8], address: &str, message: &[u8], format: Option<&str>) -> Result<String> {
+        check_message_format(format)?;
+        let key = self.payment_key(private_key, address)?;
+        let address_bytes = address_credentials(address)?.bytes;
+        Ok(sign_cip8_message(&key, &address_bytes, message).to_json())
+    }
+
+    fn verify_message(&self, address: &str, message: &[u8], signature: &str, format: Option<&str>) -> Result<String> {
+        check_message_format(format)?;
+        verify_cip8_message(address, message, &DataSignature::from_json(signature)?)?;
+        Ok("CIP-8 COSE_Sign1 (CIP-30 signData)".to_string())
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        CardanoTransaction::parse(transaction)?.details()
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        let transaction = CardanoTransaction::parse(transaction)?;
+        let witnesses = self.witness_transaction(private_key, address, &transaction)?;
+        Ok(SignedTransaction {
+            encoded: hex::encode(transaction.signed(&witnesses)?),
+            hash: Some(hex::encode(transaction.hash())),
+        })
+    }
+}
+
+/// CIP-8 (as used by CIP-30 signData) is the only Cardano message format
+fn check_message_format(format: Option<&str>) -> Result<()> {
+    match format.map(str::to_lowercase).as_deref() {
+        None | Some("cip8") | Some("cip-8") | Some("cip30") | Some("cip-30") => Ok(()),
+        Some(other) => bail!("Unknown Cardano message format '{}'. Cardano messages use CIP-8", other),
+    }
+}
+
+/// CIP-1852 stake key path (role 2) belonging to a payment key path m/1852'/1815'/account'/0/index
+pub fn stake_key_path(payment_path: &str) -> Result<String> {
+    let mut components: Vec<&str> = payment_path.trim().split('/').collect();
+    if components.len() != 6 || components[1] != "1852'" || components[4] != "0" {
+        bail!("{} is not a CIP-1852 payment key path", payment_path);
+    }
+    components[4] = "2";
+    Ok(components.join("/"))
 }
 
 #[cfg(test)]
@@ -207,4 +299,65 @@ mod tests {
         assert_eq!(wallet_keys.private_key.len(), 64); // 32 bytes as hex
         assert_eq!(wallet_keys.public_key.len(), 64); // 32 bytes as hex
     }
+
+    #[test]
+    fn test_stake_key_path() {
+        assert_eq!(stake_key_path("m/1852'/1815'/0'/0/3").unwrap(), "m/1852'/1815'/0'/2/3");
+        assert!(stake_key_path("m/1852'/1815'/0'/2/0").is_err());
+        assert!(stake_key_path("m/44'/1815'/0'/0/0").is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_wallet_stake_witness() {
+        let handler = CardanoHandler::new();
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let wallet_keys = handler.derive_from_mnemonic(mnemonic, None, 0, 0, None).unwrap();
+        let payment_key = hex::decode(&wallet_keys.private_key).unwrap();
+        assert_eq!(payment_key.len(), 64);
+
+        // Withdraw the rewards of the wallet's own stake credential
+        let stake_hash = address_credentials(&wallet_keys.address).unwrap().stake.unwrap();
+        let body = format!(
+            "a40081825820{}000180021a0002981005a1581de1{}1a004c4b40",
+            "aa".repeat(32),
+            hex::encode(&stake_hash)
+        );
+        let transaction = CardanoTransaction::parse(body.as_bytes()).unwrap();
+        assert!(transaction.requires_stake_witness(&stake_hash));
+
+        // Without the stake key the handler refuses rather than producing an incomplete witness set
+        assert!(handler.sign_transaction(&payment_key, &wallet_keys.address, body.as_bytes()).is_err());
+
+        let stake_path = stake_key_path(&wallet_keys.derivation_path).unwrap();
+        let (stake_key, _) = derive_cardano_key_from_mnemonic(mnemonic, None, &stake_path).unwrap();
+        let witnesses = CardanoHandler::with_stake_key(stake_key)
+            .witness_transaction(&payment_key, &wallet_keys.address, &transaction)
+            .unwrap();
+        assert_eq!(witnesses.len(), 2);
+        assert_eq!(key_hash(&witnesses.get(1).vkey().public_key().as_bytes()), stake_hash);
+
+        // A wrong stake key is caught before signing
+        let (wrong_key, _) = derive_cardano_key_from_mnemonic(mnemonic, None, "m/1852'/1815'/1'/2/0").unwrap();
+        assert!(CardanoHandler::with_stake_key(wrong_key)
+            .witness_transaction(&payment_key, &wallet_keys.address, &transaction)
+            .is_err());
+    }
+
+    #[test]
+    fn test_cip8_message_round_trip() {
+        let handler = CardanoHandler::new();
+        let private_key = "1e99423a4ed27608a15a2616a2b0e9e52ced330ac530edcc32c8ffc6a526aedd";
+        let wallet_keys = handler.derive_from_private_key(private_key).unwrap();
+        let key_bytes = hex::decode(private_key).unwrap();
+
+        let signature = handler.sign_message(&key_bytes, &wallet_keys.address, b"hello cardano", None).unwrap();
+        let scheme = handler.verify_message(&wallet_keys.address, b"hello cardano", &signature, None).unwrap();
+        assert!(scheme.starts_with("CIP-8"));
+        assert!(handler.verify_message(&wallet_keys.address, b"hello", &signature, None).is_err());
+        assert!(handler.sign_message(&key_bytes, &wallet_keys.address, b"hi", Some("eip191")).is_err());
+
+        // The key must match the address it signs for
+        let other = handler.derive_from_private_key("2e99423a4ed27608a15a2616a2b0e9e52ced330ac530edcc32c8ffc6a526aedd").unwrap();
+        assert!(handler.sign_message(&key_bytes, &other.address, b"hello cardano", None).is_err());
+    }
 }
\ No newline at end of file
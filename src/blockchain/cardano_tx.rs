@@ -0,0 +1,767 @@
+use anyhow::{Result, Context, bail, anyhow};
+use blake2::digest::consts::U28;
+use blake2::{Blake2b, Digest};
+use cardano_serialization_lib::{
+    make_vkey_witness, Address, BaseAddress, Certificate, Credential, EnterpriseAddress, FixedTransaction, PrivateKey,
+    RewardAddress, TransactionWitnessSet, Vkeywitness, Vkeywitnesses,
+};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde_json::{json, Value};
+
+// CBOR major types used by COSE structures
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+const MAJOR_SIMPLE: u8 = 7;
+
+const INDEFINITE_LENGTH: u8 = 31;
+const SIMPLE_FALSE: u8 = 20;
+const SIMPLE_TRUE: u8 = 21;
+const SIMPLE_NULL: u8 = 22;
+/// Deepest nesting of arrays, maps and tags accepted; COSE structures stay far below it
+const MAX_DEPTH: usize = 64;
+
+// COSE (RFC 8152) labels used by CIP-8 / CIP-30 signData
+const COSE_SIGN1_TAG: u64 = 18;
+const COSE_HEADER_ALG: i64 = 1;
+const COSE_ALG_EDDSA: i64 = -8;
+const COSE_KEY_KTY: i64 = 1;
+const COSE_KTY_OKP: i64 = 1;
+const COSE_KEY_ALG: i64 = 3;
+const COSE_KEY_CRV: i64 = -1;
+const COSE_CRV_ED25519: i64 = 6;
+const COSE_KEY_X: i64 = -2;
+
+const LOVELACE_PER_ADA: u64 = 1_000_000;
+
+// ========== CBOR ==========
+
+/// A decoded CBOR item (definite lengths only, enough for COSE structures)
+#[derive(Debug, Clone, PartialEq)]
+enum Cbor {
+    Unsigned(u64),
+    // Encodes -1 - n
+    Negative(u64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Cbor>),
+    Map(Vec<(Cbor, Cbor)>),
+    Tag(u64, Box<Cbor>),
+    Simple(u8),
+}
+
+impl Cbor {
+    fn int(value: i64) -> Self {
+        if value >= 0 {
+            Cbor::Unsigned(value as u64)
+        } else {
+            Cbor::Negative((-1 - value) as u64)
+        }
+    }
+
+    fn text(value: &str) -> Self {
+        Cbor::Text(value.to_string())
+    }
+
+    fn decode(data: &[u8]) -> Result<Self> {
+        let (item, end) = decode_item(data, 0, 0)?;
+        if end != data.len() {
+            bail!("{} trailing bytes after the CBOR item", data.len() - end);
+        }
+        Ok(item)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode_into(&mut out);
+        out
+    }
+
+    fn encode_into(&self, out: &mut Vec<u8>) {
+        match self {
+            Cbor::Unsigned(value) => write_head(out, MAJOR_UNSIGNED, *value),
+            Cbor::Negative(value) => write_head(out, MAJOR_NEGATIVE, *value),
+            Cbor::Bytes(bytes) => {
+                write_head(out, MAJOR_BYTES, bytes.len() as u64);
+                out.extend_from_slice(bytes);
+            }
+            Cbor::Text(text) => {
+                write_head(out, MAJOR_TEXT, text.len() as u64);
+                out.extend_from_slice(text.as_bytes());
+            }
+            Cbor::Array(items) => {
+                write_head(out, MAJOR_ARRAY, items.len() as u64);
+                for item in items {
+                    item.encode_into(out);
+                }
+            }
+            Cbor::Map(entries) => {
+                write_head(out, MAJOR_MAP, entries.len() as u64);
+                for (key, value) in entries {
+                    key.encode_into(out);
+                    value.encode_into(out);
+                }
+            }
+            Cbor::Tag(tag, item) => {
+                write_head(out, MAJOR_TAG, *tag);
+                item.encode_into(out);
+            }
+            Cbor::Simple(value) => write_head(out, MAJOR_SIMPLE, *value as u64),
+        }
+    }
+
+    /// Value stored under `key` when this is a map
+    fn get(&self, key: &Cbor) -> Option<&Cbor> {
+        match self {
+            Cbor::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Cbor::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+}
+
+fn write_head(out: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    match value {
+        0..=23 => out.push(major | value as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, value as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(value as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(value as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+}
+
+/// Reads an item head: (major type, additional info, argument, offset after the head)
+fn read_head(data: &[u8], pos: usize) -> Result<(u8, u8, u64, usize)> {
+    let initial = *data.get(pos).context("Truncated CBOR")?;
+    let (major, info) = (initial >> 5, initial & 0x1f);
+    let width = match info {
+        0..=23 => return Ok((major, info, info as u64, pos + 1)),
+        24 => 1,
+        25 => 2,
+        26 => 4,
+        27 => 8,
+        INDEFINITE_LENGTH => return Ok((major, info, 0, pos + 1)),
+        _ => bail!("Invalid CBOR additional information {}", info),
+    };
+    let bytes = data.get(pos + 1..pos + 1 + width).context("Truncated CBOR")?;
+    let value = bytes.iter().fold(0u64, |value, byte| (value << 8) | *byte as u64);
+    Ok((major, info, value, pos + 1 + width))
+}
+
+fn decode_item(data: &[u8], pos: usize, depth: usize) -> Result<(Cbor, usize)> {
+    if depth > MAX_DEPTH {
+        bail!("CBOR is nested more than {} levels deep", MAX_DEPTH);
+    }
+    let (major, info, value, mut pos) = read_head(data, pos)?;
+    if info == INDEFINITE_LENGTH {
+        bail!("Indefinite-length CBOR is not supported in COSE structures");
+    }
+    let item = match major {
+        MAJOR_UNSIGNED => Cbor::Unsigned(value),
+        MAJOR_NEGATIVE => Cbor::Negative(value),
+        MAJOR_BYTES | MAJOR_TEXT => {
+            let end = pos.checked_add(value as usize)
+                .filter(|end| *end <= data.len())
+                .context("Truncated CBOR")?;
+            let bytes = data[pos..end].to_vec();
+            pos = end;
+            if major == MAJOR_BYTES {
+                Cbor::Bytes(bytes)
+            } else {
+                Cbor::Text(String::from_utf8(bytes).context("CBOR text is not UTF-8")?)
+            }
+        }
+        MAJOR_ARRAY => {
+            let mut items = Vec::new();
+            for _ in 0..value {
+                let (item, next) = decode_item(data, pos, depth + 1)?;
+                items.push(item);
+                pos = next;
+            }
+            Cbor::Array(items)
+        }
+        MAJOR_MAP => {
+            let mut entries = Vec::new();
+            for _ in 0..value {
+                let (key, next) = decode_item(data, pos, depth + 1)?;
+                let (entry, next) = decode_item(data, next, depth + 1)?;
+                entries.push((key, entry));
+                pos = next;
+            }
+            Cbor::Map(entries)
+        }
+        MAJOR_TAG => {
+            let (item, next) = decode_item(data, pos, depth + 1)?;
+            pos = next;
+            Cbor::Tag(value, Box::new(item))
+        }
+        _ if info < 24 => Cbor::Simple(info),
+        _ => bail!("CBOR floats and extended simple values are not supported"),
+    };
+    Ok((item, pos))
+}
+
+// ========== TRANSACTIONS ==========
+
+/// A transaction body, or a full transaction, as produced by cardano-cli, wallets and transaction builders
+pub struct CardanoTransaction {
+    // Keeps the body, witness set and auxiliary data byte-for-byte: the transaction ID is the hash of the body exactly as encoded
+    transaction: FixedTransaction,
+    // Only a body was given, so there is no witness set to report
+    body_only: bool,
+}
+
+impl CardanoTransaction {
+    /// Parses CBOR given as hex, as a cardano-cli text envelope (`cborHex`) or as raw bytes
+    pub fn parse(input: &[u8]) -> Result<Self> {
+        let cbor = transaction_cbor(input)?;
+        match cbor.first().map(|initial| initial >> 5) {
+            Some(MAJOR_MAP) => {
+                // CSL stops after the first CBOR item; reading the body inside an unsigned transaction
+                // shows where it ends, so stray bytes never reach the transaction ID
+                let mut unsigned = vec![(MAJOR_ARRAY << 5) | 4];
+                unsigned.extend_from_slice(&cbor);
+                unsigned.extend([MAJOR_MAP << 5, (MAJOR_SIMPLE << 5) | SIMPLE_TRUE, (MAJOR_SIMPLE << 5) | SIMPLE_NULL]);
+                let transaction = FixedTransaction::from_bytes(unsigned)
+                    .map_err(|e| anyhow!("Invalid transaction body: {:?}", e))?;
+                if transaction.raw_body().len() != cbor.len() {
+                    bail!("{} trailing bytes after the transaction body", cbor.len() - transaction.raw_body().len());
+                }
+                Ok(Self { transaction, body_only: true })
+            }
+            Some(MAJOR_ARRAY) => {
+                let transaction = FixedTransaction::from_bytes(cbor)
+                    .map_err(|e| anyhow!("Invalid transaction: {:?}", e))?;
+                Ok(Self { transaction, body_only: false })
+            }
+            _ => bail!("Expected a transaction body (CBOR map) or a full transaction (CBOR array)"),
+        }
+    }
+
+    /// Transaction ID: blake2b-256 of the encoded body
+    pub fn hash(&self) -> [u8; 32] {
+        self.transaction.transaction_hash().to_bytes().try_into().expect("transaction hashes are 32 bytes")
+    }
+
+    /// Whether certificates, withdrawals or required signers name this stake key hash
+    pub fn requires_stake_witness(&self, stake_key_hash: &[u8]) -> bool {
+        let body = self.transaction.body();
+        let names_key = |credential: Credential| {
+            credential.to_keyhash().is_some_and(|hash| hash.to_bytes() == stake_key_hash)
+        };
+
+        if let Some(certificates) = body.certs() {
+            if (0..certificates.len()).any(|i| certificate_credential(&certificates.get(i)).is_some_and(names_key)) {
+                return true;
+            }
+        }
+        if let Some(withdrawals) = body.withdrawals() {
+            let reward_addresses = withdrawals.keys();
+            if (0..reward_addresses.len()).any(|i| names_key(reward_addresses.get(i).payment_cred())) {
+                return true;
+            }
+        }
+        self.required_signers().iter().any(|hash| hash.as_slice() == stake_key_hash)
+    }
+
+    /// Signs the transaction ID with each key
+    pub fn witness(&self, keys: &[PrivateKey]) -> Vkeywitnesses {
+        let hash = self.transaction.transaction_hash();
+        let mut witnesses = Vkeywitnesses::new();
+        for key in keys {
+            witnesses.add(&make_vkey_witness(&hash, key));
+        }
+        witnesses
+    }
+
+    /// A witness set holding only the given witnesses, the form CIP-30 signTx returns
+    pub fn witness_set(witnesses: &Vkeywitnesses) -> Vec<u8> {
+        let mut witness_set = TransactionWitnessSet::new();
+        witness_set.set_vkeys(witnesses);
+        witness_set.to_bytes()
+    }
+
+    /// The full transaction with the witnesses added to any it already carries.
+    /// Every other witness set entry, the body and the auxiliary data keep their original encoding.
+    pub fn signed(&self, witnesses: &Vkeywitnesses) -> Result<Vec<u8>> {
+        let existing = self.vkey_witnesses();
+        let mut transaction = self.transaction.clone();
+        for witness in (0..witnesses.len()).map(|i| witnesses.get(i)) {
+            let vkey = witness.vkey().public_key().as_bytes();
+            match existing.iter().find(|old| old.vkey().public_key().as_bytes() == vkey) {
+                // Ed25519 signatures are deterministic, so signing again yields the witness already there
+                Some(old) if *old == witness => {}
+                Some(_) => bail!("The transaction already carries a different witness from key {}", hex::encode(vkey)),
+                None => transaction.add_vkey_witness(&witness),
+            }
+        }
+        Ok(transaction.to_bytes())
+    }
+
+    pub fn details(&self) -> Result<Vec<(String, String)>> {
+        let body = self.transaction.body();
+        let mut details = vec![
+            ("Transaction Hash".to_string(), hex::encode(self.hash())),
+            ("Inputs".to_string(), body.inputs().len().to_string()),
+        ];
+
+        let outputs = body.outputs();
+        for i in 0..outputs.len() {
+            let output = outputs.get(i);
+            let address = output.address().to_bech32(None)
+                .map_err(|e| anyhow!("Invalid output address: {:?}", e))?;
+            let amount = output.amount();
+            let mut value = format!("{} to {}", format_lovelace(&amount.coin().to_str()), address);
+            if let Some(assets) = amount.multiasset() {
+                value.push_str(&format!(" (+ tokens under {} policies)", assets.len()));
+            }
+            details.push((format!("Output #{}", i + 1), value));
+        }
+
+        details.push(("Fee".to_string(), format_lovelace(&body.fee().to_str())));
+        if let Some(ttl) = body.ttl_bignum() {
+            details.push(("Valid Until Slot".to_string(), ttl.to_str()));
+        }
+        if let Some(certificates) = body.certs() {
+            details.push(("Certificates".to_string(), certificates.len().to_string()));
+        }
+        if let Some(withdrawals) = body.withdrawals() {
+            let reward_addresses = withdrawals.keys();
+            for i in 0..reward_addresses.len() {
+                let reward_address = reward_addresses.get(i);
+                let amount = withdrawals.get(&reward_address).map(|amount| amount.to_str()).unwrap_or_default();
+                let from = reward_address.to_address().to_bech32(None)
+                    .map_err(|e| anyhow!("Invalid reward address: {:?}", e))?;
+                details.push(("Withdrawal".to_string(), format!("{} from {}", format_lovelace(&amount), from)));
+            }
+        }
+        let required_signers = self.required_signers();
+        if !required_signers.is_empty() {
+            let hashes: Vec<String> = required_signers.iter().map(hex::encode).collect();
+            details.push(("Required Signers".to_string(), hashes.join(", ")));
+        }
+        if !self.body_only {
+            details.push(("Existing Witnesses".to_string(), self.vkey_witnesses().len().to_string()));
+        }
+        Ok(details)
+    }
+
+    fn required_signers(&self) -> Vec<Vec<u8>> {
+        match self.transaction.body().required_signers() {
+            Some(signers) => (0..signers.len()).map(|i| signers.get(i).to_bytes()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn vkey_witnesses(&self) -> Vec<Vkeywitness> {
+        match self.transaction.witness_set().vkeys() {
+            Some(witnesses) => (0..witnesses.len()).map(|i| witnesses.get(i)).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// Stake credential of the certificates that need the stake key's witness
+fn certificate_credential(certificate: &Certificate) -> Option<Credential> {
+    certificate.as_stake_registration().map(|cert| cert.stake_credential())
+        .or_else(|| certificate.as_stake_deregistration().map(|cert| cert.stake_credential()))
+        .or_else(|| certificate.as_stake_delegation().map(|cert| cert.stake_credential()))
+        .or_else(|| certificate.as_vote_delegation().map(|cert| cert.stake_credential()))
+        .or_else(|| certificate.as_stake_and_vote_delegation().map(|cert| cert.stake_credential()))
+        .or_else(|| certificate.as_stake_registration_and_delegation().map(|cert| cert.stake_credential()))
+        .or_else(|| certificate.as_vote_registration_and_delegation().map(|cert| cert.stake_credential()))
+        .or_else(|| certificate.as_stake_vote_registration_and_delegation().map(|cert| cert.stake_credential()))
+}
+
+fn transaction_cbor(input: &[u8]) -> Result<Vec<u8>> {
+    // Binary CBOR transactions never start with a valid UTF-8 byte
+    let text = match std::str::from_utf8(input) {
+        Ok(text) => text.trim(),
+        Err(_) => return Ok(input.to_vec()),
+    };
+    if text.starts_with('{') {
+        let envelope: Value = serde_json::from_str(text).context("Invalid transaction JSON")?;
+        let cbor_hex = envelope["cborHex"].as_str()
+            .context("Transaction JSON has no cborHex field (expected a cardano-cli text envelope)")?;
+        return hex::decode(cbor_hex).context("cborHex is not valid hex");
+    }
+    hex::decode(text.trim_start_matches("0x")).context("Transaction is not CBOR hex or a cardano-cli text envelope")
+}
+
+// ========== CIP-8 MESSAGES ==========
+
+/// CIP-30 signData result: a COSE_Sign1 structure and the COSE_Key that verifies it
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSignature {
+    pub signature: Vec<u8>,
+    pub key: Vec<u8>,
+}
+
+impl DataSignature {
+    /// `{"signature": "<COSE_Sign1 hex>", "key": "<COSE_Key hex>"}`, as dApps receive it
+    pub fn to_json(&self) -> String {
+        json!({ "signature": hex::encode(&self.signature), "key": hex::encode(&self.key) }).to_string()
+    }
+
+    pub fn from_json(text: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(text.trim())
+            .context("Cardano signatures are DataSignature JSON: {\"signature\": ..., \"key\": ...}")?;
+        let field = |name: &str| -> Result<Vec<u8>> {
+            let hex_value = value[name].as_str().with_context(|| format!("DataSignature has no '{}' field", name))?;
+            hex::decode(hex_value).with_context(|| format!("DataSignature '{}' is not hex", name))
+        };
+        Ok(Self { signature: field("signature")?, key: field("key")? })
+    }
+}
+
+/// Signs `payload` on behalf of `address` the way CIP-30 wallets answer signData
+pub fn sign_cip8_message(key: &PrivateKey, address: &[u8], payload: &[u8]) -> DataSignature {
+    let protected = Cbor::Map(vec![
+        (Cbor::int(COSE_HEADER_ALG), Cbor::int(COSE_ALG_EDDSA)),
+        (Cbor::text("address"), Cbor::Bytes(address.to_vec())),
+    ]).encode();
+    let signature = key.sign(&sig_structure(&protected, payload)).to_bytes();
+
+    let cose_sign1 = Cbor::Array(vec![
+        Cbor::Bytes(protected),
+        Cbor::Map(vec![(Cbor::text("hashed"), Cbor::Simple(SIMPLE_FALSE))]),
+        Cbor::Bytes(payload.to_vec()),
+        Cbor::Bytes(signature),
+    ]);
+    let cose_key = Cbor::Map(vec![
+        (Cbor::int(COSE_KEY_KTY), Cbor::int(COSE_KTY_OKP)),
+        (Cbor::int(COSE_KEY_ALG), Cbor::int(COSE_ALG_EDDSA)),
+        (Cbor::int(COSE_KEY_CRV), Cbor::int(COSE_CRV_ED25519)),
+        (Cbor::int(COSE_KEY_X), Cbor::Bytes(key.to_public().as_bytes())),
+    ]);
+    DataSignature { signature: cose_sign1.encode(), key: cose_key.encode() }
+}
+
+/// Checks a CIP-8 signature over `payload` made by a key behind one of `address`'s credentials
+pub fn verify_cip8_message(address: &str, payload: &[u8], data_signature: &DataSignature) -> Result<()> {
+    let credentials = address_credentials(address)?;
+
+    let cose_sign1 = match Cbor::decode(&data_signature.signature).context("signature is not valid CBOR")? {
+        Cbor::Tag(COSE_SIGN1_TAG, item) => *item,
+        item => item,
+    };
+    let items = match cose_sign1 {
+        Cbor::Array(items) if items.len() == 4 => items,
+        _ => bail!("signature is not a COSE_Sign1 structure"),
+    };
+
+    let protected = items[0].as_bytes().context("COSE_Sign1 protected headers are not a byte string")?;
+    let headers = Cbor::decode(protected).context("COSE_Sign1 protected headers are not valid CBOR")?;
+    if headers.get(&Cbor::int(COSE_HEADER_ALG)) != Some(&Cbor::int(COSE_ALG_EDDSA)) {
+        bail!("COSE_Sign1 is not signed with EdDSA");
+    }
+    match headers.get(&Cbor::text("address")).and_then(Cbor::as_bytes) {
+        Some(signed_address) if signed_address == credentials.bytes.as_slice() => {}
+        Some(_) => bail!("the signature was made for a different address"),
+        None => bail!("COSE_Sign1 has no address header"),
+    }
+
+    let signed_payload = items[2].as_bytes().context("COSE_Sign1 payload is detached or not a byte string")?;
+    let expected_payload = if items[1].get(&Cbor::text("hashed")) == Some(&Cbor::Simple(SIMPLE_TRUE)) {
+        Blake2b::<U28>::digest(payload).to_vec()
+    } else {
+        payload.to_vec()
+    };
+    if signed_payload != expected_payload.as_slice() {
+        bail!("the signed payload is not this message");
+    }
+
+    let cose_key = Cbor::decode(&data_signature.key).context("key is not valid CBOR")?;
+    let public_key: [u8; 32] = cose_key.get(&Cbor::int(COSE_KEY_X))
+        .and_then(Cbor::as_bytes)
+        .context("COSE_Key has no Ed25519 public key (-2)")?
+        .try_into()
+        .context("COSE_Key public key must be 32 bytes")?;
+    let key_hash = key_hash(&public_key);
+    if credentials.payment.as_ref() != Some(&key_hash) && credentials.stake.as_ref() != Some(&key_hash) {
+        bail!("the signing key is not a credential of {}", address.trim());
+    }
+
+    let verifying_key = VerifyingKey::from_bytes(&public_key).context("Invalid Ed25519 public key")?;
+    let signature = Signature::from_slice(items[3].as_bytes().context("COSE_Sign1 signature is not a byte string")?)
+        .context("COSE_Sign1 signature must be 64 bytes")?;
+    verifying_key.verify(&sig_structure(protected, signed_payload), &signature)
+        .context("Ed25519 signature does not verify")
+}
+
+/// COSE Sig_structure for a COSE_Sign1 without external data
+fn sig_structure(protected: &[u8], payload: &[u8]) -> Vec<u8> {
+    Cbor::Array(vec![
+        Cbor::text("Signature1"),
+        Cbor::Bytes(protected.to_vec()),
+        Cbor::Bytes(Vec::new()),
+        Cbor::Bytes(payload.to_vec()),
+    ]).encode()
+}
+
+// ========== KEYS AND ADDRESSES ==========
+
+/// Key hashes behind an address's payment and stake credentials, plus its raw bytes
+pub struct AddressCredentials {
+    pub bytes: Vec<u8>,
+    pub payment: Option<Vec<u8>>,
+    pub stake: Option<Vec<u8>>,
+}
+
+/// Credentials of a base, enterprise or reward address; script credentials come back as None
+pub fn address_credentials(address: &str) -> Result<AddressCredentials> {
+    let parsed = Address::from_bech32(address.trim())
+        .map_err(|e| anyhow!("Invalid Cardano address {}: {:?}", address.trim(), e))?;
+    let key_hash = |credential: Credential| credential.to_keyhash().map(|hash| hash.to_bytes());
+
+    let (payment, stake) = if let Some(base) = BaseAddress::from_address(&parsed) {
+        (key_hash(base.payment_cred()), key_hash(base.stake_cred()))
+    } else if let Some(enterprise) = EnterpriseAddress::from_address(&parsed) {
+        (key_hash(enterprise.payment_cred()), None)
+    } else if let Some(reward) = RewardAddress::from_address(&parsed) {
+        (None, key_hash(reward.payment_cred()))
+    } else {
+        bail!("{} is not a base, enterprise or reward address", address.trim());
+    };
+    Ok(AddressCredentials { bytes: parsed.to_bytes(), payment, stake })
+}
+
+/// Signing key from stored bytes: 64-byte BIP32-Ed25519 extended keys (mnemonic wallets) or 32-byte imported keys
+pub fn signing_key(private_key: &[u8]) -> Result<PrivateKey> {
+    match private_key.len() {
+        64 => PrivateKey::from_extended_bytes(private_key),
+        32 => PrivateKey::from_normal_bytes(private_key),
+        length => bail!("Cardano private keys are 32 or 64 bytes, got {}", length),
+    }
+    .map_err(|e| anyhow!("Invalid Cardano private key: {:?}", e))
+}
+
+/// blake2b-224 hash of a public key, as used in credentials and required signers
+pub fn key_hash(public_key: &[u8]) -> Vec<u8> {
+    Blake2b::<U28>::digest(public_key).to_vec()
+}
+
+// ========== HELPERS ==========
+
+fn format_lovelace(lovelace: &str) -> String {
+    match lovelace.parse::<u64>() {
+        Ok(lovelace) => {
+            let ada = format!("{}.{:06}", lovelace / LOVELACE_PER_ADA, lovelace % LOVELACE_PER_ADA);
+            format!("{} ADA", ada.trim_end_matches('0').trim_end_matches('.'))
+        }
+        Err(_) => format!("{} lovelace", lovelace),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cardano_serialization_lib::TransactionHash;
+
+    const PRIVATE_KEY: &str = "1e99423a4ed27608a15a2616a2b0e9e52ced330ac530edcc32c8ffc6a526aedd";
+    const PUBLIC_KEY: &str = "bad0b74d94b0516b358726a306c8506a5230457731a7e5a568a2e45cbfe2883a";
+    const KEY_HASH: &str = "edcd1ea5c3963f161b560e23f849d59ca91f145da8f70cb292d07ada";
+
+    // One input, one 2 ADA output to the key's enterprise address, 0.17 ADA fee, TTL 1000
+    const BODY: &str = "a40081825820aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa00018182581d61edcd1ea5c3963f161b560e23f849d59ca91f145da8f70cb292d07ada1a001e8480021a00029810031903e8";
+    const BODY_HASH: &str = "5795f9637d4b54237a6414adc3505cf8a84fd8538e57a52179fe5dcbfeef9bb9";
+
+    // Pays to the key's base address, deregisters its stake key and withdraws 5 ADA of rewards
+    const STAKE_BODY: &str = "a50081825820aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa00018182583901edcd1ea5c3963f161b560e23f849d59ca91f145da8f70cb292d07adaedcd1ea5c3963f161b560e23f849d59ca91f145da8f70cb292d07ada1a001e8480021a00029810048182018200581cedcd1ea5c3963f161b560e23f849d59ca91f145da8f70cb292d07ada05a1581de1edcd1ea5c3963f161b560e23f849d59ca91f145da8f70cb292d07ada1a004c4b40";
+
+    fn key() -> PrivateKey {
+        signing_key(&hex::decode(PRIVATE_KEY).unwrap()).unwrap()
+    }
+
+    // Base address using the key for both payment and stake, as standalone imports do
+    fn base_address() -> String {
+        let bytes = hex::decode(format!("01{}{}", KEY_HASH, KEY_HASH)).unwrap();
+        Address::from_bytes(bytes).unwrap().to_bech32(None).unwrap()
+    }
+
+    #[test]
+    fn test_cbor_round_trip() {
+        let item = Cbor::Map(vec![
+            (Cbor::int(1), Cbor::int(-8)),
+            (Cbor::text("address"), Cbor::Bytes(vec![0x01; 57])),
+            (Cbor::int(-2), Cbor::Array(vec![Cbor::Unsigned(70_000), Cbor::Simple(SIMPLE_TRUE)])),
+        ]);
+        let encoded = item.encode();
+        assert_eq!(&encoded[..3], &[0xa3, 0x01, 0x27]);
+        assert_eq!(Cbor::decode(&encoded).unwrap(), item);
+
+        assert!(Cbor::decode(&[0x9f, 0x01, 0x02, 0xff]).is_err());
+        assert!(Cbor::decode(&[0x58, 0x20, 0x00]).is_err());
+
+        // Deep nesting is rejected instead of exhausting the stack
+        let mut nested = vec![0x81; 100_000];
+        nested.push(0x00);
+        assert!(Cbor::decode(&nested).is_err());
+        assert!(CardanoTransaction::parse(&nested).is_err());
+        let mut shallow = vec![0x81; MAX_DEPTH];
+        shallow.push(0x00);
+        assert!(Cbor::decode(&shallow).is_ok());
+    }
+
+    #[test]
+    fn test_key_and_address() {
+        assert_eq!(hex::encode(key().to_public().as_bytes()), PUBLIC_KEY);
+        assert_eq!(hex::encode(key_hash(&hex::decode(PUBLIC_KEY).unwrap())), KEY_HASH);
+
+        let credentials = address_credentials(&base_address()).unwrap();
+        assert_eq!(credentials.payment.map(hex::encode).as_deref(), Some(KEY_HASH));
+        assert_eq!(credentials.stake.map(hex::encode).as_deref(), Some(KEY_HASH));
+        assert!(signing_key(&[0u8; 31]).is_err());
+    }
+
+    #[test]
+    fn test_sign_transaction_body() {
+        let transaction = CardanoTransaction::parse(BODY.as_bytes()).unwrap();
+        assert_eq!(hex::encode(transaction.hash()), BODY_HASH);
+
+        let details = transaction.details().unwrap();
+        assert!(details.contains(&("Fee".to_string(), "0.17 ADA".to_string())));
+        assert!(details.contains(&("Valid Until Slot".to_string(), "1000".to_string())));
+        assert!(details.iter().any(|(label, value)| label == "Output #1" && value.starts_with("2 ADA to addr1v")));
+        assert!(!details.iter().any(|(label, _)| label == "Existing Witnesses"));
+        assert!(!transaction.requires_stake_witness(&hex::decode(KEY_HASH).unwrap()));
+
+        let witnesses = transaction.witness(&[key()]);
+        let witness = witnesses.get(0);
+        assert_eq!(hex::encode(witness.vkey().public_key().as_bytes()), PUBLIC_KEY);
+        let verifying_key = VerifyingKey::from_bytes(&witness.vkey().public_key().as_bytes().try_into().unwrap()).unwrap();
+        let signature = Signature::from_slice(&witness.signature().to_bytes()).unwrap();
+        assert!(verifying_key.verify(&transaction.hash(), &signature).is_ok());
+
+        // A bare body becomes [body, witness set, true, null] with the body bytes untouched
+        let signed = transaction.signed(&witnesses).unwrap();
+        let decoded = FixedTransaction::from_bytes(signed.clone()).unwrap();
+        assert_eq!(hex::encode(decoded.raw_body()), BODY);
+        assert_eq!(decoded.witness_set().vkeys().unwrap(), witnesses);
+        assert!(decoded.is_valid());
+        assert!(decoded.raw_auxiliary_data().is_none());
+        assert!(hex::encode(&signed).ends_with("f5f6"));
+
+        // Signing the signed transaction again keeps one witness per key and the same ID
+        let resigned = CardanoTransaction::parse(&signed).unwrap();
+        assert_eq!(resigned.hash(), transaction.hash());
+        assert_eq!(resigned.signed(&witnesses).unwrap(), signed);
+
+        // A stale witness by the same key is refused rather than left next to the new one
+        let stale = Vkeywitness::new(&witness.vkey(), &key().sign(&[0u8; 32]));
+        let mut witness_set = TransactionWitnessSet::new();
+        witness_set.set_vkeys(&{ let mut set = Vkeywitnesses::new(); set.add(&stale); set });
+        let stale_hex = format!("84{}{}f5f6", BODY, hex::encode(witness_set.to_bytes()));
+        assert!(CardanoTransaction::parse(stale_hex.as_bytes()).unwrap().signed(&witnesses).is_err());
+    }
+
+    #[test]
+    fn test_merge_keeps_other_witnesses() {
+        // A co-signer's witness, already in the transaction
+        let body_hash = TransactionHash::from_bytes(hex::decode(BODY_HASH).unwrap()).unwrap();
+        let other = make_vkey_witness(&body_hash, &signing_key(&[7u8; 32]).unwrap());
+        let other_hex = hex::encode(other.to_bytes());
+        // Tagged vkey set plus a native script (key 1) that must survive untouched
+        let native_script = format!("8200581c{}", KEY_HASH);
+        let transaction_hex = format!("84{}a200d9010281{}0181{}f5f6", BODY, other_hex, native_script);
+
+        let transaction = CardanoTransaction::parse(transaction_hex.as_bytes()).unwrap();
+        let details = transaction.details().unwrap();
+        assert!(details.contains(&("Existing Witnesses".to_string(), "1".to_string())));
+
+        let witnesses = transaction.witness(&[key()]);
+        let signed = FixedTransaction::from_bytes(transaction.signed(&witnesses).unwrap()).unwrap();
+        let merged = signed.witness_set().vkeys().unwrap();
+        assert_eq!(merged.len(), 2);
+        assert!(merged.get(0) == other || merged.get(1) == other);
+        assert!(merged.get(0) == witnesses.get(0) || merged.get(1) == witnesses.get(0));
+        let witness_set = hex::encode(signed.raw_witness_set());
+        assert!(witness_set.starts_with("a200d9010282"));
+        assert!(witness_set.ends_with(&format!("0181{}", native_script)));
+
+        // An untagged vkey array stays untagged
+        let transaction_hex = format!("84{}a10081{}f5f6", BODY, other_hex);
+        let signed = CardanoTransaction::parse(transaction_hex.as_bytes()).unwrap().signed(&witnesses).unwrap();
+        let signed = FixedTransaction::from_bytes(signed).unwrap();
+        assert!(hex::encode(signed.raw_witness_set()).starts_with("a10082"));
+    }
+
+    #[test]
+    fn test_stake_witness_detection() {
+        let transaction = CardanoTransaction::parse(STAKE_BODY.as_bytes()).unwrap();
+        assert!(transaction.requires_stake_witness(&hex::decode(KEY_HASH).unwrap()));
+        assert!(!transaction.requires_stake_witness(&[0u8; 28]));
+
+        let details = transaction.details().unwrap();
+        assert!(details.contains(&("Certificates".to_string(), "1".to_string())));
+        assert!(details.iter().any(|(label, value)| label == "Withdrawal" && value.starts_with("5 ADA from stake1")));
+    }
+
+    #[test]
+    fn test_text_envelope_and_invalid_input() {
+        let envelope = format!(r#"{{"type": "TxBodyConway", "description": "", "cborHex": "{}"}}"#, BODY);
+        assert_eq!(hex::encode(CardanoTransaction::parse(envelope.as_bytes()).unwrap().hash()), BODY_HASH);
+        assert_eq!(hex::encode(CardanoTransaction::parse(&hex::decode(BODY).unwrap()).unwrap().hash()), BODY_HASH);
+
+        assert!(CardanoTransaction::parse(b"not a transaction").is_err());
+        assert!(CardanoTransaction::parse(b"8201a0").is_err());
+        assert!(CardanoTransaction::parse(format!("{}00", BODY).as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_cip8_known_signature() {
+        // COSE_Sign1 and COSE_Key built independently for "Sign in to example.com"
+        let address_bytes = hex::decode(format!("01{}{}", KEY_HASH, KEY_HASH)).unwrap();
+        let signed = sign_cip8_message(&key(), &address_bytes, b"Sign in to example.com");
+        assert_eq!(
+            hex::encode(&signed.signature),
+            "845846a201276761646472657373583901edcd1ea5c3963f161b560e23f849d59ca91f145da8f70cb292d07adaedcd1ea5c3963f161b560e23f849d59ca91f145da8f70cb292d07adaa166686173686564f4565369676e20696e20746f206578616d706c652e636f6d5840f435419b7cfa18cdf132247211ee1bbcba457de944563ccac1e9905dff6a38df2b53f4e8fef6c2bb117432329ba6cf59518b0a2d00b13c3496fe027e9cb54003"
+        );
+        assert_eq!(hex::encode(&signed.key), format!("a4010103272006215820{}", PUBLIC_KEY));
+    }
+
+    #[test]
+    fn test_cip8_verification() {
+        let address = base_address();
+        let credentials = address_credentials(&address).unwrap();
+        let signed = sign_cip8_message(&key(), &credentials.bytes, b"login nonce 42");
+
+        let round_trip = DataSignature::from_json(&signed.to_json()).unwrap();
+        assert!(verify_cip8_message(&address, b"login nonce 42", &round_trip).is_ok());
+        assert!(verify_cip8_message(&address, b"login nonce 43", &round_trip).is_err());
+
+        // Bound to the address in the protected header
+        let enterprise = Address::from_bytes(hex::decode(format!("61{}", KEY_HASH)).unwrap())
+            .unwrap()
+            .to_bech32(None)
+            .unwrap();
+        assert!(verify_cip8_message(&enterprise, b"login nonce 42", &round_trip).is_err());
+
+        // A key that is not behind the address
+        let other = signing_key(&[7u8; 32]).unwrap();
+        let forged = sign_cip8_message(&other, &credentials.bytes, b"login nonce 42");
+        assert!(verify_cip8_message(&address, b"login nonce 42", &forged).is_err());
+
+        assert!(DataSignature::from_json("deadbeef").is_err());
+    }
+}
@@ -1,5 +1,7 @@
-use anyhow::Result;
-use crate::blockchain::SupportedBlockchain;
+use anyhow::{Result, bail};
+use crate::blockchain::{SupportedBlockchain, KeyScheme};
+use crate::crypto::master_key::MasterKey;
+use crate::database::MasterAccount;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -11,6 +13,7 @@ pub struct WalletKeys {
     pub derivation_path: String,
     pub additional_data: HashMap<String, String>,     // New: blockchain-specific data
     pub secondary_addresses: HashMap<String, String>, // New: secondary addresses
+    pub secret_seed: Option<String>,                  // Chain-native seed the key came from (e.g. XRP family seed), stored sealed
 }
 
 impl WalletKeys {
@@ -29,6 +32,7 @@ impl WalletKeys {
             derivation_path,
             additional_data: HashMap::new(),
             secondary_addresses: HashMap::new(),
+            secret_seed: None,
         }
     }
 
@@ -48,9 +52,26 @@ impl WalletKeys {
             derivation_path,
             additional_data: HashMap::new(),
             secondary_addresses: HashMap::new(),
+            secret_seed: None,
         }
     }
 
+    // Helper constructor for watch-only wallets: no private key, address computed from a public key
+    // derived from an account-level xpub (shared by every handler's `derive_from_public_key`)
+    pub fn watch_only(
+        public_key: &[u8],
+        derivation_path: &str,
+        to_address: impl FnOnce(&[u8]) -> Result<String>,
+    ) -> Result<Self> {
+        let address = to_address(public_key)?;
+        Ok(Self::new_simple(
+            String::new(),
+            hex::encode(public_key),
+            address,
+            derivation_path.to_string(),
+        ))
+    }
+
     // Helper methods to add data
     pub fn add_data(&mut self, key: String, value: String) {
         self.additional_data.insert(key, value);
@@ -61,6 +82,27 @@ impl WalletKeys {
     }
 }
 
+/// Signing entry points a handler implements (see `BlockchainHandler::signing_capabilities`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SigningCapabilities {
+    pub sign_message: bool,
+    pub verify_message: bool,
+    pub sign_transaction: bool,
+}
+
+impl SigningCapabilities {
+    pub const ALL: Self = Self { sign_message: true, verify_message: true, sign_transaction: true };
+}
+
+/// Result of `BlockchainHandler::sign_transaction`
+#[derive(Debug, Clone)]
+pub struct SignedTransaction {
+    /// Signed transaction in the chain's usual broadcast or interchange form (hex, base64, JSON...)
+    pub encoded: String,
+    /// Transaction hash or ID, when it is known before broadcast
+    pub hash: Option<String>,
+}
+
 pub trait BlockchainHandler {
     fn derive_from_mnemonic(
         &self,
@@ -76,6 +118,106 @@ pub trait BlockchainHandler {
     fn validate_address(&self, address: &str) -> bool;
     
     fn get_blockchain_name(&self) -> &'static str;
+
+    /// Derives keys from an xprv or SLIP-10 master key for accounts without a mnemonic.
+    /// The child private key is derived for the chain's key scheme and then handed to
+    /// `derive_from_private_key`, so addresses match the mnemonic path.
+    fn derive_from_master_key(
+        &self,
+        master_key: &MasterKey,
+        account: u32,
+        address_index: u32,
+        custom_path: Option<&str>,
+    ) -> Result<WalletKeys> {
+        let blockchain = SupportedBlockchain::from_str(self.get_blockchain_name())?;
+        let derivation_path = match custom_path {
+            Some(path) => path.to_string(),
+            None => blockchain.get_default_derivation_path(account, address_index),
+        };
+
+        let private_key = match blockchain.key_scheme() {
+            KeyScheme::Secp256k1 => master_key.derive_secp256k1(&derivation_path)?,
+            KeyScheme::Slip10Ed25519 => master_key.derive_slip10_ed25519(&derivation_path)?,
+            KeyScheme::Ed25519 => master_key.derive_ed25519(&derivation_path)?,
+            KeyScheme::Bip32Ed25519 => {
+                bail!("{} uses BIP32-Ed25519 (CIP-1852) keys, which cannot be derived from an xprv or SLIP-10 master key", blockchain)
+            }
+        };
+
+        let mut keys = self.derive_from_private_key(&hex::encode(&private_key))?;
+        keys.derivation_path = derivation_path;
+        Ok(keys)
+    }
+
+    /// Builds watch-only keys (empty private key) for a public key derived from an
+    /// account-level xpub (see `WalletKeys::watch_only`). Chains without xpub support keep this default.
+    fn derive_from_public_key(&self, _public_key: &[u8], _derivation_path: &str) -> Result<WalletKeys> {
+        bail!("Watch-only wallets are not supported for {}", self.get_blockchain_name())
+    }
+
+    /// Which of the signing entry points below this chain implements
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities::default()
+    }
+
+    /// Signs an off-chain message with the chain's native scheme and returns the signature
+    /// in the chain's usual text form. `format` picks a variant where a chain has several.
+    fn sign_message(&self, _private_key: &[u8], _address: &str, _message: &[u8], _format: Option<&str>) -> Result<String> {
+        bail!("Message signing is not supported for {} yet", self.get_blockchain_name())
+    }
+
+    /// Checks a signature over `message` against `address` and returns the scheme that
+    /// verified it. A signature that does not verify is an error explaining why.
+    fn verify_message(&self, _address: &str, _message: &[u8], _signature: &str, _format: Option<&str>) -> Result<String> {
+        bail!("Message verification is not supported for {} yet", self.get_blockchain_name())
+    }
+
+    /// Human-readable fields of an unsigned transaction, shown for confirmation before signing
+    fn describe_transaction(&self, _transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        Ok(Vec::new())
+    }
+
+    /// Signs an unsigned transaction given in the chain's usual interchange form
+    /// (JSON, hex, base64 or a PSBT) with the key of `address`
+    fn sign_transaction(&self, _private_key: &[u8], _address: &str, _transaction: &[u8]) -> Result<SignedTransaction> {
+        bail!("Transaction signing is not supported for {} yet", self.get_blockchain_name())
+    }
+}
+
+/// Derives wallet keys from whichever secret backs the master account
+pub fn derive_for_account(
+    handler: &dyn BlockchainHandler,
+    master_account: &MasterAccount,
+    account: u32,
+    address_index: u32,
+    custom_path: Option<&str>,
+) -> Result<WalletKeys> {
+    if master_account.is_watch_only() {
+        // The xpub fixes purpose, coin type and account, so only the address index applies
+        let watch_only = master_account.watch_only_key()?;
+        let blockchain = SupportedBlockchain::from_str(handler.get_blockchain_name())?;
+        if blockchain != watch_only.blockchain {
+            bail!(
+                "Watch-only account '{}' tracks a {} xpub and cannot derive {} wallets",
+                master_account.name, watch_only.blockchain, blockchain
+            );
+        }
+        let public_key = watch_only.derive_public_key(0, address_index)?;
+        return handler.derive_from_public_key(&public_key, &watch_only.derivation_path(0, address_index));
+    }
+
+    if master_account.uses_master_key() {
+        let master_key = master_account.master_key()?;
+        handler.derive_from_master_key(&master_key, account, address_index, custom_path)
+    } else {
+        handler.derive_from_mnemonic(
+            master_account.mnemonic.expose()?,
+            master_account.passphrase_str()?,
+            account,
+            address_index,
+            custom_path,
+        )
+    }
 }
 
 pub fn get_blockchain_handler(blockchain: &SupportedBlockchain) -> Result<Box<dyn BlockchainHandler>> {
@@ -1,14 +1,33 @@
-use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys};
+use anyhow::{Result, Context, bail};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SigningCapabilities, SignedTransaction};
+use crate::blockchain::cosmos_tx::{sign_adr036, verify_adr036, CosmosSignDoc};
 use crate::crypto::bip32::{derive_secp256k1_key_from_mnemonic, private_key_to_public_key_secp256k1};
 use cosmrs::{AccountId, crypto::{PublicKey as CosmosPublicKey, secp256k1::VerifyingKey as CosmosVerifyingKey}};
+use k256::ecdsa::SigningKey;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
 use std::str::FromStr;
 
-pub struct CosmosHandler;
+pub struct CosmosHandler {
+    /// Chain ID and account number for sign docs that do not carry them (a bare TxRaw),
+    /// or to check the ones that do
+    chain_id: Option<String>,
+    account_number: Option<u64>,
+}
 
 impl CosmosHandler {
     pub fn new() -> Self {
-        Self
+        Self {
+            chain_id: None,
+            account_number: None,
+        }
+    }
+
+    pub fn for_chain(chain_id: Option<String>, account_number: Option<u64>) -> Self {
+        Self { chain_id, account_number }
+    }
+
+    fn sign_doc(&self, transaction: &[u8]) -> Result<CosmosSignDoc> {
+        CosmosSignDoc::parse(transaction, self.chain_id.as_deref(), self.account_number)
     }
 
     fn public_key_to_address(&self, public_key_bytes: &[u8]) -> Result<String> {
@@ -102,9 +121,50 @@ impl BlockchainHandler for CosmosHandler {
         AccountId::from_str(address).is_ok()
     }
 
+    fn derive_from_public_key(&self, public_key: &[u8], derivation_path: &str) -> Result<WalletKeys> {
+        WalletKeys::watch_only(public_key, derivation_path, |key| self.public_key_to_address(key))
+    }
+
     fn get_blockchain_name(&self) -> &'static str {
         "Cosmos"
     }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities::ALL
+    }
+
+    fn sign_message(&self, private_key: &[u8], address: &str, message: &[u8], format: Option<&str>) -> Result<String> {
+        check_message_format(format)?;
+        let signing_key = SigningKey::from_slice(private_key).context("Invalid secp256k1 private key")?;
+        sign_adr036(&signing_key, address, message)
+    }
+
+    fn verify_message(&self, address: &str, message: &[u8], signature: &str, format: Option<&str>) -> Result<String> {
+        check_message_format(format)?;
+        verify_adr036(address, message, signature)?;
+        Ok("ADR-036".to_string())
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        self.sign_doc(transaction)?.details()
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        let signing_key = SigningKey::from_slice(private_key).context("Invalid secp256k1 private key")?;
+        let public_key = signing_key.verifying_key().to_encoded_point(true);
+        if self.public_key_to_address(public_key.as_bytes())? != address {
+            bail!("Private key does not belong to {}", address);
+        }
+        self.sign_doc(transaction)?.sign(&signing_key)
+    }
+}
+
+/// ADR-036 is the only Cosmos message format
+fn check_message_format(format: Option<&str>) -> Result<()> {
+    match format.map(str::to_lowercase).as_deref() {
+        None | Some("adr036") | Some("adr-036") => Ok(()),
+        Some(other) => bail!("Unknown Cosmos message format '{}'. Cosmos messages use ADR-036", other),
+    }
 }
 
 #[cfg(test)]
@@ -159,6 +219,37 @@ mod tests {
         assert!(handler.validate_address(&wallet_keys.address));
     }
 
+    #[test]
+    fn test_blockchain_handler_signing() {
+        let handler = CosmosHandler::for_chain(Some("cosmoshub-4".to_string()), None);
+        let private_key = hex::decode("1e99423a4ed27608a15a2616a2b0e9e52ced330ac530edcc32c8ffc6a526aedd").unwrap();
+        let keys = handler.derive_from_private_key(&hex::encode(&private_key)).unwrap();
+
+        let signature = handler.sign_message(&private_key, &keys.address, b"Login to example.com", None).unwrap();
+        assert_eq!(handler.verify_message(&keys.address, b"Login to example.com", &signature, None).unwrap(), "ADR-036");
+        assert!(handler.verify_message(&keys.address, b"Login to example.org", &signature, None).is_err());
+        assert!(handler.sign_message(&private_key, &keys.address, b"hi", Some("eip191")).is_err());
+
+        let doc = serde_json::json!({
+            "chain_id": "cosmoshub-4",
+            "account_number": "7",
+            "sequence": "0",
+            "fee": { "amount": [], "gas": "200000" },
+            "msgs": [{ "type": "cosmos-sdk/MsgSend", "value": {
+                "from_address": keys.address, "to_address": keys.address,
+                "amount": [{ "denom": "uatom", "amount": "1" }],
+            }}],
+            "memo": "offline",
+        }).to_string();
+        let details = handler.describe_transaction(doc.as_bytes()).unwrap();
+        assert!(details.contains(&("Memo".to_string(), "offline".to_string())));
+        assert!(handler.sign_transaction(&private_key, &keys.address, doc.as_bytes()).is_ok());
+        assert!(handler.sign_transaction(&[0x22; 32], &keys.address, doc.as_bytes()).is_err());
+
+        let other_chain = CosmosHandler::for_chain(Some("osmosis-1".to_string()), None);
+        assert!(other_chain.sign_transaction(&private_key, &keys.address, doc.as_bytes()).is_err());
+    }
+
     #[test]
     fn test_cosmos_uses_correct_derivation_path() {
         let handler = CosmosHandler::new();
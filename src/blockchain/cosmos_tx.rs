@@ -0,0 +1,765 @@
+use anyhow::{Result, Context, bail};
+use alloy_primitives::B256;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use ripemd::Ripemd160;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use cosmrs::{Any, Coin};
+use cosmrs::crypto::PublicKey;
+use cosmrs::proto::cosmos::{bank, distribution, gov, staking};
+use cosmrs::proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmrs::proto::cosmos::tx::v1beta1::{AuthInfo as ProtoAuthInfo, SignDoc as ProtoSignDoc, TxBody, TxRaw};
+use cosmrs::proto::traits::Message;
+use cosmrs::tx::{AuthInfo, Body, ModeInfo, Raw, SignDoc, SignMode, SignerPublicKey};
+
+use crate::blockchain::SignedTransaction;
+use crate::blockchain::evm::sign_hash;
+
+const AMINO_SECP256K1_PUBKEY_TYPE: &str = "tendermint/PubKeySecp256k1";
+/// Longest chain ID the Cosmos SDK accepts
+const MAX_CHAIN_ID_LEN: usize = 50;
+
+/// A SIGN_MODE_DIRECT sign doc: the serialized body and auth info plus the replay-protection context
+/// Reference: https://docs.cosmos.network/main/learn/advanced/transactions
+#[derive(Debug, Clone)]
+pub struct DirectSignDoc {
+    pub body_bytes: Vec<u8>,
+    pub auth_info_bytes: Vec<u8>,
+    pub chain_id: String,
+    pub account_number: u64,
+    /// Signatures already present when the input was a (partially signed) TxRaw
+    signatures: Vec<Vec<u8>>,
+}
+
+/// What a Cosmos wallet can be asked to sign
+#[derive(Debug, Clone)]
+pub enum CosmosSignDoc {
+    /// Protobuf SignDoc (SIGN_MODE_DIRECT)
+    Direct(DirectSignDoc),
+    /// Legacy amino-JSON StdSignDoc (SIGN_MODE_LEGACY_AMINO_JSON)
+    Amino(Value),
+}
+
+impl CosmosSignDoc {
+    /// Parses an amino-JSON StdSignDoc, a direct sign doc as JSON (Keplr `signDirect` shape), or a
+    /// hex/base64 protobuf SignDoc. A protobuf TxRaw is accepted as well when `chain_id` and
+    /// `account_number` are given, since it carries neither.
+    pub fn parse(input: &[u8], chain_id: Option<&str>, account_number: Option<u64>) -> Result<Self> {
+        let text = std::str::from_utf8(input)
+            .context("Cosmos sign docs are expected as JSON, hex or base64 text")?
+            .trim();
+
+        let doc = if text.starts_with('{') {
+            let value: Value = serde_json::from_str(text).context("Invalid sign doc JSON")?;
+            if value.get("msgs").is_some() {
+                Self::Amino(parse_amino_doc(value)?)
+            } else {
+                Self::Direct(parse_direct_json(&value, chain_id, account_number)?)
+            }
+        } else {
+            Self::Direct(parse_direct_protobuf(&decode_binary(text)?, chain_id, account_number)?)
+        };
+
+        // An explicit chain ID or account number must agree with the one being signed
+        let (doc_chain_id, doc_account_number) = doc.context_fields();
+        if let Some(chain_id) = chain_id {
+            if chain_id != doc_chain_id {
+                bail!("--chain-id {} does not match the sign doc's chain ID {}", chain_id, doc_chain_id);
+            }
+        }
+        if let Some(account_number) = account_number {
+            if account_number.to_string() != doc_account_number {
+                bail!("--account-number {} does not match the sign doc's account number {}", account_number, doc_account_number);
+            }
+        }
+        Ok(doc)
+    }
+
+    fn context_fields(&self) -> (String, String) {
+        match self {
+            Self::Direct(doc) => (doc.chain_id.clone(), doc.account_number.to_string()),
+            Self::Amino(doc) => (
+                doc["chain_id"].as_str().unwrap_or_default().to_string(),
+                doc["account_number"].as_str().unwrap_or_default().to_string(),
+            ),
+        }
+    }
+
+    /// Labelled fields shown before signing
+    pub fn details(&self) -> Result<Vec<(String, String)>> {
+        match self {
+            Self::Direct(doc) => doc.details(),
+            Self::Amino(doc) => Ok(amino_details(doc)),
+        }
+    }
+
+    /// Signs with `signing_key`. Direct docs become a broadcastable TxRaw (base64); amino docs
+    /// return the signed doc and StdSignature as JSON, like Keplr's `signAmino`.
+    pub fn sign(&self, signing_key: &SigningKey) -> Result<SignedTransaction> {
+        let public_key = compressed_public_key(signing_key);
+        match self {
+            Self::Direct(doc) => {
+                let auth_info = doc.auth_info()?;
+                let index = doc.signer_index(&auth_info, &public_key)?;
+                let signature = sign_bytes(signing_key, &doc.encode()?)?;
+
+                let mut signatures = doc.signatures.clone();
+                signatures.resize(signatures.len().max(auth_info.signer_infos.len()), Vec::new());
+                signatures[index] = signature.to_vec();
+
+                let tx_raw = encode_tx_raw(&doc.body_bytes, &doc.auth_info_bytes, &signatures)?;
+                let hash = hex::encode_upper(Sha256::digest(&tx_raw));
+                Ok(SignedTransaction { encoded: BASE64.encode(tx_raw), hash: Some(hash) })
+            }
+            Self::Amino(doc) => {
+                check_amino_signer(doc, &public_key)?;
+                let signature = sign_bytes(signing_key, &amino_sign_bytes(doc))?;
+                let response = json!({
+                    "signed": doc,
+                    "signature": std_signature(&public_key, &signature),
+                });
+                Ok(SignedTransaction { encoded: response.to_string(), hash: None })
+            }
+        }
+    }
+}
+
+impl DirectSignDoc {
+    /// Protobuf SignDoc, the bytes that are hashed and signed
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let sign_doc = SignDoc {
+            body_bytes: self.body_bytes.clone(),
+            auth_info_bytes: self.auth_info_bytes.clone(),
+            chain_id: self.chain_id.clone(),
+            account_number: self.account_number,
+        };
+        sign_doc.into_bytes().map_err(|e| anyhow::anyhow!("Failed to encode SignDoc: {}", e))
+    }
+
+    fn body(&self) -> Result<Body> {
+        let body = TxBody::decode(self.body_bytes.as_slice()).context("Invalid TxBody")?;
+        Body::try_from(body).map_err(|e| anyhow::anyhow!("Invalid TxBody: {}", e))
+    }
+
+    fn auth_info(&self) -> Result<AuthInfo> {
+        let auth_info = ProtoAuthInfo::decode(self.auth_info_bytes.as_slice()).context("Invalid AuthInfo")?;
+        AuthInfo::try_from(auth_info).map_err(|e| anyhow::anyhow!("Invalid AuthInfo: {}", e))
+    }
+
+    /// Position of `public_key` among the AuthInfo signer infos, which is where its signature goes
+    fn signer_index(&self, auth_info: &AuthInfo, public_key: &[u8]) -> Result<usize> {
+        for (index, signer_info) in auth_info.signer_infos.iter().enumerate() {
+            let key = signer_info.public_key.as_ref().and_then(SignerPublicKey::single);
+            if !key.is_some_and(|key| key.type_url() == PublicKey::SECP256K1_TYPE_URL && key.to_bytes() == public_key) {
+                continue;
+            }
+            return match &signer_info.mode_info {
+                ModeInfo::Single(single) if single.mode == SignMode::Direct => Ok(index),
+                ModeInfo::Single(single) => bail!(
+                    "The wallet's signer info expects {} rather than SIGN_MODE_DIRECT", single.mode.as_str_name()
+                ),
+                ModeInfo::Multi(_) => bail!("Multisig signer infos are not supported"),
+            };
+        }
+        bail!("The transaction's AuthInfo does not list this wallet's public key as a signer")
+    }
+
+    fn details(&self) -> Result<Vec<(String, String)>> {
+        let mut details = vec![
+            ("Sign Mode".to_string(), "direct".to_string()),
+            ("Chain ID".to_string(), self.chain_id.clone()),
+            ("Account Number".to_string(), self.account_number.to_string()),
+        ];
+
+        let auth_info = self.auth_info()?;
+        for (index, signer_info) in auth_info.signer_infos.iter().enumerate() {
+            let sequence = signer_info.sequence.to_string();
+            match auth_info.signer_infos.len() {
+                1 => details.push(("Sequence".to_string(), sequence)),
+                _ => details.push((format!("Signer #{} Sequence", index), sequence)),
+            }
+        }
+        let fee = &auth_info.fee;
+        details.push(("Fee".to_string(), format_coins(&fee.amount)));
+        details.push(("Gas Limit".to_string(), fee.gas_limit.to_string()));
+        if let Some(payer) = &fee.payer {
+            details.push(("Fee Payer".to_string(), payer.to_string()));
+        }
+        if let Some(granter) = &fee.granter {
+            details.push(("Fee Granter".to_string(), granter.to_string()));
+        }
+
+        let body = self.body()?;
+        if !body.memo.is_empty() {
+            details.push(("Memo".to_string(), body.memo.clone()));
+        }
+        if body.timeout_height.value() != 0 {
+            details.push(("Timeout Height".to_string(), body.timeout_height.to_string()));
+        }
+        for (index, message) in body.messages.iter().enumerate() {
+            details.push((format!("Message #{}", index), describe_message(message)?));
+        }
+        if !self.signatures.is_empty() {
+            let present = self.signatures.iter().filter(|signature| !signature.is_empty()).count();
+            details.push(("Signatures".to_string(), format!("{} present", present)));
+        }
+        Ok(details)
+    }
+}
+
+fn parse_direct_json(value: &Value, chain_id: Option<&str>, account_number: Option<u64>) -> Result<DirectSignDoc> {
+    let field = |snake: &str, camel: &str| value.get(snake).or_else(|| value.get(camel));
+    let bytes = |snake: &str, camel: &str| -> Result<Vec<u8>> {
+        let encoded = field(snake, camel).and_then(Value::as_str)
+            .with_context(|| format!("Direct sign doc is missing {}", snake))?;
+        BASE64.decode(encoded).with_context(|| format!("{} is not base64", snake))
+    };
+
+    let body_bytes = bytes("body_bytes", "bodyBytes")?;
+    let auth_info_bytes = bytes("auth_info_bytes", "authInfoBytes")?;
+    let doc_chain_id = match field("chain_id", "chainId") {
+        Some(value) => value.as_str().context("chain_id must be a string")?.to_string(),
+        None => chain_id.context("The sign doc has no chain_id; pass --chain-id")?.to_string(),
+    };
+    let doc_account_number = match field("account_number", "accountNumber") {
+        Some(Value::String(number)) => number.parse().context("account_number is not a number")?,
+        Some(Value::Number(number)) => number.as_u64().context("account_number is not a number")?,
+        Some(_) => bail!("account_number must be a number or a string"),
+        None => account_number.context("The sign doc has no account_number; pass --account-number")?,
+    };
+    Ok(DirectSignDoc {
+        body_bytes,
+        auth_info_bytes,
+        chain_id: doc_chain_id,
+        account_number: doc_account_number,
+        signatures: Vec::new(),
+    })
+}
+
+fn parse_direct_protobuf(bytes: &[u8], chain_id: Option<&str>, account_number: Option<u64>) -> Result<DirectSignDoc> {
+    let tx_raw = TxRaw::decode(bytes).context("Invalid protobuf sign doc")?;
+    if tx_raw.body_bytes.is_empty() {
+        bail!("Missing body_bytes");
+    }
+    if tx_raw.auth_info_bytes.is_empty() {
+        bail!("Missing auth_info_bytes");
+    }
+
+    // SignDoc and TxRaw share fields 1-3; field 3 is the chain ID in a SignDoc and a signature
+    // in a TxRaw. Only a SignDoc has field 4, though it is omitted for account number 0.
+    let sign_doc = ProtoSignDoc::decode(bytes).ok().filter(|sign_doc| {
+        sign_doc.account_number != 0
+            || matches!(tx_raw.signatures.as_slice(), [value] if value.len() <= MAX_CHAIN_ID_LEN)
+    });
+    if let Some(sign_doc) = sign_doc {
+        return Ok(DirectSignDoc {
+            body_bytes: sign_doc.body_bytes,
+            auth_info_bytes: sign_doc.auth_info_bytes,
+            chain_id: sign_doc.chain_id,
+            account_number: sign_doc.account_number,
+            signatures: Vec::new(),
+        });
+    }
+
+    let (Some(chain_id), Some(account_number)) = (chain_id, account_number) else {
+        bail!("This looks like a TxRaw, which does not carry the chain ID or account number; pass --chain-id and --account-number");
+    };
+    Ok(DirectSignDoc {
+        body_bytes: tx_raw.body_bytes,
+        auth_info_bytes: tx_raw.auth_info_bytes,
+        chain_id: chain_id.to_string(),
+        account_number,
+        signatures: tx_raw.signatures,
+    })
+}
+
+fn encode_tx_raw(body_bytes: &[u8], auth_info_bytes: &[u8], signatures: &[Vec<u8>]) -> Result<Vec<u8>> {
+    // Repeated fields keep empty entries so signatures stay aligned with signer infos
+    let tx_raw = Raw::from(TxRaw {
+        body_bytes: body_bytes.to_vec(),
+        auth_info_bytes: auth_info_bytes.to_vec(),
+        signatures: signatures.to_vec(),
+    });
+    tx_raw.to_bytes().map_err(|e| anyhow::anyhow!("Failed to encode TxRaw: {}", e))
+}
+
+/// ICS-20 MsgTransfer; cosmos-sdk-proto does not include the IBC protos
+/// Reference: https://github.com/cosmos/ibc-go/blob/main/proto/ibc/applications/transfer/v1/tx.proto
+#[derive(Clone, PartialEq, prost::Message)]
+struct MsgTransfer {
+    #[prost(string, tag = "1")]
+    source_port: String,
+    #[prost(string, tag = "2")]
+    source_channel: String,
+    #[prost(message, optional, tag = "3")]
+    token: Option<ProtoCoin>,
+    #[prost(string, tag = "4")]
+    sender: String,
+    #[prost(string, tag = "5")]
+    receiver: String,
+}
+
+fn decode_message<T: Message + Default>(any: &Any) -> Result<T> {
+    T::decode(any.value.as_slice()).with_context(|| format!("Invalid {} message", any.type_url))
+}
+
+/// One-line description of a TxBody message
+fn describe_message(any: &Any) -> Result<String> {
+    Ok(match any.type_url.as_str() {
+        "/cosmos.bank.v1beta1.MsgSend" => {
+            let msg: bank::v1beta1::MsgSend = decode_message(any)?;
+            format!("Send {} from {} to {}", format_proto_coins(&msg.amount), msg.from_address, msg.to_address)
+        }
+        "/cosmos.staking.v1beta1.MsgDelegate" => {
+            let msg: staking::v1beta1::MsgDelegate = decode_message(any)?;
+            format!("Delegate {} from {} to {}", format_proto_coin(msg.amount.as_ref()), msg.delegator_address, msg.validator_address)
+        }
+        "/cosmos.staking.v1beta1.MsgUndelegate" => {
+            let msg: staking::v1beta1::MsgUndelegate = decode_message(any)?;
+            format!("Undelegate {} of {} from {}", format_proto_coin(msg.amount.as_ref()), msg.delegator_address, msg.validator_address)
+        }
+        "/cosmos.staking.v1beta1.MsgBeginRedelegate" => {
+            let msg: staking::v1beta1::MsgBeginRedelegate = decode_message(any)?;
+            format!(
+                "Redelegate {} of {} from {} to {}",
+                format_proto_coin(msg.amount.as_ref()), msg.delegator_address, msg.validator_src_address, msg.validator_dst_address
+            )
+        }
+        "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward" => {
+            let msg: distribution::v1beta1::MsgWithdrawDelegatorReward = decode_message(any)?;
+            format!("Withdraw rewards of {} from {}", msg.delegator_address, msg.validator_address)
+        }
+        "/cosmos.gov.v1beta1.MsgVote" => {
+            let msg: gov::v1beta1::MsgVote = decode_message(any)?;
+            format!("Vote {} on proposal {} as {}", vote_option(msg.option), msg.proposal_id, msg.voter)
+        }
+        "/cosmos.gov.v1.MsgVote" => {
+            let msg: gov::v1::MsgVote = decode_message(any)?;
+            format!("Vote {} on proposal {} as {}", vote_option(msg.option), msg.proposal_id, msg.voter)
+        }
+        "/ibc.applications.transfer.v1.MsgTransfer" => {
+            let msg: MsgTransfer = decode_message(any)?;
+            format!(
+                "IBC transfer of {} from {} to {} via {}/{}",
+                format_proto_coin(msg.token.as_ref()), msg.sender, msg.receiver, msg.source_port, msg.source_channel
+            )
+        }
+        _ => format!("{} ({} bytes)", any.type_url, any.value.len()),
+    })
+}
+
+fn vote_option(option: i32) -> &'static str {
+    match option {
+        1 => "yes",
+        2 => "abstain",
+        3 => "no",
+        4 => "no with veto",
+        _ => "unspecified",
+    }
+}
+
+fn format_coins(coins: &[Coin]) -> String {
+    let coins: Vec<String> = coins.iter().map(Coin::to_string).collect();
+    if coins.is_empty() { "none".to_string() } else { coins.join(", ") }
+}
+
+fn format_proto_coin(coin: Option<&ProtoCoin>) -> String {
+    coin.map_or_else(|| "?".to_string(), |coin| format!("{}{}", coin.amount, coin.denom))
+}
+
+fn format_proto_coins(coins: &[ProtoCoin]) -> String {
+    coins.iter().map(|coin| format_proto_coin(Some(coin))).collect::<Vec<_>>().join(", ")
+}
+
+// ========== AMINO JSON ==========
+
+/// Checks the StdSignDoc fields; numbers must be strings as in the SDK's amino JSON
+fn parse_amino_doc(doc: Value) -> Result<Value> {
+    for field in ["chain_id", "account_number", "sequence", "memo"] {
+        if !doc.get(field).is_some_and(Value::is_string) {
+            bail!("StdSignDoc field {} must be a string", field);
+        }
+    }
+    if !doc["msgs"].is_array() {
+        bail!("StdSignDoc field msgs must be an array");
+    }
+    if !doc["fee"]["gas"].is_string() || !doc["fee"]["amount"].is_array() {
+        bail!("StdSignDoc fee must have a gas string and an amount array");
+    }
+    Ok(doc)
+}
+
+/// Bytes signed for amino JSON: keys sorted, no whitespace, HTML characters escaped as Go does
+pub fn amino_sign_bytes(doc: &Value) -> Vec<u8> {
+    let mut json = String::new();
+    write_canonical_json(&mut json, doc);
+    json.into_bytes()
+}
+
+fn write_canonical_json(out: &mut String, value: &Value) {
+    match value {
+        Value::Object(map) => {
+            let mut keys: Vec<&String> = map.keys().collect();
+            keys.sort();
+            out.push('{');
+            for (index, key) in keys.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_json_string(out, key);
+                out.push(':');
+                write_canonical_json(out, &map[key]);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(out, item);
+            }
+            out.push(']');
+        }
+        Value::String(text) => write_json_string(out, text),
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+fn write_json_string(out: &mut String, text: &str) {
+    let escaped = Value::String(text.to_string()).to_string()
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+        .replace('\u{2028}', "\\u2028")
+        .replace('\u{2029}', "\\u2029");
+    out.push_str(&escaped);
+}
+
+/// Message fields holding an address that has to sign the message
+const AMINO_SIGNER_FIELDS: &[&str] = &[
+    "from_address", "delegator_address", "voter", "depositor", "proposer", "sender", "signer",
+    "granter", "admin", "authority", "creator", "owner",
+];
+
+/// Fields that name the signer only when the message lacks the second field: a validator signs
+/// its own messages but not delegations to it, a grantee signs MsgExec but not MsgGrant
+const AMINO_CONDITIONAL_SIGNER_FIELDS: &[(&str, &str)] = &[
+    ("validator_address", "delegator_address"),
+    ("grantee", "granter"),
+];
+
+/// Refuses to sign unless the key's address signs one of the messages or pays the fee. Addresses
+/// are compared by their bech32 data, since the doc does not say which prefix the chain uses.
+fn check_amino_signer(doc: &Value, public_key: &[u8]) -> Result<()> {
+    let hash = Ripemd160::digest(Sha256::digest(public_key));
+    let mut signers: Vec<&str> = doc["fee"]["payer"].as_str().into_iter().collect();
+    for message in doc["msgs"].as_array().into_iter().flatten() {
+        let value = &message["value"];
+        signers.extend(AMINO_SIGNER_FIELDS.iter().filter_map(|field| value[field].as_str()));
+        for (field, unless) in AMINO_CONDITIONAL_SIGNER_FIELDS {
+            if value.get(unless).is_none() {
+                signers.extend(value[field].as_str());
+            }
+        }
+        // MsgMultiSend
+        signers.extend(value["inputs"].as_array().into_iter().flatten().filter_map(|input| input["address"].as_str()));
+    }
+
+    if !signers.into_iter().any(|address| bech32::decode(address).is_ok_and(|(_, data)| data == hash.as_slice())) {
+        bail!("This wallet's address neither signs any of the sign doc's messages nor pays its fee");
+    }
+    Ok(())
+}
+
+fn amino_details(doc: &Value) -> Vec<(String, String)> {
+    let text = |value: &Value| value.as_str().unwrap_or_default().to_string();
+    let coins = |value: &Value| {
+        let coins: Vec<String> = value.as_array().into_iter().flatten()
+            .map(|coin| format!("{}{}", text(&coin["amount"]), text(&coin["denom"])))
+            .collect();
+        if coins.is_empty() { "none".to_string() } else { coins.join(", ") }
+    };
+
+    let mut details = vec![
+        ("Sign Mode".to_string(), "amino JSON".to_string()),
+        ("Chain ID".to_string(), text(&doc["chain_id"])),
+        ("Account Number".to_string(), text(&doc["account_number"])),
+        ("Sequence".to_string(), text(&doc["sequence"])),
+        ("Fee".to_string(), coins(&doc["fee"]["amount"])),
+        ("Gas Limit".to_string(), text(&doc["fee"]["gas"])),
+    ];
+    if !text(&doc["memo"]).is_empty() {
+        details.push(("Memo".to_string(), text(&doc["memo"])));
+    }
+    for (index, message) in doc["msgs"].as_array().into_iter().flatten().enumerate() {
+        let value = &message["value"];
+        let description = match message["type"].as_str().unwrap_or_default() {
+            "cosmos-sdk/MsgSend" => format!(
+                "Send {} from {} to {}",
+                coins(&value["amount"]), text(&value["from_address"]), text(&value["to_address"])
+            ),
+            "cosmos-sdk/MsgDelegate" => format!(
+                "Delegate {}{} from {} to {}",
+                text(&value["amount"]["amount"]), text(&value["amount"]["denom"]),
+                text(&value["delegator_address"]), text(&value["validator_address"])
+            ),
+            other => format!("{} {}", other, value),
+        };
+        details.push((format!("Message #{}", index), description));
+    }
+    details
+}
+
+// ========== ADR-036 ==========
+// Reference: https://github.com/cosmos/cosmos-sdk/blob/main/docs/architecture/adr-036-arbitrary-signature.md
+
+/// Amino StdSignDoc wrapping arbitrary data for off-chain signing (Keplr `signArbitrary`)
+pub fn adr036_sign_doc(signer: &str, data: &[u8]) -> Value {
+    json!({
+        "chain_id": "",
+        "account_number": "0",
+        "sequence": "0",
+        "fee": { "gas": "0", "amount": [] },
+        "msgs": [{
+            "type": "sign/MsgSignData",
+            "value": { "signer": signer, "data": BASE64.encode(data) },
+        }],
+        "memo": "",
+    })
+}
+
+/// Signs an ADR-036 message and returns the StdSignature JSON (public key and signature)
+pub fn sign_adr036(signing_key: &SigningKey, address: &str, message: &[u8]) -> Result<String> {
+    let public_key = compressed_public_key(signing_key);
+    if cosmos_address(&public_key, address_prefix(address)?)? != address {
+        bail!("Private key does not belong to {}", address);
+    }
+    let signature = sign_bytes(signing_key, &amino_sign_bytes(&adr036_sign_doc(address, message)))?;
+    Ok(std_signature(&public_key, &signature).to_string())
+}
+
+/// Verifies an ADR-036 signature given as StdSignature JSON or as a bare base64 signature,
+/// in which case the public key is recovered and checked against `address`
+pub fn verify_adr036(address: &str, message: &[u8], signature: &str) -> Result<()> {
+    let prefix = address_prefix(address)?;
+    let digest: [u8; 32] = Sha256::digest(amino_sign_bytes(&adr036_sign_doc(address, message))).into();
+    let signature = signature.trim();
+
+    let (public_key, signature) = if signature.starts_with('{') {
+        let std_signature: Value = serde_json::from_str(signature).context("Invalid StdSignature JSON")?;
+        let public_key = std_signature["pub_key"]["value"].as_str()
+            .and_then(|value| BASE64.decode(value).ok())
+            .context("StdSignature has no pub_key value")?;
+        let signature = std_signature["signature"].as_str()
+            .and_then(|value| BASE64.decode(value).ok())
+            .context("StdSignature has no base64 signature")?;
+        (Some(public_key), signature)
+    } else {
+        (None, BASE64.decode(signature).context("Cosmos signatures are expected as StdSignature JSON or base64")?)
+    };
+    let signature = Signature::from_slice(&signature).context("Signature must be 64 bytes (r || s)")?;
+
+    let candidates: Vec<VerifyingKey> = match public_key {
+        Some(public_key) => vec![VerifyingKey::from_sec1_bytes(&public_key).context("Invalid secp256k1 public key")?],
+        None => (0..2)
+            .filter_map(RecoveryId::from_byte)
+            .filter_map(|id| VerifyingKey::recover_from_prehash(&digest, &signature, id).ok())
+            .collect(),
+    };
+    for verifying_key in candidates {
+        let public_key = verifying_key.to_encoded_point(true);
+        if cosmos_address(public_key.as_bytes(), prefix)? != address {
+            continue;
+        }
+        if verifying_key.verify_prehash(&digest, &signature).is_ok() {
+            return Ok(());
+        }
+    }
+    bail!("The signature was not made by this address")
+}
+
+// ========== HELPERS ==========
+
+/// Bech32 account address: RIPEMD-160 of SHA-256 of the compressed public key
+pub fn cosmos_address(public_key: &[u8], prefix: &str) -> Result<String> {
+    let hash = Ripemd160::digest(Sha256::digest(public_key));
+    let hrp = bech32::Hrp::parse(prefix).context("Invalid bech32 prefix")?;
+    bech32::encode::<bech32::Bech32>(hrp, &hash).context("Failed to encode bech32 address")
+}
+
+fn address_prefix(address: &str) -> Result<&str> {
+    bech32::decode(address).with_context(|| format!("Invalid Cosmos address: {}", address))?;
+    Ok(address.rsplit_once('1').map(|(prefix, _)| prefix).unwrap_or_default())
+}
+
+fn compressed_public_key(signing_key: &SigningKey) -> Vec<u8> {
+    signing_key.verifying_key().to_encoded_point(true).as_bytes().to_vec()
+}
+
+/// ECDSA over SHA-256 of the sign bytes, as a 64-byte low-S `r || s`
+fn sign_bytes(signing_key: &SigningKey, message: &[u8]) -> Result<[u8; 64]> {
+    let digest: [u8; 32] = Sha256::digest(message).into();
+    Ok(sign_hash(signing_key, &B256::from(digest))?.0)
+}
+
+fn std_signature(public_key: &[u8], signature: &[u8]) -> Value {
+    json!({
+        "pub_key": { "type": AMINO_SECP256K1_PUBKEY_TYPE, "value": BASE64.encode(public_key) },
+        "signature": BASE64.encode(signature),
+    })
+}
+
+fn decode_binary(text: &str) -> Result<Vec<u8>> {
+    let text: String = text.split_whitespace().collect();
+    let stripped = text.trim_start_matches("0x");
+    if stripped.len() % 2 == 0 && !stripped.is_empty() && stripped.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return hex::decode(stripped).context("Invalid hex");
+    }
+    BASE64.decode(text.as_bytes()).context("Sign doc is neither JSON, hex nor base64")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmrs::tx::{Fee, SignerInfo};
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_slice(&[0x11; 32]).unwrap()
+    }
+
+    /// TxBody with one MsgSend and AuthInfo with a single direct-mode signer
+    fn send_body_and_auth_info(public_key: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let send = bank::v1beta1::MsgSend {
+            from_address: "cosmos1from".to_string(),
+            to_address: "cosmos1to".to_string(),
+            amount: vec![ProtoCoin { denom: "uatom".to_string(), amount: "1000000".to_string() }],
+        };
+        let body = Body::new(vec![Any::from_msg(&send).unwrap()], "thanks", 0u32);
+
+        let public_key = PublicKey::try_from(cosmrs::proto::cosmos::crypto::secp256k1::PubKey { key: public_key.to_vec() }).unwrap();
+        let fee = Fee::from_amount_and_gas(Coin::new(5000, "uatom").unwrap(), 200_000u64);
+        let auth_info = SignerInfo::single_direct(Some(public_key), 12).auth_info(fee);
+        (body.into_bytes().unwrap(), auth_info.into_bytes().unwrap())
+    }
+
+    #[test]
+    fn test_canonical_amino_json() {
+        let doc = json!({ "b": "<&>", "a": [1, { "d": "x", "c": null }] });
+        assert_eq!(
+            String::from_utf8(amino_sign_bytes(&doc)).unwrap(),
+            r#"{"a":[1,{"c":null,"d":"x"}],"b":"\u003c\u0026\u003e"}"#
+        );
+    }
+
+    #[test]
+    fn test_direct_sign_doc() {
+        let key = signing_key();
+        let public_key = compressed_public_key(&key);
+        let (body, auth_info) = send_body_and_auth_info(&public_key);
+        let doc = DirectSignDoc {
+            body_bytes: body.clone(),
+            auth_info_bytes: auth_info.clone(),
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 42,
+            signatures: Vec::new(),
+        };
+        let encoded = hex::encode(doc.encode().unwrap());
+
+        let parsed = CosmosSignDoc::parse(encoded.as_bytes(), None, None).unwrap();
+        let details = parsed.details().unwrap();
+        assert!(details.contains(&("Chain ID".to_string(), "cosmoshub-4".to_string())));
+        assert!(details.contains(&("Sequence".to_string(), "12".to_string())));
+        assert!(details.contains(&("Fee".to_string(), "5000uatom".to_string())));
+        assert!(details.contains(&("Message #0".to_string(), "Send 1000000uatom from cosmos1from to cosmos1to".to_string())));
+        assert!(CosmosSignDoc::parse(encoded.as_bytes(), Some("osmosis-1"), None).is_err());
+
+        let signed = parsed.sign(&key).unwrap();
+        let tx_raw = BASE64.decode(&signed.encoded).unwrap();
+        assert_eq!(TxRaw::decode(tx_raw.as_slice()).unwrap().body_bytes, body);
+        let tx = cosmrs::Tx::from_bytes(&tx_raw).unwrap();
+        assert_eq!(tx.body.memo, "thanks");
+        let signature = Signature::from_slice(&tx.signatures[0]).unwrap();
+        use k256::ecdsa::signature::Verifier;
+        key.verifying_key().verify(&doc.encode().unwrap(), &signature).unwrap();
+        assert_eq!(signed.hash.unwrap(), hex::encode_upper(Sha256::digest(tx_raw.as_slice())));
+
+        // An unsigned TxRaw needs the chain ID and account number from outside
+        let tx_raw = BASE64.encode(encode_tx_raw(&body, &auth_info, &[]).unwrap());
+        assert!(CosmosSignDoc::parse(tx_raw.as_bytes(), None, None).is_err());
+        let from_tx = CosmosSignDoc::parse(tx_raw.as_bytes(), Some("cosmoshub-4"), Some(42)).unwrap();
+        assert_eq!(from_tx.sign(&key).unwrap().encoded, signed.encoded);
+
+        // Keys that are not listed as signers are refused
+        assert!(parsed.sign(&SigningKey::from_slice(&[0x22; 32]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_amino_sign_doc() {
+        let key = signing_key();
+        let address = cosmos_address(&compressed_public_key(&key), "cosmos").unwrap();
+        let doc = json!({
+            "chain_id": "cosmoshub-4",
+            "account_number": "42",
+            "sequence": "12",
+            "fee": { "amount": [{ "denom": "uatom", "amount": "5000" }], "gas": "200000" },
+            "msgs": [{ "type": "cosmos-sdk/MsgSend", "value": {
+                "from_address": address, "to_address": "cosmos1to",
+                "amount": [{ "denom": "uatom", "amount": "1000000" }],
+            }}],
+            "memo": "",
+        });
+        let parsed = CosmosSignDoc::parse(doc.to_string().as_bytes(), Some("cosmoshub-4"), Some(42)).unwrap();
+        let details = parsed.details().unwrap();
+        assert!(details.contains(&("Message #0".to_string(), format!("Send 1000000uatom from {} to cosmos1to", address))));
+
+        let signed: Value = serde_json::from_str(&parsed.sign(&key).unwrap().encoded).unwrap();
+        assert_eq!(signed["signed"], doc);
+        let signature = BASE64.decode(signed["signature"]["signature"].as_str().unwrap()).unwrap();
+        use k256::ecdsa::signature::Verifier;
+        key.verifying_key().verify(&amino_sign_bytes(&doc), &Signature::from_slice(&signature).unwrap()).unwrap();
+
+        // Only the message signer or the fee payer may sign, whatever the chain's prefix
+        let other = SigningKey::from_slice(&[0x22; 32]).unwrap();
+        assert!(parsed.sign(&other).is_err());
+        let mut sponsored = doc.clone();
+        sponsored["fee"]["payer"] = json!(cosmos_address(&compressed_public_key(&other), "osmo").unwrap());
+        let sponsored = CosmosSignDoc::parse(sponsored.to_string().as_bytes(), None, None).unwrap();
+        sponsored.sign(&other).unwrap();
+
+        let mut numeric = doc.clone();
+        numeric["sequence"] = json!(12);
+        assert!(CosmosSignDoc::parse(numeric.to_string().as_bytes(), None, None).is_err());
+    }
+
+    #[test]
+    fn test_adr036_message() {
+        let key = signing_key();
+        let address = cosmos_address(&compressed_public_key(&key), "cosmos").unwrap();
+        assert_eq!(
+            String::from_utf8(amino_sign_bytes(&adr036_sign_doc(&address, b"hello"))).unwrap(),
+            format!(
+                r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","msgs":[{{"type":"sign/MsgSignData","value":{{"data":"aGVsbG8=","signer":"{}"}}}}],"sequence":"0"}}"#,
+                address
+            )
+        );
+
+        let signature = sign_adr036(&key, &address, b"hello").unwrap();
+        verify_adr036(&address, b"hello", &signature).unwrap();
+        assert!(verify_adr036(&address, b"hullo", &signature).is_err());
+
+        // A bare signature is checked by recovering the public key
+        let bare: Value = serde_json::from_str(&signature).unwrap();
+        verify_adr036(&address, b"hello", bare["signature"].as_str().unwrap()).unwrap();
+
+        let other = cosmos_address(&compressed_public_key(&SigningKey::from_slice(&[0x22; 32]).unwrap()), "cosmos").unwrap();
+        assert!(verify_adr036(&other, b"hello", &signature).is_err());
+        assert!(sign_adr036(&key, &other, b"hello").is_err());
+    }
+}
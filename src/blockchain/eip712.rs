@@ -0,0 +1,363 @@
+use anyhow::{Result, Context, bail};
+use alloy_primitives::{B256, U256, keccak256};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+
+use crate::blockchain::evm::{parse_address, parse_hex, parse_u256};
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// Domain fields in the order the spec lists them, used when `types` omits `EIP712Domain`
+const DOMAIN_FIELDS: [(&str, &str); 5] = [
+    ("name", "string"),
+    ("version", "string"),
+    ("chainId", "uint256"),
+    ("verifyingContract", "address"),
+    ("salt", "bytes32"),
+];
+
+#[derive(Debug, Clone)]
+struct TypedField {
+    name: String,
+    kind: String,
+}
+
+/// EIP-712 typed structured data, as passed to `eth_signTypedData_v4`
+/// Reference: https://eips.ethereum.org/EIPS/eip-712
+#[derive(Debug, Clone)]
+pub struct TypedData {
+    types: HashMap<String, Vec<TypedField>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+impl TypedData {
+    pub fn from_json(json: &Value) -> Result<Self> {
+        let types_json = json.get("types").and_then(Value::as_object)
+            .context("EIP-712 document needs a 'types' object")?;
+
+        let mut types = HashMap::new();
+        for (type_name, fields) in types_json {
+            let fields = fields.as_array()
+                .with_context(|| format!("EIP-712 type '{}' must be an array of fields", type_name))?;
+            let fields = fields.iter()
+                .map(|field| {
+                    let name = field.get("name").and_then(Value::as_str);
+                    let kind = field.get("type").and_then(Value::as_str);
+                    match (name, kind) {
+                        (Some(name), Some(kind)) => Ok(TypedField { name: name.to_string(), kind: kind.to_string() }),
+                        _ => bail!("Fields of EIP-712 type '{}' need a 'name' and a 'type'", type_name),
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+            types.insert(type_name.clone(), fields);
+        }
+
+        let primary_type = json.get("primaryType").and_then(Value::as_str)
+            .context("EIP-712 document needs a 'primaryType'")?
+            .to_string();
+        let domain = json.get("domain").cloned().unwrap_or_else(|| Value::Object(Default::default()));
+        let domain_fields = domain.as_object().context("EIP-712 'domain' must be an object")?;
+        let message = json.get("message").cloned()
+            .context("EIP-712 document needs a 'message'")?;
+
+        if !types.contains_key(DOMAIN_TYPE) {
+            let inferred = DOMAIN_FIELDS.iter()
+                .filter(|(name, _)| domain_fields.contains_key(*name))
+                .map(|(name, kind)| TypedField { name: name.to_string(), kind: kind.to_string() })
+                .collect();
+            types.insert(DOMAIN_TYPE.to_string(), inferred);
+        }
+        if !types.contains_key(&primary_type) {
+            bail!("primaryType '{}' is not defined in 'types'", primary_type);
+        }
+
+        Ok(Self { types, primary_type, domain, message })
+    }
+
+    /// Chain ID from the domain, if present
+    pub fn domain_chain_id(&self) -> Option<u64> {
+        let value = self.domain.get("chainId")?;
+        u64::try_from(parse_u256(value, "chainId").ok()?).ok()
+    }
+
+    /// `Name(type field,...)` followed by the referenced struct types in alphabetical order
+    pub fn encode_type(&self, type_name: &str) -> Result<String> {
+        let mut dependencies = BTreeSet::new();
+        self.collect_dependencies(type_name, &mut dependencies);
+        dependencies.remove(type_name);
+
+        std::iter::once(type_name)
+            .chain(dependencies.iter().map(String::as_str))
+            .map(|name| {
+                let fields = self.types.get(name)
+                    .with_context(|| format!("EIP-712 type '{}' is not defined", name))?;
+                let fields: Vec<String> = fields.iter().map(|field| format!("{} {}", field.kind, field.name)).collect();
+                Ok(format!("{}({})", name, fields.join(",")))
+            })
+            .collect()
+    }
+
+    fn collect_dependencies(&self, type_name: &str, found: &mut BTreeSet<String>) {
+        let base = type_name.split('[').next().unwrap_or(type_name);
+        if found.contains(base) {
+            return;
+        }
+        if let Some(fields) = self.types.get(base) {
+            found.insert(base.to_string());
+            for field in fields {
+                self.collect_dependencies(&field.kind, found);
+            }
+        }
+    }
+
+    pub fn hash_struct(&self, type_name: &str, value: &Value) -> Result<B256> {
+        let fields = self.types.get(type_name)
+            .with_context(|| format!("EIP-712 type '{}' is not defined", type_name))?;
+        let object = value.as_object()
+            .with_context(|| format!("EIP-712 value of type '{}' must be an object", type_name))?;
+
+        let mut encoded = keccak256(self.encode_type(type_name)?.as_bytes()).to_vec();
+        for field in fields {
+            let field_value = object.get(&field.name)
+                .with_context(|| format!("EIP-712 '{}' is missing field '{}'", type_name, field.name))?;
+            let word = self.encode_value(&field.kind, field_value)
+                .with_context(|| format!("Invalid value for {}.{}", type_name, field.name))?;
+            encoded.extend_from_slice(word.as_slice());
+        }
+
+        Ok(keccak256(encoded))
+    }
+
+    pub fn domain_separator(&self) -> Result<B256> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    /// keccak256(0x19 0x01 || domainSeparator || hashStruct(message))
+    pub fn signing_hash(&self) -> Result<B256> {
+        let mut encoded = vec![0x19, 0x01];
+        encoded.extend_from_slice(self.domain_separator()?.as_slice());
+        if self.primary_type != DOMAIN_TYPE {
+            encoded.extend_from_slice(self.hash_struct(&self.primary_type, &self.message)?.as_slice());
+        }
+        Ok(keccak256(encoded))
+    }
+
+    fn encode_value(&self, kind: &str, value: &Value) -> Result<B256> {
+        if let Some(element_kind) = array_element_type(kind) {
+            let items = value.as_array().context("Expected an array")?;
+            if let Some(length) = fixed_array_length(kind)? {
+                if items.len() != length {
+                    bail!("Expected {} items for {}, got {}", length, kind, items.len());
+                }
+            }
+            let mut encoded = Vec::with_capacity(items.len() * 32);
+            for item in items {
+                encoded.extend_from_slice(self.encode_value(element_kind, item)?.as_slice());
+            }
+            return Ok(keccak256(encoded));
+        }
+
+        if self.types.contains_key(kind) {
+            return self.hash_struct(kind, value);
+        }
+
+        match kind {
+            "string" => Ok(keccak256(value.as_str().context("Expected a string")?.as_bytes())),
+            "bytes" => Ok(keccak256(parse_hex(value.as_str().context("Expected a hex string")?)?)),
+            "bool" => {
+                let flag = match value {
+                    Value::Bool(flag) => *flag,
+                    Value::String(s) if s == "true" || s == "false" => s == "true",
+                    _ => bail!("Expected a boolean"),
+                };
+                Ok(B256::from(U256::from(flag as u8)))
+            }
+            "address" => {
+                let address = parse_address(value.as_str().context("Expected an address string")?)?;
+                Ok(address.into_word())
+            }
+            _ if kind.starts_with("bytes") => {
+                let size: usize = kind[5..].parse().with_context(|| format!("Unknown EIP-712 type '{}'", kind))?;
+                let bytes = parse_hex(value.as_str().context("Expected a hex string")?)?;
+                if size == 0 || size > 32 || bytes.len() > size {
+                    bail!("Value does not fit in {}", kind);
+                }
+                let mut word = [0u8; 32];
+                word[..bytes.len()].copy_from_slice(&bytes);
+                Ok(B256::from(word))
+            }
+            _ if kind.starts_with("uint") => {
+                let bits = integer_bits(&kind[4..], kind)?;
+                let number = parse_u256(value, kind)?;
+                if bits < 256 && number >= (U256::from(1) << bits) {
+                    bail!("Value does not fit in {}", kind);
+                }
+                Ok(B256::from(number))
+            }
+            _ if kind.starts_with("int") => {
+                let bits = integer_bits(&kind[3..], kind)?;
+                let negative_magnitude = match value {
+                    Value::String(s) => s.trim().strip_prefix('-').map(|digits| Value::String(digits.to_string())),
+                    Value::Number(number) => number.as_i64()
+                        .filter(|n| *n < 0)
+                        .map(|n| Value::from(n.unsigned_abs())),
+                    _ => None,
+                };
+                // int<N> covers -2^(N-1) ..= 2^(N-1) - 1
+                let limit = U256::from(1) << (bits - 1);
+                let number = match negative_magnitude {
+                    Some(magnitude) => {
+                        let magnitude = parse_u256(&magnitude, kind)?;
+                        if magnitude > limit {
+                            bail!("Value does not fit in {}", kind);
+                        }
+                        // Two's complement, sign-extended to 256 bits
+                        U256::ZERO.wrapping_sub(magnitude)
+                    }
+                    None => {
+                        let number = parse_u256(value, kind)?;
+                        if number >= limit {
+                            bail!("Value does not fit in {}", kind);
+                        }
+                        number
+                    }
+                };
+                Ok(B256::from(number))
+            }
+            _ => bail!("Unknown EIP-712 type '{}'", kind),
+        }
+    }
+}
+
+// "Person[]" -> "Person", "uint256[2][]" -> "uint256[2]"
+fn array_element_type(kind: &str) -> Option<&str> {
+    if !kind.ends_with(']') {
+        return None;
+    }
+    kind.rfind('[').map(|open| &kind[..open])
+}
+
+fn fixed_array_length(kind: &str) -> Result<Option<usize>> {
+    let open = kind.rfind('[').context("Not an array type")?;
+    let length = &kind[open + 1..kind.len() - 1];
+    if length.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(length.parse().with_context(|| format!("Invalid array length in '{}'", kind))?))
+}
+
+fn integer_bits(suffix: &str, kind: &str) -> Result<usize> {
+    if suffix.is_empty() {
+        return Ok(256);
+    }
+    match suffix.parse::<usize>() {
+        Ok(bits) if bits % 8 == 0 && (8..=256).contains(&bits) => Ok(bits),
+        _ => bail!("Unknown EIP-712 type '{}'", kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Example from the EIP-712 specification
+    fn mail() -> Value {
+        json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Person": [
+                    {"name": "name", "type": "string"},
+                    {"name": "wallet", "type": "address"}
+                ],
+                "Mail": [
+                    {"name": "from", "type": "Person"},
+                    {"name": "to", "type": "Person"},
+                    {"name": "contents", "type": "string"}
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+                "contents": "Hello, Bob!"
+            }
+        })
+    }
+
+    #[test]
+    fn test_eip712_mail_vector() {
+        let typed_data = TypedData::from_json(&mail()).unwrap();
+
+        assert_eq!(
+            typed_data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            hex::encode(typed_data.domain_separator().unwrap()),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex::encode(typed_data.hash_struct("Mail", &typed_data.message).unwrap()),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            hex::encode(typed_data.signing_hash().unwrap()),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+        assert_eq!(typed_data.domain_chain_id(), Some(1));
+    }
+
+    #[test]
+    fn test_inferred_domain_type_matches_explicit() {
+        let explicit = TypedData::from_json(&mail()).unwrap();
+
+        let mut document = mail();
+        document["types"].as_object_mut().unwrap().remove("EIP712Domain");
+        let inferred = TypedData::from_json(&document).unwrap();
+
+        assert_eq!(inferred.signing_hash().unwrap(), explicit.signing_hash().unwrap());
+    }
+
+    #[test]
+    fn test_encode_values() {
+        let typed_data = TypedData::from_json(&mail()).unwrap();
+
+        let minus_one = typed_data.encode_value("int256", &json!(-1)).unwrap();
+        assert_eq!(minus_one, B256::from([0xff; 32]));
+        assert_eq!(typed_data.encode_value("int8", &json!("-1")).unwrap(), minus_one);
+        assert!(typed_data.encode_value("uint8", &json!(256)).is_err());
+        assert!(typed_data.encode_value("int8", &json!(300)).is_err());
+        assert!(typed_data.encode_value("int8", &json!(128)).is_err());
+        assert!(typed_data.encode_value("int8", &json!(-129)).is_err());
+        assert!(typed_data.encode_value("int8", &json!(127)).is_ok());
+        assert!(typed_data.encode_value("int8", &json!("-128")).is_ok());
+        assert!(typed_data.encode_value("bytes4", &json!("0x0102030405")).is_err());
+        assert!(typed_data.encode_value("uint256[2]", &json!([1])).is_err());
+        assert!(typed_data.encode_value("Unknown", &json!({})).is_err());
+    }
+
+    #[test]
+    fn test_rejects_incomplete_documents() {
+        let mut document = mail();
+        document["primaryType"] = json!("Letter");
+        assert!(TypedData::from_json(&document).is_err());
+
+        let mut document = mail();
+        document["message"].as_object_mut().unwrap().remove("contents");
+        assert!(TypedData::from_json(&document).unwrap().signing_hash().is_err());
+    }
+}
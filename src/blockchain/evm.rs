@@ -0,0 +1,809 @@
+use anyhow::{Result, Context, bail};
+use alloy_primitives::{Address, B256, U256, keccak256};
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use serde_json::Value;
+use std::str::FromStr;
+
+use crate::blockchain::{SignedTransaction, SupportedBlockchain};
+use crate::blockchain::eip712::TypedData;
+
+/// EVM transaction envelope
+/// Reference: https://eips.ethereum.org/EIPS/eip-2718
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvmTxType {
+    /// Pre-typed transaction, signed with EIP-155 replay protection
+    Legacy,
+    /// EIP-2930 (type 1) with an access list
+    AccessList,
+    /// EIP-1559 (type 2) with priority fee and max fee
+    DynamicFee,
+}
+
+impl EvmTxType {
+    fn type_byte(&self) -> Option<u8> {
+        match self {
+            Self::Legacy => None,
+            Self::AccessList => Some(0x01),
+            Self::DynamicFee => Some(0x02),
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Legacy => "Legacy (EIP-155)",
+            Self::AccessList => "EIP-2930 (type 1)",
+            Self::DynamicFee => "EIP-1559 (type 2)",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct AccessListEntry {
+    pub address: Address,
+    pub storage_keys: Vec<B256>,
+}
+
+/// Unsigned EVM transaction
+#[derive(Debug, Clone)]
+pub struct EvmTransaction {
+    pub tx_type: EvmTxType,
+    pub chain_id: u64,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    /// Legacy and EIP-2930 gas price
+    pub gas_price: Option<U256>,
+    pub max_fee_per_gas: Option<U256>,
+    pub max_priority_fee_per_gas: Option<U256>,
+    /// `None` deploys a contract
+    pub to: Option<Address>,
+    pub value: U256,
+    pub data: Vec<u8>,
+    pub access_list: Vec<AccessListEntry>,
+}
+
+pub struct SignedEvmTransaction {
+    /// EIP-2718 encoded transaction, ready for `eth_sendRawTransaction`
+    pub raw: Vec<u8>,
+    pub hash: B256,
+    pub from: Address,
+}
+
+impl EvmTransaction {
+    /// Parses the JSON shape used by ethers/viem/`eth_signTransaction`:
+    /// `chainId`, `nonce`, `gas`/`gasLimit`, `gasPrice` or `maxFeePerGas` + `maxPriorityFeePerGas`,
+    /// `to`, `value`, `data`/`input`, `accessList` and an optional `type` (0/1/2).
+    /// Quantities may be JSON numbers, decimal strings or 0x-prefixed hex strings.
+    pub fn from_json(json: &Value) -> Result<Self> {
+        if !json.is_object() {
+            bail!("Transaction JSON must be an object");
+        }
+
+        let tx_type = match field(json, &["type"]) {
+            Some(value) => parse_tx_type(value)?,
+            None if field(json, &["maxFeePerGas"]).is_some() => EvmTxType::DynamicFee,
+            None if field(json, &["accessList"]).is_some() => EvmTxType::AccessList,
+            None => EvmTxType::Legacy,
+        };
+
+        let chain_id = parse_u64(required(json, &["chainId"])?, "chainId")?;
+        let nonce = parse_u64(required(json, &["nonce"])?, "nonce")?;
+        let gas_limit = parse_u64(required(json, &["gas", "gasLimit"])?, "gas")?;
+
+        let (gas_price, max_fee_per_gas, max_priority_fee_per_gas) = match tx_type {
+            EvmTxType::Legacy | EvmTxType::AccessList => {
+                (Some(parse_u256(required(json, &["gasPrice"])?, "gasPrice")?), None, None)
+            }
+            EvmTxType::DynamicFee => {
+                let max_fee = parse_u256(required(json, &["maxFeePerGas"])?, "maxFeePerGas")?;
+                let priority_fee = parse_u256(required(json, &["maxPriorityFeePerGas"])?, "maxPriorityFeePerGas")?;
+                if priority_fee > max_fee {
+                    bail!("maxPriorityFeePerGas ({}) exceeds maxFeePerGas ({})", priority_fee, max_fee);
+                }
+                (None, Some(max_fee), Some(priority_fee))
+            }
+        };
+
+        let to = match field(json, &["to"]) {
+            Some(Value::String(s)) if s.is_empty() => None,
+            Some(Value::String(s)) => Some(parse_address(s)?),
+            Some(_) => bail!("'to' must be an address string"),
+            None => None,
+        };
+
+        let value = match field(json, &["value"]) {
+            Some(value) => parse_u256(value, "value")?,
+            None => U256::ZERO,
+        };
+
+        let data = match field(json, &["data", "input"]) {
+            Some(Value::String(s)) => parse_hex(s).context("'data' must be hex")?,
+            Some(_) => bail!("'data' must be a hex string"),
+            None => Vec::new(),
+        };
+
+        let access_list = match field(json, &["accessList"]) {
+            Some(value) => parse_access_list(value)?,
+            None => Vec::new(),
+        };
+        if tx_type == EvmTxType::Legacy && !access_list.is_empty() {
+            bail!("Legacy transactions cannot carry an access list; use type 1 or 2");
+        }
+
+        Ok(Self {
+            tx_type,
+            chain_id,
+            nonce,
+            gas_limit,
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            to,
+            value,
+            data,
+            access_list,
+        })
+    }
+
+    /// Highest fee the transaction can pay (gas limit times gas price or max fee)
+    pub fn max_fee(&self) -> U256 {
+        let price = self.gas_price.or(self.max_fee_per_gas).unwrap_or(U256::ZERO);
+        price.saturating_mul(U256::from(self.gas_limit))
+    }
+
+    /// Labelled fields shown before signing, with amounts in the chain's native unit
+    pub fn details(&self, blockchain: &SupportedBlockchain) -> Vec<(String, String)> {
+        let symbol = native_symbol(blockchain);
+        let mut details = vec![
+            ("Type".to_string(), self.tx_type.describe().to_string()),
+            ("Chain ID".to_string(), self.chain_id.to_string()),
+            ("Nonce".to_string(), self.nonce.to_string()),
+            ("To".to_string(), match &self.to {
+                Some(to) => format_address(blockchain, to),
+                None => "(contract creation)".to_string(),
+            }),
+            ("Value".to_string(), format!("{} {}", format_units(self.value, 18), symbol)),
+            ("Gas Limit".to_string(), self.gas_limit.to_string()),
+        ];
+        match self.tx_type {
+            EvmTxType::DynamicFee => {
+                details.push(("Max Fee Per Gas".to_string(), format!("{} gwei", format_units(self.max_fee_per_gas.unwrap_or_default(), 9))));
+                details.push(("Max Priority Fee Per Gas".to_string(), format!("{} gwei", format_units(self.max_priority_fee_per_gas.unwrap_or_default(), 9))));
+            }
+            _ => details.push(("Gas Price".to_string(), format!("{} gwei", format_units(self.gas_price.unwrap_or_default(), 9)))),
+        }
+        details.push(("Max Network Fee".to_string(), format!("{} {}", format_units(self.max_fee(), 18), symbol)));
+        details.push(("Data".to_string(), if self.data.is_empty() {
+            "(none)".to_string()
+        } else {
+            format!("{} bytes (selector 0x{})", self.data.len(), hex::encode(&self.data[..self.data.len().min(4)]))
+        }));
+        if !self.access_list.is_empty() {
+            details.push(("Access List".to_string(), format!("{} address(es)", self.access_list.len())));
+        }
+        details
+    }
+
+    fn payload_fields(&self) -> Vec<Vec<u8>> {
+        let to = match &self.to {
+            Some(address) => rlp_bytes(address.as_slice()),
+            None => rlp_bytes(&[]),
+        };
+        let gas_price = self.gas_price.unwrap_or(U256::ZERO);
+
+        match self.tx_type {
+            EvmTxType::Legacy => vec![
+                rlp_uint(U256::from(self.nonce)),
+                rlp_uint(gas_price),
+                rlp_uint(U256::from(self.gas_limit)),
+                to,
+                rlp_uint(self.value),
+                rlp_bytes(&self.data),
+            ],
+            EvmTxType::AccessList => vec![
+                rlp_uint(U256::from(self.chain_id)),
+                rlp_uint(U256::from(self.nonce)),
+                rlp_uint(gas_price),
+                rlp_uint(U256::from(self.gas_limit)),
+                to,
+                rlp_uint(self.value),
+                rlp_bytes(&self.data),
+                self.rlp_access_list(),
+            ],
+            EvmTxType::DynamicFee => vec![
+                rlp_uint(U256::from(self.chain_id)),
+                rlp_uint(U256::from(self.nonce)),
+                rlp_uint(self.max_priority_fee_per_gas.unwrap_or(U256::ZERO)),
+                rlp_uint(self.max_fee_per_gas.unwrap_or(U256::ZERO)),
+                rlp_uint(U256::from(self.gas_limit)),
+                to,
+                rlp_uint(self.value),
+                rlp_bytes(&self.data),
+                self.rlp_access_list(),
+            ],
+        }
+    }
+
+    fn rlp_access_list(&self) -> Vec<u8> {
+        let entries: Vec<Vec<u8>> = self.access_list.iter()
+            .map(|entry| {
+                let keys: Vec<Vec<u8>> = entry.storage_keys.iter().map(|key| rlp_bytes(key.as_slice())).collect();
+                rlp_list(&[rlp_bytes(entry.address.as_slice()), rlp_list(&keys)])
+            })
+            .collect();
+        rlp_list(&entries)
+    }
+
+    fn envelope(&self, fields: &[Vec<u8>]) -> Vec<u8> {
+        let body = rlp_list(fields);
+        match self.tx_type.type_byte() {
+            Some(type_byte) => [vec![type_byte], body].concat(),
+            None => body,
+        }
+    }
+
+    /// Hash that is signed: keccak256 of the unsigned envelope (EIP-155 fields for legacy)
+    pub fn signing_hash(&self) -> B256 {
+        let mut fields = self.payload_fields();
+        if self.tx_type == EvmTxType::Legacy {
+            fields.push(rlp_uint(U256::from(self.chain_id)));
+            fields.push(rlp_uint(U256::ZERO));
+            fields.push(rlp_uint(U256::ZERO));
+        }
+        keccak256(self.envelope(&fields))
+    }
+
+    pub fn sign(&self, private_key: &[u8]) -> Result<SignedEvmTransaction> {
+        let signing_key = SigningKey::from_slice(private_key)
+            .context("Invalid secp256k1 private key")?;
+        let (signature, recovery_id) = sign_hash(&signing_key, &self.signing_hash())?;
+
+        let v = match self.tx_type {
+            // EIP-155: v = recovery_id + chain_id * 2 + 35
+            EvmTxType::Legacy => U256::from(self.chain_id) * U256::from(2) + U256::from(35 + recovery_id as u64),
+            _ => U256::from(recovery_id),
+        };
+
+        let mut fields = self.payload_fields();
+        fields.push(rlp_uint(v));
+        fields.push(rlp_bytes(trim_leading_zeros(&signature[..32])));
+        fields.push(rlp_bytes(trim_leading_zeros(&signature[32..])));
+
+        let raw = self.envelope(&fields);
+        Ok(SignedEvmTransaction {
+            hash: keccak256(&raw),
+            raw,
+            from: verifying_key_to_address(signing_key.verifying_key()),
+        })
+    }
+}
+
+/// Signs a 32-byte digest, returning the low-S `r || s` signature and the recovery id (0 or 1)
+pub fn sign_hash(signing_key: &SigningKey, hash: &B256) -> Result<([u8; 64], u8)> {
+    let (mut signature, mut recovery_id) = signing_key.sign_prehash_recoverable(hash.as_slice())
+        .context("Failed to sign hash")?;
+    if let Some(normalized) = signature.normalize_s() {
+        signature = normalized;
+        recovery_id = RecoveryId::new(!recovery_id.is_y_odd(), recovery_id.is_x_reduced());
+    }
+
+    let mut bytes = [0u8; 64];
+    bytes.copy_from_slice(&signature.to_bytes());
+    Ok((bytes, recovery_id.to_byte()))
+}
+
+pub fn verifying_key_to_address(verifying_key: &VerifyingKey) -> Address {
+    let uncompressed = verifying_key.to_encoded_point(false);
+    let hash = keccak256(&uncompressed.as_bytes()[1..]);
+    Address::from_slice(&hash[12..])
+}
+
+/// Recovers the signer of a 65-byte `r || s || v` signature over `hash`.
+/// `v` may be 0/1 or 27/28.
+pub fn recover_signer(hash: &B256, signature: &[u8]) -> Result<Address> {
+    if signature.len() != 65 {
+        bail!("Signature must be 65 bytes (r || s || v), got {}", signature.len());
+    }
+    let v = match signature[64] {
+        v @ (0 | 1) => v,
+        v @ (27 | 28) => v - 27,
+        other => bail!("Invalid signature recovery byte v = {}", other),
+    };
+
+    let parsed = Signature::from_slice(&signature[..64]).context("Invalid signature")?;
+    let recovery_id = RecoveryId::from_byte(v).context("Invalid recovery id")?;
+    let verifying_key = VerifyingKey::recover_from_prehash(hash.as_slice(), &parsed, recovery_id)
+        .context("Signature does not recover to a public key")?;
+
+    Ok(verifying_key_to_address(&verifying_key))
+}
+
+// ========== MESSAGES ==========
+
+/// EIP-191 version 0x45 (`personal_sign`) digest
+pub fn eip191_hash(message: &[u8]) -> B256 {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    keccak256(prefixed)
+}
+
+/// What an EVM wallet can be asked to sign off-chain
+pub enum EvmMessage {
+    /// `personal_sign` over raw bytes (UTF-8 text or decoded hex)
+    Personal(Vec<u8>),
+    /// `eth_signTypedData_v4`
+    TypedData(TypedData),
+}
+
+impl EvmMessage {
+    pub fn signing_hash(&self) -> Result<B256> {
+        match self {
+            Self::Personal(message) => Ok(eip191_hash(message)),
+            Self::TypedData(typed_data) => typed_data.signing_hash(),
+        }
+    }
+}
+
+/// Message signing shared by the EVM handlers (Ethereum, Polygon, Optimism, BNB, Cronos, XDC).
+/// Implementors only name their chain, which decides how addresses are displayed.
+pub trait EvmSigner {
+    fn evm_blockchain(&self) -> SupportedBlockchain;
+
+    /// Signs with EIP-191 or EIP-712 and returns the 65-byte `r || s || v` signature (v = 27/28)
+    fn sign_evm_message(&self, private_key: &[u8], message: &EvmMessage) -> Result<[u8; 65]> {
+        let signing_key = SigningKey::from_slice(private_key)
+            .context("Invalid secp256k1 private key")?;
+        let (signature, recovery_id) = sign_hash(&signing_key, &message.signing_hash()?)?;
+
+        let mut bytes = [0u8; 65];
+        bytes[..64].copy_from_slice(&signature);
+        bytes[64] = 27 + recovery_id;
+        Ok(bytes)
+    }
+
+    /// Address, in the chain's display format, that produced `signature`
+    fn recover_evm_signer(&self, message: &EvmMessage, signature: &[u8]) -> Result<String> {
+        let address = recover_signer(&message.signing_hash()?, signature)?;
+        Ok(format_address(&self.evm_blockchain(), &address))
+    }
+}
+
+pub fn get_evm_signer(blockchain: &SupportedBlockchain) -> Option<Box<dyn EvmSigner>> {
+    Some(match blockchain {
+        SupportedBlockchain::Ethereum => Box::new(crate::blockchain::ethereum::EthereumHandler::new(blockchain.clone())),
+        SupportedBlockchain::Polygon => Box::new(crate::blockchain::polygon::PolygonHandler::new()),
+        SupportedBlockchain::Optimism => Box::new(crate::blockchain::optimism::OptimismHandler::new()),
+        SupportedBlockchain::BinanceBNB => Box::new(crate::blockchain::binance::BinanceHandler::new()),
+        SupportedBlockchain::Cronos => Box::new(crate::blockchain::cronos::CronosHandler::new()),
+        SupportedBlockchain::XDC => Box::new(crate::blockchain::xdc::XdcHandler::new()),
+        _ => return None,
+    })
+}
+
+// ========== HANDLER INTEGRATION ==========
+// `BlockchainHandler` signing entry points shared by the EVM handlers. Transactions are the
+// unsigned JSON accepted by `EvmTransaction::from_json`.
+
+/// EIP-191 message, or with `format` = `eip712` the typed-data JSON document in `message`
+pub fn evm_message(message: &[u8], format: Option<&str>) -> Result<EvmMessage> {
+    match format.map(str::to_lowercase).as_deref() {
+        None | Some("eip191") | Some("personal") => Ok(EvmMessage::Personal(message.to_vec())),
+        Some("eip712") | Some("typed-data") => {
+            let json: Value = serde_json::from_slice(message).context("EIP-712 typed data is not valid JSON")?;
+            Ok(EvmMessage::TypedData(TypedData::from_json(&json)?))
+        }
+        Some(other) => bail!("Unknown EVM message format '{}'. Use eip191 or eip712", other),
+    }
+}
+
+pub fn sign_handler_message(signer: &dyn EvmSigner, private_key: &[u8], address: &str, message: &[u8], format: Option<&str>) -> Result<String> {
+    let message = evm_message(message, format)?;
+    let signature = signer.sign_evm_message(private_key, &message)?;
+    if !signer.recover_evm_signer(&message, &signature)?.eq_ignore_ascii_case(address.trim()) {
+        bail!("The private key does not belong to {}", address);
+    }
+    Ok(format!("0x{}", hex::encode(signature)))
+}
+
+pub fn verify_handler_message(signer: &dyn EvmSigner, address: &str, message: &[u8], signature: &str, format: Option<&str>) -> Result<String> {
+    let message = evm_message(message, format)?;
+    let signature = parse_hex(signature).context("Signature is not valid hex")?;
+    let recovered = signer.recover_evm_signer(&message, &signature)?;
+    if !recovered.eq_ignore_ascii_case(address.trim()) {
+        bail!("The signature was made by {}", recovered);
+    }
+    Ok(match message {
+        EvmMessage::Personal(_) => "EIP-191 (personal_sign)",
+        EvmMessage::TypedData(_) => "EIP-712 (typed data)",
+    }.to_string())
+}
+
+pub fn describe_handler_transaction(signer: &dyn EvmSigner, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+    let json: Value = serde_json::from_slice(transaction).context("Transaction is not valid JSON")?;
+    Ok(EvmTransaction::from_json(&json)?.details(&signer.evm_blockchain()))
+}
+
+pub fn sign_handler_transaction(signer: &dyn EvmSigner, private_key: &[u8], address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+    let json: Value = serde_json::from_slice(transaction).context("Transaction is not valid JSON")?;
+    let signed = EvmTransaction::from_json(&json)?.sign(private_key)?;
+    let from = format_address(&signer.evm_blockchain(), &signed.from);
+    if !from.eq_ignore_ascii_case(address.trim()) {
+        bail!("Signer {} does not match the wallet address {}", from, address);
+    }
+    Ok(SignedTransaction {
+        encoded: format!("0x{}", hex::encode(&signed.raw)),
+        hash: Some(format!("0x{}", hex::encode(signed.hash))),
+    })
+}
+
+// ========== HELPERS ==========
+
+/// Formats an address the way the chain displays it (XDC uses an `xdc` prefix)
+pub fn format_address(blockchain: &SupportedBlockchain, address: &Address) -> String {
+    let checksummed = address.to_string();
+    match blockchain {
+        SupportedBlockchain::XDC => checksummed.replacen("0x", "xdc", 1),
+        _ => checksummed,
+    }
+}
+
+/// Parses a 0x- or xdc-prefixed address (checksum is not enforced)
+pub fn parse_address(value: &str) -> Result<Address> {
+    let value = value.trim();
+    let hex_part = value.strip_prefix("xdc").or_else(|| value.strip_prefix("0x")).unwrap_or(value);
+    Address::from_str(hex_part).with_context(|| format!("Invalid EVM address: {}", value))
+}
+
+/// Ticker of the native currency, used when displaying amounts
+pub fn native_symbol(blockchain: &SupportedBlockchain) -> &'static str {
+    match blockchain {
+        SupportedBlockchain::Polygon => "POL",
+        SupportedBlockchain::BinanceBNB => "BNB",
+        SupportedBlockchain::Cronos => "CRO",
+        SupportedBlockchain::XDC => "XDC",
+        _ => "ETH",
+    }
+}
+
+/// Formats a wei amount with 18 decimals, e.g. `1.5`
+pub fn format_units(value: U256, decimals: u32) -> String {
+    let unit = U256::from(10).pow(U256::from(decimals));
+    let whole = value / unit;
+    let fraction = format!("{:0>width$}", (value % unit).to_string(), width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+fn field<'a>(json: &'a Value, names: &[&str]) -> Option<&'a Value> {
+    names.iter()
+        .filter_map(|name| json.get(*name))
+        .find(|value| !value.is_null())
+}
+
+fn required<'a>(json: &'a Value, names: &[&str]) -> Result<&'a Value> {
+    field(json, names).with_context(|| format!("Transaction JSON is missing '{}'", names.join("' / '")))
+}
+
+fn parse_tx_type(value: &Value) -> Result<EvmTxType> {
+    let type_number = match value {
+        Value::String(s) => match s.to_lowercase().as_str() {
+            "legacy" => 0,
+            "eip2930" => 1,
+            "eip1559" => 2,
+            _ => parse_u64(value, "type")?,
+        },
+        _ => parse_u64(value, "type")?,
+    };
+
+    match type_number {
+        0 => Ok(EvmTxType::Legacy),
+        1 => Ok(EvmTxType::AccessList),
+        2 => Ok(EvmTxType::DynamicFee),
+        other => bail!("Unsupported transaction type {} (supported: 0 legacy, 1 EIP-2930, 2 EIP-1559)", other),
+    }
+}
+
+/// Parses a quantity given as a JSON number, a decimal string or a 0x-prefixed hex string
+pub fn parse_u256(value: &Value, name: &str) -> Result<U256> {
+    match value {
+        Value::Number(number) => match number.as_u64() {
+            Some(n) => Ok(U256::from(n)),
+            None => bail!("'{}' must be a non-negative integer; use a string for large values", name),
+        },
+        Value::String(s) => {
+            let s = s.trim();
+            let parsed = match s.strip_prefix("0x") {
+                Some("") => Ok(U256::ZERO),
+                Some(hex_digits) => U256::from_str_radix(hex_digits, 16),
+                None => U256::from_str_radix(s, 10),
+            };
+            parsed.map_err(|e| anyhow::anyhow!("Invalid '{}' value '{}': {}", name, s, e))
+        }
+        _ => bail!("'{}' must be a number or a numeric string", name),
+    }
+}
+
+fn parse_u64(value: &Value, name: &str) -> Result<u64> {
+    let parsed = parse_u256(value, name)?;
+    u64::try_from(parsed).map_err(|_| anyhow::anyhow!("'{}' does not fit in 64 bits", name))
+}
+
+/// Decodes hex with or without a 0x prefix
+pub fn parse_hex(value: &str) -> Result<Vec<u8>> {
+    let value = value.trim();
+    Ok(hex::decode(value.strip_prefix("0x").unwrap_or(value))?)
+}
+
+fn parse_access_list(value: &Value) -> Result<Vec<AccessListEntry>> {
+    let entries = value.as_array().context("'accessList' must be an array")?;
+
+    entries.iter()
+        .map(|entry| {
+            let address = entry.get("address").and_then(Value::as_str)
+                .context("Access list entries need an 'address'")?;
+            let storage_keys = match entry.get("storageKeys") {
+                Some(Value::Array(keys)) => keys.iter()
+                    .map(|key| {
+                        let key = key.as_str().context("Storage keys must be hex strings")?;
+                        B256::from_str(key).with_context(|| format!("Invalid storage key: {}", key))
+                    })
+                    .collect::<Result<Vec<_>>>()?,
+                None => Vec::new(),
+                Some(_) => bail!("'storageKeys' must be an array"),
+            };
+            Ok(AccessListEntry { address: parse_address(address)?, storage_keys })
+        })
+        .collect()
+}
+
+// ========== RLP ==========
+// Reference: https://ethereum.org/en/developers/docs/data-structures-and-encoding/rlp/
+
+fn trim_leading_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn rlp_length_prefix(length: usize, offset: u8) -> Vec<u8> {
+    if length < 56 {
+        vec![offset + length as u8]
+    } else {
+        let length_bytes = (length as u64).to_be_bytes();
+        let length_bytes = trim_leading_zeros(&length_bytes);
+        let mut prefix = vec![offset + 55 + length_bytes.len() as u8];
+        prefix.extend_from_slice(length_bytes);
+        prefix
+    }
+}
+
+fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    let mut encoded = rlp_length_prefix(bytes.len(), 0x80);
+    encoded.extend_from_slice(bytes);
+    encoded
+}
+
+fn rlp_uint(value: U256) -> Vec<u8> {
+    let bytes = value.to_be_bytes::<32>();
+    rlp_bytes(trim_leading_zeros(&bytes))
+}
+
+fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut encoded = rlp_length_prefix(payload.len(), 0xc0);
+    encoded.extend_from_slice(&payload);
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PRIVATE_KEY: &str = "4646464646464646464646464646464646464646464646464646464646464646";
+
+    #[test]
+    fn test_rlp_encoding() {
+        assert_eq!(rlp_bytes(b"dog"), vec![0x83, b'd', b'o', b'g']);
+        assert_eq!(rlp_list(&[rlp_bytes(b"cat"), rlp_bytes(b"dog")]), hex::decode("c88363617483646f67").unwrap());
+        assert_eq!(rlp_uint(U256::ZERO), vec![0x80]);
+        assert_eq!(rlp_uint(U256::from(15)), vec![0x0f]);
+        assert_eq!(rlp_uint(U256::from(1024)), vec![0x82, 0x04, 0x00]);
+        assert_eq!(rlp_bytes(&[0xaa; 56])[..2], [0xb8, 0x38]);
+    }
+
+    #[test]
+    fn test_eip155_vector() {
+        // Example from https://eips.ethereum.org/EIPS/eip-155
+        let tx = EvmTransaction::from_json(&json!({
+            "chainId": 1,
+            "nonce": 9,
+            "gasPrice": "20000000000",
+            "gas": 21000,
+            "to": "0x3535353535353535353535353535353535353535",
+            "value": "1000000000000000000",
+        })).unwrap();
+        assert_eq!(tx.tx_type, EvmTxType::Legacy);
+        assert_eq!(
+            hex::encode(tx.signing_hash()),
+            "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+
+        let signed = tx.sign(&hex::decode(PRIVATE_KEY).unwrap()).unwrap();
+        assert_eq!(
+            hex::encode(&signed.raw),
+            "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83"
+        );
+        assert_eq!(signed.hash, keccak256(&signed.raw));
+        assert_eq!(signed.from.to_string(), "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+    }
+
+    #[test]
+    fn test_typed_transactions_recover_signer() {
+        let private_key = hex::decode(PRIVATE_KEY).unwrap();
+
+        for (json, type_byte) in [
+            (json!({
+                "type": "0x1",
+                "chainId": "0x89",
+                "nonce": "0x0",
+                "gasPrice": "0x6fc23ac00",
+                "gasLimit": 50000,
+                "to": "0x3535353535353535353535353535353535353535",
+                "accessList": [{
+                    "address": "0x3535353535353535353535353535353535353535",
+                    "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000001"]
+                }],
+            }), 0x01u8),
+            (json!({
+                "chainId": 10,
+                "nonce": 3,
+                "maxFeePerGas": "30000000000",
+                "maxPriorityFeePerGas": "1000000000",
+                "gas": 60000,
+                "data": "0xa9059cbb",
+            }), 0x02u8),
+        ] {
+            let tx = EvmTransaction::from_json(&json).unwrap();
+            let signed = tx.sign(&private_key).unwrap();
+            assert_eq!(signed.raw[0], type_byte);
+
+            let signing_key = SigningKey::from_slice(&private_key).unwrap();
+            let (signature, recovery_id) = sign_hash(&signing_key, &tx.signing_hash()).unwrap();
+            let tail = [rlp_bytes(trim_leading_zeros(&signature[..32])), rlp_bytes(trim_leading_zeros(&signature[32..]))].concat();
+            assert!(signed.raw.ends_with(&tail));
+
+            let recovered = VerifyingKey::recover_from_prehash(
+                tx.signing_hash().as_slice(),
+                &Signature::from_slice(&signature).unwrap(),
+                RecoveryId::from_byte(recovery_id).unwrap(),
+            ).unwrap();
+            assert_eq!(verifying_key_to_address(&recovered), signed.from);
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_json() {
+        assert!(EvmTransaction::from_json(&json!({"nonce": 0, "gas": 21000, "gasPrice": 1})).is_err());
+        assert!(EvmTransaction::from_json(&json!({
+            "chainId": 1, "nonce": 0, "gas": 21000,
+            "maxFeePerGas": 1, "maxPriorityFeePerGas": 2,
+        })).is_err());
+        assert!(EvmTransaction::from_json(&json!({"chainId": 1, "nonce": 0, "gas": 21000, "gasPrice": 1, "type": 3})).is_err());
+    }
+
+    #[test]
+    fn test_format_units_and_addresses() {
+        assert_eq!(format_units(U256::from(1_500_000_000_000_000_000u128), 18), "1.5");
+        assert_eq!(format_units(U256::from(2_000_000_000_000_000_000u128), 18), "2");
+
+        let address = parse_address("xdc3535353535353535353535353535353535353535").unwrap();
+        assert_eq!(format_address(&SupportedBlockchain::XDC, &address), "xdc3535353535353535353535353535353535353535");
+    }
+
+    #[test]
+    fn test_eip191_hash() {
+        assert_eq!(
+            hex::encode(eip191_hash(b"hello world")),
+            "d9eba16ed0ecae432b71fe008c98cc872bb4cc214d3220a36f365326cf807d68"
+        );
+    }
+
+    #[test]
+    fn test_eip712_signature_from_spec() {
+        // "Mail" example of EIP-712, signed by keccak256("cow")
+        let document = json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Person": [{"name": "name", "type": "string"}, {"name": "wallet", "type": "address"}],
+                "Mail": [
+                    {"name": "from", "type": "Person"},
+                    {"name": "to", "type": "Person"},
+                    {"name": "contents", "type": "string"}
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {"name": "Ether Mail", "version": "1", "chainId": 1, "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"},
+            "message": {
+                "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+                "contents": "Hello, Bob!"
+            }
+        });
+        let message = EvmMessage::TypedData(TypedData::from_json(&document).unwrap());
+        let signature = hex::decode(concat!(
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d",
+            "07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562",
+            "1c"
+        )).unwrap();
+
+        let signer = crate::blockchain::ethereum::EthereumHandler::new(SupportedBlockchain::Ethereum);
+        assert_eq!(
+            signer.recover_evm_signer(&message, &signature).unwrap(),
+            "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"
+        );
+
+        let cow = keccak256(b"cow");
+        let own = signer.sign_evm_message(cow.as_slice(), &message).unwrap();
+        assert_eq!(signer.recover_evm_signer(&message, &own).unwrap(), "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826");
+    }
+
+    #[test]
+    fn test_personal_sign_round_trip_on_every_evm_chain() {
+        let private_key = hex::decode(PRIVATE_KEY).unwrap();
+        let message = EvmMessage::Personal(b"Sign in to example.com".to_vec());
+
+        for blockchain in [
+            SupportedBlockchain::Ethereum, SupportedBlockchain::Polygon, SupportedBlockchain::Optimism,
+            SupportedBlockchain::BinanceBNB, SupportedBlockchain::Cronos, SupportedBlockchain::XDC,
+        ] {
+            let signer = get_evm_signer(&blockchain).unwrap();
+            let signature = signer.sign_evm_message(&private_key, &message).unwrap();
+            assert!(signature[64] == 27 || signature[64] == 28);
+
+            let recovered = signer.recover_evm_signer(&message, &signature).unwrap();
+            assert_eq!(recovered[recovered.len() - 40..], "9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"[..]);
+        }
+        assert!(get_evm_signer(&SupportedBlockchain::Tron).is_none());
+        assert!(recover_signer(&eip191_hash(b"x"), &[0u8; 64]).is_err());
+    }
+
+    #[test]
+    fn test_blockchain_handler_signing() {
+        let private_key = hex::decode(PRIVATE_KEY).unwrap();
+        let address = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+        let handler = crate::blockchain::get_blockchain_handler(&SupportedBlockchain::Ethereum).unwrap();
+        assert_eq!(handler.signing_capabilities(), crate::blockchain::SigningCapabilities::ALL);
+
+        let signature = handler.sign_message(&private_key, address, b"hello", None).unwrap();
+        assert_eq!(handler.verify_message(address, b"hello", &signature, None).unwrap(), "EIP-191 (personal_sign)");
+        assert!(handler.verify_message(address, b"hello!", &signature, None).is_err());
+        assert!(handler.sign_message(&private_key, "0x0000000000000000000000000000000000000001", b"hello", None).is_err());
+        assert!(handler.sign_message(&private_key, address, b"hello", Some("eip4361")).is_err());
+
+        let tx = br#"{"chainId": 1, "nonce": 9, "gasPrice": "20000000000", "gas": 21000,
+            "to": "0x3535353535353535353535353535353535353535", "value": "1000000000000000000"}"#;
+        let details = handler.describe_transaction(tx).unwrap();
+        assert!(details.contains(&("Value".to_string(), "1 ETH".to_string())));
+        let signed = handler.sign_transaction(&private_key, address, tx).unwrap();
+        assert!(signed.encoded.starts_with("0xf86c09"));
+        assert!(signed.hash.is_some());
+
+        // Chains without an implementation report it instead of failing silently
+        let hedera = crate::blockchain::get_blockchain_handler(&SupportedBlockchain::Hedera).unwrap();
+        assert!(!hedera.signing_capabilities().sign_transaction);
+        let error = hedera.sign_message(&private_key, "0.0.1", b"hello", None).unwrap_err();
+        assert!(error.to_string().contains("not supported"));
+    }
+}
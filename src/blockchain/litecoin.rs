@@ -1,5 +1,7 @@
 use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain, SigningCapabilities, SignedTransaction};
+use crate::blockchain::bitcoin_message::{BitcoinMessageFormat, BitcoinMessageSigner};
+use crate::crypto::psbt::{describe_psbt, sign_psbt_with_key};
 use crate::crypto::bip32::{derive_secp256k1_key_from_mnemonic, private_key_to_public_key_secp256k1};
 use litcoin::{Network, PublicKey};
 use sha2::Digest;
@@ -11,10 +13,6 @@ pub struct LitecoinHandler {
 
 impl LitecoinHandler {
     pub fn new() -> Self {
-        // Let's see what networks are available
-        println!("Available networks: {:?}", Network::Bitcoin);
-        println!("Available networks: {:?}", Network::Testnet);
-        // Try to see if there's a Network::Regtest or any other network
         Self {
             network: Network::Bitcoin, // litcoin library uses Bitcoin network constants
         }
@@ -165,6 +163,45 @@ impl BlockchainHandler for LitecoinHandler {
     fn get_blockchain_name(&self) -> &'static str {
         "Litecoin"
     }
+
+    fn derive_from_public_key(&self, public_key: &[u8], derivation_path: &str) -> Result<WalletKeys> {
+        // Only P2PKH addresses are generated for Litecoin
+        if !derivation_path.starts_with("m/44'/") {
+            return Err(anyhow::anyhow!("Litecoin watch-only wallets support BIP-44 (Ltub/xpub) keys only"));
+        }
+        WalletKeys::watch_only(public_key, derivation_path, |key| self.public_key_to_address(key))
+    }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities::ALL
+    }
+
+    fn sign_message(&self, private_key: &[u8], address: &str, message: &[u8], format: Option<&str>) -> Result<String> {
+        let format = match format {
+            Some(format) => BitcoinMessageFormat::parse(format)?,
+            None => BitcoinMessageFormat::default_for(address),
+        };
+        self.sign_bitcoin_message(private_key, address, message, format)
+    }
+
+    fn verify_message(&self, address: &str, message: &[u8], signature: &str, _format: Option<&str>) -> Result<String> {
+        // The format is recognised from the signature itself
+        self.verify_bitcoin_message(address, message, signature)
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        describe_psbt(&SupportedBlockchain::Litecoin, transaction)
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], _address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        sign_psbt_with_key(private_key, transaction)
+    }
+}
+
+impl BitcoinMessageSigner for LitecoinHandler {
+    fn message_blockchain(&self) -> SupportedBlockchain {
+        SupportedBlockchain::Litecoin
+    }
 }
 
 #[cfg(test)]
@@ -35,9 +35,53 @@ pub mod ton;
 // Phase 6 blockchain handlers
 pub mod xdc;
 
+// Shared EVM transaction encoding and signing
+pub mod evm;
+pub mod eip712;
+
+// Bitcoin-style message signing (signmessage, BIP-137, BIP-322)
+pub mod bitcoin_message;
+
+// Solana transaction decoding and off-chain messages
+pub mod solana_tx;
+
+// Stellar transaction envelopes and SEP-53 messages
+pub mod stellar_tx;
+
+// Cosmos SDK sign docs (direct and amino JSON) and ADR-036 messages
+pub mod cosmos_tx;
+
+// TRON raw_data transactions and TIP-191 messages
+pub mod tron_tx;
+
+// XRP Ledger family seeds and transaction JSON signing
+pub mod xrp_tx;
+
+// Cardano transaction witnessing and CIP-8 messages
+pub mod cardano_tx;
+
+// Algorand 25-word mnemonics and msgpack transactions
+pub mod algorand_tx;
+
+// Sui BCS transactions, intent signing and suiprivkey keys
+pub mod sui_tx;
+
 pub use common::*;
 pub use bip_standards::*;
 
+/// How a chain's private keys are derived from the account root
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyScheme {
+    /// BIP-32 secp256k1
+    Secp256k1,
+    /// Standard SLIP-10 ed25519 (with chain code)
+    Slip10Ed25519,
+    /// Hardened-only ed25519 as implemented by `derive_ed25519_key_from_mnemonic`
+    Ed25519,
+    /// BIP32-Ed25519 (Icarus) as used by CIP-1852
+    Bip32Ed25519,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SupportedBlockchain {
     Bitcoin,
@@ -125,7 +169,7 @@ impl SupportedBlockchain {
             "hedera" | "hbar" => Ok(Self::Hedera),
             "algorand" | "algo" => Ok(Self::Algorand),
             "cosmos" | "atom" => Ok(Self::Cosmos),
-            "binance" | "bnb" => Ok(Self::BinanceBNB),
+            "binance" | "bnb" | "binancebnb" => Ok(Self::BinanceBNB),
             "litecoin" | "ltc" => Ok(Self::Litecoin),
             "polygon" | "matic" => Ok(Self::Polygon),
             "polkadot" | "dot" => Ok(Self::Polkadot),
@@ -189,6 +233,16 @@ impl SupportedBlockchain {
         ]
     }
     
+    /// Every supported blockchain, in declaration order
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Bitcoin, Self::Ethereum, Self::Solana, Self::Stellar, Self::XRP,
+            Self::Cardano, Self::Tron, Self::Cronos, Self::Hedera, Self::Algorand,
+            Self::Cosmos, Self::BinanceBNB, Self::Litecoin, Self::Polygon, Self::Polkadot,
+            Self::Sui, Self::Optimism, Self::IOTA, Self::XDC, Self::TON,
+        ]
+    }
+
     pub fn get_coin_type(&self) -> Option<u32> {
         match self {
             Self::Bitcoin => Some(0),
@@ -214,6 +268,23 @@ impl SupportedBlockchain {
         }
     }
     
+    /// EIP-155 mainnet chain ID for EVM chains, `None` for everything else
+    pub fn evm_chain_id(&self) -> Option<u64> {
+        match self {
+            Self::Ethereum => Some(1),
+            Self::Optimism => Some(10),
+            Self::Cronos => Some(25),
+            Self::XDC => Some(50),
+            Self::BinanceBNB => Some(56),
+            Self::Polygon => Some(137),
+            _ => None,
+        }
+    }
+
+    pub fn is_evm(&self) -> bool {
+        self.evm_chain_id().is_some()
+    }
+
     /// Customizes derivation paths for blockchains that follow modified BIP standards
     pub fn customize_derivation_path(&self, standard_path: String) -> String {
         match self {
@@ -310,6 +381,16 @@ impl SupportedBlockchain {
         }
     }
     
+    pub fn key_scheme(&self) -> KeyScheme {
+        match self {
+            Self::Solana | Self::Stellar => KeyScheme::Slip10Ed25519,
+            Self::Hedera | Self::Algorand | Self::Polkadot |
+            Self::Sui | Self::IOTA | Self::TON => KeyScheme::Ed25519,
+            Self::Cardano => KeyScheme::Bip32Ed25519,
+            _ => KeyScheme::Secp256k1,
+        }
+    }
+
     pub fn uses_ed25519(&self) -> bool {
         matches!(self, 
             Self::Solana | Self::Stellar | Self::Cardano | 
@@ -1,5 +1,6 @@
-use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain};
+use anyhow::{Result, Context, bail};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain, SigningCapabilities, SignedTransaction};
+use crate::blockchain::solana_tx::{decode_transaction, encode_transaction, message_signing_bytes, verify_ed25519};
 use crate::crypto::ed25519_utils::private_key_to_public_key_ed25519;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::{Keypair, Signer};
@@ -145,6 +146,65 @@ impl BlockchainHandler for SolanaHandler {
     fn get_blockchain_name(&self) -> &'static str {
         "solana"
     }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities::ALL
+    }
+
+    fn sign_message(&self, private_key: &[u8], address: &str, message: &[u8], format: Option<&str>) -> Result<String> {
+        let keypair = self.private_key_to_keypair(private_key)?;
+        if keypair.pubkey().to_string() != address {
+            bail!("Private key does not belong to {}", address);
+        }
+        let (signing_bytes, _) = message_signing_bytes(message, format)?;
+        Ok(keypair.sign_message(&signing_bytes).to_string())
+    }
+
+    fn verify_message(&self, address: &str, message: &[u8], signature: &str, format: Option<&str>) -> Result<String> {
+        let public_key = Pubkey::from_str(address.trim())
+            .map_err(|_| anyhow::anyhow!("Invalid Solana address: {}", address))?
+            .to_bytes();
+        let signature = bs58::decode(signature.trim()).into_vec()
+            .context("Solana signatures are expected in base58")?;
+
+        // Without an explicit format, accept either the off-chain envelope or a raw signMessage signature
+        let formats = match format {
+            Some(format) => vec![format],
+            None => vec!["offchain", "raw"],
+        };
+        let mut last_error = None;
+        for format in formats {
+            let (signing_bytes, scheme) = match message_signing_bytes(message, Some(format)) {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    last_error = Some(e);
+                    continue;
+                }
+            };
+            match verify_ed25519(&public_key, &signing_bytes, &signature) {
+                Ok(()) => return Ok(scheme.to_string()),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("The signature was not made by this address")))
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        Ok(decode_transaction(transaction)?.0.details())
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        let keypair = self.private_key_to_keypair(private_key)?;
+        if keypair.pubkey().to_string() != address {
+            bail!("Private key does not belong to {}", address);
+        }
+        let (mut transaction, encoding) = decode_transaction(transaction)?;
+        transaction.sign(&keypair)?;
+        Ok(SignedTransaction {
+            encoded: encode_transaction(&transaction, encoding),
+            hash: transaction.transaction_id(),
+        })
+    }
 }
 
 impl SolanaHandler {
@@ -300,6 +360,49 @@ mod tests {
         println!("✅ All Solana derivation paths working correctly");
     }
 
+    #[test]
+    fn test_blockchain_handler_signing() {
+        use crate::blockchain::solana_tx::offchain_message;
+        use base64::Engine;
+
+        let handler = SolanaHandler::new();
+        let private_key = [7u8; 32];
+        let keypair = handler.private_key_to_keypair(&private_key).unwrap();
+        let address = keypair.pubkey().to_string();
+
+        // Off-chain message by default, raw signMessage on request
+        let signature = handler.sign_message(&private_key, &address, b"hello", None).unwrap();
+        let expected = keypair.sign_message(&offchain_message(b"hello").unwrap()).to_string();
+        assert_eq!(signature, expected);
+        assert_eq!(handler.verify_message(&address, b"hello", &signature, None).unwrap(), "Solana off-chain message (v0)");
+        let raw = handler.sign_message(&private_key, &address, b"hello", Some("raw")).unwrap();
+        assert_eq!(handler.verify_message(&address, b"hello", &raw, None).unwrap(), "raw ed25519 (signMessage)");
+        assert!(handler.verify_message(&address, b"hullo", &signature, None).is_err());
+        assert!(handler.sign_message(&[8u8; 32], &address, b"hello", None).is_err());
+
+        // Unsigned transfer from the wallet, given as base64
+        let mut message = vec![1, 0, 1, 3];
+        message.extend_from_slice(&keypair.pubkey().to_bytes());
+        message.extend_from_slice(&[2u8; 32]);
+        message.extend_from_slice(&[0u8; 32]);
+        message.extend_from_slice(&[3u8; 32]);
+        message.extend_from_slice(&[1, 2, 2, 0, 1, 12, 2, 0, 0, 0]);
+        message.extend_from_slice(&1_000_000u64.to_le_bytes());
+        let mut unsigned = vec![1];
+        unsigned.extend_from_slice(&[0u8; 64]);
+        unsigned.extend_from_slice(&message);
+        let encoded = base64::engine::general_purpose::STANDARD.encode(&unsigned);
+
+        let details = handler.describe_transaction(encoded.as_bytes()).unwrap();
+        assert!(details.iter().any(|(label, value)| label == "Fee Payer" && value.starts_with(&address)));
+
+        let signed = handler.sign_transaction(&private_key, &address, encoded.as_bytes()).unwrap();
+        let bytes = base64::engine::general_purpose::STANDARD.decode(&signed.encoded).unwrap();
+        let signature = keypair.sign_message(&message);
+        assert_eq!(&bytes[1..65], signature.as_ref());
+        assert_eq!(signed.hash, Some(signature.to_string()));
+    }
+
     #[test]
     fn test_parse_derivation_path() {
         let handler = SolanaHandler::new();
@@ -0,0 +1,446 @@
+use anyhow::{Result, Context, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use bincode::Options;
+use ed25519_dalek::{Signature, VerifyingKey};
+use solana_message::compiled_instruction::CompiledInstruction;
+use solana_message::VersionedMessage;
+use solana_sdk::signature::{Keypair, Signature as SolanaSignature, Signer};
+use solana_transaction::versioned::{TransactionVersion, VersionedTransaction};
+
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+const COMPUTE_BUDGET_PROGRAM: &str = "ComputeBudget111111111111111111111111111111";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const ASSOCIATED_TOKEN_PROGRAM: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+const MEMO_PROGRAM: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+const STAKE_PROGRAM: &str = "Stake11111111111111111111111111111111111111";
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+/// Largest serialized transaction accepted by the network
+const PACKET_DATA_SIZE: u64 = 1232;
+
+/// Text form a transaction arrived in; the signed transaction is returned the same way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolanaEncoding {
+    Base64,
+    Base58,
+}
+
+/// Durable nonce used instead of a recent blockhash (first instruction is AdvanceNonceAccount)
+#[derive(Debug, Clone)]
+pub struct DurableNonce {
+    pub nonce_account: String,
+    pub authority: String,
+    pub nonce: String,
+}
+
+/// A wire-format transaction (legacy or any versioned message the SDK understands)
+/// Reference: https://solana.com/docs/core/transactions
+#[derive(Debug, Clone)]
+pub struct SolanaTransaction {
+    pub transaction: VersionedTransaction,
+    /// Serialized message, which is what every signer signs
+    message_bytes: Vec<u8>,
+}
+
+impl SolanaTransaction {
+    /// Parses a serialized transaction, or a bare message with no signatures yet
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let transaction = match wire_options().deserialize::<VersionedTransaction>(bytes) {
+            Ok(transaction) => transaction,
+            Err(transaction_error) => {
+                let message = wire_options().deserialize::<VersionedMessage>(bytes)
+                    .map_err(|_| anyhow::anyhow!("Invalid Solana transaction: {}", transaction_error))?;
+                let signatures = vec![SolanaSignature::default(); message.header().num_required_signatures as usize];
+                VersionedTransaction { signatures, message }
+            }
+        };
+
+        transaction.sanitize()
+            .map_err(|e| anyhow::anyhow!("Invalid Solana transaction: {}", e))?;
+        let message_bytes = transaction.message.serialize();
+        Ok(Self { transaction, message_bytes })
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        wire_options().serialize(&self.transaction)
+            .expect("a sanitized transaction always serializes")
+    }
+
+    pub fn message(&self) -> &VersionedMessage {
+        &self.transaction.message
+    }
+
+    pub fn signatures(&self) -> &[SolanaSignature] {
+        &self.transaction.signatures
+    }
+
+    /// Signs the message with `keypair` and stores the signature in its signer slot.
+    /// Signatures of other signers are kept, so partially signed transactions can be completed.
+    pub fn sign(&mut self, keypair: &Keypair) -> Result<usize> {
+        let signer = keypair.pubkey();
+        let required = self.message().header().num_required_signatures as usize;
+        let index = self.message().static_account_keys()[..required]
+            .iter()
+            .position(|key| *key == signer)
+            .with_context(|| format!("{} is not one of the transaction's required signers", signer))?;
+
+        self.transaction.signatures[index] = keypair.sign_message(&self.message_bytes);
+        Ok(index)
+    }
+
+    pub fn signed_count(&self) -> usize {
+        self.signatures().iter().filter(|signature| **signature != SolanaSignature::default()).count()
+    }
+
+    /// The transaction ID is the fee payer's signature
+    pub fn transaction_id(&self) -> Option<String> {
+        self.signatures().first()
+            .filter(|signature| **signature != SolanaSignature::default())
+            .map(|signature| signature.to_string())
+    }
+
+    /// Labelled fields shown before signing
+    pub fn details(&self) -> Vec<(String, String)> {
+        let message = self.message();
+        let header = message.header();
+        let mut details = vec![
+            ("Version".to_string(), match self.transaction.version() {
+                TransactionVersion::Legacy(_) => "legacy".to_string(),
+                TransactionVersion::Number(version) => format!("v{}", version),
+            }),
+            ("Fee Payer".to_string(), account_name(message, 0)),
+            ("Signatures".to_string(), format!("{} of {} present", self.signed_count(), header.num_required_signatures)),
+        ];
+
+        match durable_nonce(message) {
+            Some(nonce) => {
+                details.push(("Durable Nonce".to_string(), nonce.nonce));
+                details.push(("Nonce Account".to_string(), nonce.nonce_account));
+                details.push(("Nonce Authority".to_string(), nonce.authority));
+            }
+            None => details.push(("Recent Blockhash".to_string(), message.recent_blockhash().to_string())),
+        }
+
+        for index in 0..message.static_account_keys().len() {
+            let mut flags = Vec::new();
+            if message.is_signer(index) {
+                flags.push("signer");
+            }
+            if message.is_maybe_writable(index, None) {
+                flags.push("writable");
+            }
+            let flags = if flags.is_empty() { String::new() } else { format!(" ({})", flags.join(", ")) };
+            details.push((format!("Account #{}", index), format!("{}{}", account_name(message, index), flags)));
+        }
+        for (index, lookup) in message.address_table_lookups().unwrap_or_default().iter().enumerate() {
+            details.push((
+                format!("Lookup Table #{}", index),
+                format!(
+                    "{} ({} writable, {} read-only)",
+                    lookup.account_key,
+                    lookup.writable_indexes.len(),
+                    lookup.readonly_indexes.len()
+                ),
+            ));
+        }
+        for (index, instruction) in message.instructions().iter().enumerate() {
+            details.push((format!("Instruction #{}", index), describe_instruction(message, instruction)));
+        }
+
+        details
+    }
+}
+
+/// Base58 address of a static account, or its lookup-table position for v0 loaded accounts
+fn account_name(message: &VersionedMessage, index: usize) -> String {
+    let static_keys = message.static_account_keys();
+    match static_keys.get(index) {
+        Some(key) => key.to_string(),
+        None => format!("(lookup table account #{})", index - static_keys.len()),
+    }
+}
+
+fn instruction_account(message: &VersionedMessage, instruction: &CompiledInstruction, position: usize) -> String {
+    instruction.accounts.get(position)
+        .map(|index| account_name(message, *index as usize))
+        .unwrap_or_else(|| "?".to_string())
+}
+
+/// A durable-nonce transaction starts with System `AdvanceNonceAccount`
+pub fn durable_nonce(message: &VersionedMessage) -> Option<DurableNonce> {
+    let first = message.instructions().first()?;
+    if account_name(message, first.program_id_index as usize) != SYSTEM_PROGRAM || first.data != [4, 0, 0, 0] {
+        return None;
+    }
+    Some(DurableNonce {
+        nonce_account: instruction_account(message, first, 0),
+        authority: instruction_account(message, first, 2),
+        nonce: message.recent_blockhash().to_string(),
+    })
+}
+
+fn describe_instruction(message: &VersionedMessage, instruction: &CompiledInstruction) -> String {
+    let program = account_name(message, instruction.program_id_index as usize);
+    let data = &instruction.data;
+    let account = |position: usize| instruction_account(message, instruction, position);
+
+    match program.as_str() {
+        SYSTEM_PROGRAM => match (read_u32(data, 0), read_u64(data, 4)) {
+            (Some(0), Some(lamports)) => format!("System: create account {} funded with {} by {}", account(1), format_sol(lamports), account(0)),
+            (Some(2), Some(lamports)) => format!("System: transfer {} from {} to {}", format_sol(lamports), account(0), account(1)),
+            (Some(4), _) => format!("System: advance nonce account {}", account(0)),
+            (Some(tag), _) => format!("System: instruction {}", tag),
+            _ => "System: (invalid data)".to_string(),
+        },
+        COMPUTE_BUDGET_PROGRAM => match data.first() {
+            Some(2) => format!("Compute Budget: set compute unit limit to {}", read_u32(data, 1).unwrap_or_default()),
+            Some(3) => format!("Compute Budget: set compute unit price to {} micro-lamports", read_u64(data, 1).unwrap_or_default()),
+            _ => "Compute Budget: instruction".to_string(),
+        },
+        TOKEN_PROGRAM | TOKEN_2022_PROGRAM => match data.first() {
+            Some(3) => format!("Token: transfer {} base units from {} to {}", read_u64(data, 1).unwrap_or_default(), account(0), account(1)),
+            Some(12) => format!(
+                "Token: transfer {} base units ({} decimals) of mint {} from {} to {}",
+                read_u64(data, 1).unwrap_or_default(), data.get(9).copied().unwrap_or_default(), account(1), account(0), account(2)
+            ),
+            Some(tag) => format!("Token: instruction {}", tag),
+            None => "Token: (no data)".to_string(),
+        },
+        ASSOCIATED_TOKEN_PROGRAM => format!("Associated Token: create account {} for {}", account(1), account(2)),
+        MEMO_PROGRAM => format!("Memo: {}", String::from_utf8_lossy(data)),
+        STAKE_PROGRAM => format!("Stake: instruction {}", read_u32(data, 0).unwrap_or_default()),
+        _ => format!("{}: {} account(s), {} byte(s) of data", program, instruction.accounts.len(), data.len()),
+    }
+}
+
+/// Wire encoding used by the Solana CLI and web3.js: bincode with fixed-width integers,
+/// capped at the packet size and with no trailing bytes
+fn wire_options() -> impl bincode::Options {
+    bincode::DefaultOptions::new()
+        .with_limit(PACKET_DATA_SIZE)
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+}
+
+/// Decodes a base64 or base58 transaction
+pub fn decode_transaction(data: &[u8]) -> Result<(SolanaTransaction, SolanaEncoding)> {
+    let text = std::str::from_utf8(data)
+        .context("Solana transactions are expected as base64 or base58 text")?;
+    let text: String = text.split_whitespace().collect();
+
+    // The base58 alphabet is a subset of base64's, so only accept base64 if it parses
+    let base64_result = BASE64.decode(text.as_bytes()).map(|bytes| SolanaTransaction::parse(&bytes));
+    if let Ok(Ok(transaction)) = base64_result {
+        return Ok((transaction, SolanaEncoding::Base64));
+    }
+
+    match bs58::decode(&text).into_vec() {
+        Ok(bytes) => Ok((SolanaTransaction::parse(&bytes)?, SolanaEncoding::Base58)),
+        Err(_) => match base64_result {
+            Ok(Err(error)) => Err(error),
+            _ => bail!("Transaction is neither base64 nor base58"),
+        },
+    }
+}
+
+pub fn encode_transaction(transaction: &SolanaTransaction, encoding: SolanaEncoding) -> String {
+    match encoding {
+        SolanaEncoding::Base64 => BASE64.encode(transaction.serialize()),
+        SolanaEncoding::Base58 => bs58::encode(transaction.serialize()).into_string(),
+    }
+}
+
+// ========== OFF-CHAIN MESSAGES ==========
+// Reference: the off-chain message format used by `solana sign-offchain-message`
+
+const OFFCHAIN_SIGNING_DOMAIN: &[u8; 16] = b"\xffsolana offchain";
+const OFFCHAIN_HEADER_LEN: usize = 16 + 1 + 1 + 2;
+/// Longest body that still fits a ledger packet (restricted ASCII / limited UTF-8)
+const OFFCHAIN_MAX_LEN_LEDGER: usize = 1232 - OFFCHAIN_HEADER_LEN;
+/// Longest body overall (extended UTF-8)
+const OFFCHAIN_MAX_LEN: usize = u16::MAX as usize - OFFCHAIN_HEADER_LEN;
+
+/// Version 0 off-chain message: signing domain, version, format, length and body
+pub fn offchain_message(message: &[u8]) -> Result<Vec<u8>> {
+    if message.is_empty() {
+        bail!("Off-chain messages cannot be empty");
+    }
+    let printable_ascii = message.iter().all(|byte| (0x20..=0x7e).contains(byte));
+    let utf8 = std::str::from_utf8(message).is_ok();
+    let format: u8 = if printable_ascii && message.len() <= OFFCHAIN_MAX_LEN_LEDGER {
+        0 // restricted ASCII
+    } else if utf8 && message.len() <= OFFCHAIN_MAX_LEN_LEDGER {
+        1 // limited UTF-8
+    } else if utf8 && message.len() <= OFFCHAIN_MAX_LEN {
+        2 // extended UTF-8
+    } else if !utf8 {
+        bail!("Off-chain messages must be UTF-8 text; use the raw format for binary data");
+    } else {
+        bail!("Off-chain messages are limited to {} bytes", OFFCHAIN_MAX_LEN);
+    };
+
+    let mut bytes = OFFCHAIN_SIGNING_DOMAIN.to_vec();
+    bytes.push(0); // header version
+    bytes.push(format);
+    bytes.extend_from_slice(&(message.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(message);
+    Ok(bytes)
+}
+
+/// Bytes actually signed for a message: the off-chain envelope by default, or the message
+/// itself for `raw` (wallet-adapter `signMessage`)
+pub fn message_signing_bytes(message: &[u8], format: Option<&str>) -> Result<(Vec<u8>, &'static str)> {
+    match format.map(str::to_lowercase).as_deref() {
+        None | Some("offchain") => Ok((offchain_message(message)?, "Solana off-chain message (v0)")),
+        Some("raw") => Ok((message.to_vec(), "raw ed25519 (signMessage)")),
+        Some(other) => bail!("Unknown Solana message format '{}'. Use offchain or raw", other),
+    }
+}
+
+pub fn verify_ed25519(public_key: &[u8; 32], message: &[u8], signature: &[u8]) -> Result<()> {
+    let verifying_key = VerifyingKey::from_bytes(public_key).context("Invalid ed25519 public key")?;
+    let signature: [u8; 64] = signature.try_into()
+        .map_err(|_| anyhow::anyhow!("Signature must be 64 bytes, got {}", signature.len()))?;
+    verifying_key.verify_strict(message, &Signature::from_bytes(&signature))
+        .map_err(|_| anyhow::anyhow!("The signature was not made by this address"))
+}
+
+// ========== HELPERS ==========
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(data.get(offset..offset + 8)?.try_into().ok()?))
+}
+
+fn format_sol(lamports: u64) -> String {
+    format!("{}.{:09} SOL", lamports / LAMPORTS_PER_SOL, lamports % LAMPORTS_PER_SOL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::signer::SeedDerivable;
+
+    fn keypair(seed: u8) -> Keypair {
+        Keypair::from_seed(&[seed; 32]).unwrap()
+    }
+
+    fn system_transfer(from: [u8; 32], to: [u8; 32], lamports: u64) -> Vec<u8> {
+        let mut data = 2u32.to_le_bytes().to_vec();
+        data.extend_from_slice(&lamports.to_le_bytes());
+        // header, 3 accounts (payer, recipient, system program), blockhash, 1 instruction
+        let mut message = vec![1, 0, 1, 3];
+        message.extend_from_slice(&from);
+        message.extend_from_slice(&to);
+        message.extend_from_slice(&[0u8; 32]);
+        message.extend_from_slice(&[7u8; 32]);
+        message.extend_from_slice(&[1, 2, 2, 0, 1, data.len() as u8]);
+        message.extend_from_slice(&data);
+        message
+    }
+
+    fn unsigned(message: &[u8]) -> Vec<u8> {
+        let mut transaction = vec![1];
+        transaction.extend_from_slice(&[0u8; 64]);
+        transaction.extend_from_slice(message);
+        transaction
+    }
+
+    #[test]
+    fn test_sign_legacy_transfer() {
+        let payer = keypair(1);
+        let recipient = keypair(2).pubkey().to_bytes();
+        let message = system_transfer(payer.pubkey().to_bytes(), recipient, 1_500_000_000);
+        let encoded = BASE64.encode(unsigned(&message));
+
+        let (mut transaction, encoding) = decode_transaction(encoded.as_bytes()).unwrap();
+        assert_eq!(encoding, SolanaEncoding::Base64);
+        assert!(durable_nonce(transaction.message()).is_none());
+        assert!(transaction.transaction_id().is_none());
+
+        let details = transaction.details();
+        assert_eq!(details[0], ("Version".to_string(), "legacy".to_string()));
+        let instruction = &details.iter().find(|(label, _)| label == "Instruction #0").unwrap().1;
+        assert!(instruction.starts_with("System: transfer 1.500000000 SOL"));
+
+        assert_eq!(transaction.sign(&payer).unwrap(), 0);
+        verify_ed25519(&payer.pubkey().to_bytes(), &message, transaction.signatures()[0].as_ref()).unwrap();
+        assert_eq!(transaction.transaction_id().unwrap(), bs58::encode(transaction.signatures()[0]).into_string());
+
+        // Round trip in base58, and a bare message is accepted too
+        let base58 = encode_transaction(&transaction, SolanaEncoding::Base58);
+        let (decoded, encoding) = decode_transaction(base58.as_bytes()).unwrap();
+        assert_eq!(encoding, SolanaEncoding::Base58);
+        assert_eq!(decoded.serialize(), transaction.serialize());
+        assert_eq!(SolanaTransaction::parse(&message).unwrap().signatures().len(), 1);
+
+        assert!(transaction.sign(&keypair(3)).is_err());
+    }
+
+    #[test]
+    fn test_v0_nonce_transaction() {
+        let payer = keypair(1);
+        let nonce_account = keypair(4).pubkey().to_bytes();
+        // v0 header; accounts: payer, nonce account, system program, recent blockhashes sysvar
+        let mut message = vec![0x80, 1, 0, 2, 4];
+        message.extend_from_slice(&payer.pubkey().to_bytes());
+        message.extend_from_slice(&nonce_account);
+        message.extend_from_slice(&[0u8; 32]);
+        message.extend_from_slice(&[9u8; 32]);
+        message.extend_from_slice(&[5u8; 32]); // nonce value
+        // AdvanceNonceAccount(nonce, sysvar, authority) then a memo-less system instruction on a loaded account
+        message.extend_from_slice(&[2, 2, 3, 1, 3, 0, 4, 4, 0, 0, 0]);
+        message.extend_from_slice(&[2, 1, 4, 4, 8, 0, 0, 0]);
+        // One lookup table with one writable index
+        message.extend_from_slice(&[1]);
+        message.extend_from_slice(&[6u8; 32]);
+        message.extend_from_slice(&[1, 0, 0]);
+
+        let mut transaction = SolanaTransaction::parse(&unsigned(&message)).unwrap();
+        assert!(matches!(transaction.message(), VersionedMessage::V0(_)));
+        assert_eq!(transaction.message().address_table_lookups().unwrap().len(), 1);
+
+        let nonce = durable_nonce(transaction.message()).unwrap();
+        assert_eq!(nonce.nonce_account, bs58::encode(nonce_account).into_string());
+        assert_eq!(nonce.authority, payer.pubkey().to_string());
+        assert_eq!(nonce.nonce, bs58::encode([5u8; 32]).into_string());
+        assert!(transaction.details().iter().any(|(label, _)| label == "Durable Nonce"));
+
+        transaction.sign(&payer).unwrap();
+        verify_ed25519(&payer.pubkey().to_bytes(), &message, transaction.signatures()[0].as_ref()).unwrap();
+        assert!(transaction.details().iter().any(|(label, value)| label == "Lookup Table #0" && value.ends_with("(1 writable, 0 read-only)")));
+
+        // Indexes beyond the static and loaded accounts are rejected
+        let mut broken = message.clone();
+        let position = broken.len() - 36 - 8 + 2; // account index of the second instruction
+        broken[position] = 9;
+        assert!(SolanaTransaction::parse(&unsigned(&broken)).is_err());
+
+        // Message versions the SDK does not know are rejected rather than misread
+        let mut future = message.clone();
+        future[0] = 0x81;
+        assert!(SolanaTransaction::parse(&unsigned(&future)).is_err());
+    }
+
+    #[test]
+    fn test_offchain_message() {
+        let bytes = offchain_message(b"Hello").unwrap();
+        assert_eq!(&bytes[..16], b"\xffsolana offchain");
+        assert_eq!(&bytes[16..20], &[0, 0, 5, 0]);
+        assert_eq!(&bytes[20..], b"Hello");
+
+        assert_eq!(offchain_message("héllo".as_bytes()).unwrap()[17], 1);
+        assert_eq!(offchain_message(&[b'a'; 2000]).unwrap()[17], 2);
+        assert!(offchain_message(b"").is_err());
+        assert!(offchain_message(&[0xff, 0xfe]).is_err());
+
+        let (raw, _) = message_signing_bytes(b"Hello", Some("raw")).unwrap();
+        assert_eq!(raw, b"Hello");
+        assert!(message_signing_bytes(b"Hello", Some("siws")).is_err());
+    }
+}
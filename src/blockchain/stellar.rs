@@ -1,13 +1,27 @@
-use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain};
+use anyhow::{Result, Context, bail};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain, SigningCapabilities, SignedTransaction};
+use crate::blockchain::stellar_tx::{decode_account_id, sign_sep53_message, verify_sep53_message, StellarEnvelope, StellarNetwork};
 use crate::crypto::ed25519_utils::{derive_slip10_ed25519_key_from_mnemonic, private_key_to_public_key_ed25519};
+use ed25519_dalek::SigningKey;
 use stellar_base::crypto::{PublicKey as StellarPublicKey, SodiumKeyPair};
 
-pub struct StellarHandler;
+pub struct StellarHandler {
+    /// Network whose passphrase transaction signatures commit to
+    network: StellarNetwork,
+    /// Sign transactions the wallet is not a source of, as an extra signer of a multisig account
+    co_signer: bool,
+}
 
 impl StellarHandler {
     pub fn new() -> Self {
-        Self
+        Self {
+            network: StellarNetwork::Public,
+            co_signer: false,
+        }
+    }
+
+    pub fn for_network(network: StellarNetwork, co_signer: bool) -> Self {
+        Self { network, co_signer }
     }
 }
 
@@ -80,6 +94,45 @@ impl BlockchainHandler for StellarHandler {
     fn get_blockchain_name(&self) -> &'static str {
         "stellar"
     }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities::ALL
+    }
+
+    fn sign_message(&self, private_key: &[u8], address: &str, message: &[u8], format: Option<&str>) -> Result<String> {
+        check_message_format(format)?;
+        let signing_key = self.signing_key(private_key, address)?;
+        Ok(sign_sep53_message(&signing_key, message))
+    }
+
+    fn verify_message(&self, address: &str, message: &[u8], signature: &str, format: Option<&str>) -> Result<String> {
+        check_message_format(format)?;
+        verify_sep53_message(&decode_account_id(address)?, message, signature)?;
+        Ok("SEP-53".to_string())
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        let envelope = StellarEnvelope::from_base64(&String::from_utf8_lossy(transaction))?;
+        envelope.details(&self.network)
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        let signing_key = self.signing_key(private_key, address)?;
+        let mut envelope = StellarEnvelope::from_base64(&String::from_utf8_lossy(transaction))?;
+        envelope.sign(&signing_key, &self.network, self.co_signer)?;
+        Ok(SignedTransaction {
+            encoded: envelope.to_base64()?,
+            hash: Some(hex::encode(envelope.hash(&self.network)?)),
+        })
+    }
+}
+
+/// SEP-53 is the only Stellar message format
+fn check_message_format(format: Option<&str>) -> Result<()> {
+    match format.map(str::to_lowercase).as_deref() {
+        None | Some("sep53") | Some("sep-53") => Ok(()),
+        Some(other) => bail!("Unknown Stellar message format '{}'. Stellar messages use SEP-53", other),
+    }
 }
 
 impl StellarHandler {
@@ -101,6 +154,16 @@ impl StellarHandler {
         Ok(keypair.secret_key().secret_seed())
     }
 
+    fn signing_key(&self, private_key: &[u8], address: &str) -> Result<SigningKey> {
+        let seed: [u8; 32] = private_key.try_into()
+            .map_err(|_| anyhow::anyhow!("Stellar private key must be 32 bytes"))?;
+        let signing_key = SigningKey::from_bytes(&seed);
+        if self.public_key_to_address(signing_key.verifying_key().as_bytes())? != address {
+            bail!("Private key does not belong to {}", address);
+        }
+        Ok(signing_key)
+    }
+
     fn public_key_to_address(&self, public_key_bytes: &[u8]) -> Result<String> {
         if public_key_bytes.len() != 32 {
             return Err(anyhow::anyhow!("Invalid public key length for Stellar"));
@@ -183,6 +246,47 @@ mod tests {
         assert_eq!(keys.address.len(), 56);
     }
 
+    #[test]
+    fn test_blockchain_handler_signing() {
+        use base64::Engine;
+        use base64::engine::general_purpose::STANDARD as BASE64;
+
+        let handler = StellarHandler::for_network(StellarNetwork::Testnet, false);
+        let private_key = [5u8; 32];
+        let keys = handler.derive_from_private_key(&hex::encode(private_key)).unwrap();
+
+        let signature = handler.sign_message(&private_key, &keys.address, b"I own this account", None).unwrap();
+        assert_eq!(handler.verify_message(&keys.address, b"I own this account", &signature, None).unwrap(), "SEP-53");
+        assert!(handler.verify_message(&keys.address, b"I own that account", &signature, None).is_err());
+        assert!(handler.sign_message(&private_key, &keys.address, b"hi", Some("eip191")).is_err());
+        assert!(handler.sign_message(&[6u8; 32], &keys.address, b"hi", None).is_err());
+
+        // v1 envelope: BUMP_SEQUENCE from the wallet's account, no memo, no signatures
+        let public_key = hex::decode(&keys.public_key).unwrap();
+        let mut xdr = vec![0, 0, 0, 2, 0, 0, 0, 0];
+        xdr.extend_from_slice(&public_key);
+        xdr.extend_from_slice(&100u32.to_be_bytes());
+        xdr.extend_from_slice(&7i64.to_be_bytes());
+        xdr.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]); // PRECOND_NONE, MEMO_NONE
+        xdr.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 11]);
+        xdr.extend_from_slice(&9i64.to_be_bytes());
+        xdr.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]); // ext, no signatures
+        let unsigned = BASE64.encode(&xdr);
+
+        let details = handler.describe_transaction(unsigned.as_bytes()).unwrap();
+        assert!(details.contains(&("Network".to_string(), "testnet".to_string())));
+        assert!(details.contains(&("Operation #0".to_string(), "Bump sequence to 9".to_string())));
+
+        let signed = handler.sign_transaction(&private_key, &keys.address, unsigned.as_bytes()).unwrap();
+        let envelope = StellarEnvelope::from_base64(&signed.encoded).unwrap();
+        assert_eq!(envelope.signatures().len(), 1);
+        assert_eq!(signed.hash, Some(hex::encode(envelope.hash(&StellarNetwork::Testnet).unwrap())));
+
+        // The same envelope signed for the public network carries a different signature
+        let public = StellarHandler::new().sign_transaction(&private_key, &keys.address, unsigned.as_bytes()).unwrap();
+        assert_ne!(public.encoded, signed.encoded);
+    }
+
     #[test]
     fn test_stellar_expected_addresses() {
         let handler = StellarHandler::new();
@@ -0,0 +1,1243 @@
+use anyhow::{Result, Context, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use sha2::{Digest, Sha256};
+use stellar_base::crypto::{encode_account_id, PublicKey as StellarPublicKey};
+use stellar_xdr as xdr;
+use stellar_xdr::{Limits, ReadXdr, WriteXdr};
+
+pub const PUBLIC_NETWORK_PASSPHRASE: &str = "Public Global Stellar Network ; September 2015";
+pub const TESTNET_NETWORK_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+pub const FUTURENET_NETWORK_PASSPHRASE: &str = "Test SDF Future Network ; October 2022";
+
+const MAX_SIGNATURES: usize = 20;
+const STROOPS_PER_XLM: i64 = 10_000_000;
+
+/// Nesting limit while decoding (Soroban values and claim predicates are recursive); the same
+/// limit stellar-core and the Soroban host read XDR with
+const MAX_XDR_DEPTH: u32 = 500;
+/// stellar-core rejects claim predicates nested deeper than this
+const MAX_PREDICATE_DEPTH: usize = 4;
+
+/// Network a transaction is signed for; its passphrase is hashed into every signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StellarNetwork {
+    Public,
+    Testnet,
+    Futurenet,
+    Custom(String),
+}
+
+impl StellarNetwork {
+    /// Accepts a well-known network name or a full network passphrase
+    pub fn parse(network: &str) -> Result<Self> {
+        let network = network.trim();
+        match network.to_lowercase().as_str() {
+            "public" | "pubnet" | "mainnet" => return Ok(Self::Public),
+            "testnet" => return Ok(Self::Testnet),
+            "futurenet" => return Ok(Self::Futurenet),
+            _ => {}
+        }
+        match network {
+            PUBLIC_NETWORK_PASSPHRASE => Ok(Self::Public),
+            TESTNET_NETWORK_PASSPHRASE => Ok(Self::Testnet),
+            FUTURENET_NETWORK_PASSPHRASE => Ok(Self::Futurenet),
+            // Passphrases are sentences; a single word is more likely a mistyped network name
+            custom if custom.contains(' ') => Ok(Self::Custom(custom.to_string())),
+            other => bail!("Unknown Stellar network '{}'. Use public, testnet, futurenet or a full network passphrase", other),
+        }
+    }
+
+    pub fn passphrase(&self) -> &str {
+        match self {
+            Self::Public => PUBLIC_NETWORK_PASSPHRASE,
+            Self::Testnet => TESTNET_NETWORK_PASSPHRASE,
+            Self::Futurenet => FUTURENET_NETWORK_PASSPHRASE,
+            Self::Custom(passphrase) => passphrase,
+        }
+    }
+
+    pub fn network_id(&self) -> [u8; 32] {
+        Sha256::digest(self.passphrase().as_bytes()).into()
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Public => "public".to_string(),
+            Self::Testnet => "testnet".to_string(),
+            Self::Futurenet => "futurenet".to_string(),
+            Self::Custom(passphrase) => format!("custom (\"{}\")", passphrase),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeKind {
+    V0,
+    V1,
+    FeeBump,
+}
+
+/// Decoded fields of a Transaction (the inner transaction of a fee bump)
+#[derive(Debug, Clone)]
+pub struct StellarTransaction {
+    pub source_account: String,
+    /// Maximum fee in stroops for the whole transaction
+    pub fee: u32,
+    pub sequence: i64,
+    pub preconditions: Vec<(String, String)>,
+    pub memo: Option<String>,
+    pub operations: Vec<String>,
+    /// Authorization entries, resource fee and footprint of a Soroban transaction
+    pub soroban: Vec<(String, String)>,
+    /// Keys of the source account and of every operation source; any of them may need to sign
+    source_keys: Vec<[u8; 32]>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FeeBump {
+    pub fee_source: String,
+    pub fee: i64,
+    pub inner_signatures: usize,
+    fee_source_key: [u8; 32],
+}
+
+/// A TransactionEnvelope (v0, v1 or fee bump) with its decorated signatures
+/// Reference: https://developers.stellar.org/docs/learn/encyclopedia/transactions-specialized/transaction-envelopes
+#[derive(Debug, Clone)]
+pub struct StellarEnvelope {
+    pub kind: EnvelopeKind,
+    pub transaction: StellarTransaction,
+    pub fee_bump: Option<FeeBump>,
+    envelope: xdr::TransactionEnvelope,
+}
+
+impl StellarEnvelope {
+    /// Decodes the whole envelope with the stellar-xdr types of the current protocol
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        let limits = Limits { depth: MAX_XDR_DEPTH, len: bytes.len() };
+        let envelope = xdr::TransactionEnvelope::from_xdr(bytes, limits)
+            .context("Invalid Stellar transaction envelope")?;
+        // Signatures commit to the re-encoded transaction, so it has to be the one that was read
+        if envelope.to_xdr(Limits::none())? != bytes {
+            bail!("Stellar transaction envelope is not canonical XDR");
+        }
+        Self::decode(envelope).context("Invalid Stellar transaction envelope")
+    }
+
+    pub fn from_base64(text: &str) -> Result<Self> {
+        let text: String = text.split_whitespace().collect();
+        let bytes = BASE64.decode(text.as_bytes())
+            .context("Stellar transactions are expected as base64 TransactionEnvelope XDR")?;
+        Self::parse(&bytes)
+    }
+
+    fn decode(envelope: xdr::TransactionEnvelope) -> Result<Self> {
+        let (kind, transaction, fee_bump) = match &envelope {
+            // v0 transactions are read (and hashed) as the equivalent v1 transaction
+            xdr::TransactionEnvelope::TxV0(v0) => {
+                (EnvelopeKind::V0, describe_transaction(&xdr::Transaction::from(&v0.tx))?, None)
+            }
+            xdr::TransactionEnvelope::Tx(v1) => (EnvelopeKind::V1, describe_transaction(&v1.tx)?, None),
+            xdr::TransactionEnvelope::TxFeeBump(outer) => {
+                let xdr::FeeBumpTransactionInnerTx::Tx(inner) = &outer.tx.inner_tx;
+                let fee_bump = FeeBump {
+                    fee_source: outer.tx.fee_source.to_string(),
+                    fee: outer.tx.fee,
+                    inner_signatures: inner.signatures.len(),
+                    fee_source_key: muxed_key(&outer.tx.fee_source),
+                };
+                (EnvelopeKind::FeeBump, describe_transaction(&inner.tx)?, Some(fee_bump))
+            }
+        };
+        Ok(Self { kind, transaction, fee_bump, envelope })
+    }
+
+    pub fn serialize(&self) -> Result<Vec<u8>> {
+        self.envelope.to_xdr(Limits::none()).context("Failed to encode the transaction envelope")
+    }
+
+    pub fn to_base64(&self) -> Result<String> {
+        Ok(BASE64.encode(self.serialize()?))
+    }
+
+    /// Signatures on the envelope (the outer ones for a fee bump)
+    pub fn signatures(&self) -> &[xdr::DecoratedSignature] {
+        match &self.envelope {
+            xdr::TransactionEnvelope::TxV0(envelope) => &envelope.signatures,
+            xdr::TransactionEnvelope::Tx(envelope) => &envelope.signatures,
+            xdr::TransactionEnvelope::TxFeeBump(envelope) => &envelope.signatures,
+        }
+    }
+
+    fn signatures_mut(&mut self) -> &mut xdr::VecM<xdr::DecoratedSignature, 20> {
+        match &mut self.envelope {
+            xdr::TransactionEnvelope::TxV0(envelope) => &mut envelope.signatures,
+            xdr::TransactionEnvelope::Tx(envelope) => &mut envelope.signatures,
+            xdr::TransactionEnvelope::TxFeeBump(envelope) => &mut envelope.signatures,
+        }
+    }
+
+    /// Transaction hash: SHA-256 of the TransactionSignaturePayload for `network`.
+    /// v0 envelopes are hashed as the equivalent v1 transaction, as stellar-core does.
+    pub fn hash(&self, network: &StellarNetwork) -> Result<[u8; 32]> {
+        self.envelope.hash(network.network_id()).context("Failed to encode the transaction payload")
+    }
+
+    /// Whether `public_key` is the fee source of a fee bump, otherwise the source account or an
+    /// operation source: the keys the transaction itself names as signers
+    pub fn is_source(&self, public_key: &[u8; 32]) -> bool {
+        match &self.fee_bump {
+            Some(fee_bump) => fee_bump.fee_source_key == *public_key,
+            None => self.transaction.source_keys.contains(public_key),
+        }
+    }
+
+    /// Appends the decorated signature of `signing_key` over the transaction hash.
+    /// The key must be a source (see `is_source`) unless `co_signer` is set: an additional signer
+    /// of a multisig source account is not named anywhere in the transaction.
+    pub fn sign(&mut self, signing_key: &SigningKey, network: &StellarNetwork, co_signer: bool) -> Result<()> {
+        let hash = self.hash(network)?;
+        let verifying_key = signing_key.verifying_key();
+        let signer = account_id(verifying_key.as_bytes());
+        if !co_signer && !self.is_source(verifying_key.as_bytes()) {
+            match &self.fee_bump {
+                Some(fee_bump) => bail!(
+                    "{} is not the fee source of this fee bump transaction ({}); sign as a co-signer if it is a signer of that account",
+                    signer, fee_bump.fee_source
+                ),
+                None => bail!(
+                    "{} is neither the source account nor an operation source of this transaction; sign as a co-signer if it is a signer of one of them",
+                    signer
+                ),
+            }
+        }
+
+        let hint = signature_hint(verifying_key.as_bytes());
+        let already_signed = self.signatures().iter().any(|existing| {
+            existing.hint.0 == hint && verify_signature(&verifying_key, &hash, &existing.signature.0)
+        });
+        if already_signed {
+            bail!("The envelope already carries a signature from {}", signer);
+        }
+        if self.signatures().len() >= MAX_SIGNATURES {
+            bail!("The envelope already holds the maximum of {} signatures", MAX_SIGNATURES);
+        }
+
+        let signature = signing_key.sign(&hash).to_bytes();
+        let mut signatures = self.signatures().to_vec();
+        signatures.push(xdr::DecoratedSignature {
+            hint: xdr::SignatureHint(hint),
+            signature: xdr::Signature(signature.as_slice().try_into()?),
+        });
+        *self.signatures_mut() = signatures.try_into()?;
+        Ok(())
+    }
+
+    /// Labelled fields shown before signing
+    pub fn details(&self, network: &StellarNetwork) -> Result<Vec<(String, String)>> {
+        let transaction = &self.transaction;
+        let mut details = vec![
+            ("Envelope".to_string(), match self.kind {
+                EnvelopeKind::V0 => "transaction (v0)".to_string(),
+                EnvelopeKind::V1 => "transaction".to_string(),
+                EnvelopeKind::FeeBump => "fee bump transaction".to_string(),
+            }),
+            ("Network".to_string(), network.describe()),
+        ];
+
+        if let Some(fee_bump) = &self.fee_bump {
+            details.push(("Fee Source".to_string(), fee_bump.fee_source.clone()));
+            details.push(("Fee".to_string(), format_fee(fee_bump.fee)));
+            details.push(("Inner Signatures".to_string(), fee_bump.inner_signatures.to_string()));
+        }
+
+        details.push(("Source Account".to_string(), transaction.source_account.clone()));
+        let fee_label = if self.fee_bump.is_some() { "Inner Fee" } else { "Fee" };
+        details.push((fee_label.to_string(), format_fee(transaction.fee as i64)));
+        details.push(("Sequence".to_string(), transaction.sequence.to_string()));
+        details.extend(transaction.preconditions.iter().cloned());
+        if let Some(memo) = &transaction.memo {
+            details.push(("Memo".to_string(), memo.clone()));
+        }
+        for (index, operation) in transaction.operations.iter().enumerate() {
+            details.push((format!("Operation #{}", index), operation.clone()));
+        }
+        details.extend(transaction.soroban.iter().cloned());
+
+        details.push(("Signatures".to_string(), self.signatures().len().to_string()));
+        details.push(("Transaction Hash".to_string(), hex::encode(self.hash(network)?)));
+        Ok(details)
+    }
+}
+
+impl StellarTransaction {
+    fn push_operation(&mut self, description: String, source: Option<&xdr::MuxedAccount>) {
+        match source {
+            Some(source) => {
+                self.operations.push(format!("{} (source {})", description, source));
+                self.source_keys.push(muxed_key(source));
+            }
+            None => self.operations.push(description),
+        }
+    }
+}
+
+fn describe_transaction(tx: &xdr::Transaction) -> Result<StellarTransaction> {
+    let mut transaction = StellarTransaction {
+        source_account: tx.source_account.to_string(),
+        fee: tx.fee,
+        sequence: tx.seq_num.0,
+        preconditions: describe_preconditions(&tx.cond),
+        memo: describe_memo(&tx.memo),
+        operations: Vec::new(),
+        soroban: Vec::new(),
+        source_keys: vec![muxed_key(&tx.source_account)],
+    };
+
+    // A Soroban transaction holds exactly one Soroban operation and carries SorobanTransactionData
+    let soroban_operations = tx.operations.iter().filter(|operation| is_soroban(&operation.body)).count();
+    let soroban_data = match (&tx.ext, soroban_operations) {
+        (xdr::TransactionExt::V0, 0) => None,
+        (xdr::TransactionExt::V1(data), 1) if tx.operations.len() == 1 => Some(data),
+        (xdr::TransactionExt::V1(_), 0) => bail!("Soroban transaction data on a transaction without a Soroban operation"),
+        _ => bail!("A Soroban operation must be the only operation of a transaction with Soroban data"),
+    };
+
+    for (index, operation) in tx.operations.iter().enumerate() {
+        let description = describe_operation(&operation.body)
+            .with_context(|| format!("Failed to decode operation #{}", index))?;
+        transaction.push_operation(description, operation.source_account.as_ref());
+        if let xdr::OperationBody::InvokeHostFunction(op) = &operation.body {
+            for (entry, authorization) in op.auth.iter().enumerate() {
+                transaction.soroban.push((format!("Authorization #{}", entry), describe_authorization(authorization)));
+            }
+        }
+    }
+
+    if let Some(data) = soroban_data {
+        transaction.soroban.push(("Resource Fee".to_string(), format_fee(data.resource_fee)));
+        let footprint = &data.resources.footprint;
+        for key in footprint.read_only.iter() {
+            transaction.soroban.push(("Reads".to_string(), ledger_key(key)));
+        }
+        for key in footprint.read_write.iter() {
+            transaction.soroban.push(("Writes".to_string(), ledger_key(key)));
+        }
+    }
+    Ok(transaction)
+}
+
+fn is_soroban(body: &xdr::OperationBody) -> bool {
+    matches!(
+        body,
+        xdr::OperationBody::InvokeHostFunction(_)
+            | xdr::OperationBody::ExtendFootprintTtl(_)
+            | xdr::OperationBody::RestoreFootprint(_)
+    )
+}
+
+/// Describes one operation body as a single line
+fn describe_operation(body: &xdr::OperationBody) -> Result<String> {
+    use xdr::OperationBody as Body;
+    Ok(match body {
+        Body::CreateAccount(op) => format!(
+            "Create account {} with {}", op.destination, format_amount(op.starting_balance, "XLM")
+        ),
+        Body::Payment(op) => format!(
+            "Payment of {} to {}", format_amount(op.amount, &asset(&op.asset)), op.destination
+        ),
+        Body::PathPaymentStrictReceive(op) => format!(
+            "Path payment (strict receive) of {} to {}, sending at most {}{}",
+            format_amount(op.dest_amount, &asset(&op.dest_asset)), op.destination,
+            format_amount(op.send_max, &asset(&op.send_asset)), asset_path(&op.path)
+        ),
+        Body::PathPaymentStrictSend(op) => format!(
+            "Path payment (strict send) of {} to {}, receiving at least {}{}",
+            format_amount(op.send_amount, &asset(&op.send_asset)), op.destination,
+            format_amount(op.dest_min, &asset(&op.dest_asset)), asset_path(&op.path)
+        ),
+        Body::ManageSellOffer(op) => {
+            let (selling, buying) = (asset(&op.selling), asset(&op.buying));
+            format!(
+                "Sell {} for {} at {} {}/{} ({})",
+                format_amount(op.amount, &selling), buying, price(&op.price)?, buying, selling, offer(op.offer_id)
+            )
+        }
+        Body::ManageBuyOffer(op) => {
+            let (selling, buying) = (asset(&op.selling), asset(&op.buying));
+            format!(
+                "Buy {} with {} at {} {}/{} ({})",
+                format_amount(op.buy_amount, &buying), selling, price(&op.price)?, selling, buying, offer(op.offer_id)
+            )
+        }
+        Body::CreatePassiveSellOffer(op) => {
+            let (selling, buying) = (asset(&op.selling), asset(&op.buying));
+            format!(
+                "Passive sell offer of {} for {} at {} {}/{}",
+                format_amount(op.amount, &selling), buying, price(&op.price)?, buying, selling
+            )
+        }
+        Body::SetOptions(op) => describe_set_options(op),
+        Body::ChangeTrust(op) => {
+            let line = change_trust_asset(&op.line);
+            match op.limit {
+                0 => format!("Remove trustline to {}", line),
+                i64::MAX => format!("Trust {}", line),
+                limit => format!("Trust {} up to {}", line, format_amount(limit, "")),
+            }
+        }
+        Body::AllowTrust(op) => format!("Allow trust (flags {}) of {} for {}", op.authorize, op.asset, op.trustor),
+        Body::AccountMerge(destination) => format!("Merge account into {}", destination),
+        Body::Inflation => "Inflation".to_string(),
+        Body::ManageData(op) => match &op.data_value {
+            Some(value) => format!("Set data entry '{}' to 0x{}", op.data_name.0, hex::encode(&value.0)),
+            None => format!("Delete data entry '{}'", op.data_name.0),
+        },
+        Body::BumpSequence(op) => format!("Bump sequence to {}", op.bump_to.0),
+        Body::CreateClaimableBalance(op) => {
+            let mut claimants = Vec::new();
+            for xdr::Claimant::ClaimantTypeV0(claimant) in op.claimants.iter() {
+                check_claim_predicate(&claimant.predicate, 1)?;
+                claimants.push(claimant.destination.to_string());
+            }
+            format!(
+                "Create claimable balance of {} for {}",
+                format_amount(op.amount, &asset(&op.asset)), claimants.join(", ")
+            )
+        }
+        Body::ClaimClaimableBalance(op) => format!("Claim claimable balance {}", balance_id(&op.balance_id)),
+        Body::BeginSponsoringFutureReserves(op) => {
+            format!("Begin sponsoring future reserves of {}", op.sponsored_id)
+        }
+        Body::EndSponsoringFutureReserves => "End sponsoring future reserves".to_string(),
+        Body::RevokeSponsorship(op) => describe_revoke_sponsorship(op),
+        Body::Clawback(op) => format!(
+            "Claw back {} from {}", format_amount(op.amount, &asset(&op.asset)), op.from
+        ),
+        Body::ClawbackClaimableBalance(op) => format!("Claw back claimable balance {}", balance_id(&op.balance_id)),
+        Body::SetTrustLineFlags(op) => format!(
+            "Set trustline flags of {} for {} (clear {}, set {})",
+            asset(&op.asset), op.trustor, op.clear_flags, op.set_flags
+        ),
+        Body::LiquidityPoolDeposit(op) => format!(
+            "Deposit up to {} and {} into liquidity pool {} (price {} to {})",
+            format_amount(op.max_amount_a, "A"), format_amount(op.max_amount_b, "B"),
+            pool_id(&op.liquidity_pool_id), price(&op.min_price)?, price(&op.max_price)?
+        ),
+        Body::LiquidityPoolWithdraw(op) => format!(
+            "Withdraw {} pool shares from liquidity pool {} (at least {} and {})",
+            format_amount(op.amount, ""), pool_id(&op.liquidity_pool_id),
+            format_amount(op.min_amount_a, "A"), format_amount(op.min_amount_b, "B")
+        ),
+        Body::InvokeHostFunction(op) => describe_host_function(&op.host_function),
+        Body::ExtendFootprintTtl(op) => format!("Extend the TTL of the footprint entries to {} ledgers", op.extend_to),
+        Body::RestoreFootprint(_) => "Restore the archived footprint entries".to_string(),
+    })
+}
+
+fn describe_set_options(op: &xdr::SetOptionsOp) -> String {
+    let mut changes = Vec::new();
+    if let Some(destination) = &op.inflation_dest {
+        changes.push(format!("inflation destination {}", destination));
+    }
+    let values = [
+        ("clear flags", op.clear_flags),
+        ("set flags", op.set_flags),
+        ("master weight", op.master_weight),
+        ("low threshold", op.low_threshold),
+        ("medium threshold", op.med_threshold),
+        ("high threshold", op.high_threshold),
+    ];
+    for (label, value) in values {
+        if let Some(value) = value {
+            changes.push(format!("{} {}", label, value));
+        }
+    }
+    if let Some(home_domain) = &op.home_domain {
+        changes.push(format!("home domain '{}'", home_domain.0));
+    }
+    if let Some(signer) = &op.signer {
+        changes.push(match signer.weight {
+            0 => format!("remove signer {}", signer.key),
+            weight => format!("signer {} with weight {}", signer.key, weight),
+        });
+    }
+    if changes.is_empty() {
+        return "Set options (no changes)".to_string();
+    }
+    format!("Set options: {}", changes.join(", "))
+}
+
+fn describe_revoke_sponsorship(op: &xdr::RevokeSponsorshipOp) -> String {
+    let target = match op {
+        xdr::RevokeSponsorshipOp::LedgerEntry(key) => ledger_key(key),
+        xdr::RevokeSponsorshipOp::Signer(signer) => format!("signer {} of {}", signer.signer_key, signer.account_id),
+    };
+    format!("Revoke sponsorship of {}", target)
+}
+
+fn describe_memo(memo: &xdr::Memo) -> Option<String> {
+    match memo {
+        xdr::Memo::None => None,
+        xdr::Memo::Text(text) => Some(format!("text \"{}\"", text)),
+        xdr::Memo::Id(id) => Some(format!("id {}", id)),
+        xdr::Memo::Hash(hash) => Some(format!("hash {}", hex::encode(hash.0))),
+        xdr::Memo::Return(hash) => Some(format!("return {}", hex::encode(hash.0))),
+    }
+}
+
+fn describe_preconditions(preconditions: &xdr::Preconditions) -> Vec<(String, String)> {
+    let conditions = match preconditions {
+        xdr::Preconditions::None => return Vec::new(),
+        xdr::Preconditions::Time(bounds) => return time_bounds_preconditions(Some(bounds)),
+        xdr::Preconditions::V2(conditions) => conditions,
+    };
+    let mut details = time_bounds_preconditions(conditions.time_bounds.as_ref());
+    if let Some(bounds) = &conditions.ledger_bounds {
+        let max = if bounds.max_ledger == 0 { "no limit".to_string() } else { bounds.max_ledger.to_string() };
+        details.push(("Ledger Bounds".to_string(), format!("{} to {}", bounds.min_ledger, max)));
+    }
+    if let Some(min_sequence) = &conditions.min_seq_num {
+        details.push(("Min Sequence".to_string(), min_sequence.0.to_string()));
+    }
+    if conditions.min_seq_age.0 != 0 {
+        details.push(("Min Sequence Age".to_string(), format!("{} seconds", conditions.min_seq_age.0)));
+    }
+    if conditions.min_seq_ledger_gap != 0 {
+        details.push(("Min Sequence Ledger Gap".to_string(), conditions.min_seq_ledger_gap.to_string()));
+    }
+    for signer in conditions.extra_signers.iter() {
+        details.push(("Extra Signer".to_string(), signer.to_string()));
+    }
+    details
+}
+
+fn time_bounds_preconditions(time_bounds: Option<&xdr::TimeBounds>) -> Vec<(String, String)> {
+    time_bounds
+        .map(|bounds| ("Time Bounds".to_string(), format_time_bounds(bounds.min_time.0, bounds.max_time.0)))
+        .into_iter()
+        .collect()
+}
+
+/// stellar-xdr bounds the overall nesting; stellar-core is stricter for claim predicates
+fn check_claim_predicate(predicate: &xdr::ClaimPredicate, depth: usize) -> Result<()> {
+    if depth > MAX_PREDICATE_DEPTH {
+        bail!("Claim predicate is nested more than {} levels deep", MAX_PREDICATE_DEPTH);
+    }
+    match predicate {
+        xdr::ClaimPredicate::And(predicates) | xdr::ClaimPredicate::Or(predicates) => {
+            for predicate in predicates.iter() {
+                check_claim_predicate(predicate, depth + 1)?;
+            }
+        }
+        xdr::ClaimPredicate::Not(Some(predicate)) => check_claim_predicate(predicate, depth + 1)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+// ========== SOROBAN ==========
+// Reference: https://github.com/stellar/stellar-xdr/blob/curr/Stellar-transaction.x
+
+fn describe_host_function(function: &xdr::HostFunction) -> String {
+    match function {
+        xdr::HostFunction::InvokeContract(call) => format!("Invoke {}", contract_call(call)),
+        xdr::HostFunction::CreateContract(args) => format!(
+            "Create contract running {} from {}",
+            contract_executable(&args.executable), contract_id_preimage(&args.contract_id_preimage)
+        ),
+        xdr::HostFunction::CreateContractV2(args) => format!(
+            "Create contract running {} from {}, constructor arguments ({})",
+            contract_executable(&args.executable), contract_id_preimage(&args.contract_id_preimage),
+            sc_vals(&args.constructor_args)
+        ),
+        xdr::HostFunction::UploadContractWasm(wasm) => format!(
+            "Upload contract Wasm of {} bytes (hash {})", wasm.len(), hex::encode(Sha256::digest(wasm))
+        ),
+    }
+}
+
+/// `function(arguments) on contract C...`
+fn contract_call(call: &xdr::InvokeContractArgs) -> String {
+    format!("{}({}) on contract {}", call.function_name.0, sc_vals(&call.args), call.contract_address)
+}
+
+fn contract_executable(executable: &xdr::ContractExecutable) -> String {
+    match executable {
+        xdr::ContractExecutable::Wasm(hash) => format!("Wasm {}", hex::encode(hash.0)),
+        xdr::ContractExecutable::StellarAsset => "the Stellar Asset Contract".to_string(),
+        xdr::ContractExecutable::ExternalRef(reference) => {
+            format!("executable \"{}\" of {}", reference.tag.0, reference.executable_owner)
+        }
+    }
+}
+
+fn contract_id_preimage(preimage: &xdr::ContractIdPreimage) -> String {
+    match preimage {
+        xdr::ContractIdPreimage::Address(from) => format!("{} with salt {}", from.address, hex::encode(from.salt.0)),
+        xdr::ContractIdPreimage::Asset(from) => format!("asset {}", asset(from)),
+    }
+}
+
+/// Who authorizes what: the address whose signature the entry carries (or the transaction's
+/// source account) and the invocation tree it covers
+fn describe_authorization(entry: &xdr::SorobanAuthorizationEntry) -> String {
+    let address = |credentials: &xdr::SorobanAddressCredentials| format!(
+        "{} (nonce {}, valid until ledger {})",
+        credentials.address, credentials.nonce, credentials.signature_expiration_ledger
+    );
+    let signer = match &entry.credentials {
+        xdr::SorobanCredentials::SourceAccount => "Source account".to_string(),
+        xdr::SorobanCredentials::Address(credentials) | xdr::SorobanCredentials::AddressV2(credentials) => {
+            address(credentials)
+        }
+        xdr::SorobanCredentials::AddressWithDelegates(credentials) => format!(
+            "{} with {} delegate(s)", address(&credentials.address_credentials), credentials.delegates.len()
+        ),
+    };
+    format!("{} authorizes {}", signer, authorized_invocation(&entry.root_invocation))
+}
+
+fn authorized_invocation(invocation: &xdr::SorobanAuthorizedInvocation) -> String {
+    let function = match &invocation.function {
+        xdr::SorobanAuthorizedFunction::ContractFn(call) => contract_call(call),
+        xdr::SorobanAuthorizedFunction::CreateContractHostFn(args) => format!(
+            "creating a contract running {} from {}",
+            contract_executable(&args.executable), contract_id_preimage(&args.contract_id_preimage)
+        ),
+        xdr::SorobanAuthorizedFunction::CreateContractV2HostFn(args) => format!(
+            "creating a contract running {} from {}, constructor arguments ({})",
+            contract_executable(&args.executable), contract_id_preimage(&args.contract_id_preimage),
+            sc_vals(&args.constructor_args)
+        ),
+    };
+    if invocation.sub_invocations.is_empty() {
+        return function;
+    }
+    let calls: Vec<String> = invocation.sub_invocations.iter().map(authorized_invocation).collect();
+    format!("{}, which calls [{}]", function, calls.join("; "))
+}
+
+/// A contract value as it would be written in Rust: symbols bare, strings quoted, bytes in hex
+fn sc_val(value: &xdr::ScVal) -> String {
+    use xdr::ScVal;
+    match value {
+        ScVal::Bool(value) => value.to_string(),
+        ScVal::Void => "void".to_string(),
+        ScVal::Error(error) => format!("error {:?}", error),
+        ScVal::U32(value) => value.to_string(),
+        ScVal::I32(value) => value.to_string(),
+        ScVal::U64(value) => value.to_string(),
+        ScVal::I64(value) => value.to_string(),
+        ScVal::Timepoint(time) => format_timestamp(time.0),
+        ScVal::Duration(duration) => format!("{} seconds", duration.0),
+        ScVal::U128(value) => value.to_string(),
+        ScVal::I128(value) => value.to_string(),
+        ScVal::U256(value) => value.to_string(),
+        ScVal::I256(value) => value.to_string(),
+        ScVal::Bytes(bytes) => format!("0x{}", hex::encode(&bytes.0)),
+        ScVal::String(text) => format!("\"{}\"", text.0),
+        ScVal::Symbol(symbol) => symbol.0.to_string(),
+        ScVal::Vec(items) => format!("[{}]", items.as_ref().map(|items| sc_vals(&items.0)).unwrap_or_default()),
+        ScVal::Map(entries) => {
+            let entries: Vec<String> = entries.iter()
+                .flat_map(|map| map.0.iter())
+                .map(|entry| format!("{}: {}", sc_val(&entry.key), sc_val(&entry.val)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        ScVal::Address(address) => address.to_string(),
+        ScVal::ContractInstance(_) => "contract instance".to_string(),
+        ScVal::LedgerKeyContractInstance => "contract instance key".to_string(),
+        ScVal::LedgerKeyNonce(key) => format!("nonce {}", key.nonce),
+        ScVal::ExecutableTag(tag) => format!("executable tag \"{}\"", tag.0),
+    }
+}
+
+fn sc_vals(values: &[xdr::ScVal]) -> String {
+    values.iter().map(sc_val).collect::<Vec<_>>().join(", ")
+}
+
+// ========== SEP-53 MESSAGES ==========
+// Reference: https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0053.md
+
+const SEP53_PREFIX: &[u8] = b"Stellar Signed Message:\n";
+
+/// SHA-256 of the prefixed message; this hash is what the account key signs
+pub fn sep53_message_hash(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(SEP53_PREFIX);
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+pub fn sign_sep53_message(signing_key: &SigningKey, message: &[u8]) -> String {
+    BASE64.encode(signing_key.sign(&sep53_message_hash(message)).to_bytes())
+}
+
+pub fn verify_sep53_message(public_key: &[u8; 32], message: &[u8], signature: &str) -> Result<()> {
+    let signature = BASE64.decode(signature.trim()).context("SEP-53 signatures are expected in base64")?;
+    let verifying_key = VerifyingKey::from_bytes(public_key).context("Invalid ed25519 public key")?;
+    if !verify_signature(&verifying_key, &sep53_message_hash(message), &signature) {
+        bail!("The signature was not made by this account");
+    }
+    Ok(())
+}
+
+// ========== STRKEYS ==========
+// Reference: https://github.com/stellar/stellar-protocol/blob/master/ecosystem/sep-0023.md
+
+/// G... account ID of an ed25519 public key
+pub fn account_id(public_key: &[u8; 32]) -> String {
+    encode_account_id(public_key)
+}
+
+/// Public key behind a G... account ID
+pub fn decode_account_id(address: &str) -> Result<[u8; 32]> {
+    StellarPublicKey::from_account_id(address.trim())
+        .map(|public_key| public_key.0)
+        .map_err(|e| anyhow::anyhow!("Invalid Stellar account ID {}: {}", address, e))
+}
+
+// ========== HELPERS ==========
+
+fn muxed_key(account: &xdr::MuxedAccount) -> [u8; 32] {
+    match account {
+        xdr::MuxedAccount::Ed25519(key) => key.0,
+        xdr::MuxedAccount::MuxedEd25519(muxed) => muxed.ed25519.0,
+    }
+}
+
+fn asset(asset: &xdr::Asset) -> String {
+    match asset {
+        xdr::Asset::Native => "XLM".to_string(),
+        xdr::Asset::CreditAlphanum4(asset) => format!("{}:{}", asset.asset_code, asset.issuer),
+        xdr::Asset::CreditAlphanum12(asset) => format!("{}:{}", asset.asset_code, asset.issuer),
+    }
+}
+
+fn change_trust_asset(line: &xdr::ChangeTrustAsset) -> String {
+    match line {
+        xdr::ChangeTrustAsset::Native => "XLM".to_string(),
+        xdr::ChangeTrustAsset::CreditAlphanum4(asset) => format!("{}:{}", asset.asset_code, asset.issuer),
+        xdr::ChangeTrustAsset::CreditAlphanum12(asset) => format!("{}:{}", asset.asset_code, asset.issuer),
+        xdr::ChangeTrustAsset::PoolShare(xdr::LiquidityPoolParameters::LiquidityPoolConstantProduct(pool)) => {
+            format!("liquidity pool {} / {} ({} bps fee)", self::asset(&pool.asset_a), self::asset(&pool.asset_b), pool.fee)
+        }
+    }
+}
+
+fn asset_path(path: &[xdr::Asset]) -> String {
+    if path.is_empty() {
+        return String::new();
+    }
+    format!(" via {}", path.iter().map(asset).collect::<Vec<_>>().join(" -> "))
+}
+
+/// One ledger entry, as named by sponsorship revocations and Soroban footprints
+fn ledger_key(key: &xdr::LedgerKey) -> String {
+    use xdr::LedgerKey;
+    match key {
+        LedgerKey::Account(key) => format!("account {}", key.account_id),
+        LedgerKey::Trustline(key) => {
+            let line = match &key.asset {
+                xdr::TrustLineAsset::Native => "XLM".to_string(),
+                xdr::TrustLineAsset::CreditAlphanum4(asset) => format!("{}:{}", asset.asset_code, asset.issuer),
+                xdr::TrustLineAsset::CreditAlphanum12(asset) => format!("{}:{}", asset.asset_code, asset.issuer),
+                xdr::TrustLineAsset::PoolShare(pool) => format!("liquidity pool {}", pool_id(pool)),
+            };
+            format!("trustline to {} of {}", line, key.account_id)
+        }
+        LedgerKey::Offer(key) => format!("offer {} of {}", key.offer_id, key.seller_id),
+        LedgerKey::Data(key) => format!("data entry '{}' of {}", key.data_name.0, key.account_id),
+        LedgerKey::ClaimableBalance(key) => format!("claimable balance {}", balance_id(&key.balance_id)),
+        LedgerKey::LiquidityPool(key) => format!("liquidity pool {}", pool_id(&key.liquidity_pool_id)),
+        LedgerKey::ContractData(key) => {
+            let durability = match key.durability {
+                xdr::ContractDataDurability::Temporary => "temporary",
+                xdr::ContractDataDurability::Persistent => "persistent",
+            };
+            format!("{} data {} of contract {}", durability, sc_val(&key.key), key.contract)
+        }
+        LedgerKey::ContractCode(key) => format!("contract code {}", hex::encode(key.hash.0)),
+        LedgerKey::ConfigSetting(key) => format!("config setting {}", key.config_setting_id),
+        LedgerKey::Ttl(key) => format!("TTL of entry {}", hex::encode(key.key_hash.0)),
+    }
+}
+
+fn price(price: &xdr::Price) -> Result<String> {
+    if price.d == 0 {
+        bail!("Price has a zero denominator");
+    }
+    Ok(format!("{}/{}", price.n, price.d))
+}
+
+fn offer(offer_id: i64) -> String {
+    if offer_id == 0 { "new offer".to_string() } else { format!("offer {}", offer_id) }
+}
+
+/// Claimable balance ID as Horizon shows it: the type as 4 bytes of hex, then the hash
+fn balance_id(id: &xdr::ClaimableBalanceId) -> String {
+    let xdr::ClaimableBalanceId::ClaimableBalanceIdTypeV0(hash) = id;
+    format!("00000000{}", hex::encode(hash.0))
+}
+
+fn pool_id(id: &xdr::PoolId) -> String {
+    hex::encode(id.0 .0)
+}
+
+fn signature_hint(public_key: &[u8; 32]) -> [u8; 4] {
+    let mut hint = [0u8; 4];
+    hint.copy_from_slice(&public_key[28..]);
+    hint
+}
+
+fn verify_signature(verifying_key: &VerifyingKey, message: &[u8], signature: &[u8]) -> bool {
+    match <[u8; 64]>::try_from(signature) {
+        Ok(signature) => verifying_key.verify_strict(message, &Signature::from_bytes(&signature)).is_ok(),
+        Err(_) => false,
+    }
+}
+
+fn format_amount(stroops: i64, asset: &str) -> String {
+    let sign = if stroops < 0 { "-" } else { "" };
+    let stroops = stroops.unsigned_abs();
+    let amount = format!("{}{}.{:07}", sign, stroops / STROOPS_PER_XLM as u64, stroops % STROOPS_PER_XLM as u64);
+    if asset.is_empty() { amount } else { format!("{} {}", amount, asset) }
+}
+
+fn format_fee(stroops: i64) -> String {
+    format!("{} stroops ({})", stroops, format_amount(stroops, "XLM"))
+}
+
+fn format_timestamp(seconds: u64) -> String {
+    chrono::DateTime::from_timestamp(seconds as i64, 0)
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| seconds.to_string())
+}
+
+fn format_time_bounds(min: u64, max: u64) -> String {
+    let min = if min == 0 { "any time".to_string() } else { format_timestamp(min) };
+    let max = if max == 0 { "no expiry".to_string() } else { format_timestamp(max) };
+    format!("{} to {}", min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENVELOPE_TYPE_TX_V0: u32 = 0;
+    const ENVELOPE_TYPE_TX: u32 = 2;
+    const ENVELOPE_TYPE_TX_FEE_BUMP: u32 = 5;
+    const KEY_TYPE_ED25519: u32 = 0;
+    const PRECOND_NONE: u32 = 0;
+    const PRECOND_V2: u32 = 2;
+    const CREATE_CLAIMABLE_BALANCE: u32 = 14;
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn write_var_opaque(bytes: &mut Vec<u8>, data: &[u8]) {
+        bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(data);
+        bytes.resize(bytes.len() + (4 - data.len() % 4) % 4, 0);
+    }
+
+    /// v1 transaction with a text memo and the given operations (source flag, type and body
+    /// already encoded), up to and excluding the ext
+    fn transaction_xdr(source: &[u8; 32], preconditions: &[u8], operations: &[Vec<u8>]) -> Vec<u8> {
+        let mut xdr = Vec::new();
+        xdr.extend_from_slice(&KEY_TYPE_ED25519.to_be_bytes());
+        xdr.extend_from_slice(source);
+        xdr.extend_from_slice(&100u32.to_be_bytes()); // fee
+        xdr.extend_from_slice(&4_294_967_297i64.to_be_bytes()); // sequence
+        xdr.extend_from_slice(preconditions);
+        xdr.extend_from_slice(&1u32.to_be_bytes()); // MEMO_TEXT
+        write_var_opaque(&mut xdr, b"rent");
+        xdr.extend_from_slice(&(operations.len() as u32).to_be_bytes());
+        for operation in operations {
+            xdr.extend_from_slice(operation);
+        }
+        xdr
+    }
+
+    fn payment_operation(destination: &[u8; 32]) -> Vec<u8> {
+        let mut xdr = Vec::new();
+        xdr.extend_from_slice(&0u32.to_be_bytes()); // no operation source
+        xdr.extend_from_slice(&1u32.to_be_bytes()); // PAYMENT
+        xdr.extend_from_slice(&KEY_TYPE_ED25519.to_be_bytes());
+        xdr.extend_from_slice(destination);
+        xdr.extend_from_slice(&0u32.to_be_bytes()); // native asset
+        xdr.extend_from_slice(&125_000_000i64.to_be_bytes());
+        xdr
+    }
+
+    /// v1 envelope with one native payment and a text memo, no signatures
+    fn payment_envelope(source: &[u8; 32], destination: &[u8; 32]) -> Vec<u8> {
+        let mut xdr = ENVELOPE_TYPE_TX.to_be_bytes().to_vec();
+        xdr.extend_from_slice(&transaction_xdr(source, &PRECOND_NONE.to_be_bytes(), &[payment_operation(destination)]));
+        xdr.extend_from_slice(&0u32.to_be_bytes()); // ext
+        xdr.extend_from_slice(&0u32.to_be_bytes()); // no signatures
+        xdr
+    }
+
+    fn account_address(public_key: &[u8; 32]) -> xdr::ScAddress {
+        xdr::ScAddress::Account(xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(*public_key))))
+    }
+
+    /// v1 envelope calling transfer(source, destination, 12.5) on a token contract, authorized by
+    /// the source account, with its Soroban data and no signatures
+    fn soroban_envelope(source: &[u8; 32], destination: &[u8; 32]) -> Vec<u8> {
+        let token = xdr::ScAddress::Contract(xdr::ContractId(xdr::Hash([7u8; 32])));
+        let call = xdr::InvokeContractArgs {
+            contract_address: token.clone(),
+            function_name: xdr::ScSymbol("transfer".try_into().unwrap()),
+            args: vec![
+                xdr::ScVal::Address(account_address(source)),
+                xdr::ScVal::Address(account_address(destination)),
+                xdr::ScVal::I128(xdr::Int128Parts { hi: 0, lo: 125_000_000 }),
+            ].try_into().unwrap(),
+        };
+        let authorization = xdr::SorobanAuthorizationEntry {
+            credentials: xdr::SorobanCredentials::SourceAccount,
+            root_invocation: xdr::SorobanAuthorizedInvocation {
+                function: xdr::SorobanAuthorizedFunction::ContractFn(call.clone()),
+                sub_invocations: xdr::VecM::default(),
+            },
+        };
+        let operation = xdr::Operation {
+            source_account: None,
+            body: xdr::OperationBody::InvokeHostFunction(xdr::InvokeHostFunctionOp {
+                host_function: xdr::HostFunction::InvokeContract(call),
+                auth: vec![authorization].try_into().unwrap(),
+            }),
+        };
+        let balance = xdr::LedgerKey::ContractData(xdr::LedgerKeyContractData {
+            contract: token,
+            key: xdr::ScVal::Vec(Some(xdr::ScVec(vec![
+                xdr::ScVal::Symbol(xdr::ScSymbol("Balance".try_into().unwrap())),
+                xdr::ScVal::Address(account_address(source)),
+            ].try_into().unwrap()))),
+            durability: xdr::ContractDataDurability::Persistent,
+        });
+        let data = xdr::SorobanTransactionData {
+            ext: xdr::SorobanTransactionDataExt::V0,
+            resources: xdr::SorobanResources {
+                footprint: xdr::LedgerFootprint {
+                    read_only: xdr::VecM::default(),
+                    read_write: vec![balance].try_into().unwrap(),
+                },
+                instructions: 2_000_000,
+                disk_read_bytes: 1_000,
+                write_bytes: 500,
+            },
+            resource_fee: 60_000,
+        };
+        let transaction = xdr::Transaction {
+            source_account: xdr::MuxedAccount::Ed25519(xdr::Uint256(*source)),
+            fee: 60_100,
+            seq_num: xdr::SequenceNumber(4_294_967_297),
+            cond: xdr::Preconditions::None,
+            memo: xdr::Memo::None,
+            operations: vec![operation].try_into().unwrap(),
+            ext: xdr::TransactionExt::V1(data),
+        };
+        xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope { tx: transaction, signatures: xdr::VecM::default() })
+            .to_xdr(Limits::none())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_strkey_round_trip() {
+        // SEP-23 test vectors
+        let address = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+        let public_key = decode_account_id(address).unwrap();
+        assert_eq!(account_id(&public_key), address);
+        let muxed = xdr::MuxedAccount::MuxedEd25519(xdr::MuxedAccountMed25519 { id: 0, ed25519: xdr::Uint256(public_key) });
+        assert_eq!(muxed.to_string(), "MA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJUAAAAAAAAAAAACJUQ");
+
+        assert!(decode_account_id("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGA").is_err());
+        assert!(decode_account_id("SAKICEVQLYWGSOJS4WW7HZJWAHZVEEBS527LHK5V4MLJALYKICQCJXMW").is_err());
+    }
+
+    #[test]
+    fn test_network_selection() {
+        assert_eq!(StellarNetwork::parse("testnet").unwrap(), StellarNetwork::Testnet);
+        assert_eq!(StellarNetwork::parse(PUBLIC_NETWORK_PASSPHRASE).unwrap(), StellarNetwork::Public);
+        assert_eq!(
+            StellarNetwork::parse("Standalone Network ; February 2017").unwrap().passphrase(),
+            "Standalone Network ; February 2017"
+        );
+        assert!(StellarNetwork::parse("testnt").is_err());
+        assert_eq!(
+            hex::encode(StellarNetwork::Public.network_id()),
+            "7ac33997544e3175d266bd022439b22cdb16508c01163f26e5cb2a3e1045a979"
+        );
+    }
+
+    #[test]
+    fn test_payment_signing() {
+        let key = signing_key(1);
+        let source = key.verifying_key().to_bytes();
+        let destination = signing_key(2).verifying_key().to_bytes();
+        let xdr = payment_envelope(&source, &destination);
+
+        let mut envelope = StellarEnvelope::parse(&xdr).unwrap();
+        assert_eq!(envelope.kind, EnvelopeKind::V1);
+        assert_eq!(envelope.serialize().unwrap(), xdr);
+        assert_eq!(envelope.transaction.memo.as_deref(), Some("text \"rent\""));
+        assert_eq!(
+            envelope.transaction.operations[0],
+            format!("Payment of 12.5000000 XLM to {}", account_id(&destination))
+        );
+
+        // The hash commits to the network passphrase
+        let network = StellarNetwork::Testnet;
+        assert_ne!(envelope.hash(&network).unwrap(), envelope.hash(&StellarNetwork::Public).unwrap());
+
+        envelope.sign(&key, &network, false).unwrap();
+        assert_eq!(envelope.signatures().len(), 1);
+        assert_eq!(envelope.signatures()[0].hint.0, source[28..]);
+        let signature: [u8; 64] = envelope.signatures()[0].signature.0.to_vec().try_into().unwrap();
+        key.verifying_key().verify_strict(&envelope.hash(&network).unwrap(), &Signature::from_bytes(&signature)).unwrap();
+        assert!(envelope.sign(&key, &network, false).is_err());
+
+        // Only the source account (or an operation source) may sign
+        assert!(envelope.sign(&signing_key(2), &network, false).is_err());
+
+        // Signed envelope round-trips through base64 with the signature appended
+        let signed = StellarEnvelope::from_base64(&envelope.to_base64().unwrap()).unwrap();
+        assert_eq!(signed.signatures().len(), 1);
+        assert_eq!(signed.serialize().unwrap().len(), xdr.len() + 4 + 4 + 64);
+    }
+
+    #[test]
+    fn test_co_signer_signing() {
+        // A multisig account's extra signer is neither the source nor an operation source
+        let source = signing_key(1);
+        let co_signer = signing_key(2);
+        let xdr = payment_envelope(&source.verifying_key().to_bytes(), &[9u8; 32]);
+        let network = StellarNetwork::Public;
+
+        let mut envelope = StellarEnvelope::parse(&xdr).unwrap();
+        assert!(!envelope.is_source(co_signer.verifying_key().as_bytes()));
+        assert!(envelope.sign(&co_signer, &network, false).is_err());
+        envelope.sign(&co_signer, &network, true).unwrap();
+        envelope.sign(&source, &network, false).unwrap();
+        assert_eq!(envelope.signatures().len(), 2);
+        assert_eq!(envelope.signatures()[0].hint.0, co_signer.verifying_key().as_bytes()[28..]);
+
+        // Signing twice is still refused
+        assert!(envelope.sign(&co_signer, &network, true).is_err());
+    }
+
+    #[test]
+    fn test_v0_envelope_hashes_as_v1() {
+        let key = signing_key(3);
+        let source = key.verifying_key().to_bytes();
+        let v1 = payment_envelope(&source, &[9u8; 32]);
+
+        // Same transaction as a v0 envelope: no source key type, and type 0
+        let mut v0 = ENVELOPE_TYPE_TX_V0.to_be_bytes().to_vec();
+        v0.extend_from_slice(&v1[8..]);
+
+        let v0 = StellarEnvelope::parse(&v0).unwrap();
+        let v1 = StellarEnvelope::parse(&v1).unwrap();
+        assert_eq!(v0.kind, EnvelopeKind::V0);
+        assert_eq!(v0.transaction.source_account, v1.transaction.source_account);
+        assert_eq!(v0.hash(&StellarNetwork::Public).unwrap(), v1.hash(&StellarNetwork::Public).unwrap());
+    }
+
+    #[test]
+    fn test_fee_bump_requires_fee_source() {
+        let inner_key = signing_key(5);
+        let fee_key = signing_key(6);
+        let inner = payment_envelope(&inner_key.verifying_key().to_bytes(), &[9u8; 32]);
+
+        let mut xdr = ENVELOPE_TYPE_TX_FEE_BUMP.to_be_bytes().to_vec();
+        xdr.extend_from_slice(&KEY_TYPE_ED25519.to_be_bytes());
+        xdr.extend_from_slice(fee_key.verifying_key().as_bytes());
+        xdr.extend_from_slice(&400i64.to_be_bytes());
+        xdr.extend_from_slice(&inner); // ENVELOPE_TYPE_TX, the transaction and its signatures
+        xdr.extend_from_slice(&0u32.to_be_bytes()); // ext
+        xdr.extend_from_slice(&0u32.to_be_bytes()); // no signatures
+
+        let mut envelope = StellarEnvelope::parse(&xdr).unwrap();
+        assert_eq!(envelope.kind, EnvelopeKind::FeeBump);
+        assert_eq!(envelope.serialize().unwrap(), xdr);
+        assert!(envelope.sign(&inner_key, &StellarNetwork::Public, false).is_err());
+        envelope.sign(&fee_key, &StellarNetwork::Public, false).unwrap();
+    }
+
+    #[test]
+    fn test_revoke_sponsorship_is_decoded() {
+        let key = signing_key(7);
+        let sponsored = signing_key(8).verifying_key().to_bytes();
+        let mut operation = vec![0, 0, 0, 0];
+        operation.extend_from_slice(&18u32.to_be_bytes()); // REVOKE_SPONSORSHIP
+        operation.extend_from_slice(&0u32.to_be_bytes()); // ledger entry
+        operation.extend_from_slice(&0u32.to_be_bytes()); // ACCOUNT
+        operation.extend_from_slice(&KEY_TYPE_ED25519.to_be_bytes());
+        operation.extend_from_slice(&sponsored);
+
+        let mut xdr = ENVELOPE_TYPE_TX.to_be_bytes().to_vec();
+        xdr.extend_from_slice(&transaction_xdr(&key.verifying_key().to_bytes(), &PRECOND_NONE.to_be_bytes(), &[operation]));
+        xdr.extend_from_slice(&[0; 8]); // ext, no signatures
+
+        let mut envelope = StellarEnvelope::parse(&xdr).unwrap();
+        assert_eq!(
+            envelope.transaction.operations[0],
+            format!("Revoke sponsorship of account {}", account_id(&sponsored))
+        );
+        envelope.sign(&key, &StellarNetwork::Public, false).unwrap();
+    }
+
+    #[test]
+    fn test_soroban_envelope_signing() {
+        let key = signing_key(4);
+        let source = key.verifying_key().to_bytes();
+        let destination = signing_key(5).verifying_key().to_bytes();
+        let xdr = soroban_envelope(&source, &destination);
+        let token = "CADQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQOBYHA4DQP5KR";
+
+        let mut envelope = StellarEnvelope::parse(&xdr).unwrap();
+        assert_eq!(envelope.serialize().unwrap(), xdr);
+        let call = format!("transfer({}, {}, 125000000) on contract {}", account_id(&source), account_id(&destination), token);
+        assert_eq!(envelope.transaction.operations[0], format!("Invoke {}", call));
+        assert_eq!(envelope.transaction.soroban, vec![
+            ("Authorization #0".to_string(), format!("Source account authorizes {}", call)),
+            ("Resource Fee".to_string(), "60000 stroops (0.0060000 XLM)".to_string()),
+            ("Writes".to_string(), format!("persistent data [Balance, {}] of contract {}", account_id(&source), token)),
+        ]);
+
+        // The hash covers the Soroban data at the end of the transaction
+        let mut changed = xdr.clone();
+        changed[xdr.len() - 9] ^= 1;
+        let changed = StellarEnvelope::parse(&changed).unwrap();
+        assert_ne!(changed.hash(&StellarNetwork::Public).unwrap(), envelope.hash(&StellarNetwork::Public).unwrap());
+
+        envelope.sign(&key, &StellarNetwork::Public, false).unwrap();
+        assert!(envelope.sign(&signing_key(5), &StellarNetwork::Public, false).is_err());
+        let signed = StellarEnvelope::parse(&envelope.serialize().unwrap()).unwrap();
+        assert_eq!(signed.signatures().len(), 1);
+        assert_eq!(signed.hash(&StellarNetwork::Public).unwrap(), envelope.hash(&StellarNetwork::Public).unwrap());
+        assert_eq!(signed.serialize().unwrap(), envelope.serialize().unwrap());
+    }
+
+    #[test]
+    fn test_soroban_operation_must_be_alone() {
+        let source = signing_key(4).verifying_key().to_bytes();
+        let mut envelope = xdr::TransactionEnvelope::from_xdr(soroban_envelope(&source, &[9u8; 32]), Limits::none()).unwrap();
+        let xdr::TransactionEnvelope::Tx(v1) = &mut envelope else { unreachable!() };
+
+        // Soroban data without its operation
+        let payment = xdr::Operation::from_xdr(payment_operation(&[9u8; 32]), Limits::none()).unwrap();
+        let mut classic = v1.clone();
+        classic.tx.operations = vec![payment.clone()].try_into().unwrap();
+        let classic = xdr::TransactionEnvelope::Tx(classic).to_xdr(Limits::none()).unwrap();
+        assert!(StellarEnvelope::parse(&classic).is_err());
+
+        // A Soroban operation next to a classic one
+        let mut operations = v1.tx.operations.to_vec();
+        operations.push(payment);
+        v1.tx.operations = operations.try_into().unwrap();
+        assert!(StellarEnvelope::parse(&envelope.to_xdr(Limits::none()).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_v2_preconditions() {
+        let key = signing_key(9);
+        let mut preconditions = PRECOND_V2.to_be_bytes().to_vec();
+        preconditions.extend_from_slice(&0u32.to_be_bytes()); // no time bounds
+        preconditions.extend_from_slice(&1u32.to_be_bytes()); // ledger bounds
+        preconditions.extend_from_slice(&100u32.to_be_bytes());
+        preconditions.extend_from_slice(&0u32.to_be_bytes());
+        preconditions.extend_from_slice(&0u32.to_be_bytes()); // no min sequence
+        preconditions.extend_from_slice(&0u64.to_be_bytes()); // min sequence age
+        preconditions.extend_from_slice(&0u32.to_be_bytes()); // min sequence ledger gap
+        preconditions.extend_from_slice(&0u32.to_be_bytes()); // no extra signers
+
+        let mut xdr = ENVELOPE_TYPE_TX.to_be_bytes().to_vec();
+        xdr.extend_from_slice(&transaction_xdr(&key.verifying_key().to_bytes(), &preconditions, &[payment_operation(&[9u8; 32])]));
+        xdr.extend_from_slice(&[0; 8]); // ext, no signatures
+
+        let mut envelope = StellarEnvelope::parse(&xdr).unwrap();
+        assert_eq!(envelope.serialize().unwrap(), xdr);
+        assert_eq!(envelope.transaction.preconditions, vec![("Ledger Bounds".to_string(), "100 to no limit".to_string())]);
+        envelope.sign(&key, &StellarNetwork::Public, false).unwrap();
+    }
+
+    #[test]
+    fn test_malformed_envelopes_are_rejected() {
+        let source = signing_key(4).verifying_key().to_bytes();
+        let xdr = payment_envelope(&source, &[9u8; 32]);
+        for length in [4, 60, xdr.len() - 1] {
+            assert!(StellarEnvelope::parse(&xdr[..length]).is_err());
+        }
+        let mut trailing = xdr.clone();
+        trailing.extend_from_slice(&[0; 4]);
+        assert!(StellarEnvelope::parse(&trailing).is_err());
+
+        // An unknown operation type is rejected
+        let mut unknown = xdr.clone();
+        let position = 4 + 36 + 4 + 8 + 4 + 4 + 8 + 4 + 4;
+        assert_eq!(unknown[position..position + 4], 1u32.to_be_bytes());
+        unknown[position..position + 4].copy_from_slice(&99u32.to_be_bytes());
+        assert!(StellarEnvelope::parse(&unknown).is_err());
+
+        let soroban = soroban_envelope(&source, &[9u8; 32]);
+        assert!(StellarEnvelope::parse(&soroban[..soroban.len() - 2]).is_err());
+    }
+
+    #[test]
+    fn test_claim_predicate_depth() {
+        // CreateClaimableBalance of 1 XLM to one claimant behind `depth` nested NOT predicates
+        let claimable_balance_envelope = |depth: usize| {
+            let mut operation = vec![0, 0, 0, 0];
+            operation.extend_from_slice(&CREATE_CLAIMABLE_BALANCE.to_be_bytes());
+            operation.extend_from_slice(&0u32.to_be_bytes()); // native asset
+            operation.extend_from_slice(&10_000_000i64.to_be_bytes());
+            operation.extend_from_slice(&1u32.to_be_bytes()); // one claimant
+            operation.extend_from_slice(&0u32.to_be_bytes()); // CLAIMANT_TYPE_V0
+            operation.extend_from_slice(&KEY_TYPE_ED25519.to_be_bytes());
+            operation.extend_from_slice(&[7u8; 32]);
+            for _ in 0..depth {
+                operation.extend_from_slice(&3u32.to_be_bytes()); // NOT
+                operation.extend_from_slice(&1u32.to_be_bytes()); // predicate present
+            }
+            operation.extend_from_slice(&0u32.to_be_bytes()); // unconditional
+            let mut xdr = ENVELOPE_TYPE_TX.to_be_bytes().to_vec();
+            xdr.extend_from_slice(&transaction_xdr(&[1u8; 32], &PRECOND_NONE.to_be_bytes(), &[operation]));
+            xdr.extend_from_slice(&0u32.to_be_bytes()); // ext
+            xdr.extend_from_slice(&0u32.to_be_bytes()); // no signatures
+            xdr
+        };
+
+        let envelope = StellarEnvelope::parse(&claimable_balance_envelope(3)).unwrap();
+        assert_eq!(envelope.transaction.operations.len(), 1);
+        assert!(StellarEnvelope::parse(&claimable_balance_envelope(4)).is_err());
+        // Deep nesting stops at the decoder's depth limit instead of overflowing the stack
+        assert!(StellarEnvelope::parse(&claimable_balance_envelope(100_000)).is_err());
+    }
+
+    #[test]
+    fn test_sep53_message() {
+        // SEP-53 test vector
+        let seed = stellar_base::crypto::decode_secret_seed("SAKICEVQLYWGSOJS4WW7HZJWAHZVEEBS527LHK5V4MLJALYKICQCJXMW").unwrap();
+        let key = SigningKey::from_bytes(&seed.try_into().unwrap());
+        let address = "GBXFXNDLV4LSWA4VB7YIL5GBD7BVNR22SGBTDKMO2SBZZHDXSKZYCP7L";
+        assert_eq!(account_id(key.verifying_key().as_bytes()), address);
+
+        let signature = sign_sep53_message(&key, b"Hello, World!");
+        assert_eq!(
+            signature,
+            "fO5dbYhXUhBMhe6kId/cuVq/AfEnHRHEvsP8vXh03M1uLpi5e46yO2Q8rEBzu3feXQewcQE5GArp88u6ePK6BA=="
+        );
+        let public_key = decode_account_id(address).unwrap();
+        verify_sep53_message(&public_key, b"Hello, World!", &signature).unwrap();
+        assert!(verify_sep53_message(&public_key, b"Hello, World?", &signature).is_err());
+    }
+}
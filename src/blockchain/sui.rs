@@ -1,6 +1,8 @@
-use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys};
+use anyhow::{Result, Context, bail};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SigningCapabilities, SignedTransaction};
+use crate::blockchain::sui_tx::{self, SuiTransaction};
 use crate::crypto::ed25519_utils::{derive_ed25519_key_from_mnemonic, private_key_to_public_key_ed25519};
+use sui_crypto::ed25519::Ed25519PrivateKey;
 use sui_sdk_types::{Address as SuiAddress, Ed25519PublicKey};
 
 pub struct SuiHandler;
@@ -46,6 +48,25 @@ impl SuiHandler {
             Err(_) => false,
         }
     }
+
+    fn signing_key(&self, private_key: &[u8], address: &str) -> Result<Ed25519PrivateKey> {
+        let secret: [u8; 32] = private_key.try_into()
+            .map_err(|_| anyhow::anyhow!("Sui private key must be 32 bytes"))?;
+        let private_key = Ed25519PrivateKey::new(secret);
+        if sui_tx::private_key_address(&private_key).to_string() != address.to_lowercase() {
+            bail!("Private key does not belong to {}", address);
+        }
+        Ok(private_key)
+    }
+}
+
+/// The `suiprivkey1...` form of a stored private key, as imported by Sui wallets and `sui keytool import`
+pub fn export_private_key(private_key_hex: &str) -> Result<String> {
+    let secret: [u8; 32] = hex::decode(private_key_hex)
+        .context("Invalid hex private key")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Sui private key must be 32 bytes"))?;
+    sui_tx::encode_private_key(&secret)
 }
 
 impl BlockchainHandler for SuiHandler {
@@ -84,9 +105,12 @@ impl BlockchainHandler for SuiHandler {
     }
 
     fn derive_from_private_key(&self, private_key_hex: &str) -> Result<WalletKeys> {
-        // Parse private key from hex
-        let private_key_bytes = hex::decode(private_key_hex)
-            .context("Invalid hex private key")?;
+        // Sui wallets export Bech32 suiprivkey1... keys; hex keys are accepted as before
+        let private_key_bytes = if sui_tx::looks_like_private_key(private_key_hex) {
+            sui_tx::decode_private_key(private_key_hex)?.to_vec()
+        } else {
+            hex::decode(private_key_hex).context("Invalid hex private key")?
+        };
 
         if private_key_bytes.len() != 32 {
             anyhow::bail!("Sui private key must be 32 bytes");
@@ -114,6 +138,49 @@ impl BlockchainHandler for SuiHandler {
     fn get_blockchain_name(&self) -> &'static str {
         "Sui"
     }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities::ALL
+    }
+
+    fn sign_message(&self, private_key: &[u8], address: &str, message: &[u8], format: Option<&str>) -> Result<String> {
+        check_message_format(format)?;
+        let signing_key = self.signing_key(private_key, address)?;
+        sui_tx::sign_personal_message(&signing_key, message)
+    }
+
+    fn verify_message(&self, address: &str, message: &[u8], signature: &str, format: Option<&str>) -> Result<String> {
+        check_message_format(format)?;
+        sui_tx::verify_personal_message(address, message, signature)?;
+        Ok("Sui personal message".to_string())
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        Ok(SuiTransaction::parse(transaction)?.details())
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        let signing_key = self.signing_key(private_key, address)?;
+        let transaction = SuiTransaction::parse(transaction)?;
+        // Sponsored transactions are signed by both the sender and the gas owner
+        let wallet = sui_tx::private_key_address(&signing_key);
+        if transaction.sender() != wallet && transaction.gas_owner() != wallet {
+            bail!(
+                "This wallet ({}) is neither the transaction sender ({}) nor its gas owner ({})",
+                address,
+                transaction.sender(),
+                transaction.gas_owner()
+            );
+        }
+        transaction.sign(&signing_key)
+    }
+}
+
+fn check_message_format(format: Option<&str>) -> Result<()> {
+    match format.map(str::to_lowercase).as_deref() {
+        None | Some("personal") | Some("personal-message") => Ok(()),
+        Some(other) => bail!("Unknown Sui message format '{}'. Sui messages use signPersonalMessage", other),
+    }
 }
 
 #[cfg(test)]
@@ -161,4 +228,64 @@ mod tests {
         assert!(keys.address.starts_with("0x"));
         assert!(keys.address.len() >= 40);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sui_private_key_bech32_import_export() {
+        let handler = SuiHandler::new();
+        let private_key = "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+        let exported = export_private_key(private_key).unwrap();
+        assert_eq!(exported, "suiprivkey1qqfrg4ncjz4ummcjx3t83y9tehh3ydzk0zg2hn00zg69v7ys40x77cw2aqh");
+
+        let keys = handler.derive_from_private_key(&exported).unwrap();
+        assert_eq!(keys.private_key, private_key);
+        assert_eq!(keys.address, "0x2bab814efca01c4474a6c1a689f8c2194c1681a2dceb14bc356f6afacec300f5");
+    }
+
+    #[test]
+    fn test_sui_sponsored_transaction_signing() {
+        use base64::Engine;
+        use sui_sdk_types::{GasPayment, ProgrammableTransaction, Transaction, TransactionExpiration, TransactionKind};
+
+        let handler = SuiHandler::new();
+        let sender_key = hex::decode("1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef").unwrap();
+        let sponsor_key = [7u8; 32];
+        let sender = handler.derive_from_private_key(&hex::encode(&sender_key)).unwrap().address;
+        let sponsor = handler.derive_from_private_key(&hex::encode(sponsor_key)).unwrap().address;
+
+        let transaction = Transaction {
+            kind: TransactionKind::ProgrammableTransaction(ProgrammableTransaction { inputs: vec![], commands: vec![] }),
+            sender: sender.parse().unwrap(),
+            gas_payment: GasPayment { objects: vec![], owner: sponsor.parse().unwrap(), price: 1000, budget: 5_000_000 },
+            expiration: TransactionExpiration::None,
+        };
+        let encoded = base64::engine::general_purpose::STANDARD.encode(bcs::to_bytes(&transaction).unwrap());
+
+        let details = handler.describe_transaction(encoded.as_bytes()).unwrap();
+        assert!(details.contains(&("Gas Sponsor".to_string(), sponsor.clone())));
+
+        // Both the sender and the gas sponsor sign the same digest
+        let by_sender = handler.sign_transaction(&sender_key, &sender, encoded.as_bytes()).unwrap();
+        let by_sponsor = handler.sign_transaction(&sponsor_key, &sponsor, encoded.as_bytes()).unwrap();
+        assert_eq!(by_sender.hash, by_sponsor.hash);
+        assert_ne!(by_sender.encoded, by_sponsor.encoded);
+
+        let other_key = [9u8; 32];
+        let other = handler.derive_from_private_key(&hex::encode(other_key)).unwrap().address;
+        assert!(handler.sign_transaction(&other_key, &other, encoded.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_sui_message_signing() {
+        let handler = SuiHandler::new();
+        let private_key = hex::decode("1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef").unwrap();
+        let address = "0x2bab814efca01c4474a6c1a689f8c2194c1681a2dceb14bc356f6afacec300f5";
+
+        let signature = handler.sign_message(&private_key, address, b"Sign in to example.com", None).unwrap();
+        let scheme = handler.verify_message(address, b"Sign in to example.com", &signature, None).unwrap();
+        assert_eq!(scheme, "Sui personal message");
+        assert!(handler.sign_message(&private_key, address, b"hi", Some("eip191")).is_err());
+
+        let other = format!("0x{}", "ab".repeat(32));
+        assert!(handler.sign_message(&private_key, &other, b"hi", None).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+use std::borrow::Cow;
+use anyhow::{Result, Context, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sui_crypto::ed25519::{Ed25519PrivateKey, Ed25519Verifier};
+use sui_crypto::{SuiSigner, SuiVerifier};
+use sui_sdk_types::{
+    Address, Argument, Command, Input, PersonalMessage, ProgrammableTransaction, SimpleSignature, Transaction,
+    TransactionExpiration, TransactionKind, TypeTag, UserSignature,
+};
+use crate::blockchain::SignedTransaction;
+
+// Bech32 prefix of exported private keys (sui keytool export, Sui Wallet)
+const PRIVATE_KEY_HRP: &str = "suiprivkey";
+
+// Signature scheme flag in front of exported keys
+const ED25519_FLAG: u8 = 0x00;
+
+const MIST_PER_SUI: u64 = 1_000_000_000;
+
+// ========== PRIVATE KEYS ==========
+
+pub fn looks_like_private_key(value: &str) -> bool {
+    value.trim().starts_with("suiprivkey1")
+}
+
+/// Decodes a `suiprivkey1...` key: Bech32 of the scheme flag and the 32-byte secret
+pub fn decode_private_key(value: &str) -> Result<[u8; 32]> {
+    let (hrp, data) = bech32::decode(value.trim()).context("Invalid suiprivkey Bech32 string")?;
+    if hrp.as_str() != PRIVATE_KEY_HRP {
+        bail!("Expected a {} key, got prefix '{}'", PRIVATE_KEY_HRP, hrp);
+    }
+    match data.split_first() {
+        Some((&ED25519_FLAG, secret)) => secret.try_into()
+            .map_err(|_| anyhow::anyhow!("Sui private key must be 32 bytes, got {}", secret.len())),
+        Some((flag, _)) => bail!("Only ed25519 Sui keys are supported (key scheme flag 0x{:02x})", flag),
+        None => bail!("Empty Sui private key"),
+    }
+}
+
+pub fn encode_private_key(secret: &[u8; 32]) -> Result<String> {
+    let hrp = bech32::Hrp::parse(PRIVATE_KEY_HRP).context("Invalid bech32 prefix")?;
+    bech32::encode::<bech32::Bech32>(hrp, &[&[ED25519_FLAG][..], secret].concat())
+        .context("Failed to encode suiprivkey")
+}
+
+// ========== SIGNATURES ==========
+
+/// Sui address of a private key: BLAKE2b-256 of the scheme flag and the public key
+pub fn private_key_address(private_key: &Ed25519PrivateKey) -> Address {
+    private_key.public_key().derive_address()
+}
+
+/// signPersonalMessage: the message is hashed as a BCS byte vector under the PersonalMessage intent.
+/// Returns the serialized signature (base64 of flag || signature || public key)
+pub fn sign_personal_message(private_key: &Ed25519PrivateKey, message: &[u8]) -> Result<String> {
+    let signature = private_key.sign_personal_message(&PersonalMessage(Cow::Borrowed(message)))
+        .map_err(|e| anyhow::anyhow!("Failed to sign the message: {}", e))?;
+    Ok(signature.to_base64())
+}
+
+pub fn verify_personal_message(address: &str, message: &[u8], signature: &str) -> Result<()> {
+    let address: Address = address.parse().context("Invalid Sui address")?;
+    let signature = UserSignature::from_base64(signature.trim())
+        .map_err(|e| anyhow::anyhow!("Invalid Sui serialized signature: {}", e))?;
+    let UserSignature::Simple(SimpleSignature::Ed25519 { public_key, .. }) = &signature else {
+        bail!("Only ed25519 Sui signatures are supported (scheme {:?})", signature.scheme());
+    };
+
+    let signer = public_key.derive_address();
+    if signer != address {
+        bail!("The signature's public key belongs to {}, not {}", signer, address);
+    }
+    Ed25519Verifier::new()
+        .verify_personal_message(&PersonalMessage(Cow::Borrowed(message)), &signature)
+        .map_err(|_| anyhow::anyhow!("Signature does not match the message"))
+}
+
+// ========== TRANSACTIONS ==========
+
+/// BCS-encoded `TransactionData` (V1 programmable transactions), e.g. from
+/// `sui client ... --serialize-unsigned-transaction` or a dApp's `Transaction.build()`
+pub struct SuiTransaction {
+    pub transaction: Transaction,
+}
+
+impl SuiTransaction {
+    /// Accepts base64 (the usual transport), hex or raw BCS bytes
+    pub fn parse(input: &[u8]) -> Result<Self> {
+        let bytes = transaction_bytes(input);
+        let transaction: Transaction = bcs::from_bytes(&bytes).context("Invalid Sui TransactionData")?;
+        // The signing digest hashes the re-serialized transaction, so it must be the input byte for byte
+        if bcs::to_bytes(&transaction)? != bytes {
+            bail!("Sui TransactionData is not canonically encoded");
+        }
+        if !matches!(transaction.kind, TransactionKind::ProgrammableTransaction(_)) {
+            bail!("Only programmable transactions can be signed (system transaction kinds are not user-signable)");
+        }
+        Ok(Self { transaction })
+    }
+
+    pub fn sender(&self) -> Address {
+        self.transaction.sender
+    }
+
+    /// Owner of the gas coins: the sender, or a sponsor paying for the transaction
+    pub fn gas_owner(&self) -> Address {
+        self.transaction.gas_payment.owner
+    }
+
+    fn programmable(&self) -> &ProgrammableTransaction {
+        match &self.transaction.kind {
+            TransactionKind::ProgrammableTransaction(programmable) => programmable,
+            _ => unreachable!("parse only accepts programmable transactions"),
+        }
+    }
+
+    /// Transaction digest as shown by explorers: base58 of BLAKE2b-256("TransactionData::" || bytes)
+    pub fn digest(&self) -> String {
+        self.transaction.digest().to_string()
+    }
+
+    pub fn details(&self) -> Vec<(String, String)> {
+        let gas = &self.transaction.gas_payment;
+        let mut details = vec![
+            ("Sender".to_string(), self.sender().to_string()),
+            ("Gas Budget".to_string(), format_mist(gas.budget)),
+            ("Gas Price".to_string(), format!("{} MIST", gas.price)),
+            ("Gas Payment".to_string(), format!("{} coin(s)", gas.objects.len())),
+        ];
+        if self.gas_owner() != self.sender() {
+            details.push(("Gas Sponsor".to_string(), self.gas_owner().to_string()));
+        }
+        if let TransactionExpiration::Epoch(epoch) = self.transaction.expiration {
+            details.push(("Expires".to_string(), format!("after epoch {}", epoch)));
+        }
+        details.push(("Transaction Digest".to_string(), self.digest()));
+
+        for (index, command) in self.programmable().commands.iter().enumerate() {
+            details.push((format!("Command #{}", index + 1), self.describe_command(command)));
+        }
+        details
+    }
+
+    /// Signs the transaction's intent digest with the wallet key, as sender or as gas sponsor;
+    /// the result is the serialized signature to submit alongside the transaction bytes
+    pub fn sign(&self, private_key: &Ed25519PrivateKey) -> Result<SignedTransaction> {
+        let signature = private_key.sign_transaction(&self.transaction)
+            .map_err(|e| anyhow::anyhow!("Failed to sign the transaction: {}", e))?;
+        Ok(SignedTransaction {
+            encoded: signature.to_base64(),
+            hash: Some(self.digest()),
+        })
+    }
+
+    fn describe_command(&self, command: &Command) -> String {
+        let list = |arguments: &[Argument]| {
+            arguments.iter().map(|argument| self.describe_argument(argument)).collect::<Vec<_>>().join(", ")
+        };
+        match command {
+            Command::MoveCall(call) => {
+                let types = if call.type_arguments.is_empty() {
+                    String::new()
+                } else {
+                    format!("<{}>", call.type_arguments.iter().map(format_type_tag).collect::<Vec<_>>().join(", "))
+                };
+                format!(
+                    "MoveCall {}::{}::{}{}({})",
+                    short_address(&call.package),
+                    call.module,
+                    call.function,
+                    types,
+                    list(&call.arguments)
+                )
+            }
+            Command::TransferObjects(transfer) => {
+                format!("TransferObjects [{}] to {}", list(&transfer.objects), self.describe_argument(&transfer.address))
+            }
+            Command::SplitCoins(split) => {
+                format!("SplitCoins {} into [{}]", self.describe_argument(&split.coin), list(&split.amounts))
+            }
+            Command::MergeCoins(merge) => {
+                format!("MergeCoins [{}] into {}", list(&merge.coins_to_merge), self.describe_argument(&merge.coin))
+            }
+            Command::Publish(publish) => format!("Publish a package of {} module(s)", publish.modules.len()),
+            Command::MakeMoveVector(vector) => format!(
+                "MakeMoveVec<{}> [{}]",
+                vector.type_.as_ref().map(format_type_tag).unwrap_or_else(|| "_".to_string()),
+                list(&vector.elements)
+            ),
+            Command::Upgrade(upgrade) => format!(
+                "Upgrade package {} with {} module(s) (ticket {})",
+                short_address(&upgrade.package),
+                upgrade.modules.len(),
+                self.describe_argument(&upgrade.ticket)
+            ),
+            other => format!("{:?}", other),
+        }
+    }
+
+    /// Pure inputs of 8 and 32 bytes are shown as the u64 amount or address they almost always are
+    fn describe_argument(&self, argument: &Argument) -> String {
+        match argument {
+            Argument::Gas => "gas coin".to_string(),
+            Argument::Input(index) => match self.programmable().inputs.get(*index as usize) {
+                Some(Input::Pure { value }) if value.len() == 8 => {
+                    u64::from_le_bytes(value.as_slice().try_into().unwrap_or_default()).to_string()
+                }
+                Some(Input::Pure { value }) if value.len() == 32 => format!("0x{}", hex::encode(value)),
+                Some(Input::Pure { value }) => format!("pure 0x{}", hex::encode(value)),
+                Some(Input::ImmutableOrOwned(object)) => format!("object {}", object.object_id()),
+                Some(Input::Shared { object_id, mutable, .. }) => {
+                    format!("shared object {}{}", object_id, if *mutable { " (mutable)" } else { "" })
+                }
+                Some(Input::Receiving(object)) => format!("receiving object {}", object.object_id()),
+                Some(other) => format!("{:?}", other),
+                None => format!("input {} (missing)", index),
+            },
+            Argument::Result(command) => format!("result of #{}", command + 1),
+            Argument::NestedResult(command, index) => format!("result {} of #{}", index, command + 1),
+        }
+    }
+}
+
+fn transaction_bytes(input: &[u8]) -> Vec<u8> {
+    if let Ok(text) = std::str::from_utf8(input) {
+        let text = text.trim();
+        let hex_text = text.strip_prefix("0x").unwrap_or(text);
+        if !hex_text.is_empty() && hex_text.chars().all(|c| c.is_ascii_hexdigit()) {
+            if let Ok(bytes) = hex::decode(hex_text) {
+                return bytes;
+            }
+        }
+        if let Ok(bytes) = BASE64.decode(text) {
+            return bytes;
+        }
+    }
+    input.to_vec()
+}
+
+// ========== HELPERS ==========
+
+/// Move type tag rendered the way the Sui CLI prints it, e.g. 0x2::coin::Coin<0x2::sui::SUI>
+fn format_type_tag(type_tag: &TypeTag) -> String {
+    match type_tag {
+        TypeTag::Vector(element) => format!("vector<{}>", format_type_tag(element)),
+        TypeTag::Struct(tag) => {
+            let parameters = if tag.type_params.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", tag.type_params.iter().map(format_type_tag).collect::<Vec<_>>().join(", "))
+            };
+            format!("{}::{}::{}{}", short_address(&tag.address), tag.module, tag.name, parameters)
+        }
+        primitive => primitive.to_string(),
+    }
+}
+
+/// Package addresses with leading zeros trimmed (0x2 for the Sui framework)
+fn short_address(address: &Address) -> String {
+    let hex = hex::encode(address.as_bytes());
+    let trimmed = hex.trim_start_matches('0');
+    format!("0x{}", if trimmed.is_empty() { "0" } else { trimmed })
+}
+
+fn format_mist(mist: u64) -> String {
+    let sui = format!("{}.{:09}", mist / MIST_PER_SUI, mist % MIST_PER_SUI);
+    format!("{} SUI", sui.trim_end_matches('0').trim_end_matches('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef";
+    const ADDRESS: &str = "0x2bab814efca01c4474a6c1a689f8c2194c1681a2dceb14bc356f6afacec300f5";
+    const SUI_PRIVATE_KEY: &str = "suiprivkey1qqfrg4ncjz4ummcjx3t83y9tehh3ydzk0zg2hn00zg69v7ys40x77cw2aqh";
+
+    // Split 1 SUI off the gas coin and send it to 0x0001..1f, then call 0x2::pay::split<0x2::sui::SUI>
+    const TRANSACTION: &str = "AAADAAgAypo7AAAAAAAgAAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8BADMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzMzBwAAAAAAAAAgREREREREREREREREREREREREREREREREREREREREREQDAgABAQAAAQEDAAAAAAEBAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAgNwYXkFc3BsaXQBBwAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACA3N1aQNTVUkAAgECAAEAACurgU78oBxEdKbBpon4whlMFoGi3OsUvDVvavrOwwD1ARERERERERERERERERERERERERERERERERERERERERERBQAAAAAAAAAgIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIiIrq4FO/KAcRHSmwaaJ+MIZTBaBotzrFLw1b2r6zsMA9egDAAAAAAAAQEtMAAAAAAAA";
+    const TRANSACTION_DIGEST: &str = "AzytwGBa8Xe6GYQvz4L9Cw3gTAho3yLkrxYzXAgMvfPW";
+    const TRANSACTION_SIGNATURE: &str = "AN72AINvLuK4oiJxz5kZTth9uv9C8hhQEgB0qjpsNfoxwwMlLvFLtMzeoDrUi0i3p4agpMvs9t2Zw1NnSkOeUQpczqrZwgLCNs2Ml3KQqETj8vChWpsxiSINvg+RIesM0g==";
+    const MESSAGE_SIGNATURE: &str = "AFSkorIV3oFjQXtk8BVfvCJfRDr3sRxGwvkSCI+xgx60jXxvCzy3eQLysT1hEVWx9V+wc/suY5ZSYWH85jMNWwZczqrZwgLCNs2Ml3KQqETj8vChWpsxiSINvg+RIesM0g==";
+
+    fn private_key() -> Ed25519PrivateKey {
+        Ed25519PrivateKey::new(hex::decode(PRIVATE_KEY).unwrap().try_into().unwrap())
+    }
+
+    #[test]
+    fn test_private_key_bech32() {
+        let secret: [u8; 32] = hex::decode(PRIVATE_KEY).unwrap().try_into().unwrap();
+        assert_eq!(encode_private_key(&secret).unwrap(), SUI_PRIVATE_KEY);
+        assert_eq!(decode_private_key(SUI_PRIVATE_KEY).unwrap(), secret);
+        assert!(looks_like_private_key(SUI_PRIVATE_KEY));
+
+        // Wrong prefix and a broken checksum
+        let hrp = bech32::Hrp::parse("suipubkey").unwrap();
+        let other = bech32::encode::<bech32::Bech32>(hrp, &[&[0u8][..], &secret].concat()).unwrap();
+        assert!(decode_private_key(&other).is_err());
+        let corrupted = format!("{}q", &SUI_PRIVATE_KEY[..SUI_PRIVATE_KEY.len() - 1]);
+        assert!(decode_private_key(&corrupted).is_err());
+
+        assert_eq!(private_key_address(&private_key()).to_string(), ADDRESS);
+    }
+
+    #[test]
+    fn test_transaction_decoding() {
+        let transaction = SuiTransaction::parse(TRANSACTION.as_bytes()).unwrap();
+        assert_eq!(transaction.sender().to_string(), ADDRESS);
+        assert_eq!(transaction.digest(), TRANSACTION_DIGEST);
+        assert_eq!(transaction.programmable().inputs.len(), 3);
+        assert_eq!(transaction.transaction.gas_payment.price, 1000);
+
+        let details = transaction.details();
+        assert!(details.contains(&("Gas Budget".to_string(), "0.005 SUI".to_string())));
+        assert!(details.contains(&("Command #1".to_string(), "SplitCoins gas coin into [1000000000]".to_string())));
+        let recipient = format!("0x{}", hex::encode((0u8..32).collect::<Vec<_>>()));
+        assert!(details.contains(&("Command #2".to_string(), format!("TransferObjects [result 0 of #1] to {}", recipient))));
+        let coin = format!("object 0x{}", "33".repeat(32));
+        assert!(details.contains(&(
+            "Command #3".to_string(),
+            format!("MoveCall 0x2::pay::split<0x2::sui::SUI>({}, 1000000000)", coin)
+        )));
+        assert!(!details.iter().any(|(label, _)| label == "Gas Sponsor"));
+
+        // The same bytes as hex, and raw BCS, decode identically
+        let bytes = BASE64.decode(TRANSACTION).unwrap();
+        assert_eq!(SuiTransaction::parse(hex::encode(&bytes).as_bytes()).unwrap().digest(), TRANSACTION_DIGEST);
+        assert_eq!(SuiTransaction::parse(&bytes).unwrap().digest(), TRANSACTION_DIGEST);
+
+        // Truncated and padded transactions are rejected
+        assert!(SuiTransaction::parse(&bytes[..bytes.len() - 1]).is_err());
+        assert!(SuiTransaction::parse(&[&bytes[..], &[0]].concat()).is_err());
+    }
+
+    #[test]
+    fn test_transaction_signing() {
+        let transaction = SuiTransaction::parse(TRANSACTION.as_bytes()).unwrap();
+        let signed = transaction.sign(&private_key()).unwrap();
+        assert_eq!(signed.encoded, TRANSACTION_SIGNATURE);
+        assert_eq!(signed.hash.as_deref(), Some(TRANSACTION_DIGEST));
+    }
+
+    #[test]
+    fn test_personal_message() {
+        assert_eq!(sign_personal_message(&private_key(), b"hello").unwrap(), MESSAGE_SIGNATURE);
+        assert!(verify_personal_message(ADDRESS, b"hello", MESSAGE_SIGNATURE).is_ok());
+        assert!(verify_personal_message(ADDRESS, b"hello!", MESSAGE_SIGNATURE).is_err());
+
+        let other = format!("0x{}", "ab".repeat(32));
+        assert!(verify_personal_message(&other, b"hello", MESSAGE_SIGNATURE).is_err());
+        // A transaction signature does not verify as a personal message
+        assert!(verify_personal_message(ADDRESS, b"hello", TRANSACTION_SIGNATURE).is_err());
+    }
+}
@@ -1,8 +1,10 @@
-use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain};
+use anyhow::{Result, Context, bail};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain, SigningCapabilities, SignedTransaction};
+use crate::blockchain::tron_tx::{recover_tip191_signer, sign_tip191_message, signing_key_address, TronTransaction};
 use crate::crypto::bip32::{derive_secp256k1_key_from_mnemonic, private_key_to_public_key_secp256k1};
 use anychain_tron::{TronAddress, TronPublicKey, TronFormat};
 use anychain_core::PublicKey;
+use k256::ecdsa::SigningKey;
 use std::str::FromStr;
 
 pub struct TronHandler;
@@ -30,6 +32,14 @@ impl TronHandler {
 
         Ok(address_string)
     }
+
+    fn signing_key(&self, private_key: &[u8], address: &str) -> Result<SigningKey> {
+        let signing_key = SigningKey::from_slice(private_key).context("Invalid secp256k1 private key")?;
+        if signing_key_address(&signing_key) != address {
+            bail!("Private key does not belong to {}", address);
+        }
+        Ok(signing_key)
+    }
 }
 
 impl BlockchainHandler for TronHandler {
@@ -101,9 +111,53 @@ impl BlockchainHandler for TronHandler {
         }
     }
 
+    fn derive_from_public_key(&self, public_key: &[u8], derivation_path: &str) -> Result<WalletKeys> {
+        WalletKeys::watch_only(public_key, derivation_path, |key| self.public_key_to_address(key))
+    }
+
     fn get_blockchain_name(&self) -> &'static str {
         "TRON"
     }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        SigningCapabilities::ALL
+    }
+
+    fn sign_message(&self, private_key: &[u8], address: &str, message: &[u8], format: Option<&str>) -> Result<String> {
+        check_message_format(format)?;
+        let signing_key = self.signing_key(private_key, address)?;
+        sign_tip191_message(&signing_key, message)
+    }
+
+    fn verify_message(&self, address: &str, message: &[u8], signature: &str, format: Option<&str>) -> Result<String> {
+        check_message_format(format)?;
+        let signer = recover_tip191_signer(message, signature)?;
+        if signer != address.trim() {
+            bail!("The signature was made by {}", signer);
+        }
+        Ok("TIP-191 (signMessageV2)".to_string())
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        TronTransaction::parse(transaction)?.details()
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        let signing_key = self.signing_key(private_key, address)?;
+        let transaction = TronTransaction::parse(transaction)?;
+        if transaction.contract.owner != address {
+            bail!("Transaction is owned by {}, not this wallet ({})", transaction.contract.owner, address);
+        }
+        transaction.sign(&signing_key)
+    }
+}
+
+/// TIP-191 is the only TRON message format
+fn check_message_format(format: Option<&str>) -> Result<()> {
+    match format.map(str::to_lowercase).as_deref() {
+        None | Some("tip191") | Some("tip-191") => Ok(()),
+        Some(other) => bail!("Unknown TRON message format '{}'. TRON messages use TIP-191", other),
+    }
 }
 
 #[cfg(test)]
@@ -149,4 +203,38 @@ mod tests {
         assert_eq!(wallet_keys.private_key.len(), 64); // 32 bytes as hex
         assert_eq!(wallet_keys.public_key.len(), 66); // 33 bytes compressed as hex
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_blockchain_handler_signing() {
+        let handler = TronHandler::new();
+        assert_eq!(handler.signing_capabilities(), SigningCapabilities::ALL);
+
+        let private_key = format!("{:064x}", 1);
+        let keys = handler.derive_from_private_key(&private_key).unwrap();
+        assert_eq!(keys.address, "TMVQGm1qAQYVdetCeGRRkTWYYrLXuHK2HC");
+        let private_key = hex::decode(&keys.private_key).unwrap();
+
+        let signature = handler.sign_message(&private_key, &keys.address, b"Sign in to example.com", None).unwrap();
+        assert_eq!(handler.verify_message(&keys.address, b"Sign in to example.com", &signature, None).unwrap(), "TIP-191 (signMessageV2)");
+        assert!(handler.verify_message("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t", b"Sign in to example.com", &signature, None).is_err());
+        assert!(handler.sign_message(&private_key, &keys.address, b"hi", Some("eip191")).is_err());
+        assert!(handler.sign_message(&private_key, "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t", b"hi", None).is_err());
+
+        // TransferContract of 1 TRX from the wallet to the USDT contract address
+        let raw_data = concat!(
+            "0a02db4a2208a1dd6b8ae5d0b17440e0a499ffbc315a67080112630a2d747970652e676f6f676c65617069732e636f6d",
+            "2f70726f746f636f6c2e5472616e73666572436f6e747261637412320a15417e5f4552091a69125d5dfcb7b8c2659029",
+            "395bdf121541a614f803b6fd780986a42c78ec9c7f77e6ded13c18c0843d7080d095ffbc31"
+        );
+        let details = handler.describe_transaction(raw_data.as_bytes()).unwrap();
+        assert!(details.contains(&("Amount".to_string(), "1 TRX".to_string())));
+
+        let signed = handler.sign_transaction(&private_key, &keys.address, raw_data.as_bytes()).unwrap();
+        assert!(signed.hash.is_some());
+        assert!(signed.encoded.starts_with("0a"));
+
+        let other_key = hex::decode(format!("{:064x}", 2)).unwrap();
+        let other = handler.derive_from_private_key(&hex::encode(&other_key)).unwrap();
+        assert!(handler.sign_transaction(&other_key, &other.address, raw_data.as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,588 @@
+use anyhow::{Result, Context, bail};
+use alloy_primitives::{keccak256, B256, U256};
+use anychain_tron::protocol::Tron::{transaction, Transaction};
+use anychain_tron::protocol::Tron::transaction::contract::ContractType;
+use anychain_tron::protocol::asset_issue_contract::TransferAssetContract;
+use anychain_tron::protocol::balance_contract::{
+    DelegateResourceContract, FreezeBalanceContract, FreezeBalanceV2Contract, TransferContract,
+    UnDelegateResourceContract, UnfreezeBalanceContract, UnfreezeBalanceV2Contract,
+    WithdrawBalanceContract, WithdrawExpireUnfreezeContract,
+};
+use anychain_tron::protocol::common::ResourceCode;
+use anychain_tron::protocol::smart_contract::TriggerSmartContract;
+use anychain_tron::protocol::witness_contract::VoteWitnessContract;
+use k256::ecdsa::SigningKey;
+use protobuf::{EnumOrUnknown, Message, MessageField, MessageFull};
+use protobuf::well_known_types::any::Any;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use crate::blockchain::SignedTransaction;
+use crate::blockchain::evm::{format_units, recover_signer, sign_hash, verifying_key_to_address};
+
+// Mainnet addresses are 0x41 followed by the 20-byte account hash
+const ADDRESS_PREFIX: u8 = 0x41;
+const TRX_DECIMALS: u32 = 6;
+
+// TRC-20 function selectors
+const SELECTOR_TRANSFER: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+const SELECTOR_APPROVE: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+const SELECTOR_TRANSFER_FROM: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+
+/// A transaction's single contract, decoded for display
+#[derive(Debug, Clone)]
+pub struct TronContract {
+    pub contract_type: ContractType,
+    pub name: &'static str,
+    pub owner: String,
+    pub permission_id: i32,
+    pub fields: Vec<(String, String)>,
+}
+
+/// An unsigned (or partially signed) transaction: the `raw_data` protobuf plus any signatures so far
+#[derive(Debug, Clone)]
+pub struct TronTransaction {
+    raw_data: Vec<u8>,
+    raw: transaction::Raw,
+    signatures: Vec<Vec<u8>>,
+    /// TronWeb's JSON form, kept so the signed result comes back in the same shape
+    json: Option<Map<String, Value>>,
+    pub contract: TronContract,
+}
+
+impl TronTransaction {
+    /// Accepts `raw_data` hex, a full `Transaction` protobuf in hex, or TronWeb JSON with `raw_data_hex`
+    pub fn parse(input: &[u8]) -> Result<Self> {
+        let text = std::str::from_utf8(input).context("TRON transactions are expected as hex or JSON text")?.trim();
+
+        let (raw_data, signatures, json) = if text.starts_with('{') {
+            let object: Map<String, Value> = serde_json::from_str(text).context("Invalid TRON transaction JSON")?;
+            let raw_data_hex = object.get("raw_data_hex").and_then(Value::as_str)
+                .context("TRON transaction JSON has no raw_data_hex")?;
+            let raw_data = decode_hex(raw_data_hex).context("Invalid raw_data_hex")?;
+
+            let mut signatures = Vec::new();
+            for signature in object.get("signature").and_then(Value::as_array).into_iter().flatten() {
+                let signature = signature.as_str().context("Signatures must be hex strings")?;
+                signatures.push(decode_hex(signature).context("Invalid signature hex")?);
+            }
+            if let Some(tx_id) = object.get("txID").and_then(Value::as_str) {
+                if !tx_id.eq_ignore_ascii_case(&hex::encode(Sha256::digest(&raw_data))) {
+                    bail!("txID {} does not match raw_data_hex; the JSON has been altered", tx_id);
+                }
+            }
+            (raw_data, signatures, Some(object))
+        } else {
+            let bytes = decode_hex(text).context("TRON transaction is neither hex nor JSON")?;
+            // raw_data always carries a contract; a Transaction holds raw_data as a nested message
+            match transaction::Raw::parse_from_bytes(&bytes) {
+                Ok(raw) if !raw.contract.is_empty() => (bytes, Vec::new(), None),
+                _ => {
+                    let transaction = Transaction::parse_from_bytes(&bytes).context("Invalid TRON transaction protobuf")?;
+                    let raw = transaction.raw_data.as_ref().context("No contract found in TRON transaction")?;
+                    (raw.write_to_bytes()?, transaction.signature, None)
+                }
+            }
+        };
+
+        let raw = transaction::Raw::parse_from_bytes(&raw_data).context("Invalid TRON raw_data protobuf")?;
+        // java-tron hashes the re-serialized raw_data, so other encodings would sign the wrong ID
+        if raw.write_to_bytes()? != raw_data {
+            bail!("TRON raw_data is not canonically encoded; its transaction ID would not match the network's");
+        }
+        let contract = decode_contract(&raw)?;
+        Ok(Self { raw_data, raw, signatures, json, contract })
+    }
+
+    /// Transaction ID: SHA-256 of `raw_data`, which is also the digest that gets signed
+    pub fn tx_id(&self) -> [u8; 32] {
+        Sha256::digest(&self.raw_data).into()
+    }
+
+    pub fn details(&self) -> Result<Vec<(String, String)>> {
+        let mut details = vec![
+            ("Type".to_string(), self.contract.name.to_string()),
+            ("Owner".to_string(), self.contract.owner.clone()),
+        ];
+        details.extend(self.contract.fields.iter().cloned());
+
+        if self.raw.fee_limit > 0 {
+            details.push(("Fee Limit".to_string(), format_trx(self.raw.fee_limit)));
+        }
+        if !self.raw.data.is_empty() {
+            details.push(("Memo".to_string(), String::from_utf8_lossy(&self.raw.data).into_owned()));
+        }
+        if self.contract.permission_id > 0 {
+            details.push(("Permission ID".to_string(), self.contract.permission_id.to_string()));
+        }
+        details.push(("Expiration".to_string(), format_timestamp(self.raw.expiration)));
+        if !self.signatures.is_empty() {
+            details.push(("Existing Signatures".to_string(), self.signatures.len().to_string()));
+        }
+        details.push(("Transaction ID".to_string(), hex::encode(self.tx_id())));
+
+        Ok(details)
+    }
+
+    /// Appends a 65-byte `r || s || v` signature and returns the transaction in the form it came in:
+    /// TronWeb JSON, or a `Transaction` protobuf as hex (for `broadcasthex`)
+    pub fn sign(mut self, signing_key: &SigningKey) -> Result<SignedTransaction> {
+        let tx_id = B256::from(self.tx_id());
+        let signer = verifying_key_to_address(signing_key.verifying_key());
+        for existing in &self.signatures {
+            if recover_signer(&tx_id, existing).is_ok_and(|address| address == signer) {
+                bail!("Transaction is already signed by this key");
+            }
+        }
+
+        let (signature, recovery_id) = sign_hash(signing_key, &tx_id)?;
+        let mut signature = signature.to_vec();
+        signature.push(recovery_id + 27);
+        self.signatures.push(signature);
+
+        let encoded = match self.json.take() {
+            Some(mut object) => {
+                let signatures = self.signatures.iter().map(|signature| Value::String(hex::encode(signature))).collect();
+                object.insert("signature".to_string(), Value::Array(signatures));
+                object.insert("txID".to_string(), Value::String(hex::encode(tx_id)));
+                serde_json::to_string(&object)?
+            }
+            None => {
+                let transaction = Transaction {
+                    raw_data: MessageField::some(self.raw),
+                    signature: self.signatures,
+                    ..Default::default()
+                };
+                hex::encode(transaction.write_to_bytes()?)
+            }
+        };
+
+        Ok(SignedTransaction {
+            encoded,
+            hash: Some(hex::encode(tx_id)),
+        })
+    }
+}
+
+/// The contract parameter as `M`, which its type URL must name
+fn unpack<M: MessageFull>(parameter: &Any) -> Result<M> {
+    let name = M::descriptor().name().to_string();
+    parameter.unpack::<M>()
+        .with_context(|| format!("Invalid {} parameter", name))?
+        .with_context(|| format!("Contract parameter is {}, not a {}", parameter.type_url, name))
+}
+
+fn decode_contract(raw: &transaction::Raw) -> Result<TronContract> {
+    let contract = match raw.contract.as_slice() {
+        [contract] => contract,
+        [] => bail!("TRON transaction has no contract"),
+        _ => bail!("TRON transactions carry exactly one contract, found {}", raw.contract.len()),
+    };
+    let contract_type = contract.type_.enum_value()
+        .map_err(|number| anyhow::anyhow!("TRON contract type {} is not supported for signing", number))?;
+    let parameter = contract.parameter.as_ref().context("TRON contract has no parameter")?;
+
+    let mut fields = Vec::new();
+    let (name, owner) = match contract_type {
+        ContractType::TransferContract => {
+            let value: TransferContract = unpack(parameter)?;
+            fields.push(("Recipient".to_string(), format_address(&value.to_address)));
+            fields.push(("Amount".to_string(), format_trx(value.amount)));
+            ("TransferContract (TRX transfer)", value.owner_address)
+        }
+        ContractType::TransferAssetContract => {
+            let value: TransferAssetContract = unpack(parameter)?;
+            let asset = String::from_utf8_lossy(&value.asset_name);
+            fields.push(("Recipient".to_string(), format_address(&value.to_address)));
+            fields.push(("Amount".to_string(), format!("{} of TRC-10 token {}", value.amount, asset)));
+            ("TransferAssetContract (TRC-10 transfer)", value.owner_address)
+        }
+        ContractType::VoteWitnessContract => {
+            let value: VoteWitnessContract = unpack(parameter)?;
+            for vote in &value.votes {
+                fields.push((format!("Vote for {}", format_address(&vote.vote_address)), vote.vote_count.to_string()));
+            }
+            ("VoteWitnessContract (vote)", value.owner_address)
+        }
+        ContractType::FreezeBalanceContract => {
+            let value: FreezeBalanceContract = unpack(parameter)?;
+            fields.push(("Amount".to_string(), format_trx(value.frozen_balance)));
+            fields.push(("Duration".to_string(), format!("{} days", value.frozen_duration)));
+            fields.push(("Resource".to_string(), format_resource(value.resource)));
+            if !value.receiver_address.is_empty() {
+                fields.push(("Recipient".to_string(), format_address(&value.receiver_address)));
+            }
+            ("FreezeBalanceContract (freeze)", value.owner_address)
+        }
+        ContractType::UnfreezeBalanceContract => {
+            let value: UnfreezeBalanceContract = unpack(parameter)?;
+            fields.push(("Resource".to_string(), format_resource(value.resource)));
+            if !value.receiver_address.is_empty() {
+                fields.push(("Recipient".to_string(), format_address(&value.receiver_address)));
+            }
+            ("UnfreezeBalanceContract (unfreeze)", value.owner_address)
+        }
+        ContractType::WithdrawBalanceContract => {
+            let value: WithdrawBalanceContract = unpack(parameter)?;
+            ("WithdrawBalanceContract (claim rewards)", value.owner_address)
+        }
+        ContractType::TriggerSmartContract => {
+            let value: TriggerSmartContract = unpack(parameter)?;
+            fields.push(("Smart Contract".to_string(), format_address(&value.contract_address)));
+            if value.call_value > 0 {
+                fields.push(("Call Value".to_string(), format_trx(value.call_value)));
+            }
+            if value.call_token_value > 0 {
+                fields.push(("Call Token Value".to_string(), format!("{} of TRC-10 token {}", value.call_token_value, value.token_id)));
+            }
+            describe_call(&value.data, &mut fields);
+            ("TriggerSmartContract (contract call)", value.owner_address)
+        }
+        ContractType::FreezeBalanceV2Contract => {
+            let value: FreezeBalanceV2Contract = unpack(parameter)?;
+            fields.push(("Amount".to_string(), format_trx(value.frozen_balance)));
+            fields.push(("Resource".to_string(), format_resource(value.resource)));
+            ("FreezeBalanceV2Contract (stake)", value.owner_address)
+        }
+        ContractType::UnfreezeBalanceV2Contract => {
+            let value: UnfreezeBalanceV2Contract = unpack(parameter)?;
+            fields.push(("Amount".to_string(), format_trx(value.unfreeze_balance)));
+            fields.push(("Resource".to_string(), format_resource(value.resource)));
+            ("UnfreezeBalanceV2Contract (unstake)", value.owner_address)
+        }
+        ContractType::WithdrawExpireUnfreezeContract => {
+            let value: WithdrawExpireUnfreezeContract = unpack(parameter)?;
+            ("WithdrawExpireUnfreezeContract (withdraw unstaked TRX)", value.owner_address)
+        }
+        ContractType::DelegateResourceContract => {
+            let value: DelegateResourceContract = unpack(parameter)?;
+            fields.push(("Resource".to_string(), format_resource(value.resource)));
+            fields.push(("Amount".to_string(), format_trx(value.balance)));
+            fields.push(("Recipient".to_string(), format_address(&value.receiver_address)));
+            if value.lock {
+                fields.push(("Lock Period".to_string(), format!("{} blocks", value.lock_period)));
+            }
+            ("DelegateResourceContract (delegate)", value.owner_address)
+        }
+        ContractType::UnDelegateResourceContract => {
+            let value: UnDelegateResourceContract = unpack(parameter)?;
+            fields.push(("Resource".to_string(), format_resource(value.resource)));
+            fields.push(("Amount".to_string(), format_trx(value.balance)));
+            fields.push(("Recipient".to_string(), format_address(&value.receiver_address)));
+            ("UnDelegateResourceContract (undelegate)", value.owner_address)
+        }
+        other => bail!("TRON contract type {:?} is not supported for signing", other),
+    };
+
+    if owner.is_empty() {
+        bail!("TRON contract has no owner address");
+    }
+    Ok(TronContract {
+        contract_type,
+        name,
+        owner: format_address(&owner),
+        permission_id: contract.Permission_id,
+        fields,
+    })
+}
+
+/// Decodes the common TRC-20 calls; anything else is shown as its selector
+fn describe_call(data: &[u8], fields: &mut Vec<(String, String)>) {
+    let Some((selector, arguments)) = data.split_first_chunk::<4>() else {
+        if !data.is_empty() {
+            fields.push(("Data".to_string(), format!("0x{}", hex::encode(data))));
+        }
+        return;
+    };
+
+    let words: Vec<&[u8]> = arguments.chunks(32).collect();
+    let word_address = |index: usize| match words.get(index) {
+        Some(word) if word.len() == 32 => format_address(&[&[ADDRESS_PREFIX][..], &word[12..]].concat()),
+        _ => "(missing)".to_string(),
+    };
+    let word_amount = |index: usize| match words.get(index) {
+        Some(word) if word.len() == 32 => format!("{} (token base units)", U256::from_be_slice(word)),
+        _ => "(missing)".to_string(),
+    };
+
+    match *selector {
+        SELECTOR_TRANSFER => {
+            fields.push(("Method".to_string(), "transfer(address,uint256) (TRC-20)".to_string()));
+            fields.push(("Recipient".to_string(), word_address(0)));
+            fields.push(("Amount".to_string(), word_amount(1)));
+        }
+        SELECTOR_APPROVE => {
+            fields.push(("Method".to_string(), "approve(address,uint256) (TRC-20)".to_string()));
+            fields.push(("Spender".to_string(), word_address(0)));
+            fields.push(("Allowance".to_string(), word_amount(1)));
+        }
+        SELECTOR_TRANSFER_FROM => {
+            fields.push(("Method".to_string(), "transferFrom(address,address,uint256) (TRC-20)".to_string()));
+            fields.push(("From".to_string(), word_address(0)));
+            fields.push(("Recipient".to_string(), word_address(1)));
+            fields.push(("Amount".to_string(), word_amount(2)));
+        }
+        _ => {
+            fields.push(("Method".to_string(), format!("0x{} (not decoded)", hex::encode(selector))));
+            fields.push(("Data".to_string(), format!("{} bytes", data.len())));
+        }
+    }
+}
+
+// ========== TIP-191 MESSAGES ==========
+
+/// TIP-191 digest (TronWeb `signMessageV2`): keccak256 of the prefixed message
+pub fn tip191_hash(message: &[u8]) -> B256 {
+    let mut prefixed = format!("\x19TRON Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+    keccak256(prefixed)
+}
+
+/// 65-byte `r || s || v` signature (v = 27/28) as 0x-prefixed hex, as TronWeb returns it
+pub fn sign_tip191_message(signing_key: &SigningKey, message: &[u8]) -> Result<String> {
+    let (signature, recovery_id) = sign_hash(signing_key, &tip191_hash(message))?;
+    Ok(format!("0x{}{:02x}", hex::encode(signature), recovery_id + 27))
+}
+
+/// Recovers the TRON address that produced a TIP-191 signature
+pub fn recover_tip191_signer(message: &[u8], signature: &str) -> Result<String> {
+    let signature = decode_hex(signature).context("Signature is not valid hex")?;
+    let signer = recover_signer(&tip191_hash(message), &signature)?;
+    Ok(tron_address(signer.as_slice()))
+}
+
+// ========== ADDRESSES ==========
+
+/// Base58check address (T...) of a 20-byte account hash
+pub fn tron_address(account: &[u8]) -> String {
+    let payload = [&[ADDRESS_PREFIX][..], account].concat();
+    let checksum = Sha256::digest(Sha256::digest(&payload));
+    bs58::encode([&payload[..], &checksum[..4]].concat()).into_string()
+}
+
+/// The signing key's TRON address
+pub fn signing_key_address(signing_key: &SigningKey) -> String {
+    tron_address(verifying_key_to_address(signing_key.verifying_key()).as_slice())
+}
+
+/// Addresses inside contracts are 21 bytes starting with 0x41
+fn format_address(bytes: &[u8]) -> String {
+    match bytes {
+        [ADDRESS_PREFIX, account @ ..] if account.len() == 20 => tron_address(account),
+        [] => "(none)".to_string(),
+        other => format!("0x{} (not a TRON address)", hex::encode(other)),
+    }
+}
+
+// ========== HELPERS ==========
+
+fn decode_hex(text: &str) -> Result<Vec<u8>> {
+    let text = text.trim();
+    Ok(hex::decode(text.strip_prefix("0x").unwrap_or(text))?)
+}
+
+fn format_trx(sun: i64) -> String {
+    match u64::try_from(sun) {
+        Ok(sun) => format!("{} TRX", format_units(U256::from(sun), TRX_DECIMALS)),
+        Err(_) => format!("{} sun (negative)", sun),
+    }
+}
+
+fn format_resource(resource: EnumOrUnknown<ResourceCode>) -> String {
+    match resource.enum_value() {
+        Ok(resource) => format!("{:?}", resource),
+        Err(other) => format!("unknown ({})", other),
+    }
+}
+
+fn format_timestamp(millis: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(millis)
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| millis.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anychain_tron::protocol::smart_contract::CreateSmartContract;
+    use anychain_tron::protocol::witness_contract::vote_witness_contract::Vote;
+
+    const OWNER: &str = "TMVQGm1qAQYVdetCeGRRkTWYYrLXuHK2HC";
+    const USDT: &str = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+
+    // Private key 1: the well-known 0x7E5F4552... account
+    fn signing_key() -> SigningKey {
+        let mut private_key = [0u8; 32];
+        private_key[31] = 1;
+        SigningKey::from_slice(&private_key).unwrap()
+    }
+
+    fn owner_bytes() -> Vec<u8> {
+        hex::decode("417e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap()
+    }
+
+    fn usdt_bytes() -> Vec<u8> {
+        hex::decode("41a614f803b6fd780986a42c78ec9c7f77e6ded13c").unwrap()
+    }
+
+    fn raw_data<M: MessageFull>(contract_type: ContractType, value: &M) -> transaction::Raw {
+        let mut contract = transaction::Contract::new();
+        contract.type_ = contract_type.into();
+        contract.parameter = MessageField::some(Any::pack(value).unwrap());
+
+        let mut raw = transaction::Raw::new();
+        raw.ref_block_bytes = vec![0xdb, 0x4a];
+        raw.ref_block_hash = vec![0xa1, 0xdd, 0x6b, 0x8a, 0xe5, 0xd0, 0xb1, 0x74];
+        raw.expiration = 1_700_000_060_000;
+        raw.contract.push(contract);
+        raw.timestamp = 1_700_000_000_000;
+        raw
+    }
+
+    fn transfer(amount: i64) -> Vec<u8> {
+        let value = TransferContract {
+            owner_address: owner_bytes(),
+            to_address: usdt_bytes(),
+            amount,
+            ..Default::default()
+        };
+        raw_data(ContractType::TransferContract, &value).write_to_bytes().unwrap()
+    }
+
+    fn detail<'a>(details: &'a [(String, String)], label: &str) -> Option<&'a str> {
+        details.iter().find(|(name, _)| name == label).map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn test_addresses() {
+        assert_eq!(tron_address(&owner_bytes()[1..]), OWNER);
+        assert_eq!(format_address(&usdt_bytes()), USDT);
+        assert_eq!(signing_key_address(&signing_key()), OWNER);
+    }
+
+    #[test]
+    fn test_trx_transfer_signing() {
+        let raw = transfer(1_500_000);
+        let tx = TronTransaction::parse(hex::encode(&raw).as_bytes()).unwrap();
+        let details = tx.details().unwrap();
+        assert_eq!(detail(&details, "Owner"), Some(OWNER));
+        assert_eq!(detail(&details, "Recipient"), Some(USDT));
+        assert_eq!(detail(&details, "Amount"), Some("1.5 TRX"));
+        assert_eq!(detail(&details, "Expiration"), Some("2023-11-14 22:14:20 UTC"));
+
+        let signed = tx.sign(&signing_key()).unwrap();
+        let tx_id: [u8; 32] = Sha256::digest(&raw).into();
+        assert_eq!(signed.hash, Some(hex::encode(tx_id)));
+
+        // The result is a Transaction protobuf that parses back with its signature
+        let resigned = TronTransaction::parse(signed.encoded.as_bytes()).unwrap();
+        assert_eq!(resigned.raw_data, raw);
+        assert_eq!(resigned.signatures.len(), 1);
+        assert_eq!(resigned.signatures[0].len(), 65);
+        let signer = recover_signer(&B256::from(tx_id), &resigned.signatures[0]).unwrap();
+        assert_eq!(tron_address(signer.as_slice()), OWNER);
+
+        assert!(resigned.sign(&signing_key()).is_err());
+    }
+
+    #[test]
+    fn test_trc20_transfer_decoding() {
+        let mut data = SELECTOR_TRANSFER.to_vec();
+        data.extend_from_slice(&[0u8; 12]);
+        data.extend_from_slice(&owner_bytes()[1..]);
+        data.extend_from_slice(U256::from(25_000_000u64).to_be_bytes::<32>().as_slice());
+
+        let value = TriggerSmartContract {
+            owner_address: owner_bytes(),
+            contract_address: usdt_bytes(),
+            data,
+            ..Default::default()
+        };
+        let mut raw = raw_data(ContractType::TriggerSmartContract, &value);
+        raw.fee_limit = 30_000_000;
+
+        let raw = raw.write_to_bytes().unwrap();
+        let details = TronTransaction::parse(hex::encode(&raw).as_bytes()).unwrap().details().unwrap();
+        assert_eq!(detail(&details, "Smart Contract"), Some(USDT));
+        assert_eq!(detail(&details, "Method"), Some("transfer(address,uint256) (TRC-20)"));
+        assert_eq!(detail(&details, "Recipient"), Some(OWNER));
+        assert_eq!(detail(&details, "Amount"), Some("25000000 (token base units)"));
+        assert_eq!(detail(&details, "Fee Limit"), Some("30 TRX"));
+    }
+
+    #[test]
+    fn test_freeze_and_vote_decoding() {
+        let value = FreezeBalanceV2Contract {
+            owner_address: owner_bytes(),
+            frozen_balance: 10_000_000,
+            resource: ResourceCode::ENERGY.into(),
+            ..Default::default()
+        };
+        let raw = raw_data(ContractType::FreezeBalanceV2Contract, &value).write_to_bytes().unwrap();
+        let details = TronTransaction::parse(hex::encode(&raw).as_bytes()).unwrap().details().unwrap();
+        assert_eq!(detail(&details, "Amount"), Some("10 TRX"));
+        assert_eq!(detail(&details, "Resource"), Some("ENERGY"));
+
+        let value = VoteWitnessContract {
+            owner_address: owner_bytes(),
+            votes: vec![Vote { vote_address: usdt_bytes(), vote_count: 42, ..Default::default() }],
+            ..Default::default()
+        };
+        let raw = raw_data(ContractType::VoteWitnessContract, &value).write_to_bytes().unwrap();
+        let details = TronTransaction::parse(hex::encode(&raw).as_bytes()).unwrap().details().unwrap();
+        assert_eq!(detail(&details, &format!("Vote for {}", USDT)), Some("42"));
+    }
+
+    #[test]
+    fn test_tronweb_json_signing() {
+        let raw = transfer(1_000_000);
+        let json = format!(
+            r#"{{"visible": false, "txID": "{}", "raw_data_hex": "{}", "raw_data": {{}}}}"#,
+            hex::encode(Sha256::digest(&raw)),
+            hex::encode(&raw)
+        );
+        let signed = TronTransaction::parse(json.as_bytes()).unwrap().sign(&signing_key()).unwrap();
+        let signed: Value = serde_json::from_str(&signed.encoded).unwrap();
+        assert_eq!(signed["signature"].as_array().unwrap().len(), 1);
+        assert_eq!(signed["visible"], Value::Bool(false));
+
+        // A txID that does not match raw_data_hex means the JSON was tampered with
+        let tampered = json.replace(&hex::encode(Sha256::digest(&raw)), &hex::encode([0u8; 32]));
+        assert!(TronTransaction::parse(tampered.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_unsupported_transactions() {
+        // CreateSmartContract is not decoded
+        let value = CreateSmartContract { owner_address: owner_bytes(), ..Default::default() };
+        let raw = raw_data(ContractType::CreateSmartContract, &value).write_to_bytes().unwrap();
+        assert!(TronTransaction::parse(hex::encode(&raw).as_bytes()).is_err());
+
+        // The parameter must be the message the contract type names
+        let value = TransferAssetContract { owner_address: owner_bytes(), ..Default::default() };
+        let raw = raw_data(ContractType::TransferContract, &value).write_to_bytes().unwrap();
+        assert!(TronTransaction::parse(hex::encode(&raw).as_bytes()).is_err());
+
+        let mut raw = transaction::Raw::new();
+        raw.expiration = 1_700_000_060_000;
+        assert!(TronTransaction::parse(hex::encode(raw.write_to_bytes().unwrap()).as_bytes()).is_err());
+
+        // An explicitly encoded default field changes the bytes but not the ID java-tron computes
+        let mut raw = transfer(1_000_000);
+        raw.extend_from_slice(&[0x18, 0x00]);
+        assert!(TronTransaction::parse(hex::encode(&raw).as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_tip191_messages() {
+        let signature = sign_tip191_message(&signing_key(), b"hello world").unwrap();
+        assert!(signature.starts_with("0x"));
+        assert_eq!(signature.len(), 2 + 130);
+        assert!(signature.ends_with("1b") || signature.ends_with("1c"));
+
+        assert_eq!(recover_tip191_signer(b"hello world", &signature).unwrap(), OWNER);
+        assert_ne!(recover_tip191_signer(b"hello world!", &signature).unwrap(), OWNER);
+
+        // Same layout as EIP-191 but a different prefix, so the digests differ
+        assert_ne!(tip191_hash(b"hello world"), crate::blockchain::evm::eip191_hash(b"hello world"));
+    }
+}
@@ -1,6 +1,7 @@
-use anyhow::{Result, Context};
-use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain};
-use crate::crypto::bip32::{derive_secp256k1_key_from_mnemonic, private_key_to_public_key_secp256k1};
+use anyhow::{Result, Context, bail};
+use crate::blockchain::{BlockchainHandler, WalletKeys, SupportedBlockchain, SigningCapabilities, SignedTransaction};
+use crate::blockchain::xrp_tx::{FamilySeed, XrpKey, XrpKeyType, XrpTransaction};
+use crate::crypto::bip32::derive_secp256k1_key_from_mnemonic;
 use xrpl::core::addresscodec::encode_classic_address;
 
 pub struct XrpHandler;
@@ -30,6 +31,30 @@ impl XrpHandler {
         encode_classic_address(&account_id)
             .map_err(|e| anyhow::anyhow!("Failed to encode XRP address: {:?}", e))
     }
+
+    fn wallet_keys_for(&self, key: &XrpKey, derivation_path: &str) -> Result<WalletKeys> {
+        let public_key_bytes = key.public_key()?;
+        let address = self.public_key_to_address(&public_key_bytes)?;
+
+        let mut wallet_keys = WalletKeys::new_simple(
+            key.storage_hex(),
+            hex::encode(&public_key_bytes),
+            address,
+            derivation_path.to_string(),
+        );
+        if key.key_type == XrpKeyType::Ed25519 {
+            wallet_keys.add_data("key_type".to_string(), key.key_type.as_str().to_string());
+        }
+        Ok(wallet_keys)
+    }
+
+    fn signing_key(&self, private_key: &[u8], address: &str) -> Result<XrpKey> {
+        let key = XrpKey::from_bytes(private_key)?;
+        if self.public_key_to_address(&key.public_key()?)? != address {
+            bail!("Private key does not belong to {}", address);
+        }
+        Ok(key)
+    }
 }
 
 impl BlockchainHandler for XrpHandler {
@@ -66,22 +91,21 @@ impl BlockchainHandler for XrpHandler {
     }
 
     fn derive_from_private_key(&self, private_key: &str) -> Result<WalletKeys> {
-        // Parse private key from hex
+        // Family seeds (s... / sEd...) are what most XRP wallets hand out; the seed is kept
+        // alongside the derived key because it cannot be recomputed from the key later
+        if FamilySeed::looks_like(private_key) {
+            let seed = FamilySeed::parse(private_key)?;
+            let mut wallet_keys = self.wallet_keys_for(&seed.derive_key()?, "N/A (from family seed)")?;
+            wallet_keys.secret_seed = Some(seed.encode());
+            return Ok(wallet_keys);
+        }
+
+        // Parse private key from hex (00- or ED-prefixed keys select the key type)
         let private_key_bytes = hex::decode(private_key)
             .context("Invalid hex private key")?;
+        let key = XrpKey::from_bytes(&private_key_bytes)?;
 
-        // Derive public key from private key using our crypto utils
-        let public_key_bytes = private_key_to_public_key_secp256k1(&private_key_bytes)?;
-
-        // Generate XRP address using xrpl library's address codec
-        let address = self.public_key_to_address(&public_key_bytes)?;
-
-        Ok(WalletKeys::new_simple(
-            private_key.to_string(),
-            hex::encode(&public_key_bytes),
-            address,
-            "N/A (from private key)".to_string(),
-        ))
+        self.wallet_keys_for(&key, "N/A (from private key)")
     }
 
     fn validate_address(&self, address: &str) -> bool {
@@ -91,9 +115,31 @@ impl BlockchainHandler for XrpHandler {
         is_valid_classic_address(address)
     }
 
+    fn derive_from_public_key(&self, public_key: &[u8], derivation_path: &str) -> Result<WalletKeys> {
+        WalletKeys::watch_only(public_key, derivation_path, |key| self.public_key_to_address(key))
+    }
+
     fn get_blockchain_name(&self) -> &'static str {
         "XRP"
     }
+
+    fn signing_capabilities(&self) -> SigningCapabilities {
+        // The XRP Ledger has no standard off-chain message format
+        SigningCapabilities { sign_transaction: true, ..SigningCapabilities::default() }
+    }
+
+    fn describe_transaction(&self, transaction: &[u8]) -> Result<Vec<(String, String)>> {
+        Ok(XrpTransaction::parse(transaction)?.details())
+    }
+
+    fn sign_transaction(&self, private_key: &[u8], address: &str, transaction: &[u8]) -> Result<SignedTransaction> {
+        let key = self.signing_key(private_key, address)?;
+        let transaction = XrpTransaction::parse(transaction)?;
+        if transaction.account() != Some(address) {
+            bail!("Transaction Account {} is not this wallet ({})", transaction.account().unwrap_or_default(), address);
+        }
+        transaction.sign(&key)
+    }
 }
 
 #[cfg(test)]
@@ -137,4 +183,55 @@ mod tests {
         assert!(wallet_keys.address.len() >= 25);
         assert!(wallet_keys.address.len() <= 34);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_xrp_family_seed_import() {
+        let handler = XrpHandler::new();
+
+        let ed25519 = handler.derive_from_private_key("sEdSKaCy2JT7JaM7v95H9SxkhP9wS2r").unwrap();
+        assert_eq!(ed25519.address, "rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD");
+        assert_eq!(ed25519.secret_seed.as_deref(), Some("sEdSKaCy2JT7JaM7v95H9SxkhP9wS2r"));
+        assert_eq!(ed25519.additional_data.get("key_type").map(String::as_str), Some("ed25519"));
+
+        let secp256k1 = handler.derive_from_private_key("sp5fghtJtpUorTwvof1NpDXAzNwf5").unwrap();
+        assert_eq!(secp256k1.address, "rU6K7V3Po4snVhBBaU29sesqs2qTQJWDw1");
+
+        // The genesis account's well-known seed
+        let genesis = handler.derive_from_private_key("snoPBrXtMeMyMHUVTgbuqAfg1SUTb").unwrap();
+        assert_eq!(genesis.address, "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh");
+
+        // The stored hex key re-imports to the same account, without a seed
+        let reimported = handler.derive_from_private_key(&ed25519.private_key).unwrap();
+        assert_eq!(reimported.address, ed25519.address);
+        assert!(reimported.secret_seed.is_none());
+
+        assert!(handler.derive_from_private_key("sEdSKaCy2JT7JaM7v95H9SxkhP9wS2s").is_err());
+    }
+
+    #[test]
+    fn test_blockchain_handler_signing() {
+        let handler = XrpHandler::new();
+        let capabilities = handler.signing_capabilities();
+        assert!(capabilities.sign_transaction);
+        assert!(!capabilities.sign_message);
+
+        let keys = handler.derive_from_private_key("sEdSKaCy2JT7JaM7v95H9SxkhP9wS2r").unwrap();
+        let private_key = hex::decode(&keys.private_key).unwrap();
+        let tx = format!(
+            r#"{{"TransactionType": "Payment", "Account": "{}", "Destination": "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh", "Amount": "1000000", "Fee": "12", "Sequence": 1}}"#,
+            keys.address
+        );
+
+        let details = handler.describe_transaction(tx.as_bytes()).unwrap();
+        assert!(details.contains(&("Amount".to_string(), "1 XRP".to_string())));
+
+        let signed = handler.sign_transaction(&private_key, &keys.address, tx.as_bytes()).unwrap();
+        assert!(signed.hash.is_some());
+        assert!(hex::decode(&signed.encoded).is_ok());
+
+        // Wrong wallet for the key, and a transaction for another account
+        assert!(handler.sign_transaction(&private_key, "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh", tx.as_bytes()).is_err());
+        let other = tx.replace(&keys.address, "rU6K7V3Po4snVhBBaU29sesqs2qTQJWDw1");
+        assert!(handler.sign_transaction(&private_key, &keys.address, other.as_bytes()).is_err());
+    }
+}
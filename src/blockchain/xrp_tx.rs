@@ -0,0 +1,526 @@
+use anyhow::{Result, Context, bail};
+use ed25519_dalek::Signer;
+use k256::ecdsa::signature::hazmat::PrehashSigner;
+use k256::elliptic_curve::sec1::ToEncodedPoint;
+use k256::SecretKey;
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256, Sha512};
+use xrpl::core::binarycodec::{encode, encode_for_signing};
+use crate::blockchain::SignedTransaction;
+use crate::crypto::bip32::private_key_to_public_key_secp256k1;
+
+// Family seed version prefixes: "s..." for secp256k1 seeds, "sEd..." for ed25519 seeds
+const SEED_PREFIX_SECP256K1: &[u8] = &[0x21];
+const SEED_PREFIX_ED25519: &[u8] = &[0x01, 0xE1, 0x4B];
+const SEED_ENTROPY_LENGTH: usize = 16;
+
+// ed25519 public (and, by convention, private) keys carry this prefix byte on the XRP Ledger
+const ED25519_KEY_PREFIX: u8 = 0xED;
+
+// Hash prefix of the transaction ID ("TXN\0"); the signing prefix "STX\0" is added by encode_for_signing
+const TRANSACTION_ID_PREFIX: &[u8] = b"TXN\0";
+
+const DROPS_PER_XRP: u64 = 1_000_000;
+
+/// Key algorithm behind an XRP Ledger account
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XrpKeyType {
+    Secp256k1,
+    Ed25519,
+}
+
+impl XrpKeyType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Secp256k1 => "secp256k1",
+            Self::Ed25519 => "ed25519",
+        }
+    }
+}
+
+// ========== FAMILY SEEDS ==========
+
+/// A family seed (`s...`): 16 bytes of entropy and the key type it derives
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FamilySeed {
+    pub entropy: [u8; SEED_ENTROPY_LENGTH],
+    pub key_type: XrpKeyType,
+}
+
+impl FamilySeed {
+    /// Family seeds start with 's'; hex private keys never contain that letter
+    pub fn looks_like(input: &str) -> bool {
+        input.trim().starts_with('s')
+    }
+
+    pub fn parse(seed: &str) -> Result<Self> {
+        let payload = decode_base58_check(seed.trim()).context("Invalid XRP family seed")?;
+
+        let (key_type, entropy) = if let Some(entropy) = payload.strip_prefix(SEED_PREFIX_ED25519) {
+            (XrpKeyType::Ed25519, entropy)
+        } else if let Some(entropy) = payload.strip_prefix(SEED_PREFIX_SECP256K1) {
+            (XrpKeyType::Secp256k1, entropy)
+        } else {
+            bail!("Invalid XRP family seed: unknown version prefix");
+        };
+
+        let entropy = entropy.try_into()
+            .map_err(|_| anyhow::anyhow!("Invalid XRP family seed: expected {} bytes of entropy, found {}", SEED_ENTROPY_LENGTH, entropy.len()))?;
+        Ok(Self { entropy, key_type })
+    }
+
+    pub fn encode(&self) -> String {
+        let prefix = match self.key_type {
+            XrpKeyType::Secp256k1 => SEED_PREFIX_SECP256K1,
+            XrpKeyType::Ed25519 => SEED_PREFIX_ED25519,
+        };
+        encode_base58_check(&[prefix, &self.entropy[..]].concat())
+    }
+
+    /// The account key rippled derives from this seed (account index 0)
+    pub fn derive_key(&self) -> Result<XrpKey> {
+        let private_key = match self.key_type {
+            XrpKeyType::Ed25519 => sha512_half(&self.entropy),
+            XrpKeyType::Secp256k1 => derive_secp256k1_account_key(&self.entropy)?,
+        };
+        Ok(XrpKey { key_type: self.key_type, private_key })
+    }
+}
+
+// Root key: first valid SHA-512Half(seed || seq). Account key: root + first valid
+// SHA-512Half(root_public || account_index || seq), modulo the curve order.
+fn derive_secp256k1_account_key(entropy: &[u8]) -> Result<[u8; 32]> {
+    let root = first_valid_secret(|sequence| [entropy, &sequence.to_be_bytes()].concat())?;
+    let root_public = root.public_key().to_encoded_point(true);
+
+    let account_index = 0u32.to_be_bytes();
+    let tweak = first_valid_secret(|sequence| {
+        [root_public.as_bytes(), &account_index, &sequence.to_be_bytes()].concat()
+    })?;
+
+    let account = *root.to_nonzero_scalar() + *tweak.to_nonzero_scalar();
+    let account = SecretKey::from_bytes(&account.to_bytes())
+        .map_err(|_| anyhow::anyhow!("Family seed derived an invalid secp256k1 key"))?;
+
+    let mut private_key = [0u8; 32];
+    private_key.copy_from_slice(&account.to_bytes());
+    Ok(private_key)
+}
+
+fn first_valid_secret(input: impl Fn(u32) -> Vec<u8>) -> Result<SecretKey> {
+    for sequence in 0..=u32::MAX {
+        if let Ok(secret) = SecretKey::from_slice(&sha512_half(&input(sequence))) {
+            return Ok(secret);
+        }
+    }
+    bail!("No valid secp256k1 key found for family seed")
+}
+
+// ========== KEYS ==========
+
+/// A signing key together with its algorithm. Stored as 32-byte hex for secp256k1 and as
+/// "ed" + 32-byte hex for ed25519, following the XRPL convention of prefixing ed25519 keys.
+#[derive(Clone)]
+pub struct XrpKey {
+    pub key_type: XrpKeyType,
+    private_key: [u8; 32],
+}
+
+impl XrpKey {
+    /// Accepts a raw 32-byte secp256k1 key, or a 33-byte key with the 0x00 (secp256k1) or 0xED (ed25519) prefix
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (key_type, private_key) = match bytes {
+            [ED25519_KEY_PREFIX, key @ ..] if key.len() == 32 => (XrpKeyType::Ed25519, key),
+            [0x00, key @ ..] if key.len() == 32 => (XrpKeyType::Secp256k1, key),
+            key if key.len() == 32 => (XrpKeyType::Secp256k1, key),
+            _ => bail!("XRP private key must be 32 bytes, or 33 bytes with a 00 or ED prefix (got {} bytes)", bytes.len()),
+        };
+
+        let mut key = [0u8; 32];
+        key.copy_from_slice(private_key);
+        Ok(Self { key_type, private_key: key })
+    }
+
+    /// Hex form kept in the database
+    pub fn storage_hex(&self) -> String {
+        match self.key_type {
+            XrpKeyType::Secp256k1 => hex::encode(self.private_key),
+            XrpKeyType::Ed25519 => format!("ed{}", hex::encode(self.private_key)),
+        }
+    }
+
+    /// The 33-byte public key used for addresses and SigningPubKey
+    pub fn public_key(&self) -> Result<Vec<u8>> {
+        match self.key_type {
+            XrpKeyType::Secp256k1 => private_key_to_public_key_secp256k1(&self.private_key),
+            XrpKeyType::Ed25519 => {
+                let signing_key = ed25519_dalek::SigningKey::from_bytes(&self.private_key);
+                Ok([&[ED25519_KEY_PREFIX][..], signing_key.verifying_key().as_bytes()].concat())
+            }
+        }
+    }
+
+    /// secp256k1 signs SHA-512Half of the message (DER, low S); ed25519 signs the message itself
+    pub fn sign(&self, message: &[u8]) -> Result<Vec<u8>> {
+        match self.key_type {
+            XrpKeyType::Secp256k1 => {
+                let signing_key = k256::ecdsa::SigningKey::from_slice(&self.private_key)
+                    .context("Invalid secp256k1 private key")?;
+                let signature: k256::ecdsa::Signature = signing_key.sign_prehash(&sha512_half(message))
+                    .context("Failed to sign XRPL transaction")?;
+                let signature = signature.normalize_s().unwrap_or(signature);
+                Ok(signature.to_der().as_bytes().to_vec())
+            }
+            XrpKeyType::Ed25519 => {
+                let signing_key = ed25519_dalek::SigningKey::from_bytes(&self.private_key);
+                Ok(signing_key.sign(message).to_bytes().to_vec())
+            }
+        }
+    }
+}
+
+// ========== TRANSACTIONS ==========
+
+/// An unsigned transaction in XRPL JSON form, as produced by autofill or a wallet's "prepare" step
+#[derive(Debug, Clone)]
+pub struct XrpTransaction {
+    fields: Map<String, Value>,
+}
+
+impl XrpTransaction {
+    /// Offline signing cannot look anything up, so Fee and Sequence must already be filled in
+    pub fn parse(json: &[u8]) -> Result<Self> {
+        let value: Value = serde_json::from_slice(json).context("XRPL transactions are expected as JSON")?;
+        let fields = match value {
+            // Accept the {"tx_json": {...}} wrapper used by rippled's sign/submit requests
+            Value::Object(mut object) if object.contains_key("tx_json") => match object.remove("tx_json") {
+                Some(Value::Object(fields)) => fields,
+                _ => bail!("tx_json must be a JSON object"),
+            },
+            Value::Object(fields) => fields,
+            _ => bail!("XRPL transaction must be a JSON object"),
+        };
+
+        let transaction = Self { fields };
+        if transaction.string_field("TransactionType").is_none() {
+            bail!("Transaction has no TransactionType");
+        }
+        if transaction.account().is_none() {
+            bail!("Transaction has no Account");
+        }
+        match transaction.string_field("Fee") {
+            Some(fee) if fee.parse::<u64>().is_ok() => {}
+            Some(fee) => bail!("Fee must be a whole number of drops, found '{}'", fee),
+            None => bail!("Transaction has no Fee; fill in Fee, Sequence and LastLedgerSequence (autofill on an online machine) before signing offline"),
+        }
+        if !transaction.fields.get("Sequence").is_some_and(Value::is_u64) {
+            bail!("Transaction has no Sequence; use the account's next sequence (or 0 with a TicketSequence)");
+        }
+
+        Ok(transaction)
+    }
+
+    pub fn account(&self) -> Option<&str> {
+        self.string_field("Account")
+    }
+
+    pub fn details(&self) -> Vec<(String, String)> {
+        let mut details = vec![
+            ("Type".to_string(), self.string_field("TransactionType").unwrap_or_default().to_string()),
+            ("Account".to_string(), self.account().unwrap_or_default().to_string()),
+        ];
+
+        if let Some(destination) = self.string_field("Destination") {
+            details.push(("Destination".to_string(), destination.to_string()));
+        }
+        for (field, label) in [("DestinationTag", "Destination Tag"), ("SourceTag", "Source Tag")] {
+            if let Some(tag) = self.fields.get(field).and_then(Value::as_u64) {
+                details.push((label.to_string(), tag.to_string()));
+            }
+        }
+        for (field, label) in [("Amount", "Amount"), ("DeliverMax", "Deliver Max"), ("SendMax", "Send Max"), ("LimitAmount", "Limit")] {
+            if let Some(amount) = self.fields.get(field) {
+                details.push((label.to_string(), format_amount(amount)));
+            }
+        }
+
+        details.push(("Fee".to_string(), format_drops(self.string_field("Fee").unwrap_or_default())));
+        if let Some(sequence) = self.fields.get("Sequence").and_then(Value::as_u64) {
+            details.push(("Sequence".to_string(), sequence.to_string()));
+        }
+        if let Some(ticket) = self.fields.get("TicketSequence").and_then(Value::as_u64) {
+            details.push(("Ticket Sequence".to_string(), ticket.to_string()));
+        }
+        match self.fields.get("LastLedgerSequence").and_then(Value::as_u64) {
+            Some(ledger) => details.push(("Last Ledger Sequence".to_string(), ledger.to_string())),
+            None => details.push(("Last Ledger Sequence".to_string(), "(none - the transaction never expires)".to_string())),
+        }
+        if let Some(network_id) = self.fields.get("NetworkID").and_then(Value::as_u64) {
+            details.push(("Network ID".to_string(), network_id.to_string()));
+        }
+        if let Some(memos) = self.fields.get("Memos").and_then(Value::as_array) {
+            details.push(("Memos".to_string(), memos.len().to_string()));
+        }
+
+        details
+    }
+
+    /// Sets SigningPubKey and TxnSignature and returns the binary `tx_blob` (hex) with its hash
+    pub fn sign(mut self, key: &XrpKey) -> Result<SignedTransaction> {
+        if self.fields.contains_key("TxnSignature") {
+            bail!("Transaction already has a TxnSignature");
+        }
+        if self.fields.contains_key("Signers") {
+            bail!("Multi-signed XRPL transactions are not supported");
+        }
+
+        let public_key = hex::encode_upper(key.public_key()?);
+        if let Some(existing) = self.string_field("SigningPubKey") {
+            if !existing.is_empty() && !existing.eq_ignore_ascii_case(&public_key) {
+                bail!("Transaction's SigningPubKey {} does not match the wallet's key {}", existing, public_key);
+            }
+        }
+        self.fields.insert("SigningPubKey".to_string(), Value::String(public_key));
+
+        let signing_hex = encode_for_signing(&Value::Object(self.fields.clone()))
+            .map_err(|e| anyhow::anyhow!("Failed to encode XRPL transaction for signing: {:?}", e))?;
+        let signing_bytes = hex::decode(&signing_hex).context("Binary codec returned invalid hex")?;
+
+        let signature = key.sign(&signing_bytes)?;
+        self.fields.insert("TxnSignature".to_string(), Value::String(hex::encode_upper(signature)));
+
+        let tx_blob = encode(&Value::Object(self.fields))
+            .map_err(|e| anyhow::anyhow!("Failed to encode signed XRPL transaction: {:?}", e))?;
+        let hash = transaction_hash(&hex::decode(&tx_blob).context("Binary codec returned invalid hex")?);
+
+        Ok(SignedTransaction {
+            encoded: tx_blob.to_uppercase(),
+            hash: Some(hash),
+        })
+    }
+
+    fn string_field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).and_then(Value::as_str)
+    }
+}
+
+/// Transaction ID: SHA-512Half of "TXN\0" followed by the signed blob, as uppercase hex
+pub fn transaction_hash(tx_blob: &[u8]) -> String {
+    hex::encode_upper(sha512_half(&[TRANSACTION_ID_PREFIX, tx_blob].concat()))
+}
+
+// ========== HELPERS ==========
+
+fn sha512_half(data: &[u8]) -> [u8; 32] {
+    let digest = Sha512::digest(data);
+    let mut half = [0u8; 32];
+    half.copy_from_slice(&digest[..32]);
+    half
+}
+
+fn encode_base58_check(payload: &[u8]) -> String {
+    let checksum = Sha256::digest(Sha256::digest(payload));
+    bs58::encode([payload, &checksum[..4]].concat())
+        .with_alphabet(bs58::Alphabet::RIPPLE)
+        .into_string()
+}
+
+fn decode_base58_check(encoded: &str) -> Result<Vec<u8>> {
+    let bytes = bs58::decode(encoded)
+        .with_alphabet(bs58::Alphabet::RIPPLE)
+        .into_vec()
+        .context("not valid base58")?;
+    if bytes.len() < 5 {
+        bail!("too short");
+    }
+
+    let (payload, checksum) = bytes.split_at(bytes.len() - 4);
+    if Sha256::digest(Sha256::digest(payload))[..4] != *checksum {
+        bail!("checksum mismatch");
+    }
+    Ok(payload.to_vec())
+}
+
+fn format_amount(amount: &Value) -> String {
+    match amount {
+        Value::String(drops) => format_drops(drops),
+        Value::Object(issued) => {
+            let field = |name: &str| issued.get(name).and_then(Value::as_str).unwrap_or_default();
+            if issued.contains_key("mpt_issuance_id") {
+                format!("{} (MPT {})", field("value"), field("mpt_issuance_id"))
+            } else {
+                format!("{} {} (issuer {})", field("value"), field("currency"), field("issuer"))
+            }
+        }
+        other => other.to_string(),
+    }
+}
+
+fn format_drops(drops: &str) -> String {
+    match drops.parse::<u64>() {
+        Ok(drops) => {
+            let xrp = format!("{}.{:06}", drops / DROPS_PER_XRP, drops % DROPS_PER_XRP);
+            format!("{} XRP", xrp.trim_end_matches('0').trim_end_matches('.'))
+        }
+        Err(_) => format!("{} drops", drops),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ED25519_SEED: &str = "sEdSKaCy2JT7JaM7v95H9SxkhP9wS2r";
+    const SECP256K1_SEED: &str = "sp5fghtJtpUorTwvof1NpDXAzNwf5";
+
+    fn payment(account: &str) -> String {
+        format!(r#"{{
+            "TransactionType": "Payment",
+            "Account": "{}",
+            "Destination": "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh",
+            "Amount": "1250000",
+            "Fee": "12",
+            "Sequence": 7,
+            "LastLedgerSequence": 90000000,
+            "Flags": 0
+        }}"#, account)
+    }
+
+    #[test]
+    fn test_family_seed_decoding() {
+        let ed25519 = FamilySeed::parse(ED25519_SEED).unwrap();
+        assert_eq!(ed25519.key_type, XrpKeyType::Ed25519);
+        assert_eq!(hex::encode(ed25519.entropy), "0102030405060708090a0b0c0d0e0f10");
+        assert_eq!(ed25519.encode(), ED25519_SEED);
+
+        let secp256k1 = FamilySeed::parse(SECP256K1_SEED).unwrap();
+        assert_eq!(secp256k1.key_type, XrpKeyType::Secp256k1);
+        assert_eq!(secp256k1.entropy, ed25519.entropy);
+        assert_eq!(secp256k1.encode(), SECP256K1_SEED);
+
+        // Broken checksum and non-seed payloads
+        assert!(FamilySeed::parse("sp5fghtJtpUorTwvof1NpDXAzNwf6").is_err());
+        assert!(FamilySeed::parse("rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh").is_err());
+        assert!(FamilySeed::looks_like(SECP256K1_SEED));
+        assert!(!FamilySeed::looks_like("1e99423a4ed27608a15a2616a2b0e9e52ced330ac530edcc32c8ffc6a526aedd"));
+    }
+
+    #[test]
+    fn test_family_seed_key_derivation() {
+        let ed25519 = FamilySeed::parse(ED25519_SEED).unwrap().derive_key().unwrap();
+        assert_eq!(ed25519.storage_hex(), "edb4c4e046826bd26190d09715fc31f4e6a728204eadd112905b08b14b7f15c4f3");
+        assert_eq!(
+            hex::encode_upper(ed25519.public_key().unwrap()),
+            "ED01FA53FA5A7E77798F882ECE20B1ABC00BB358A9E55A202D0D0676BD0CE37A63"
+        );
+
+        let secp256k1 = FamilySeed::parse(SECP256K1_SEED).unwrap().derive_key().unwrap();
+        assert_eq!(secp256k1.storage_hex(), "d78b9735c3f26501c7337b8a5727fd53a6efdbc6aa55984f098488561f985e23");
+        assert_eq!(
+            hex::encode_upper(secp256k1.public_key().unwrap()),
+            "030D58EB48B4420B1F7B9DF55087E0E29FEF0E8468F9A6825B01CA2C361042D435"
+        );
+    }
+
+    #[test]
+    fn test_key_from_bytes() {
+        let ed25519 = FamilySeed::parse(ED25519_SEED).unwrap().derive_key().unwrap();
+        let stored = hex::decode(ed25519.storage_hex()).unwrap();
+        let reloaded = XrpKey::from_bytes(&stored).unwrap();
+        assert_eq!(reloaded.key_type, XrpKeyType::Ed25519);
+        assert_eq!(reloaded.public_key().unwrap(), ed25519.public_key().unwrap());
+
+        let raw = [7u8; 32];
+        let prefixed = [&[0u8][..], &raw[..]].concat();
+        assert_eq!(XrpKey::from_bytes(&raw).unwrap().key_type, XrpKeyType::Secp256k1);
+        assert_eq!(XrpKey::from_bytes(&prefixed).unwrap().storage_hex(), hex::encode(raw));
+        assert!(XrpKey::from_bytes(&[7u8; 31]).is_err());
+        assert!(XrpKey::from_bytes(&[&[0x01u8][..], &raw[..]].concat()).is_err());
+    }
+
+    #[test]
+    fn test_transaction_parsing_and_details() {
+        let tx = XrpTransaction::parse(payment("rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD").as_bytes()).unwrap();
+        let details = tx.details();
+        assert!(details.contains(&("Type".to_string(), "Payment".to_string())));
+        assert!(details.contains(&("Amount".to_string(), "1.25 XRP".to_string())));
+        assert!(details.contains(&("Fee".to_string(), "0.000012 XRP".to_string())));
+        assert!(details.contains(&("Sequence".to_string(), "7".to_string())));
+
+        let wrapped = format!(r#"{{"tx_json": {}}}"#, payment("rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD"));
+        assert_eq!(XrpTransaction::parse(wrapped.as_bytes()).unwrap().account(), Some("rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD"));
+
+        // Offline signing needs Fee and Sequence up front
+        let no_fee = r#"{"TransactionType": "Payment", "Account": "rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD", "Sequence": 7}"#;
+        assert!(XrpTransaction::parse(no_fee.as_bytes()).is_err());
+        let no_sequence = r#"{"TransactionType": "Payment", "Account": "rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD", "Fee": "12"}"#;
+        assert!(XrpTransaction::parse(no_sequence.as_bytes()).is_err());
+
+        assert_eq!(format_amount(&serde_json::json!({
+            "currency": "USD", "value": "10.5", "issuer": "rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh"
+        })), "10.5 USD (issuer rHb9CJAWyB4rj91VRWn96DkukG4bwdtyTh)");
+        assert_eq!(format_drops("1000000"), "1 XRP");
+    }
+
+    #[test]
+    fn test_ed25519_transaction_signing() {
+        use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+        let key = FamilySeed::parse(ED25519_SEED).unwrap().derive_key().unwrap();
+        let tx = XrpTransaction::parse(payment("rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD").as_bytes()).unwrap();
+        let signed = tx.clone().sign(&key).unwrap();
+
+        let blob = hex::decode(&signed.encoded).unwrap();
+        assert_eq!(signed.hash.as_deref(), Some(transaction_hash(&blob).as_str()));
+        assert!(signed.encoded.contains("ED01FA53FA5A7E77798F882ECE20B1ABC00BB358A9E55A202D0D0676BD0CE37A63"));
+
+        // The signature covers the STX-prefixed signing encoding
+        let mut unsigned = tx.fields.clone();
+        unsigned.insert("SigningPubKey".to_string(), Value::String(hex::encode_upper(key.public_key().unwrap())));
+        let signing_bytes = hex::decode(encode_for_signing(&Value::Object(unsigned)).unwrap()).unwrap();
+        assert!(signing_bytes.starts_with(b"STX\0"));
+
+        let signature = key.sign(&signing_bytes).unwrap();
+        assert!(signed.encoded.contains(&hex::encode_upper(&signature)));
+        let public_key: [u8; 32] = key.public_key().unwrap()[1..].try_into().unwrap();
+        let verifying_key = VerifyingKey::from_bytes(&public_key).unwrap();
+        verifying_key.verify(&signing_bytes, &Signature::from_slice(&signature).unwrap()).unwrap();
+
+        // Deterministic: signing again gives the same blob
+        assert_eq!(tx.sign(&key).unwrap().encoded, signed.encoded);
+    }
+
+    #[test]
+    fn test_secp256k1_transaction_signing() {
+        use k256::ecdsa::signature::hazmat::PrehashVerifier;
+
+        let key = FamilySeed::parse(SECP256K1_SEED).unwrap().derive_key().unwrap();
+        let tx = XrpTransaction::parse(payment("rU6K7V3Po4snVhBBaU29sesqs2qTQJWDw1").as_bytes()).unwrap();
+        let signed = tx.clone().sign(&key).unwrap();
+        assert!(signed.encoded.contains("030D58EB48B4420B1F7B9DF55087E0E29FEF0E8468F9A6825B01CA2C361042D435"));
+
+        let mut unsigned = tx.fields.clone();
+        unsigned.insert("SigningPubKey".to_string(), Value::String(hex::encode_upper(key.public_key().unwrap())));
+        let signing_bytes = hex::decode(encode_for_signing(&Value::Object(unsigned)).unwrap()).unwrap();
+
+        let der = key.sign(&signing_bytes).unwrap();
+        let signature = k256::ecdsa::Signature::from_der(&der).unwrap();
+        assert!(signature.normalize_s().is_none(), "signature must use low S");
+        let verifying_key = k256::ecdsa::VerifyingKey::from_sec1_bytes(&key.public_key().unwrap()).unwrap();
+        verifying_key.verify_prehash(&sha512_half(&signing_bytes), &signature).unwrap();
+        assert!(signed.encoded.contains(&hex::encode_upper(&der)));
+    }
+
+    #[test]
+    fn test_signing_rejects_foreign_or_signed_transactions() {
+        let key = FamilySeed::parse(ED25519_SEED).unwrap().derive_key().unwrap();
+
+        let mut tx = XrpTransaction::parse(payment("rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD").as_bytes()).unwrap();
+        tx.fields.insert("SigningPubKey".to_string(), Value::String("030D58EB48B4420B1F7B9DF55087E0E29FEF0E8468F9A6825B01CA2C361042D435".to_string()));
+        assert!(tx.sign(&key).is_err());
+
+        let mut tx = XrpTransaction::parse(payment("rLUEXYuLiQptky37CqLcm9USQpPiz5rkpD").as_bytes()).unwrap();
+        tx.fields.insert("TxnSignature".to_string(), Value::String("00".to_string()));
+        assert!(tx.sign(&key).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::{Result, Context};
 use clap::Args;
-use crate::database::Database;
+use crate::database::{Database, WATCH_ONLY_KEY_NOTICE};
+use crate::blockchain::algorand::export_mnemonic;
+use crate::blockchain::sui::export_private_key as export_sui_private_key;
 
 #[derive(Args)]
 pub struct ShowAddressGroupArgs {
@@ -17,6 +19,10 @@ pub struct ShowAddressGroupArgs {
 }
 
 pub fn execute(args: ShowAddressGroupArgs, db: &Database) -> Result<()> {
+    if args.include_sensitive {
+        db.require_unlocked()?;
+    }
+
     println!("🗂️  Address Group Details");
     println!("Account: {}", args.account);
     println!("Wallet Group: {}", args.wallet_group);
@@ -139,8 +145,23 @@ pub fn execute(args: ShowAddressGroupArgs, db: &Database) -> Result<()> {
             }
 
             // Show private key only if sensitive flag is used
-            if args.include_sensitive {
-                println!("      🔒 Private Key: {}", subwallet.private_key);
+            if subwallet.is_watch_only() {
+                println!("      🔒 Private Key: {}", WATCH_ONLY_KEY_NOTICE);
+            } else if args.include_sensitive {
+                let private_key = subwallet.private_key.expose()?;
+                println!("      🔒 Private Key: {}", private_key);
+                if wallet.blockchain == "algorand" {
+                    match export_mnemonic(private_key) {
+                        Ok(mnemonic) => println!("      🌱 Algorand Mnemonic: {}", mnemonic),
+                        Err(e) => println!("      🌱 Algorand Mnemonic: (conversion error: {})", e),
+                    }
+                }
+                if wallet.blockchain == "sui" {
+                    match export_sui_private_key(private_key) {
+                        Ok(sui_key) => println!("      🔒 Sui Private Key: {}", sui_key),
+                        Err(e) => println!("      🔒 Sui Private Key: (conversion error: {})", e),
+                    }
+                }
             } else {
                 println!("      🔒 Private Key: (use --include-sensitive to view)");
             }
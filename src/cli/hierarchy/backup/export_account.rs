@@ -0,0 +1,77 @@
+use anyhow::{Result, Context};
+use clap::Args;
+use std::fs;
+use std::io::Write;
+use crate::database::Database;
+use crate::crypto::backup;
+use crate::crypto::vault::KdfParams;
+use crate::utils::prompt_new_password;
+
+#[derive(Args)]
+pub struct ExportAccountArgs {
+    #[arg(long, help = "Account name to export")]
+    pub account: String,
+    #[arg(long, help = "Output file for the encrypted backup")]
+    pub output: String,
+    #[arg(long, help = "Overwrite the output file if it already exists")]
+    pub force: bool,
+}
+
+pub fn execute(args: ExportAccountArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("📦 Exporting account: {}", args.account);
+
+    if std::path::Path::new(&args.output).exists() && !args.force {
+        println!("\n❌ Output file '{}' already exists.", args.output);
+        println!("💡 Use --force to overwrite it.");
+        return Ok(());
+    }
+
+    let account_backup = match db.export_account_tree(&args.account)? {
+        Some(account_backup) => account_backup,
+        None => {
+            println!("\n❌ Account '{}' not found.", args.account);
+            return Ok(());
+        }
+    };
+
+    let wallet_count: usize = account_backup.wallet_groups.iter().map(|g| g.wallets.len()).sum();
+    let subwallet_count: usize = account_backup.wallet_groups.iter()
+        .flat_map(|g| g.wallets.iter())
+        .flat_map(|w| w.address_groups.iter())
+        .map(|ag| ag.subwallets.len())
+        .sum();
+
+    println!("   Wallet groups: {}", account_backup.wallet_groups.len());
+    println!("   Wallets: {}", wallet_count);
+    println!("   Subwallets: {}", subwallet_count);
+
+    let payload = serde_json::to_string(&account_backup)
+        .context("Failed to serialize account")?;
+
+    let password = prompt_new_password("Backup password")?;
+    let envelope = backup::seal_backup(&password, &payload, &KdfParams::default())?;
+    let contents = serde_json::to_string_pretty(&envelope)?;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&args.output)
+        .with_context(|| format!("Failed to create backup file {}", args.output))?;
+    file.write_all(contents.as_bytes())?;
+
+    println!("\n🎉 Account '{}' exported successfully!", args.account);
+    println!("   File: {}", args.output);
+    println!("   Format: {} v{}", backup::BACKUP_FORMAT, backup::BACKUP_VERSION);
+
+    println!("\n⚠️  IMPORTANT:");
+    println!("   • The backup contains your mnemonic and private keys, protected only by the backup password");
+    println!("   • Store it offline and do not reuse the vault password");
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anyhow::{Result, Context};
+use clap::Args;
+use std::fs;
+use crate::database::Database;
+use super::manifest::AccountManifest;
+
+#[derive(Args)]
+pub struct ExportManifestArgs {
+    #[arg(long, help = "Account name to describe")]
+    pub account: String,
+    #[arg(long, help = "Output file for the public manifest (JSON)")]
+    pub output: String,
+    #[arg(long, help = "Overwrite the output file if it already exists")]
+    pub force: bool,
+}
+
+pub fn execute(args: ExportManifestArgs, db: &Database) -> Result<()> {
+    println!("🧾 Exporting public manifest for account: {}", args.account);
+
+    if std::path::Path::new(&args.output).exists() && !args.force {
+        println!("\n❌ Output file '{}' already exists.", args.output);
+        println!("💡 Use --force to overwrite it.");
+        return Ok(());
+    }
+
+    let tree = match db.get_account_outline(&args.account)? {
+        Some(tree) => tree,
+        None => {
+            println!("\n❌ Account '{}' not found.", args.account);
+            return Ok(());
+        }
+    };
+
+    let manifest = AccountManifest::from_account_outline(&tree)?;
+    let contents = serde_json::to_string_pretty(&manifest)?;
+    fs::write(&args.output, contents)
+        .with_context(|| format!("Failed to write manifest {}", args.output))?;
+
+    let wallet_count: usize = manifest.wallet_groups.iter().map(|g| g.wallets.len()).sum();
+
+    println!("\n🎉 Manifest written to {}", args.output);
+    println!("   Wallet groups: {}", manifest.wallet_groups.len());
+    println!("   Wallets: {}", wallet_count);
+    println!("   Passphrase required: {}", if manifest.uses_passphrase { "yes" } else { "no" });
+
+    println!("\n💡 The manifest contains no secrets (no mnemonic, passphrase or private keys).");
+    println!("   Restore later with: wallet-backup restore --manifest {}", args.output);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anyhow::{Result, Context};
+use clap::Args;
+use std::fs;
+use crate::database::{Database, AccountBackup};
+use crate::crypto::backup::{self, BackupEnvelope};
+use crate::utils::prompt_password;
+
+#[derive(Args)]
+pub struct ImportAccountArgs {
+    #[arg(long, help = "Encrypted backup file created by export-account")]
+    pub input: String,
+    #[arg(long, help = "Import under a different account name")]
+    pub name: Option<String>,
+}
+
+pub fn execute(args: ImportAccountArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("📥 Importing account backup: {}", args.input);
+
+    let contents = fs::read_to_string(&args.input)
+        .with_context(|| format!("Failed to read backup file {}", args.input))?;
+    let envelope: BackupEnvelope = serde_json::from_str(&contents)
+        .context("Invalid backup file")?;
+
+    println!("   Format: {} v{}", envelope.format, envelope.version);
+    println!("   Created: {}", envelope.created_at.format("%Y-%m-%d %H:%M:%S UTC"));
+
+    let password = prompt_password("Backup password")?;
+    let payload = backup::open_backup(&password, &envelope)?;
+    let account_backup: AccountBackup = serde_json::from_str(&payload)
+        .context("Backup payload is not a valid account tree")?;
+
+    let account_name = args.name.clone().unwrap_or_else(|| account_backup.name.clone());
+
+    let summary = db.import_account_tree(&account_backup, &account_name)
+        .context("Failed to import account (no changes were made)")?;
+
+    println!("\n🎉 Account '{}' imported successfully!", account_name);
+    println!("   Wallet groups: {}", summary.wallet_groups);
+    println!("   Wallets: {}", summary.wallets);
+    println!("   Address groups: {}", summary.address_groups);
+    println!("   Subwallets: {}", summary.subwallets);
+    println!("   Next account index: {}", account_backup.next_account_index);
+
+    println!("\n💡 Next steps:");
+    println!("   • Review the account: wallet-backup show-account --account \"{}\"", account_name);
+    println!("   • List wallet groups: wallet-backup list-wallet-groups --account \"{}\"", account_name);
+
+    Ok(())
+}
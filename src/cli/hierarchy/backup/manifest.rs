@@ -0,0 +1,177 @@
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use crate::database::{AccountOutline, WalletOutline};
+use crate::blockchain::{SupportedBlockchain, BipStandard};
+
+/// Identifies a public (secret-free) account manifest
+pub const MANIFEST_FORMAT: &str = "wallet-backup/account-manifest";
+
+/// Current manifest version (bump when the layout changes)
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Non-secret description of an account tree; every key can be re-derived from the mnemonic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountManifest {
+    pub format: String,
+    pub version: u32,
+    pub name: String,
+    pub uses_passphrase: bool,
+    pub next_account_index: u32,
+    pub wallet_groups: Vec<ManifestWalletGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestWalletGroup {
+    pub name: String,
+    pub description: Option<String>,
+    pub account_index: u32,
+    pub wallets: Vec<ManifestWallet>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestWallet {
+    pub label: Option<String>,
+    pub blockchain: String,
+    pub address: String,
+    pub derivation_path: String,
+    pub bip: Option<String>, // Bitcoin address type (e.g. "84"), inferred from the path purpose
+    pub sol_path: Option<String>, // "trust-wallet" or "standard" for Solana
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub address_groups: Vec<ManifestAddressGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestAddressGroup {
+    pub name: String,
+    pub blockchain: String,
+    pub address_group_index: u32,
+    pub next_address_index: u32,
+    pub subwallets: Vec<ManifestWallet>,
+}
+
+impl AccountManifest {
+    /// Builds the manifest from the secret-free outline of an account tree
+    pub fn from_account_outline(tree: &AccountOutline) -> Result<Self> {
+        let mut wallet_groups = Vec::new();
+        for group in &tree.wallet_groups {
+            let mut wallets = Vec::new();
+            for base in &group.wallets {
+                let mut entry = ManifestWallet::from_wallet(base)?;
+                for address_group in &base.address_groups {
+                    entry.address_groups.push(ManifestAddressGroup {
+                        name: address_group.name.clone(),
+                        blockchain: address_group.blockchain.clone(),
+                        address_group_index: address_group.address_group_index,
+                        next_address_index: address_group.next_address_index,
+                        subwallets: address_group.subwallets.iter()
+                            .map(ManifestWallet::from_wallet)
+                            .collect::<Result<Vec<_>>>()?,
+                    });
+                }
+                wallets.push(entry);
+            }
+
+            wallet_groups.push(ManifestWalletGroup {
+                name: group.name.clone(),
+                description: group.description.clone(),
+                account_index: group.account_index,
+                wallets,
+            });
+        }
+
+        Ok(Self {
+            format: MANIFEST_FORMAT.to_string(),
+            version: MANIFEST_VERSION,
+            name: tree.name.clone(),
+            uses_passphrase: tree.uses_passphrase,
+            next_account_index: tree.next_account_index,
+            wallet_groups,
+        })
+    }
+
+    /// Checks the format marker and version of a loaded manifest
+    pub fn validate(&self) -> Result<()> {
+        if self.format != MANIFEST_FORMAT {
+            bail!("Not an account manifest (format: {})", self.format);
+        }
+        if self.version > MANIFEST_VERSION {
+            bail!("Unsupported manifest version {} (this build supports up to {})", self.version, MANIFEST_VERSION);
+        }
+        Ok(())
+    }
+}
+
+impl ManifestWallet {
+    fn from_wallet(wallet: &WalletOutline) -> Result<Self> {
+        let derivation_path = match &wallet.derivation_path {
+            Some(path) => path.clone(),
+            None => bail!("Wallet {} has no derivation path and cannot be re-derived", wallet.address),
+        };
+        let blockchain = SupportedBlockchain::from_str(&wallet.blockchain)?;
+
+        let bip = if blockchain == SupportedBlockchain::Bitcoin {
+            bip_from_derivation_path(&derivation_path).map(|bip| bip.get_purpose().to_string())
+        } else {
+            None
+        };
+
+        let sol_path = if blockchain == SupportedBlockchain::Solana {
+            match derivation_path.split('/').count() {
+                4 => Some("trust-wallet".to_string()),
+                5 => Some("standard".to_string()),
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        Ok(Self {
+            label: wallet.label.clone(),
+            blockchain: wallet.blockchain.clone(),
+            address: wallet.address.clone(),
+            derivation_path,
+            bip,
+            sol_path,
+            notes: wallet.notes.clone(),
+            address_groups: Vec::new(),
+        })
+    }
+}
+
+/// Infers the BIP standard from the purpose level of a path like "m/84'/0'/0'/0/0"
+pub fn bip_from_derivation_path(path: &str) -> Option<BipStandard> {
+    BipStandard::from_derivation_path(path)
+}
+
+/// Extracts the account (third level) and address index (last level) from a derivation path
+pub fn path_indices(path: &str) -> (u32, u32) {
+    let levels: Vec<u32> = path.split('/')
+        .skip(1)
+        .filter_map(|level| level.trim_end_matches('\'').parse().ok())
+        .collect();
+
+    let account = levels.get(2).copied().unwrap_or(0);
+    let address_index = if levels.len() > 3 { *levels.last().unwrap() } else { 0 };
+    (account, address_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bip_from_derivation_path() {
+        assert_eq!(bip_from_derivation_path("m/84'/0'/0'/0/0"), Some(BipStandard::Bip84));
+        assert_eq!(bip_from_derivation_path("m/44'/0'/3'/0/0"), Some(BipStandard::Bip44));
+        assert_eq!(bip_from_derivation_path("m/49'/0'/0'/0/7"), Some(BipStandard::Bip49));
+        assert_eq!(bip_from_derivation_path("m/1852'/1815'/0'/0/0"), None);
+    }
+
+    #[test]
+    fn test_path_indices() {
+        assert_eq!(path_indices("m/44'/60'/2'/0/5"), (2, 5));
+        assert_eq!(path_indices("m/44'/501'/3'"), (3, 0));
+        assert_eq!(path_indices("m/44'/501'/1'/0'"), (1, 0));
+    }
+}
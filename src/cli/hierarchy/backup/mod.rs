@@ -0,0 +1,10 @@
+pub mod export_account;
+pub mod import_account;
+pub mod manifest;
+pub mod export_manifest;
+pub mod restore;
+
+pub use export_account::ExportAccountArgs;
+pub use import_account::ImportAccountArgs;
+pub use export_manifest::ExportManifestArgs;
+pub use restore::RestoreArgs;
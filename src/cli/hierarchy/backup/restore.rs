@@ -0,0 +1,196 @@
+use anyhow::{Result, Context};
+use clap::Args;
+use chrono::Utc;
+use std::fs;
+use crate::database::{Database, Wallet, AccountBackup, WalletGroupBackup, BaseWalletBackup, AddressGroupBackup};
+use crate::blockchain::{SupportedBlockchain, get_blockchain_handler, BipStandard, WalletKeys};
+use crate::blockchain::bitcoin::BitcoinHandler;
+use crate::crypto::bip39_utils::{validate_mnemonic_phrase, normalize_mnemonic, generate_seed_from_mnemonic};
+use crate::utils::prompt_password;
+use super::manifest::{AccountManifest, ManifestWallet, bip_from_derivation_path, path_indices};
+
+#[derive(Args)]
+pub struct RestoreArgs {
+    #[arg(long, help = "Public manifest created by export-manifest")]
+    pub manifest: String,
+    #[arg(long, help = "Restore under a different account name")]
+    pub name: Option<String>,
+    #[arg(long, help = "BIP-39 mnemonic phrase (prompted for if omitted)")]
+    pub mnemonic: Option<String>,
+    #[arg(long, help = "BIP-39 passphrase (prompted for if the manifest requires one)")]
+    pub passphrase: Option<String>,
+    #[arg(long, help = "Only re-derive and verify addresses; do not write to the database")]
+    pub verify_only: bool,
+}
+
+pub fn execute(args: RestoreArgs, db: &Database) -> Result<()> {
+    if !args.verify_only {
+        db.require_unlocked()?;
+    }
+
+    println!("♻️  Restoring account from manifest: {}", args.manifest);
+
+    let contents = fs::read_to_string(&args.manifest)
+        .with_context(|| format!("Failed to read manifest {}", args.manifest))?;
+    let manifest: AccountManifest = serde_json::from_str(&contents)
+        .context("Invalid manifest file")?;
+    manifest.validate()?;
+
+    let account_name = args.name.clone().unwrap_or_else(|| manifest.name.clone());
+    println!("   Account: {}", account_name);
+
+    let mnemonic = match &args.mnemonic {
+        Some(mnemonic) => mnemonic.clone(),
+        None => prompt_password("Mnemonic phrase")?,
+    };
+    let mnemonic = normalize_mnemonic(&mnemonic);
+    validate_mnemonic_phrase(&mnemonic).context("Invalid mnemonic phrase")?;
+    println!("✓ Mnemonic phrase validated");
+
+    let passphrase = match (&args.passphrase, manifest.uses_passphrase) {
+        (Some(passphrase), _) => Some(passphrase.clone()),
+        (None, true) => Some(prompt_password("BIP-39 passphrase")?),
+        (None, false) => None,
+    };
+
+    let seed = generate_seed_from_mnemonic(&mnemonic, passphrase.as_deref())
+        .context("Failed to generate seed from mnemonic")?;
+    let master_private_key = hex::encode(&seed[0..32]);
+
+    // Re-derive every wallet and compare against the manifest
+    let mut mismatches = Vec::new();
+    let mut verified = 0usize;
+    let mut wallet_groups = Vec::new();
+
+    for group in &manifest.wallet_groups {
+        let mut wallets = Vec::new();
+        for entry in &group.wallets {
+            let wallet = rederive(entry, &mnemonic, passphrase.as_deref(), &mut mismatches)?;
+            verified += 1;
+
+            let mut address_groups = Vec::new();
+            for address_group in &entry.address_groups {
+                let mut subwallets = Vec::new();
+                for sub_entry in &address_group.subwallets {
+                    subwallets.push(rederive(sub_entry, &mnemonic, passphrase.as_deref(), &mut mismatches)?);
+                    verified += 1;
+                }
+
+                address_groups.push(AddressGroupBackup {
+                    blockchain: address_group.blockchain.clone(),
+                    name: address_group.name.clone(),
+                    address_group_index: address_group.address_group_index,
+                    next_address_index: address_group.next_address_index,
+                    created_at: Utc::now(),
+                    subwallets,
+                });
+            }
+
+            wallets.push(BaseWalletBackup { wallet, address_groups });
+        }
+
+        wallet_groups.push(WalletGroupBackup {
+            name: group.name.clone(),
+            description: group.description.clone(),
+            account_index: group.account_index,
+            created_at: Utc::now(),
+            wallets,
+        });
+    }
+
+    println!("✓ Re-derived {} wallet(s)", verified);
+
+    if !mismatches.is_empty() {
+        println!("\n❌ {} address(es) did not match the manifest:", mismatches.len());
+        for (path, expected, actual) in &mismatches {
+            println!("   • {} expected {} but derived {}", path, expected, actual);
+        }
+        println!("\n💡 Check the mnemonic and passphrase. Nothing was written to the database.");
+        return Ok(());
+    }
+
+    println!("✓ All addresses match the manifest");
+
+    if args.verify_only {
+        println!("\n🎉 Verification complete (no changes made).");
+        return Ok(());
+    }
+
+    let tree = AccountBackup {
+        name: account_name.clone(),
+        mnemonic,
+        master_private_key,
+        passphrase,
+        next_account_index: manifest.next_account_index,
+        created_at: Utc::now(),
+        wallet_groups,
+    };
+
+    let summary = db.import_account_tree(&tree, &account_name)
+        .context("Failed to restore account (no changes were made)")?;
+
+    println!("\n🎉 Account '{}' restored successfully!", account_name);
+    println!("   Wallet groups: {}", summary.wallet_groups);
+    println!("   Wallets: {}", summary.wallets);
+    println!("   Address groups: {}", summary.address_groups);
+    println!("   Subwallets: {}", summary.subwallets);
+
+    println!("\n💡 Next steps:");
+    println!("   • Review the account: wallet-backup show-account --account \"{}\"", account_name);
+
+    Ok(())
+}
+
+// Re-derives one manifest entry from the mnemonic, recording an address mismatch if there is one
+fn rederive(
+    entry: &ManifestWallet,
+    mnemonic: &str,
+    passphrase: Option<&str>,
+    mismatches: &mut Vec<(String, String, String)>,
+) -> Result<Wallet> {
+    let blockchain = SupportedBlockchain::from_str(&entry.blockchain)?;
+    let keys = derive_keys(&blockchain, mnemonic, passphrase, &entry.derivation_path)
+        .with_context(|| format!("Failed to re-derive {} wallet at {}", blockchain, entry.derivation_path))?;
+
+    if keys.address != entry.address {
+        mismatches.push((format!("{} {}", blockchain, entry.derivation_path), entry.address.clone(), keys.address.clone()));
+    }
+
+    Ok(Wallet {
+        id: None,
+        wallet_group_id: None,
+        address_group_id: None,
+        blockchain: blockchain.to_string(),
+        address: keys.address.clone(),
+        address_with_checksum: keys.address_with_checksum.clone(),
+        private_key: keys.private_key.into(),
+        public_key: Some(keys.public_key),
+        derivation_path: Some(keys.derivation_path),
+        label: entry.label.clone(),
+        source_type: "mnemonic".to_string(),
+        explorer_url: Some(blockchain.get_explorer_url(&keys.address)),
+        notes: entry.notes.clone(),
+        created_at: Utc::now(),
+        additional_data: keys.additional_data,
+        secondary_addresses: keys.secondary_addresses,
+    })
+}
+
+fn derive_keys(blockchain: &SupportedBlockchain, mnemonic: &str, passphrase: Option<&str>, path: &str) -> Result<WalletKeys> {
+    let (account, address_index) = path_indices(path);
+    let handler = get_blockchain_handler(blockchain)?;
+    let mut keys = handler.derive_from_mnemonic(mnemonic, passphrase, account, address_index, Some(path))?;
+
+    // Bitcoin encodes the address type in the path purpose (44 = legacy, 49 = nested SegWit, 84 = native SegWit, 86 = Taproot)
+    if *blockchain == SupportedBlockchain::Bitcoin {
+        let bip = bip_from_derivation_path(path).unwrap_or(BipStandard::Bip84);
+        let public_key_bytes = hex::decode(&keys.public_key).context("Invalid public key")?;
+        let bitcoin_handler = BitcoinHandler::new();
+        keys.address = bitcoin_handler.public_key_to_address_with_bip(&public_key_bytes, bip)?;
+        if bip == BipStandard::Bip86 {
+            bitcoin_handler.add_taproot_details(&mut keys, &public_key_bytes)?;
+        }
+    }
+
+    Ok(keys)
+}
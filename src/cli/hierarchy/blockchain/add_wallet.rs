@@ -2,8 +2,8 @@ use anyhow::{Result, Context};
 use clap::Args;
 use chrono::Utc;
 
-use crate::database::{Database, Wallet};
-use crate::blockchain::{SupportedBlockchain, get_blockchain_handler, BipStandard};
+use crate::database::{Database, MasterAccount, Wallet};
+use crate::blockchain::{SupportedBlockchain, get_blockchain_handler, derive_for_account, BipStandard};
 
 #[derive(Args)]
 pub struct AddWalletArgs {
@@ -19,13 +19,15 @@ pub struct AddWalletArgs {
     pub account_index: Option<u32>,
     #[arg(long, help = "Starting address index (default: 0)")]
     pub address_index: Option<u32>,
-    #[arg(long, help = "BIP standard to use (44, 49, 84). If not specified, uses blockchain default")]
+    #[arg(long, help = "BIP standard to use (44, 49, 84, 86 for Bitcoin Taproot). If not specified, uses blockchain default")]
     pub bip: Option<String>,
     #[arg(long, help = "Solana derivation path variant: 'trust-wallet' (m/44'/501'/0', default) or 'standard' (m/44'/501'/0'/0', BIP-44). Only applies to Solana blockchain")]
     pub sol_path: Option<String>,
 }
 
 pub fn execute(args: AddWalletArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
     println!("Adding wallet '{}' to wallet group: {}", args.name, args.wallet_group);
     println!("Master account: {}", args.account);
     println!("Blockchain: {}", args.blockchain);
@@ -109,8 +111,7 @@ pub fn execute(args: AddWalletArgs, db: &Database) -> Result<()> {
 
     match process_blockchain(
         db,
-        &master_account.mnemonic,
-        master_account.passphrase.as_deref(),
+        &master_account,
         wallet_group.id.unwrap(),
         &blockchain,
         account_index,
@@ -139,8 +140,7 @@ pub fn execute(args: AddWalletArgs, db: &Database) -> Result<()> {
 
 fn process_blockchain(
     db: &Database,
-    mnemonic: &str,
-    passphrase: Option<&str>,
+    master_account: &MasterAccount,
     wallet_group_id: i64,
     blockchain: &SupportedBlockchain,
     account_index: u32,
@@ -157,12 +157,21 @@ fn process_blockchain(
     let handler = get_blockchain_handler(blockchain)?;
 
     // Derive wallet keys with BIP standard if specified
-    let wallet_keys = if let Some(bip) = bip_standard {
+    let wallet_keys = if master_account.is_watch_only() {
+        // The account xpub fixes purpose and account index; --bip/--sol-path do not apply
+        derive_for_account(handler.as_ref(), master_account, effective_account_index, 0, None)
+            .context("Failed to derive watch-only keys")?
+    } else if let Some(bip) = bip_standard {
         // For Bitcoin, use the specialized BIP-aware method
         if *blockchain == SupportedBlockchain::Bitcoin {
             let bitcoin_handler = crate::blockchain::bitcoin::BitcoinHandler::new();
-            bitcoin_handler.derive_with_bip(mnemonic, passphrase, effective_account_index, 0, bip)
-                .context("Failed to derive Bitcoin keys with BIP standard")?
+            if master_account.uses_master_key() {
+                bitcoin_handler.derive_from_master_key_with_bip(&master_account.master_key()?, effective_account_index, 0, bip)
+                    .context("Failed to derive Bitcoin keys with BIP standard")?
+            } else {
+                bitcoin_handler.derive_with_bip(master_account.mnemonic.expose()?, master_account.passphrase_str()?, effective_account_index, 0, bip)
+                    .context("Failed to derive Bitcoin keys with BIP standard")?
+            }
         } else {
             // For other blockchains, use default derivation path (includes blockchain-specific customizations)
             let derivation_path = if *blockchain == SupportedBlockchain::Solana && sol_path.is_some() {
@@ -175,13 +184,13 @@ fn process_blockchain(
             } else {
                 blockchain.get_default_derivation_path(effective_account_index, 0)
             };
-            handler.derive_from_mnemonic(
-                mnemonic,
-                passphrase,
+            derive_for_account(
+                handler.as_ref(),
+                master_account,
                 effective_account_index,
                 0, // Use 0 for the base wallet derivation
                 Some(&derivation_path),
-            ).context("Failed to derive keys from mnemonic with BIP standard")?
+            ).context("Failed to derive keys with BIP standard")?
         }
     } else {
         // Use default derivation (with role and BIP support for Cardano)
@@ -196,13 +205,13 @@ fn process_blockchain(
             None
         };
 
-        handler.derive_from_mnemonic(
-            mnemonic,
-            passphrase,
+        derive_for_account(
+            handler.as_ref(),
+            master_account,
             effective_account_index,
             0, // Use 0 for the base wallet derivation
             custom_path.as_deref(), // use custom path if needed, otherwise default
-        ).context("Failed to derive keys from mnemonic")?
+        ).context("Failed to derive keys")?
     };
 
     // Create BASE WALLET record (address_group_id = None)
@@ -213,11 +222,11 @@ fn process_blockchain(
         blockchain: blockchain.to_string(),
         address: wallet_keys.address.clone(),
         address_with_checksum: wallet_keys.address_with_checksum.clone(),
-        private_key: wallet_keys.private_key,
+        private_key: wallet_keys.private_key.into(),
         public_key: Some(wallet_keys.public_key),
         derivation_path: Some(wallet_keys.derivation_path),
         label: Some(wallet_name.to_string()),
-        source_type: "mnemonic".to_string(),
+        source_type: master_account.wallet_source_type().to_string(),
         explorer_url: Some(blockchain.get_explorer_url(&wallet_keys.address)),
         notes: None,
         created_at: Utc::now(),
@@ -229,4 +238,4 @@ fn process_blockchain(
     let wallet_id = db.create_wallet(&wallet)?;
 
     Ok(wallet_id)
-}
\ No newline at end of file
+}
@@ -1,7 +1,14 @@
 use anyhow::{Result, Context};
 use clap::Args;
 use crate::database::Database;
-use crate::crypto::bip39_utils::{validate_mnemonic_phrase, normalize_mnemonic, generate_seed_from_mnemonic};
+use crate::crypto::bip39_utils::{
+    validate_mnemonic_phrase, normalize_mnemonic, generate_seed_from_mnemonic, entropy_to_mnemonic,
+    entropy_length_for_words, generate_os_entropy, normalize_dice_rolls, normalize_coin_flips, mix_entropy,
+};
+use crate::crypto::master_key::{MasterKey, MASTER_KEY_MNEMONIC_PLACEHOLDER};
+use crate::crypto::watch_only::{WatchOnlyKey, WATCH_ONLY_MNEMONIC_PLACEHOLDER};
+use crate::blockchain::{SupportedBlockchain, BipStandard};
+use crate::utils::{prompt_input, confirm_action};
 
 #[derive(Args)]
 pub struct CreateMasterArgs {
@@ -9,18 +16,41 @@ pub struct CreateMasterArgs {
     pub name: String,
     #[arg(long, help = "BIP-39 mnemonic phrase (12-24 words)", conflicts_with = "master_private")]
     pub mnemonic: Option<String>,
-    #[arg(long, help = "Master private key (alternative to mnemonic)", conflicts_with = "mnemonic")]
+    #[arg(long, help = "Master key instead of a mnemonic: a depth-0 xprv/tprv (secp256k1 chains) or a 64-byte hex SLIP-10 ed25519 master secret (ed25519 chains)", conflicts_with = "mnemonic")]
     pub master_private: Option<String>,
+    #[arg(long, help = "Account-level xpub/ypub/zpub/Ltub/Mtub for a watch-only account (no secrets stored)", conflicts_with_all = ["mnemonic", "master_private", "passphrase"])]
+    pub watch_only: Option<String>,
+    #[arg(long, default_value = "bitcoin", requires = "watch_only", help = "Blockchain the watch-only xpub belongs to")]
+    pub blockchain: String,
+    #[arg(long, requires = "watch_only", help = "BIP purpose of the watch-only xpub (44, 49, 84). Default: implied by the key prefix")]
+    pub bip: Option<String>,
     #[arg(long, help = "Optional passphrase for additional security")]
     pub passphrase: Option<String>,
+    #[arg(long, default_value = "24", help = "Word count when generating a new mnemonic (12, 15, 18, 21, 24)")]
+    pub words: usize,
+    #[arg(long, help = "Dice rolls (1-6) to mix into the generated entropy", conflicts_with_all = ["mnemonic", "master_private", "watch_only", "coins"])]
+    pub dice: Option<String>,
+    #[arg(long, help = "Coin flips (H/T or 1/0) to mix into the generated entropy", conflicts_with_all = ["mnemonic", "master_private", "watch_only", "dice"])]
+    pub coins: Option<String>,
+    #[arg(long, help = "Show the generated words once, then quiz random positions before saving", conflicts_with_all = ["mnemonic", "master_private", "watch_only"])]
+    pub verify_backup: bool,
 }
 
 pub fn execute(args: CreateMasterArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
     println!("Creating account: {}", args.name);
 
-    // Validate input - must have either mnemonic or master_private
-    let (normalized_mnemonic, master_private_key, source_type) = match (&args.mnemonic, &args.master_private) {
-        (Some(mnemonic), None) => {
+    // Check if account already exists
+    if let Some(_existing) = db.get_master_account_by_name(&args.name)? {
+        println!("❌ Account '{}' already exists.", args.name);
+        println!("   Use a different account name or delete the existing account first.");
+        return Ok(());
+    }
+
+    // Validate input - mnemonic, master_private, watch-only xpub, or none (generate a new mnemonic)
+    let (normalized_mnemonic, master_private_key, source_type) = match (&args.mnemonic, &args.master_private, &args.watch_only) {
+        (Some(mnemonic), None, None) => {
             // Using mnemonic
             let normalized_mnemonic = normalize_mnemonic(mnemonic);
             validate_mnemonic_phrase(&normalized_mnemonic)
@@ -35,35 +65,66 @@ pub fn execute(args: CreateMasterArgs, db: &Database) -> Result<()> {
             let master_private = hex::encode(&seed[0..32]);
             (normalized_mnemonic, master_private, "mnemonic")
         },
-        (None, Some(master_private)) => {
+        (None, Some(master_private), None) => {
             // Using master private key
-            println!("✓ Using provided master private key");
+            let master_key = MasterKey::parse(master_private)
+                .context("Invalid master private key")?;
+            if args.passphrase.is_some() {
+                println!("⚠️  --passphrase is ignored: the master key already includes any BIP-39 passphrase");
+            }
+            println!("✓ Using provided {}", master_key.describe());
             // For mnemonic storage, we'll store a placeholder since we don't have the original mnemonic
-            ("[MASTER_PRIVATE_KEY_SOURCE]".to_string(), master_private.clone(), "master_private_key")
+            (MASTER_KEY_MNEMONIC_PLACEHOLDER.to_string(), master_key.to_storage_string(), "master_private_key")
+        },
+        (None, None, Some(xpub)) => {
+            // Watch-only: keep the account-level public key, no secret at all
+            let blockchain = SupportedBlockchain::from_str(&args.blockchain)?;
+            let bip = args.bip.as_deref().map(BipStandard::from_str).transpose()?;
+            let watch_only = WatchOnlyKey::new(blockchain, xpub, bip)
+                .context("Invalid watch-only extended public key")?;
+            println!(
+                "✓ Watch-only {} key validated ({}, account {})",
+                watch_only.prefix.as_str(),
+                watch_only.account_path(),
+                watch_only.account_index()
+            );
+            (WATCH_ONLY_MNEMONIC_PLACEHOLDER.to_string(), watch_only.to_storage_string(), "watch_only")
         },
-        (None, None) => {
-            return Err(anyhow::anyhow!("Must provide either --mnemonic or --master-private"));
+        (None, None, None) => {
+            // No seed supplied: generate a fresh mnemonic
+            let mnemonic = generate_mnemonic(&args)?;
+            if args.verify_backup {
+                if !run_backup_quiz(&mnemonic)? {
+                    println!("\n❌ Backup verification failed. The account was NOT saved.");
+                    println!("   Run the command again to generate a new mnemonic.");
+                    return Ok(());
+                }
+                println!("✓ Backup verified");
+            }
+
+            let seed = generate_seed_from_mnemonic(&mnemonic, args.passphrase.as_deref())
+                .context("Failed to generate seed from mnemonic")?;
+            let master_private = hex::encode(&seed[0..32]);
+            (mnemonic, master_private, "mnemonic")
         },
-        (Some(_), Some(_)) => {
-            return Err(anyhow::anyhow!("Cannot provide both --mnemonic and --master-private"));
+        _ => {
+            return Err(anyhow::anyhow!("Provide only one of --mnemonic, --master-private or --watch-only"));
         }
     };
 
-    println!("✓ Master private key derived");
-
-    // Check if account already exists
-    if let Some(_existing) = db.get_master_account_by_name(&args.name)? {
-        println!("❌ Account '{}' already exists.", args.name);
-        println!("   Use a different account name or delete the existing account first.");
-        return Ok(());
+    if source_type != "watch_only" {
+        println!("✓ Master private key derived");
     }
 
+    // A master key is already past the BIP-39 passphrase step
+    let passphrase = if args.master_private.is_some() { None } else { args.passphrase.as_deref() };
+
     // Create master account in database
     let master_id = db.create_master_account(
         &args.name,
         &normalized_mnemonic,
         &master_private_key,
-        args.passphrase.as_deref(),
+        passphrase,
     ).context("Failed to create master account in database")?;
 
     // Success message
@@ -72,13 +133,101 @@ pub fn execute(args: CreateMasterArgs, db: &Database) -> Result<()> {
     println!("   Account ID: {}", master_id);
     println!("   Next Account Index: 0 (ready for wallet groups)");
 
-    if args.passphrase.is_some() {
+    if passphrase.is_some() {
         println!("   Passphrase: ✓ (protected)");
     }
 
+    if source_type == "watch_only" {
+        println!("   Watch-only: ✓ ({} addresses only, nothing can be signed)", args.blockchain);
+    }
+
+    if args.mnemonic.is_none() && args.master_private.is_none() && args.watch_only.is_none() && !args.verify_backup {
+        println!("\n🔒 Generated Mnemonic ({} words):", args.words);
+        println!("   {}", normalized_mnemonic);
+        println!("\n⚠️  Write these words down now and store them offline.");
+        println!("   Use --verify-backup next time to be quizzed before the account is saved.");
+    }
+
     println!("\n💡 Next steps:");
     println!("   1. Create a wallet group: wallet-backup add-wallet-group --account \"{}\" --name \"PersonalWallet\"", args.name);
     println!("   2. List accounts: wallet-backup list-accounts");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// Generates a mnemonic from OS randomness, optionally mixed with dice rolls or coin flips
+fn generate_mnemonic(args: &CreateMasterArgs) -> Result<String> {
+    let entropy_len = entropy_length_for_words(args.words)?;
+    println!("🎲 Generating new {}-word mnemonic ({} bits of entropy)", args.words, entropy_len * 8);
+
+    let os_entropy = generate_os_entropy(entropy_len)?;
+
+    let user_input = match (&args.dice, &args.coins) {
+        (Some(rolls), _) => {
+            let rolls = normalize_dice_rolls(rolls)?;
+            println!("   Dice rolls: {} ({} rolls, ~{:.0} bits)", rolls, rolls.len(), rolls.len() as f64 * 6f64.log2());
+            Some(format!("dice:{}", rolls))
+        }
+        (None, Some(flips)) => {
+            let flips = normalize_coin_flips(flips)?;
+            println!("   Coin flips: {} ({} flips, {} bits)", flips, flips.len(), flips.len());
+            Some(format!("coins:{}", flips))
+        }
+        (None, None) => None,
+    };
+
+    let entropy = match user_input {
+        Some(input) => {
+            let mixed = mix_entropy(&os_entropy, &input);
+            println!("\n🔬 Entropy processing:");
+            println!("   1. OS entropy:     {}", hex::encode(&os_entropy));
+            println!("   2. User input:     \"{}\"", input);
+            println!("   3. SHA-256(1 || 2) truncated to {} bytes:", entropy_len);
+            println!("      {}", hex::encode(&mixed));
+            println!("   (hash the raw OS entropy bytes followed by the UTF-8 user input to reproduce this value)");
+            mixed
+        }
+        None => os_entropy,
+    };
+
+    let mnemonic = entropy_to_mnemonic(&entropy)?;
+    println!("✓ Mnemonic generated");
+    Ok(mnemonic)
+}
+
+// Shows the words once, clears the screen and asks for three random positions
+fn run_backup_quiz(mnemonic: &str) -> Result<bool> {
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+
+    println!("\n📝 Write down these words in order. They will be shown only once:\n");
+    for (i, word) in words.iter().enumerate() {
+        println!("   {:>2}. {}", i + 1, word);
+    }
+    println!();
+
+    if !confirm_action("Have you written down all words?")? {
+        return Ok(false);
+    }
+
+    // Clear the screen so the quiz cannot be answered by scrolling up
+    print!("\x1b[2J\x1b[H");
+
+    let mut positions: Vec<usize> = Vec::new();
+    while positions.len() < 3 {
+        let position = rand::random_range(0..words.len());
+        if !positions.contains(&position) {
+            positions.push(position);
+        }
+    }
+    positions.sort();
+
+    println!("🧠 Backup quiz");
+    for position in positions {
+        let answer = prompt_input(&format!("   Word #{}", position + 1))?;
+        if answer.trim().to_lowercase() != words[position] {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
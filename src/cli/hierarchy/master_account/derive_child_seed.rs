@@ -0,0 +1,118 @@
+use anyhow::{Result, Context, bail};
+use clap::Args;
+use crate::database::Database;
+use crate::crypto::bip85;
+use crate::crypto::bip39_utils::generate_seed_from_mnemonic;
+
+#[derive(Args)]
+pub struct DeriveChildSeedArgs {
+    #[arg(long, help = "Name of the parent master account")]
+    pub account: String,
+    #[arg(long, default_value = "0", help = "BIP-85 child index")]
+    pub index: u32,
+    #[arg(long, default_value = "mnemonic", help = "Output type: mnemonic, wif, xprv, hex, base64, base85")]
+    pub app: String,
+    #[arg(long, default_value = "12", help = "Mnemonic word count (12, 18, 24)")]
+    pub words: u32,
+    #[arg(long, default_value = "0", help = "BIP-85 language index (0=English, 1=Japanese, 2=Korean, 3=Spanish, 4=Chinese Simplified, 5=Chinese Traditional, 6=French, 7=Italian, 8=Czech, 9=Portuguese)")]
+    pub language: u32,
+    #[arg(long, help = "Hex entropy length in bytes (16-64, default 32) or password length (base64: 20-86, base85: 10-80, default 20)")]
+    pub length: Option<u32>,
+    #[arg(long, help = "Register the child mnemonic as a new linked master account with this name")]
+    pub register_as: Option<String>,
+}
+
+pub fn execute(args: DeriveChildSeedArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("🌱 Deriving BIP-85 child from account: {}", args.account);
+
+    let master_account = match db.get_master_account_by_name(&args.account)? {
+        Some(account) => account,
+        None => {
+            println!("❌ Master account '{}' not found.", args.account);
+            println!("   Use 'wallet-backup list-accounts' to see available accounts.");
+            return Ok(());
+        }
+    };
+
+    let app = args.app.to_lowercase();
+    if args.register_as.is_some() && app != "mnemonic" {
+        bail!("--register-as is only supported with --app mnemonic");
+    }
+
+    // BIP-85 is defined on the BIP-32 root, so xprv-backed accounts work as well
+    let master_key = master_account.bip32_root()?;
+
+    let (label, path, value) = match app.as_str() {
+        "mnemonic" | "bip39" => (
+            format!("BIP-39 mnemonic ({} words)", args.words),
+            format!("m/{}'/39'/{}'/{}'/{}'", bip85::BIP85_PURPOSE, args.language, args.words, args.index),
+            bip85::derive_mnemonic(&master_key, args.language, args.words, args.index)?,
+        ),
+        "wif" => (
+            "WIF private key".to_string(),
+            format!("m/{}'/2'/{}'", bip85::BIP85_PURPOSE, args.index),
+            bip85::derive_wif(&master_key, args.index)?,
+        ),
+        "xprv" => (
+            "Extended private key".to_string(),
+            format!("m/{}'/32'/{}'", bip85::BIP85_PURPOSE, args.index),
+            bip85::derive_xprv(&master_key, args.index)?,
+        ),
+        "hex" => {
+            let length = args.length.unwrap_or(32);
+            (
+                format!("Hex entropy ({} bytes)", length),
+                format!("m/{}'/128169'/{}'/{}'", bip85::BIP85_PURPOSE, length, args.index),
+                bip85::derive_hex(&master_key, length, args.index)?,
+            )
+        }
+        "base64" => {
+            let length = args.length.unwrap_or(20);
+            (
+                format!("Base64 password ({} chars)", length),
+                format!("m/{}'/707764'/{}'/{}'", bip85::BIP85_PURPOSE, length, args.index),
+                bip85::derive_password_base64(&master_key, length, args.index)?,
+            )
+        }
+        "base85" => {
+            let length = args.length.unwrap_or(20);
+            (
+                format!("Base85 password ({} chars)", length),
+                format!("m/{}'/707785'/{}'/{}'", bip85::BIP85_PURPOSE, length, args.index),
+                bip85::derive_password_base85(&master_key, length, args.index)?,
+            )
+        }
+        _ => bail!("Unsupported BIP-85 application: {}. Use mnemonic, wif, xprv, hex, base64 or base85", args.app),
+    };
+
+    println!("✓ Child derived");
+    println!("\n🔒 {}:", label);
+    println!("   Path: {}", path);
+    println!("   Value: {}", value);
+
+    if let Some(child_name) = &args.register_as {
+        if db.get_master_account_by_name(child_name)?.is_some() {
+            println!("\n❌ Account '{}' already exists. Child was not registered.", child_name);
+            return Ok(());
+        }
+
+        let child_seed = generate_seed_from_mnemonic(&value, None)
+            .context("Failed to generate seed from child mnemonic")?;
+        let child_master_private = hex::encode(&child_seed[0..32]);
+
+        let child_id = db.create_master_account(child_name, &value, &child_master_private, None)
+            .context("Failed to create child master account")?;
+        db.create_account_link(child_id, master_account.id.unwrap(), "bip85", args.index)?;
+
+        println!("\n🎉 Child account '{}' registered (ID: {})", child_name, child_id);
+        println!("   Parent: {} (BIP-85 index {})", args.account, args.index);
+    }
+
+    println!("\n⚠️  SECURITY WARNING:");
+    println!("   • Anyone with the parent mnemonic can re-derive this child");
+    println!("   • The child can be recreated at any time from the parent, index and parameters above");
+
+    Ok(())
+}
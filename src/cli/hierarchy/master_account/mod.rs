@@ -1,7 +1,9 @@
 pub mod create_master;
 pub mod list_accounts;
 pub mod show_account;
+pub mod derive_child_seed;
 
 pub use create_master::*;
 pub use list_accounts::*;
-pub use show_account::*;
\ No newline at end of file
+pub use show_account::*;
+pub use derive_child_seed::*;
\ No newline at end of file
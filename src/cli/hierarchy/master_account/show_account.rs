@@ -11,6 +11,10 @@ pub struct ShowAccountArgs {
 }
 
 pub fn execute(args: ShowAccountArgs, db: &Database) -> Result<()> {
+    if args.include_sensitive {
+        db.require_unlocked()?;
+    }
+
     println!("🔍 Master Account Details");
     println!("Account Name: {}", args.account);
 
@@ -31,6 +35,21 @@ pub fn execute(args: ShowAccountArgs, db: &Database) -> Result<()> {
     println!("   Next Account Index: {}", master_account.next_account_index);
     println!("   Created: {}", master_account.created_at.format("%Y-%m-%d %H:%M:%S UTC"));
     println!("   Updated: {}", master_account.updated_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    if !db.is_unlocked() {
+        println!("   Key Source: (unlock the vault to view)");
+    } else if master_account.is_watch_only() {
+        match master_account.watch_only_key() {
+            Ok(key) => println!("   Key Source: watch-only {} {} ({})", key.blockchain, key.prefix.as_str(), key.account_path()),
+            Err(_) => println!("   Key Source: watch-only (unreadable xpub record)"),
+        }
+    } else if master_account.uses_master_key() {
+        match master_account.master_key() {
+            Ok(master_key) => println!("   Key Source: {}", master_key.describe()),
+            Err(_) => println!("   Key Source: master private key (unsupported format, cannot derive wallets)"),
+        }
+    } else {
+        println!("   Key Source: BIP-39 mnemonic");
+    }
 
     if let Some(ref passphrase) = master_account.passphrase {
         if !passphrase.is_empty() {
@@ -42,6 +61,18 @@ pub fn execute(args: ShowAccountArgs, db: &Database) -> Result<()> {
         println!("   Passphrase: (none)");
     }
 
+    if let Some(link) = db.get_account_link(master_account.id.unwrap())? {
+        println!("   Derived From: {} ({} index {})", link.parent_account_name, link.link_type.to_uppercase(), link.derivation_index);
+    }
+
+    let child_accounts = db.list_child_accounts(master_account.id.unwrap())?;
+    if !child_accounts.is_empty() {
+        let names: Vec<String> = child_accounts.iter()
+            .map(|(name, _, index)| format!("{} (#{})", name, index))
+            .collect();
+        println!("   Child Accounts: {}", names.join(", "));
+    }
+
     // Get wallet groups count
     let wallet_groups = db.list_wallet_groups(master_account.id.unwrap())?;
     println!("   Wallet Groups: {}", wallet_groups.len());
@@ -76,9 +107,13 @@ pub fn execute(args: ShowAccountArgs, db: &Database) -> Result<()> {
     // Show sensitive information if requested
     if args.include_sensitive {
         println!("\n🔒 Sensitive Information:");
-        println!("   Mnemonic Phrase: {}", master_account.mnemonic);
+        if master_account.is_watch_only() {
+            println!("   Mnemonic Phrase: (none - watch-only account, no secret is stored)");
+        } else if !master_account.uses_master_key() {
+            println!("   Mnemonic Phrase: {}", master_account.mnemonic.expose()?);
+        }
 
-        if let Some(ref passphrase) = master_account.passphrase {
+        if let Some(passphrase) = master_account.passphrase_str()? {
             if !passphrase.is_empty() {
                 println!("   Passphrase: {}", passphrase);
             } else {
@@ -88,7 +123,12 @@ pub fn execute(args: ShowAccountArgs, db: &Database) -> Result<()> {
             println!("   Passphrase: (none)");
         }
 
-        println!("   Master Private Key: {}", master_account.master_private_key);
+        if master_account.is_watch_only() {
+            println!("   Master Private Key: (none - watch-only account)");
+            println!("   Extended Public Key: {}", master_account.watch_only_key()?.encoded());
+        } else {
+            println!("   Master Private Key: {}", master_account.master_private_key.expose()?);
+        }
 
         println!("\n⚠️  SECURITY WARNING:");
         println!("   • Never share your mnemonic phrase or private key with anyone");
@@ -97,7 +137,7 @@ pub fn execute(args: ShowAccountArgs, db: &Database) -> Result<()> {
 
         println!("\n💡 Import Instructions:");
         println!("   • Most wallets: Use the 12-24 word mnemonic phrase above");
-        if let Some(ref passphrase) = master_account.passphrase {
+        if let Some(passphrase) = master_account.passphrase_str()? {
             if !passphrase.is_empty() {
                 println!("   • Include passphrase: \"{}\" (25th word/additional security)", passphrase);
             }
@@ -8,6 +8,10 @@ pub mod subwallet;
 pub mod utility;
 pub mod rename;
 pub mod remove;
+pub mod vault;
+pub mod backup;
+pub mod shares;
+pub mod signing;
 
 // Re-export the command modules
 pub use master_account::*;
@@ -19,4 +23,8 @@ pub use address_group::*;
 pub use subwallet::*;
 pub use utility::*;
 pub use rename::*;
-pub use remove::*;
\ No newline at end of file
+pub use remove::*;
+pub use vault::*;
+pub use backup::*;
+pub use shares::*;
+pub use signing::*;
\ No newline at end of file
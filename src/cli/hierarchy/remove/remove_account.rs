@@ -7,7 +7,7 @@ use crate::crypto::validate_mnemonic_with_account;
 pub struct RemoveAccountArgs {
     #[arg(long, help = "Name of the master account to remove")]
     pub account: String,
-    #[arg(long, help = "Mnemonic phrase for verification (required for account removal)")]
+    #[arg(long, help = "Mnemonic phrase for verification (the xprv/SLIP-10 master key for key-backed accounts)")]
     pub mnemonic: String,
     #[arg(long, help = "Passphrase for mnemonic (if used during account creation)")]
     pub passphrase: Option<String>,
@@ -16,6 +16,8 @@ pub struct RemoveAccountArgs {
 }
 
 pub fn execute(args: RemoveAccountArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
     println!("🗑️  Removing master account");
     println!("Account: {}", args.account);
 
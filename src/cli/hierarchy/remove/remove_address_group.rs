@@ -22,6 +22,8 @@ pub struct RemoveAddressGroupArgs {
 }
 
 pub fn execute(args: RemoveAddressGroupArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
     println!("🗑️  Removing address group");
     println!("Account: {}", args.account);
     println!("Wallet Group: {}", args.wallet_group);
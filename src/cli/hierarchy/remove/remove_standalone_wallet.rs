@@ -15,6 +15,8 @@ pub struct RemoveStandaloneWalletArgs {
 }
 
 pub fn execute(args: RemoveStandaloneWalletArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
     println!("🗑️  Removing standalone wallet");
 
     let identifier = if let Some(ref name) = args.wallet {
@@ -56,7 +58,7 @@ pub fn execute(args: RemoveStandaloneWalletArgs, db: &Database) -> Result<()> {
     };
 
     // Verify private key matches the wallet
-    if wallet.private_key != args.private_key {
+    if wallet.private_key.expose()? != args.private_key {
         println!("\n❌ Invalid private key for standalone wallet.");
         println!("   The provided private key does not match this wallet.");
         return Ok(());
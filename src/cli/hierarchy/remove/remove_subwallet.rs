@@ -26,6 +26,8 @@ pub struct RemoveSubwalletArgs {
 }
 
 pub fn execute(args: RemoveSubwalletArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
     println!("🗑️  Removing subwallet");
     println!("Account: {}", args.account);
     println!("Wallet Group: {}", args.wallet_group);
@@ -22,6 +22,8 @@ pub struct RemoveWalletArgs {
 }
 
 pub fn execute(args: RemoveWalletArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
     println!("🗑️  Removing wallet");
     println!("Account: {}", args.account);
     println!("Wallet Group: {}", args.wallet_group);
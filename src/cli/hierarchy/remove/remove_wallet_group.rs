@@ -18,6 +18,8 @@ pub struct RemoveWalletGroupArgs {
 }
 
 pub fn execute(args: RemoveWalletGroupArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
     println!("🗑️  Removing wallet group");
     println!("Account: {}", args.account);
     println!("Wallet Group: {}", args.wallet_group);
@@ -0,0 +1,7 @@
+pub mod split_shares;
+pub mod recover_account;
+pub mod verify_shares;
+
+pub use split_shares::SplitSharesArgs;
+pub use recover_account::RecoverAccountArgs;
+pub use verify_shares::VerifySharesArgs;
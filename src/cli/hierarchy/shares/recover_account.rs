@@ -0,0 +1,64 @@
+use anyhow::{Result, Context};
+use clap::Args;
+use crate::database::Database;
+use crate::crypto::bip39_utils::{entropy_to_mnemonic, generate_seed_from_mnemonic};
+use crate::crypto::slip39;
+use crate::utils::prompt_secret_list;
+
+#[derive(Args)]
+pub struct RecoverAccountArgs {
+    #[arg(long, help = "Name for the recovered account")]
+    pub name: String,
+    #[arg(long = "share", help = "SLIP-39 share mnemonic (repeat for each share; prompted for if omitted)")]
+    pub shares: Vec<String>,
+    #[arg(long, help = "SLIP-39 passphrase used when the shares were created")]
+    pub share_passphrase: Option<String>,
+    #[arg(long, help = "Optional BIP-39 passphrase for the recovered account")]
+    pub passphrase: Option<String>,
+}
+
+pub fn execute(args: RecoverAccountArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("🧩 Recovering account from SLIP-39 shares: {}", args.name);
+
+    if db.get_master_account_by_name(&args.name)?.is_some() {
+        println!("❌ Account '{}' already exists.", args.name);
+        println!("   Use a different account name or delete the existing account first.");
+        return Ok(());
+    }
+
+    let shares = if args.shares.is_empty() {
+        prompt_secret_list("Enter SLIP-39 shares")?
+    } else {
+        args.shares.clone()
+    };
+
+    let entropy = slip39::combine_shares(&shares, args.share_passphrase.as_deref().unwrap_or(""))?;
+    println!("✓ {} share(s) recombined ({} bits of entropy)", shares.len(), entropy.len() * 8);
+
+    let mnemonic = entropy_to_mnemonic(&entropy)
+        .context("Recovered secret is not valid BIP-39 entropy")?;
+
+    let seed = generate_seed_from_mnemonic(&mnemonic, args.passphrase.as_deref())
+        .context("Failed to generate seed from mnemonic")?;
+    let master_private_key = hex::encode(&seed[0..32]);
+
+    let master_id = db.create_master_account(
+        &args.name,
+        &mnemonic,
+        &master_private_key,
+        args.passphrase.as_deref(),
+    ).context("Failed to create master account in database")?;
+
+    println!("\n🎉 Account recovered successfully!");
+    println!("   Account Name: {}", args.name);
+    println!("   Account ID: {}", master_id);
+    println!("   Mnemonic words: {}", mnemonic.split_whitespace().count());
+
+    println!("\n💡 Next steps:");
+    println!("   1. Create a wallet group: wallet-backup add-wallet-group --account \"{}\" --name \"PersonalWallet\"", args.name);
+    println!("   2. Or restore a full tree: wallet-backup restore --manifest <file> --name \"{}\"", args.name);
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anyhow::{Result, Context};
+use clap::Args;
+use crate::database::Database;
+use crate::crypto::bip39_utils::mnemonic_to_entropy;
+use crate::crypto::slip39::{self, ShareGroup};
+
+#[derive(Args)]
+pub struct SplitSharesArgs {
+    #[arg(long, help = "Name of the master account to split")]
+    pub account: String,
+    #[arg(long, value_delimiter = ',', required = true, help = "Share groups as M-of-N, comma separated (e.g. '2-of-3' or '2-of-3,3-of-5')")]
+    pub groups: Vec<String>,
+    #[arg(long, default_value = "1", help = "Number of groups required to recover the seed")]
+    pub group_threshold: u8,
+    #[arg(long, help = "Optional SLIP-39 passphrase protecting the shares (different from the BIP-39 passphrase)")]
+    pub share_passphrase: Option<String>,
+}
+
+pub fn execute(args: SplitSharesArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("🧩 Splitting account seed into SLIP-39 shares");
+    println!("Account: {}", args.account);
+
+    let master_account = match db.get_master_account_by_name(&args.account)? {
+        Some(account) => account,
+        None => {
+            println!("❌ Master account '{}' not found.", args.account);
+            println!("   Use 'wallet-backup list-accounts' to see available accounts.");
+            return Ok(());
+        }
+    };
+
+    let entropy = mnemonic_to_entropy(master_account.mnemonic.expose()?)
+        .context("This account has no BIP-39 mnemonic to split")?;
+
+    let groups = args.groups.iter()
+        .map(|spec| ShareGroup::parse(spec))
+        .collect::<Result<Vec<_>>>()?;
+
+    println!("✓ Entropy loaded ({} bits)", entropy.len() * 8);
+    println!("   Group threshold: {} of {}", args.group_threshold, groups.len());
+
+    let share_groups = slip39::split_secret(
+        &entropy,
+        args.group_threshold,
+        &groups,
+        args.share_passphrase.as_deref().unwrap_or(""),
+    )?;
+
+    for (group_index, (group, shares)) in groups.iter().zip(share_groups.iter()).enumerate() {
+        println!("\n📦 Group {} ({}-of-{})", group_index + 1, group.threshold, group.count);
+        for (share_index, share) in shares.iter().enumerate() {
+            println!("   Share {}: {}", share_index + 1, share);
+        }
+    }
+
+    println!("\n🎉 {} share(s) generated successfully!", share_groups.iter().map(|g| g.len()).sum::<usize>());
+
+    println!("\n⚠️  IMPORTANT:");
+    println!("   • Give each share to a different officer and never store enough shares together to recover the seed");
+    println!("   • Shares recombine to the BIP-39 entropy; the BIP-39 passphrase (if any) is NOT included");
+    if args.share_passphrase.is_some() {
+        println!("   • The SLIP-39 share passphrase is required for recovery - record it separately");
+    }
+
+    println!("\n💡 Next steps:");
+    println!("   • Check the shares: wallet-backup verify-shares --account \"{}\"", args.account);
+
+    Ok(())
+}
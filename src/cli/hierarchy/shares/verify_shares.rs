@@ -0,0 +1,52 @@
+use anyhow::{Result, Context, bail};
+use clap::Args;
+use crate::database::Database;
+use crate::crypto::bip39_utils::mnemonic_to_entropy;
+use crate::crypto::slip39;
+use crate::utils::prompt_secret_list;
+
+#[derive(Args)]
+pub struct VerifySharesArgs {
+    #[arg(long, help = "Name of the master account the shares belong to")]
+    pub account: String,
+    #[arg(long = "share", help = "SLIP-39 share mnemonic (repeat for each share; prompted for if omitted)")]
+    pub shares: Vec<String>,
+    #[arg(long, help = "SLIP-39 passphrase used when the shares were created")]
+    pub share_passphrase: Option<String>,
+}
+
+pub fn execute(args: VerifySharesArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("🔍 Verifying SLIP-39 shares for account: {}", args.account);
+
+    let master_account = match db.get_master_account_by_name(&args.account)? {
+        Some(account) => account,
+        None => {
+            println!("❌ Master account '{}' not found.", args.account);
+            return Ok(());
+        }
+    };
+
+    let stored_entropy = mnemonic_to_entropy(master_account.mnemonic.expose()?)
+        .context("This account has no BIP-39 mnemonic to compare against")?;
+
+    let shares = if args.shares.is_empty() {
+        prompt_secret_list("Enter SLIP-39 shares")?
+    } else {
+        args.shares.clone()
+    };
+
+    let recovered = slip39::combine_shares(&shares, args.share_passphrase.as_deref().unwrap_or(""))
+        .context("Shares could not be recombined")?;
+
+    if recovered != stored_entropy {
+        bail!(
+            "The shares recombine to a DIFFERENT seed than '{}'. Check that the shares and share passphrase belong to this account.",
+            args.account
+        );
+    }
+
+    println!("\n✅ The {} share(s) recombine to the stored seed of '{}'.", shares.len(), args.account);
+    Ok(())
+}
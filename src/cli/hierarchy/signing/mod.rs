@@ -0,0 +1,13 @@
+pub mod sign_psbt;
+pub mod sign_evm_tx;
+pub mod sign_cardano_tx;
+pub mod sign_message;
+pub mod verify_message;
+pub mod sign;
+
+pub use sign_psbt::*;
+pub use sign_evm_tx::*;
+pub use sign_cardano_tx::*;
+pub use sign_message::*;
+pub use verify_message::*;
+pub use sign::*;
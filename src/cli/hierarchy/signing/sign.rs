@@ -0,0 +1,190 @@
+use anyhow::{Result, Context, bail};
+use clap::Args;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::database::Database;
+use crate::blockchain::{get_blockchain_handler, BlockchainHandler, SupportedBlockchain};
+use crate::blockchain::cosmos::CosmosHandler;
+use crate::blockchain::stellar::StellarHandler;
+use crate::blockchain::stellar_tx::StellarNetwork;
+use super::sign_message::{message_bytes, print_message_bytes};
+
+#[derive(Args)]
+pub struct SignArgs {
+    #[arg(long, help = "Address or name of the wallet that signs", required_unless_present = "capabilities")]
+    pub wallet: Option<String>,
+    #[arg(long, help = "Message to sign with the chain's native message scheme", conflicts_with = "tx")]
+    pub message: Option<String>,
+    #[arg(long, help = "Treat --message as 0x-prefixed hex bytes instead of text", requires = "message")]
+    pub hex: bool,
+    #[arg(long, help = "Message format on chains with several (e.g. eip712, bip322-simple, bip322-full)", requires = "message")]
+    pub format: Option<String>,
+    #[arg(long, help = "Unsigned transaction in the chain's usual form (JSON, hex, base64 or PSBT), or a path to a file holding it")]
+    pub tx: Option<String>,
+    #[arg(long, help = "Stellar network: public, testnet, futurenet or a custom network passphrase [default: public]", requires = "tx")]
+    pub network: Option<String>,
+    #[arg(long, help = "Sign a Stellar transaction the wallet is not a source of, as an extra signer of a multisig account", requires = "tx")]
+    pub co_signer: bool,
+    #[arg(long, help = "Cosmos chain ID, required for a bare TxRaw and checked against sign docs", requires = "tx")]
+    pub chain_id: Option<String>,
+    #[arg(long, help = "Cosmos account number, required for a bare TxRaw and checked against sign docs", requires = "tx")]
+    pub account_number: Option<u64>,
+    #[arg(long, help = "Also write the signature or signed transaction to this file")]
+    pub output: Option<PathBuf>,
+    #[arg(long, help = "List which chains support message and transaction signing, then exit")]
+    pub capabilities: bool,
+    #[arg(long, help = "Skip confirmation prompt")]
+    pub yes: bool,
+}
+
+pub fn execute(args: SignArgs, db: &Database) -> Result<()> {
+    if args.capabilities {
+        return print_capabilities();
+    }
+    if args.message.is_none() && args.tx.is_none() {
+        bail!("Provide --message or --tx");
+    }
+
+    db.require_unlocked()?;
+
+    let identifier = args.wallet.as_deref().unwrap_or_default();
+    println!("✍️  Signing with wallet: {}", identifier);
+
+    let wallet = match db.find_wallet(identifier)? {
+        Some(wallet) => wallet,
+        None => {
+            println!("❌ Wallet '{}' not found.", identifier);
+            println!("   Use 'wallet-backup list-wallets' or 'list-standalone-wallets' to find the address or name.");
+            return Ok(());
+        }
+    };
+    if wallet.is_watch_only() {
+        bail!("Wallet {} is watch-only; there is no private key to sign with", wallet.address);
+    }
+
+    let blockchain = SupportedBlockchain::from_str(&wallet.blockchain)?;
+    let handler = transaction_handler(&blockchain, &args)?;
+    let capabilities = handler.signing_capabilities();
+
+    println!("\n📊 Signing Request:");
+    println!("   Wallet: {} ({})", wallet.label.as_deref().unwrap_or("unnamed"), wallet.address);
+    println!("   Blockchain: {}", blockchain);
+
+    let payload = if args.message.is_some() {
+        if !capabilities.sign_message {
+            bail!("Message signing is not supported for {} yet (see 'wallet-backup sign --capabilities')", blockchain);
+        }
+        let message = message_bytes(args.message.as_deref(), args.hex)?;
+        if let Some(format) = &args.format {
+            println!("   Format: {}", format);
+        }
+        print_message_bytes(&message);
+        message
+    } else {
+        if !capabilities.sign_transaction {
+            bail!("Transaction signing is not supported for {} yet (see 'wallet-backup sign --capabilities')", blockchain);
+        }
+        let tx = args.tx.as_deref().unwrap_or_default();
+        let transaction = if Path::new(tx).is_file() {
+            std::fs::read(tx).with_context(|| format!("Failed to read transaction file {}", tx))?
+        } else {
+            tx.trim().as_bytes().to_vec()
+        };
+        for (label, value) in handler.describe_transaction(&transaction)? {
+            println!("   {}: {}", label, value);
+        }
+        if args.co_signer {
+            println!("\n⚠️  Co-signing: the wallet signs even if the transaction does not name it as a source.");
+            println!("   Only continue if it is a signer of the source account and you expect to approve this transaction.");
+        }
+        transaction
+    };
+
+    if !args.yes {
+        let what = if args.message.is_some() { "message" } else { "transaction" };
+        print!("\nSign this {}? (y/N): ", what);
+        io::stdout().flush()?;
+        let mut confirm_input = String::new();
+        io::stdin().read_line(&mut confirm_input)?;
+        let confirmation = confirm_input.trim().to_lowercase();
+
+        if confirmation != "y" && confirmation != "yes" {
+            println!("❌ Signing cancelled.");
+            return Ok(());
+        }
+    }
+
+    let private_key = hex::decode(wallet.private_key.expose()?.trim_start_matches("0x"))
+        .context("Stored private key is not hex")?;
+
+    let result = if args.message.is_some() {
+        let signature = handler.sign_message(&private_key, &wallet.address, &payload, args.format.as_deref())?;
+        println!("\n🎉 Message signed!");
+        println!("   Signer: {}", wallet.address);
+        println!("   Signature: {}", signature);
+        signature
+    } else {
+        let signed = handler.sign_transaction(&private_key, &wallet.address, &payload)?;
+        println!("\n🎉 Transaction signed!");
+        if let Some(hash) = &signed.hash {
+            println!("   Transaction Hash: {}", hash);
+        }
+        println!("   Signed Transaction: {}", signed.encoded);
+        signed.encoded
+    };
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, format!("{}\n", result))
+            .with_context(|| format!("Failed to write result to {}", path.display()))?;
+        println!("   Written to: {}", path.display());
+    }
+
+    println!("\n💡 Next steps:");
+    if args.message.is_some() {
+        println!("   • Check it with 'wallet-backup verify-message --address {}' before handing it over", wallet.address);
+    } else {
+        println!("   • Broadcast the signed transaction from an online machine");
+    }
+
+    Ok(())
+}
+
+/// The chain's handler, configured with the chain-specific transaction options
+fn transaction_handler(blockchain: &SupportedBlockchain, args: &SignArgs) -> Result<Box<dyn BlockchainHandler>> {
+    if (args.network.is_some() || args.co_signer) && *blockchain != SupportedBlockchain::Stellar {
+        bail!("--network and --co-signer only apply to Stellar transactions");
+    }
+    if (args.chain_id.is_some() || args.account_number.is_some()) && *blockchain != SupportedBlockchain::Cosmos {
+        bail!("--chain-id and --account-number only apply to Cosmos transactions");
+    }
+
+    Ok(match blockchain {
+        SupportedBlockchain::Stellar => {
+            let network = match &args.network {
+                Some(network) => StellarNetwork::parse(network)?,
+                None => StellarNetwork::Public,
+            };
+            Box::new(StellarHandler::for_network(network, args.co_signer))
+        }
+        SupportedBlockchain::Cosmos => Box::new(CosmosHandler::for_chain(args.chain_id.clone(), args.account_number)),
+        _ => get_blockchain_handler(blockchain)?,
+    })
+}
+
+fn print_capabilities() -> Result<()> {
+    println!("✍️  Signing support by blockchain:\n");
+    println!("   {:<12} {:<10} {:<10} {:<12}", "Blockchain", "Message", "Verify", "Transaction");
+    for blockchain in SupportedBlockchain::all() {
+        let capabilities = get_blockchain_handler(&blockchain)?.signing_capabilities();
+        let mark = |supported: bool| if supported { "✓" } else { "–" };
+        println!(
+            "   {:<12} {:<10} {:<10} {:<12}",
+            blockchain.to_string(),
+            mark(capabilities.sign_message),
+            mark(capabilities.verify_message),
+            mark(capabilities.sign_transaction),
+        );
+    }
+    Ok(())
+}
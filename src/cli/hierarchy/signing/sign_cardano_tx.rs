@@ -0,0 +1,134 @@
+use anyhow::{Result, Context, bail};
+use clap::Args;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::database::{Database, Wallet};
+use crate::blockchain::SupportedBlockchain;
+use crate::blockchain::cardano::{stake_key_path, CardanoHandler};
+use crate::blockchain::cardano_tx::{address_credentials, CardanoTransaction};
+use crate::crypto::ed25519_utils::derive_cardano_key_from_mnemonic;
+
+#[derive(Args)]
+pub struct SignCardanoTxArgs {
+    #[arg(long, help = "Address or name of the Cardano wallet that signs")]
+    pub wallet: String,
+    #[arg(long, help = "Transaction body or full transaction as CBOR hex or a cardano-cli text envelope, or a path to a file holding it")]
+    pub tx: String,
+    #[arg(long, help = "Print only the new vkey witnesses as a witness set (CIP-30 signTx style) instead of the signed transaction")]
+    pub witness_set: bool,
+    #[arg(long, help = "Also write the signed transaction or witness set hex to this file")]
+    pub output: Option<PathBuf>,
+    #[arg(long, help = "Skip confirmation prompt")]
+    pub yes: bool,
+}
+
+pub fn execute(args: SignCardanoTxArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("✍️  Signing Cardano transaction with wallet: {}", args.wallet);
+
+    let wallet = match db.find_wallet(&args.wallet)? {
+        Some(wallet) => wallet,
+        None => {
+            println!("❌ Wallet '{}' not found.", args.wallet);
+            println!("   Use 'wallet-backup list-wallets' or 'list-standalone-wallets' to find the address or name.");
+            return Ok(());
+        }
+    };
+
+    if SupportedBlockchain::from_str(&wallet.blockchain)? != SupportedBlockchain::Cardano {
+        bail!("sign-cardano-tx needs a Cardano wallet, not {}", wallet.blockchain);
+    }
+    if wallet.is_watch_only() {
+        bail!("Wallet {} is watch-only; there is no private key to sign with", wallet.address);
+    }
+
+    let input = if Path::new(&args.tx).is_file() {
+        std::fs::read(&args.tx).with_context(|| format!("Failed to read transaction file {}", args.tx))?
+    } else {
+        args.tx.trim().as_bytes().to_vec()
+    };
+    let transaction = CardanoTransaction::parse(&input)?;
+
+    // Only the payment key is stored; the stake key is re-derived when the transaction needs it
+    let credentials = address_credentials(&wallet.address)?;
+    let needs_stake_key = credentials.stake.as_ref()
+        .is_some_and(|hash| transaction.requires_stake_witness(hash) && credentials.payment.as_ref() != Some(hash));
+
+    println!("\n📊 Transaction Details:");
+    println!("   Wallet: {} ({})", wallet.label.as_deref().unwrap_or("unnamed"), wallet.address);
+    for (label, value) in transaction.details()? {
+        println!("   {}: {}", label, value);
+    }
+    println!("   Witnesses: payment key{}", if needs_stake_key { " and stake key" } else { "" });
+
+    if !args.yes {
+        print!("\nSign this transaction? (y/N): ");
+        io::stdout().flush()?;
+        let mut confirm_input = String::new();
+        io::stdin().read_line(&mut confirm_input)?;
+        let confirmation = confirm_input.trim().to_lowercase();
+
+        if confirmation != "y" && confirmation != "yes" {
+            println!("❌ Signing cancelled.");
+            return Ok(());
+        }
+    }
+
+    let handler = if needs_stake_key {
+        CardanoHandler::with_stake_key(derive_stake_key(db, &wallet)?)
+    } else {
+        CardanoHandler::new()
+    };
+    let private_key = hex::decode(wallet.private_key.expose()?.trim_start_matches("0x"))
+        .context("Stored private key is not hex")?;
+    let witnesses = handler.witness_transaction(&private_key, &wallet.address, &transaction)?;
+
+    let result = if args.witness_set {
+        hex::encode(CardanoTransaction::witness_set(&witnesses))
+    } else {
+        hex::encode(transaction.signed(&witnesses)?)
+    };
+
+    println!("\n🎉 Transaction signed with {} witness{}!", witnesses.len(), if witnesses.len() == 1 { "" } else { "es" });
+    println!("   Transaction Hash: {}", hex::encode(transaction.hash()));
+    if args.witness_set {
+        println!("   Witness Set: {}", result);
+    } else {
+        println!("   Signed Transaction: {}", result);
+    }
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, format!("{}\n", result))
+            .with_context(|| format!("Failed to write result to {}", path.display()))?;
+        println!("   Written to: {}", path.display());
+    }
+
+    println!("\n💡 Next steps:");
+    if args.witness_set {
+        println!("   • Hand the witness set back to the wallet or dApp that assembles the transaction");
+    } else {
+        println!("   • Broadcast from an online machine: cardano-cli transaction submit, or a submit API");
+    }
+
+    Ok(())
+}
+
+/// Re-derives the CIP-1852 stake key (role 2) that sits next to the wallet's payment key
+fn derive_stake_key(db: &Database, wallet: &Wallet) -> Result<Vec<u8>> {
+    let account = db.get_master_account_for_wallet(wallet)?
+        .with_context(|| format!("Wallet {} has no master account to derive its stake key from", wallet.address))?;
+    if account.is_watch_only() || account.uses_master_key() {
+        bail!("Account '{}' has no mnemonic; Cardano stake keys can only be derived from one", account.name);
+    }
+
+    let payment_path = wallet.derivation_path.as_deref()
+        .with_context(|| format!("Wallet {} has no derivation path", wallet.address))?;
+    let (stake_key, _) = derive_cardano_key_from_mnemonic(
+        account.mnemonic.expose()?,
+        account.passphrase_str()?,
+        &stake_key_path(payment_path)?,
+    )?;
+    Ok(stake_key)
+}
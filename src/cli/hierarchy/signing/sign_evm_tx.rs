@@ -0,0 +1,105 @@
+use anyhow::{Result, Context, bail};
+use clap::Args;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use crate::database::Database;
+use crate::blockchain::SupportedBlockchain;
+use crate::blockchain::evm::{format_address, EvmTransaction};
+
+#[derive(Args)]
+pub struct SignEvmTxArgs {
+    #[arg(long, help = "Address or name of the EVM wallet that signs (wallet, subwallet or standalone wallet)")]
+    pub wallet: String,
+    #[arg(long, help = "Unsigned transaction as JSON, or a path to a JSON file")]
+    pub tx: String,
+    #[arg(long, help = "Also write the raw transaction hex to this file")]
+    pub output: Option<PathBuf>,
+    #[arg(long, help = "Skip confirmation prompt")]
+    pub yes: bool,
+}
+
+pub fn execute(args: SignEvmTxArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("✍️  Signing EVM transaction with wallet: {}", args.wallet);
+
+    let wallet = match db.find_wallet(&args.wallet)? {
+        Some(wallet) => wallet,
+        None => {
+            println!("❌ Wallet '{}' not found.", args.wallet);
+            println!("   Use 'wallet-backup list-wallets' or 'list-standalone-wallets' to find the address or name.");
+            return Ok(());
+        }
+    };
+
+    let blockchain = SupportedBlockchain::from_str(&wallet.blockchain)?;
+    if !blockchain.is_evm() {
+        bail!("sign-evm-tx needs an EVM wallet (ethereum, polygon, optimism, binance, cronos, xdc), not {}", blockchain);
+    }
+    if wallet.is_watch_only() {
+        bail!("Wallet {} is watch-only; there is no private key to sign with", wallet.address);
+    }
+
+    let json_text = if args.tx.trim_start().starts_with('{') {
+        args.tx.clone()
+    } else {
+        std::fs::read_to_string(&args.tx)
+            .with_context(|| format!("Failed to read transaction file {}", args.tx))?
+    };
+    let json: serde_json::Value = serde_json::from_str(&json_text).context("Transaction is not valid JSON")?;
+    let tx = EvmTransaction::from_json(&json)?;
+
+    println!("\n📊 Transaction Details:");
+    println!("   Wallet: {} ({})", wallet.label.as_deref().unwrap_or("unnamed"), wallet.address);
+    for (label, value) in tx.details(&blockchain) {
+        println!("   {}: {}", label, value);
+    }
+
+    let expected_chain_id = blockchain.evm_chain_id().unwrap_or_default();
+    if tx.chain_id != expected_chain_id {
+        println!("\n⚠️  Chain ID {} is not {} mainnet ({}); make sure this is the intended network.",
+            tx.chain_id, blockchain, expected_chain_id);
+    }
+
+    if !args.yes {
+        print!("\nSign this transaction? (y/N): ");
+        io::stdout().flush()?;
+        let mut confirm_input = String::new();
+        io::stdin().read_line(&mut confirm_input)?;
+        let confirmation = confirm_input.trim().to_lowercase();
+
+        if confirmation != "y" && confirmation != "yes" {
+            println!("❌ Signing cancelled.");
+            return Ok(());
+        }
+    }
+
+    let private_key = hex::decode(wallet.private_key.expose()?.trim_start_matches("0x"))
+        .context("Stored private key is not hex")?;
+    let signed = tx.sign(&private_key)?;
+
+    if format_address(&blockchain, &signed.from).to_lowercase() != wallet.address.to_lowercase() {
+        bail!("Signer {} does not match the wallet address {}", signed.from, wallet.address);
+    }
+
+    let raw_hex = format!("0x{}", hex::encode(&signed.raw));
+    if let Some(path) = &args.output {
+        std::fs::write(path, format!("{}\n", raw_hex))
+            .with_context(|| format!("Failed to write raw transaction to {}", path.display()))?;
+    }
+
+    println!("\n🎉 Transaction signed!");
+    println!("   From: {}", format_address(&blockchain, &signed.from));
+    println!("   Transaction Hash: 0x{}", hex::encode(signed.hash));
+    println!("   Raw Transaction: {}", raw_hex);
+    if let Some(path) = &args.output {
+        println!("   Written to: {}", path.display());
+    }
+
+    println!("\n💡 Next steps:");
+    println!("   • Broadcast from an online machine: eth_sendRawTransaction with the raw transaction");
+    println!("   • cast publish <raw> --rpc-url <url>, or paste it into the explorer's broadcast page");
+
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use anyhow::{Result, Context, bail};
+use clap::Args;
+use serde_json::Value;
+use std::io::{self, Write};
+
+use crate::database::{Database, Wallet};
+use crate::blockchain::{get_blockchain_handler, SupportedBlockchain};
+use crate::blockchain::eip712::TypedData;
+use crate::blockchain::evm::{get_evm_signer, parse_hex, EvmMessage};
+
+#[derive(Args)]
+pub struct SignMessageArgs {
+    #[arg(long, help = "Address or name of the wallet that signs")]
+    pub wallet: String,
+    #[arg(long, help = "Message to sign (EIP-191 on EVM chains, signmessage / BIP-322 on Bitcoin and Litecoin, off-chain message on Solana, SEP-53 on Stellar, ADR-036 on Cosmos, personal messages on Sui)", conflicts_with = "typed_data", required_unless_present = "typed_data")]
+    pub message: Option<String>,
+    #[arg(long, help = "Treat --message as 0x-prefixed hex bytes instead of text", requires = "message")]
+    pub hex: bool,
+    #[arg(long, help = "EIP-712 typed data as JSON, or a path to a JSON file (eth_signTypedData_v4)")]
+    pub typed_data: Option<String>,
+    #[arg(long, help = "Signature format on non-EVM chains, e.g. Bitcoin legacy (BIP-137), bip322-simple or bip322-full, or Solana offchain or raw [default: legacy, bip322-simple for Taproot, offchain]")]
+    pub format: Option<String>,
+    #[arg(long, help = "Skip confirmation prompt")]
+    pub yes: bool,
+}
+
+pub fn execute(args: SignMessageArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("✍️  Signing message with wallet: {}", args.wallet);
+
+    let wallet = match db.find_wallet(&args.wallet)? {
+        Some(wallet) => wallet,
+        None => {
+            println!("❌ Wallet '{}' not found.", args.wallet);
+            println!("   Use 'wallet-backup list-wallets' or 'list-standalone-wallets' to find the address or name.");
+            return Ok(());
+        }
+    };
+    if wallet.is_watch_only() {
+        bail!("Wallet {} is watch-only; there is no private key to sign with", wallet.address);
+    }
+
+    let blockchain = SupportedBlockchain::from_str(&wallet.blockchain)?;
+    let signer = match get_evm_signer(&blockchain) {
+        Some(signer) => signer,
+        // Everything else goes through the chain's BlockchainHandler::sign_message
+        None => return sign_with_handler(&args, &wallet, &blockchain),
+    };
+    if args.format.is_some() {
+        bail!("--format does not apply to EVM wallets; use --typed-data for EIP-712");
+    }
+
+    let message = evm_message_from_args(args.message.as_deref(), args.hex, args.typed_data.as_deref())?;
+
+    println!("\n📊 Message Details:");
+    println!("   Wallet: {} ({})", wallet.label.as_deref().unwrap_or("unnamed"), wallet.address);
+    print_evm_message(&message);
+    if let EvmMessage::TypedData(typed_data) = &message {
+        if let (Some(domain_chain_id), Some(wallet_chain_id)) = (typed_data.domain_chain_id(), blockchain.evm_chain_id()) {
+            if domain_chain_id != wallet_chain_id {
+                println!("\n⚠️  Domain chain ID {} is not {} mainnet ({}); make sure this is the intended network.",
+                    domain_chain_id, blockchain, wallet_chain_id);
+            }
+        }
+    }
+
+    if !args.yes {
+        print!("\nSign this message? (y/N): ");
+        io::stdout().flush()?;
+        let mut confirm_input = String::new();
+        io::stdin().read_line(&mut confirm_input)?;
+        let confirmation = confirm_input.trim().to_lowercase();
+
+        if confirmation != "y" && confirmation != "yes" {
+            println!("❌ Signing cancelled.");
+            return Ok(());
+        }
+    }
+
+    let private_key = hex::decode(wallet.private_key.expose()?.trim_start_matches("0x"))
+        .context("Stored private key is not hex")?;
+    let signature = signer.sign_evm_message(&private_key, &message)?;
+
+    println!("\n🎉 Message signed!");
+    println!("   Signer: {}", signer.recover_evm_signer(&message, &signature)?);
+    println!("   Hash: 0x{}", hex::encode(message.signing_hash()?));
+    println!("   Signature: 0x{}", hex::encode(signature));
+
+    println!("\n💡 Next steps:");
+    println!("   • Check it with 'wallet-backup verify-message' before handing it over");
+
+    Ok(())
+}
+
+fn sign_with_handler(args: &SignMessageArgs, wallet: &Wallet, blockchain: &SupportedBlockchain) -> Result<()> {
+    if args.typed_data.is_some() {
+        bail!("EIP-712 typed data can only be signed by EVM wallets");
+    }
+    let handler = get_blockchain_handler(blockchain)?;
+    if !handler.signing_capabilities().sign_message {
+        bail!("Message signing is not supported for {} wallets yet", blockchain);
+    }
+    let message = message_bytes(args.message.as_deref(), args.hex)?;
+
+    println!("\n📊 Message Details:");
+    println!("   Wallet: {} ({})", wallet.label.as_deref().unwrap_or("unnamed"), wallet.address);
+    if let Some(format) = &args.format {
+        println!("   Format: {}", format);
+    }
+    print_message_bytes(&message);
+
+    if !args.yes {
+        print!("\nSign this message? (y/N): ");
+        io::stdout().flush()?;
+        let mut confirm_input = String::new();
+        io::stdin().read_line(&mut confirm_input)?;
+        let confirmation = confirm_input.trim().to_lowercase();
+
+        if confirmation != "y" && confirmation != "yes" {
+            println!("❌ Signing cancelled.");
+            return Ok(());
+        }
+    }
+
+    let private_key = hex::decode(wallet.private_key.expose()?.trim_start_matches("0x"))
+        .context("Stored private key is not hex")?;
+    let signature = handler.sign_message(&private_key, &wallet.address, &message, args.format.as_deref())?;
+
+    println!("\n🎉 Message signed!");
+    println!("   Signer: {}", wallet.address);
+    println!("   Signature: {}", signature);
+
+    println!("\n💡 Next steps:");
+    println!("   • Check it with 'wallet-backup verify-message --address {}' before handing it over", wallet.address);
+
+    Ok(())
+}
+
+/// Raw message bytes from --message, decoding it when --hex is set
+pub fn message_bytes(message: Option<&str>, hex: bool) -> Result<Vec<u8>> {
+    let message = message.context("Provide --message")?;
+    if hex {
+        parse_hex(message).context("--message is not valid hex")
+    } else {
+        Ok(message.as_bytes().to_vec())
+    }
+}
+
+/// Builds the EIP-191 or EIP-712 message described by the --message/--hex/--typed-data options
+pub fn evm_message_from_args(message: Option<&str>, hex: bool, typed_data: Option<&str>) -> Result<EvmMessage> {
+    if let Some(typed_data) = typed_data {
+        let json_text = if typed_data.trim_start().starts_with('{') {
+            typed_data.to_string()
+        } else {
+            std::fs::read_to_string(typed_data)
+                .with_context(|| format!("Failed to read typed data file {}", typed_data))?
+        };
+        let json: Value = serde_json::from_str(&json_text).context("Typed data is not valid JSON")?;
+        return Ok(EvmMessage::TypedData(TypedData::from_json(&json)?));
+    }
+
+    if message.is_none() {
+        bail!("Provide --message or --typed-data");
+    }
+    Ok(EvmMessage::Personal(message_bytes(message, hex)?))
+}
+
+pub fn print_evm_message(message: &EvmMessage) {
+    match message {
+        EvmMessage::Personal(bytes) => {
+            println!("   Standard: EIP-191 (personal_sign)");
+            print_message_bytes(bytes);
+        }
+        EvmMessage::TypedData(typed_data) => {
+            println!("   Standard: EIP-712 (typed data)");
+            println!("   Domain:");
+            if let Some(domain) = typed_data.domain.as_object() {
+                for (name, value) in domain {
+                    println!("      {}: {}", name, display_value(value));
+                }
+            }
+            println!("   Primary Type: {}", typed_data.primary_type);
+            println!("   Message:");
+            let pretty = serde_json::to_string_pretty(&typed_data.message).unwrap_or_default();
+            for line in pretty.lines() {
+                println!("      {}", line);
+            }
+        }
+    }
+}
+
+pub fn print_message_bytes(bytes: &[u8]) {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => println!("   Message: {}", text),
+        Err(_) => println!("   Message (hex): 0x{}", hex::encode(bytes)),
+    }
+    println!("   Length: {} bytes", bytes.len());
+}
+
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
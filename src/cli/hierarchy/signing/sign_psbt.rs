@@ -0,0 +1,247 @@
+use anyhow::{Result, Context, bail};
+use clap::Args;
+use bitcoin::bip32::{DerivationPath, Fingerprint, Xpriv};
+use bitcoin::secp256k1::Secp256k1;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::database::{Database, Wallet};
+use crate::blockchain::SupportedBlockchain;
+use crate::crypto::bip32::private_key_to_public_key_secp256k1;
+use crate::crypto::psbt::{
+    decode_psbt, encode_psbt, format_amount, input_key_origins, input_signature_count, is_change_output,
+    script_address, sign_psbt, summarize_psbt, PsbtSigningKeys,
+};
+
+#[derive(Args)]
+pub struct SignPsbtArgs {
+    #[arg(long, help = "PSBT file to sign (base64 text or binary BIP-174)")]
+    pub psbt: PathBuf,
+    #[arg(long, help = "Where to write the signed PSBT (default: <name>.signed.<ext> next to the input)")]
+    pub output: Option<PathBuf>,
+    #[arg(long, help = "Only use wallets of this master account")]
+    pub account: Option<String>,
+    #[arg(long, help = "Skip confirmation prompt")]
+    pub yes: bool,
+}
+
+// An input key that belongs to a wallet in the vault
+struct MatchedKey {
+    input: usize,
+    account: String,
+    wallet: String,
+    path: String,
+    blockchain: SupportedBlockchain,
+}
+
+pub fn execute(args: SignPsbtArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("✍️  Signing PSBT: {}", args.psbt.display());
+
+    let data = std::fs::read(&args.psbt)
+        .with_context(|| format!("Failed to read PSBT file {}", args.psbt.display()))?;
+    let (mut psbt, encoding) = decode_psbt(&data)?;
+
+    let origins = input_key_origins(&psbt);
+    if origins.is_empty() {
+        println!("❌ The PSBT has no BIP-32 derivation entries for its inputs.");
+        println!("   Ask the wallet that created it to include key origins (fingerprint and path).");
+        return Ok(());
+    }
+    let fingerprints: BTreeSet<Fingerprint> = origins.iter().map(|origin| origin.source.0).collect();
+
+    let account_names = match &args.account {
+        Some(name) => vec![name.clone()],
+        None => db.list_master_accounts()?.into_iter().map(|account| account.name).collect(),
+    };
+
+    let secp = Secp256k1::new();
+    let mut keys = PsbtSigningKeys::default();
+    let mut matched = Vec::new();
+    let mut own_roots: BTreeMap<Fingerprint, Xpriv> = BTreeMap::new();
+
+    for name in account_names {
+        let master_account = match db.get_master_account_by_name(&name)? {
+            Some(account) => account,
+            None => {
+                println!("❌ Master account '{}' not found.", name);
+                println!("   Use 'wallet-backup list-accounts' to see available accounts.");
+                return Ok(());
+            }
+        };
+
+        // Watch-only and SLIP-10 accounts have no BIP-32 root to sign with
+        let root = match master_account.bip32_root() {
+            Ok(root) => root,
+            Err(_) => continue,
+        };
+        let fingerprint = root.fingerprint(&secp);
+        if !fingerprints.contains(&fingerprint) {
+            continue;
+        }
+        own_roots.insert(fingerprint, root);
+
+        let tree = db.get_account_tree(&name)?
+            .with_context(|| format!("Master account '{}' disappeared while signing", name))?;
+        let wallets = tree.wallet_groups.iter()
+            .flat_map(|group| group.wallets.iter())
+            .flat_map(|base| std::iter::once(&base.wallet)
+                .chain(base.address_groups.iter().flat_map(|ag| ag.subwallets.iter())));
+
+        for wallet in wallets {
+            let blockchain = match SupportedBlockchain::from_str(&wallet.blockchain) {
+                Ok(chain @ (SupportedBlockchain::Bitcoin | SupportedBlockchain::Litecoin)) => chain,
+                _ => continue,
+            };
+            let path = match wallet.derivation_path.as_deref().map(DerivationPath::from_str) {
+                Some(Ok(path)) => path,
+                _ => continue,
+            };
+
+            for origin in origins.iter().filter(|origin| origin.source == (fingerprint, path.clone())) {
+                let secret = hex::decode(wallet.private_key.expose()?.trim_start_matches("0x"))
+                    .with_context(|| format!("Stored private key of {} is not hex", wallet.address))?;
+                if !origin.matches_public_key(&private_key_to_public_key_secp256k1(&secret)?) {
+                    println!("⚠️  Input #{}: wallet {} has path {} but a different public key; skipping",
+                        origin.input, wallet_name(wallet), origin.source.1);
+                    continue;
+                }
+
+                keys.insert(origin.source.clone(), &secret)?;
+                matched.push(MatchedKey {
+                    input: origin.input,
+                    account: name.clone(),
+                    wallet: wallet_name(wallet),
+                    path: wallet.derivation_path.clone().unwrap_or_default(),
+                    blockchain: blockchain.clone(),
+                });
+            }
+        }
+    }
+
+    if keys.is_empty() {
+        println!("❌ None of the PSBT inputs belong to wallets in this vault.");
+        let listed: Vec<String> = fingerprints.iter().map(|fingerprint| fingerprint.to_string()).collect();
+        println!("   Fingerprints requested by the PSBT: {}", listed.join(", "));
+        println!("   Add the wallets at the requested paths with 'wallet-backup add-wallet' or 'add-subwallet' first.");
+        return Ok(());
+    }
+
+    let chains: BTreeSet<String> = matched.iter().map(|key| key.blockchain.to_string()).collect();
+    if chains.len() > 1 {
+        bail!("PSBT inputs match wallets on several chains ({}); refusing to sign", chains.into_iter().collect::<Vec<_>>().join(", "));
+    }
+    let blockchain = matched[0].blockchain.clone();
+    let unit = if blockchain == SupportedBlockchain::Litecoin { "LTC" } else { "BTC" };
+
+    let mut by_input: BTreeMap<usize, Vec<&MatchedKey>> = BTreeMap::new();
+    for key in &matched {
+        by_input.entry(key.input).or_default().push(key);
+    }
+
+    // Summary before signing
+    let summary = summarize_psbt(&psbt)?;
+    println!("\n📊 Transaction Summary ({}):", blockchain);
+    println!("   Inputs:");
+    for (index, tx_input) in psbt.unsigned_tx.input.iter().enumerate() {
+        let amount = match summary.inputs[index] {
+            Some(value) if summary.unverified_inputs.contains(&index) => format!("{}, ⚠️ amount unverified", format_amount(value, unit)),
+            Some(value) => format_amount(value, unit),
+            None => "amount unknown".to_string(),
+        };
+        println!("   #{} {} ({})", index, tx_input.previous_output, amount);
+        match by_input.get(&index) {
+            Some(keys) => {
+                for key in keys {
+                    println!("      ✓ {} / {} ({})", key.account, key.wallet, key.path);
+                }
+            }
+            None => println!("      – not signed by this vault"),
+        }
+    }
+
+    println!("   Outputs:");
+    for (index, (script, value)) in summary.outputs.iter().enumerate() {
+        let destination = script_address(script, &blockchain)
+            .unwrap_or_else(|| format!("script {}", hex::encode(script.as_bytes())));
+        let change = is_change_output(&psbt, index, &own_roots);
+        println!("   #{} {} {}{}", index, destination, format_amount(*value, unit), if change { " (change)" } else { "" });
+    }
+
+    println!("   Total Out: {}", format_amount(summary.total_out()?, unit));
+    match summary.fee()? {
+        Some(fee) => println!("   Fee: {}", format_amount(fee, unit)),
+        None if summary.total_in()?.is_some() => println!("   ⚠️  Fee: outputs exceed inputs - the transaction is invalid"),
+        None => println!("   ⚠️  Fee: unknown - some inputs are missing their UTXO data"),
+    }
+    if !summary.unverified_inputs.is_empty() {
+        println!("   ⚠️  Amounts marked unverified come from witness_utxo only; the PSBT creator could have lied about them");
+        println!("      (CVE-2020-14199). Ask the wallet to include the previous transactions (non_witness_utxo).");
+    }
+
+    if !args.yes {
+        print!("\nSign {} input(s)? (y/N): ", by_input.len());
+        io::stdout().flush()?;
+        let mut confirm_input = String::new();
+        io::stdin().read_line(&mut confirm_input)?;
+        let confirmation = confirm_input.trim().to_lowercase();
+
+        if confirmation != "y" && confirmation != "yes" {
+            println!("❌ Signing cancelled.");
+            return Ok(());
+        }
+    }
+
+    let before: Vec<usize> = psbt.inputs.iter().map(input_signature_count).collect();
+    let errors = sign_psbt(&mut psbt, &keys);
+
+    let mut signed = 0;
+    println!();
+    for index in by_input.keys() {
+        if input_signature_count(&psbt.inputs[*index]) > before[*index] {
+            signed += 1;
+            println!("✓ Input #{} signed", index);
+        } else {
+            let reason = errors.get(index).map(String::as_str).unwrap_or("no signature was produced");
+            println!("❌ Input #{} not signed: {}", index, reason);
+        }
+    }
+
+    if signed == 0 {
+        println!("\n❌ No inputs were signed; the PSBT was not written.");
+        return Ok(());
+    }
+
+    let output_path = args.output.clone().unwrap_or_else(|| default_output_path(&args.psbt));
+    std::fs::write(&output_path, encode_psbt(&psbt, encoding))
+        .with_context(|| format!("Failed to write signed PSBT to {}", output_path.display()))?;
+
+    println!("\n🎉 Signed {} of {} input(s)", signed, psbt.inputs.len());
+    println!("   Signed PSBT: {}", output_path.display());
+
+    println!("\n💡 Next steps:");
+    println!("   • Finalize and broadcast from the wallet that created the PSBT (Sparrow, Electrum, ...)");
+    println!("   • Bitcoin Core: finalizepsbt \"<psbt>\" then sendrawtransaction \"<hex>\"");
+    if signed < psbt.inputs.len() {
+        println!("   • Remaining inputs need signatures from their other key holders");
+    }
+
+    Ok(())
+}
+
+fn wallet_name(wallet: &Wallet) -> String {
+    wallet.label.clone().unwrap_or_else(|| wallet.address.clone())
+}
+
+// tx.psbt -> tx.signed.psbt
+fn default_output_path(input: &Path) -> PathBuf {
+    let stem = input.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_else(|| "psbt".to_string());
+    let file_name = match input.extension() {
+        Some(extension) => format!("{}.signed.{}", stem, extension.to_string_lossy()),
+        None => format!("{}.signed", stem),
+    };
+    input.with_file_name(file_name)
+}
@@ -0,0 +1,156 @@
+use anyhow::{Result, Context, bail};
+use clap::Args;
+
+use crate::database::Database;
+use crate::blockchain::{get_blockchain_handler, SupportedBlockchain};
+use crate::blockchain::evm::{get_evm_signer, parse_hex};
+use crate::blockchain::stellar_tx::decode_account_id;
+use super::sign_message::{evm_message_from_args, message_bytes, print_evm_message, print_message_bytes};
+
+#[derive(Args)]
+pub struct VerifyMessageArgs {
+    #[arg(long, help = "Signed message (EIP-191, signmessage / BIP-137 or BIP-322)", conflicts_with = "typed_data", required_unless_present = "typed_data")]
+    pub message: Option<String>,
+    #[arg(long, help = "Treat --message as 0x-prefixed hex bytes instead of text", requires = "message")]
+    pub hex: bool,
+    #[arg(long, help = "Signed EIP-712 typed data as JSON, or a path to a JSON file")]
+    pub typed_data: Option<String>,
+    #[arg(long, help = "Signature in the chain's usual form: 0x-prefixed hex (r || s || v) for EVM chains, base64 for Bitcoin, Litecoin and Stellar, base58 for Solana, StdSignature JSON or base64 for Cosmos, DataSignature JSON for Cardano, serialized base64 for Sui")]
+    pub signature: String,
+    #[arg(long, help = "Expected signer address (optional for EVM chains, required elsewhere)")]
+    pub address: Option<String>,
+    #[arg(long, help = "Chain of the signer (default: inferred from --address, otherwise ethereum)")]
+    pub blockchain: Option<String>,
+}
+
+pub fn execute(args: VerifyMessageArgs, db: &Database) -> Result<()> {
+    println!("🔍 Verifying message signature");
+
+    // An xdc-prefixed expected address implies the XDC display format
+    let blockchain = match (&args.address, &args.blockchain) {
+        (Some(address), _) if address.starts_with("xdc") => SupportedBlockchain::XDC,
+        (_, Some(name)) => SupportedBlockchain::from_str(name)?,
+        (Some(address), None) => address_blockchain(address),
+        (None, None) => SupportedBlockchain::Ethereum,
+    };
+    let signer = match get_evm_signer(&blockchain) {
+        Some(signer) => signer,
+        // Everything else goes through the chain's BlockchainHandler::verify_message
+        None => return verify_with_handler(&args, &blockchain, db),
+    };
+
+    let message = evm_message_from_args(args.message.as_deref(), args.hex, args.typed_data.as_deref())?;
+    let signature = parse_hex(&args.signature).context("--signature is not valid hex")?;
+
+    println!("\n📊 Message Details:");
+    print_evm_message(&message);
+
+    let recovered = match signer.recover_evm_signer(&message, &signature) {
+        Ok(address) => address,
+        Err(e) => {
+            println!("\n❌ Invalid signature: {}", e);
+            return Ok(());
+        }
+    };
+
+    if let Some(expected) = &args.address {
+        if !recovered.eq_ignore_ascii_case(expected.trim()) {
+            println!("\n❌ Signature is NOT from {}", expected);
+            println!("   It was produced by: {}", recovered);
+            return Ok(());
+        }
+    }
+
+    println!("\n✓ Signature is valid");
+    println!("   Signer: {}", recovered);
+
+    // The same key has the same address on every EVM chain; XDC wallets store it with an xdc prefix
+    let xdc_form = recovered.replacen("0x", "xdc", 1);
+    let evm_form = recovered.replacen("xdc", "0x", 1);
+    let stored = match db.find_wallet(&evm_form)? {
+        Some(wallet) => Some(wallet),
+        None => db.find_wallet(&xdc_form)?,
+    };
+    match stored {
+        Some(wallet) => println!(
+            "   Stored Wallet: {} ({}, {})",
+            wallet.label.as_deref().unwrap_or("unnamed"),
+            wallet.blockchain,
+            wallet.address
+        ),
+        None => println!("   Stored Wallet: (none - the signer is not in this vault)"),
+    }
+
+    Ok(())
+}
+
+fn verify_with_handler(args: &VerifyMessageArgs, blockchain: &SupportedBlockchain, db: &Database) -> Result<()> {
+    if args.typed_data.is_some() {
+        bail!("EIP-712 typed data can only be verified for EVM chains");
+    }
+    let handler = get_blockchain_handler(blockchain)?;
+    if !handler.signing_capabilities().verify_message {
+        bail!("Message verification is not supported for {} yet", blockchain);
+    }
+    // Most schemes (BIP-322, ed25519) cannot name the signer from the signature alone
+    let address = args.address.as_deref()
+        .with_context(|| format!("--address is required to verify {} signatures", blockchain))?;
+    let message = message_bytes(args.message.as_deref(), args.hex)?;
+
+    println!("\n📊 Message Details:");
+    println!("   Blockchain: {}", blockchain);
+    println!("   Address: {}", address);
+    print_message_bytes(&message);
+
+    let scheme = match handler.verify_message(address, &message, &args.signature, None) {
+        Ok(scheme) => scheme,
+        Err(e) => {
+            println!("\n❌ Signature is NOT valid for {}: {}", address, e);
+            return Ok(());
+        }
+    };
+
+    println!("\n✓ Signature is valid");
+    println!("   Signer: {}", address);
+    println!("   Format: {}", scheme);
+    match db.find_wallet(address)? {
+        Some(wallet) => println!(
+            "   Stored Wallet: {} ({}, {})",
+            wallet.label.as_deref().unwrap_or("unnamed"),
+            wallet.blockchain,
+            wallet.address
+        ),
+        None => println!("   Stored Wallet: (none - the signer is not in this vault)"),
+    }
+
+    Ok(())
+}
+
+/// Guesses the chain from an address format when --blockchain is not given
+fn address_blockchain(address: &str) -> SupportedBlockchain {
+    let address = address.trim();
+    if address.starts_with("0x") && address.len() == 66 {
+        // 32-byte Sui addresses; EVM addresses are 20 bytes
+        SupportedBlockchain::Sui
+    } else if address.starts_with("0x") {
+        SupportedBlockchain::Ethereum
+    } else if decode_account_id(address).is_ok() {
+        SupportedBlockchain::Stellar
+    } else if bs58::decode(address).into_vec().is_ok_and(|bytes| bytes.len() == 25 && bytes[0] == 0x41) {
+        // TRON T... addresses carry the 0x41 version byte
+        SupportedBlockchain::Tron
+    } else if bs58::decode(address).into_vec().is_ok_and(|bytes| bytes.len() == 32) {
+        // Base58 public keys; legacy Bitcoin/Litecoin addresses decode to 25 bytes
+        SupportedBlockchain::Solana
+    } else if ["addr1", "addr_test1", "stake1", "stake_test1"].iter().any(|prefix| address.starts_with(prefix)) {
+        // Cardano bech32 addresses, checked before the generic bech32 (Cosmos) case
+        SupportedBlockchain::Cardano
+    } else if bech32::decode(address).is_ok_and(|(hrp, _)| !["bc", "tb", "bcrt", "ltc", "tltc"].contains(&hrp.as_str())) {
+        // Bech32 account addresses of Cosmos SDK chains (cosmos1..., osmo1...)
+        SupportedBlockchain::Cosmos
+    } else if address.to_lowercase().starts_with("ltc1") || address.starts_with('L') || address.starts_with('M') {
+        SupportedBlockchain::Litecoin
+    } else {
+        SupportedBlockchain::Bitcoin
+    }
+}
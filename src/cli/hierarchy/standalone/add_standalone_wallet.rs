@@ -2,12 +2,12 @@ use anyhow::{Result, Context};
 use clap::Args;
 use chrono::Utc;
 
-use crate::database::{Database, Wallet};
+use crate::database::{Database, Wallet, WALLET_SECRET_SEED};
 use crate::blockchain::{SupportedBlockchain, get_blockchain_handler};
 
 #[derive(Args)]
 pub struct AddStandaloneWalletArgs {
-    #[arg(long, help = "Private key for the wallet (hex format)")]
+    #[arg(long, help = "Private key for the wallet (hex format, an XRP family seed s..., a Sui suiprivkey1..., or an Algorand 25-word mnemonic)")]
     pub private_key: String,
     #[arg(long, help = "Blockchain for the wallet (e.g., 'bitcoin', 'ethereum', 'solana')")]
     pub blockchain: String,
@@ -18,6 +18,8 @@ pub struct AddStandaloneWalletArgs {
 }
 
 pub fn execute(args: AddStandaloneWalletArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
     println!("Adding standalone wallet: {}", args.name);
     println!("Blockchain: {}", args.blockchain);
 
@@ -58,7 +60,7 @@ pub fn execute(args: AddStandaloneWalletArgs, db: &Database) -> Result<()> {
         blockchain: blockchain.to_string(),
         address: wallet_keys.address.clone(),
         address_with_checksum: wallet_keys.address_with_checksum.clone(),
-        private_key: wallet_keys.private_key,
+        private_key: wallet_keys.private_key.into(),
         public_key: Some(wallet_keys.public_key),
         derivation_path: None, // No derivation path for imported private key
         label: Some(args.name.clone()),
@@ -72,6 +74,9 @@ pub fn execute(args: AddStandaloneWalletArgs, db: &Database) -> Result<()> {
 
     // Insert into database
     let wallet_id = db.create_wallet(&wallet)?;
+    if let Some(seed) = &wallet_keys.secret_seed {
+        db.set_wallet_secret(wallet_id, WALLET_SECRET_SEED, seed)?;
+    }
 
     // Success message
     println!("\n🎉 Standalone wallet created successfully!");
@@ -90,6 +95,10 @@ pub fn execute(args: AddStandaloneWalletArgs, db: &Database) -> Result<()> {
         println!("   Explorer: {}", explorer);
     }
 
+    if wallet_keys.secret_seed.is_some() {
+        println!("   Family Seed: stored (view with show-standalone-wallet --include-sensitive)");
+    }
+
     if let Some(notes) = &args.notes {
         println!("   Notes: {}", notes);
     }
@@ -1,6 +1,8 @@
 use anyhow::{Result, Context};
 use clap::Args;
-use crate::database::Database;
+use crate::database::{Database, WALLET_SECRET_SEED};
+use crate::blockchain::algorand::export_mnemonic;
+use crate::blockchain::sui::export_private_key as export_sui_private_key;
 
 #[derive(Args)]
 pub struct ShowStandaloneWalletArgs {
@@ -13,6 +15,10 @@ pub struct ShowStandaloneWalletArgs {
 }
 
 pub fn execute(args: ShowStandaloneWalletArgs, db: &Database) -> Result<()> {
+    if args.include_sensitive {
+        db.require_unlocked()?;
+    }
+
     println!("📱 Standalone Wallet Details");
 
     // Validate that either name or address is provided
@@ -155,7 +161,30 @@ pub fn execute(args: ShowStandaloneWalletArgs, db: &Database) -> Result<()> {
     if args.include_sensitive {
         println!("\n🔒 Sensitive Information");
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-        println!("   🔑 Private Key: {}", wallet.private_key);
+        let private_key = wallet.private_key.expose()?;
+        println!("   🔑 Private Key: {}", private_key);
+
+        // XRP family seeds cannot be recomputed from a key, so only imported seeds can be shown
+        if wallet.blockchain == "xrp" {
+            match db.get_wallet_secret(wallet.id.unwrap(), WALLET_SECRET_SEED)? {
+                Some(seed) => println!("   🌱 Family Seed: {}", seed),
+                None => println!("   🌱 Family Seed: (not available - the wallet was imported from a private key)"),
+            }
+        }
+
+        // The Algorand mnemonic encodes the key itself, so it is available for every Algorand wallet
+        if wallet.blockchain == "algorand" {
+            match export_mnemonic(private_key) {
+                Ok(mnemonic) => println!("   🌱 Algorand Mnemonic: {}", mnemonic),
+                Err(e) => println!("   🌱 Algorand Mnemonic: (conversion error: {})", e),
+            }
+        }
+        if wallet.blockchain == "sui" {
+            match export_sui_private_key(private_key) {
+                Ok(sui_key) => println!("   🔑 Sui Private Key: {}", sui_key),
+                Err(e) => println!("   🔑 Sui Private Key: (conversion error: {})", e),
+            }
+        }
     } else {
         println!("\n🔒 Sensitive Information");
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
@@ -3,7 +3,7 @@ use clap::Args;
 use chrono::Utc;
 
 use crate::database::{Database, Wallet};
-use crate::blockchain::{SupportedBlockchain, get_blockchain_handler};
+use crate::blockchain::{SupportedBlockchain, get_blockchain_handler, derive_for_account};
 
 #[derive(Args)]
 pub struct AddSubwalletArgs {
@@ -22,6 +22,8 @@ pub struct AddSubwalletArgs {
 }
 
 pub fn execute(args: AddSubwalletArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
     println!("💰 Adding subwallet '{}' to address group", args.name);
     println!("Account: {}", args.account);
     println!("Wallet Group: {}", args.wallet_group);
@@ -136,13 +138,13 @@ pub fn execute(args: AddSubwalletArgs, db: &Database) -> Result<()> {
     // Derivation path: m/44'/coin_type'/account_index'/wallet_index/address_index
     let handler = get_blockchain_handler(&blockchain)?;
 
-    let subwallet_keys = handler.derive_from_mnemonic(
-        &master_account.mnemonic,
-        master_account.passphrase.as_deref(),
+    let subwallet_keys = derive_for_account(
+        handler.as_ref(),
+        &master_account,
         wallet_group.account_index,
         address_index,
         None, // use default derivation path
-    ).context("Failed to derive subwallet keys from mnemonic")?;
+    ).context("Failed to derive subwallet keys")?;
 
     println!("✓ Subwallet keys derived successfully");
     println!("   Derivation Path: {}", subwallet_keys.derivation_path);
@@ -156,11 +158,11 @@ pub fn execute(args: AddSubwalletArgs, db: &Database) -> Result<()> {
         blockchain: blockchain.to_string(),
         address: subwallet_keys.address.clone(),
         address_with_checksum: subwallet_keys.address_with_checksum.clone(),
-        private_key: subwallet_keys.private_key,
+        private_key: subwallet_keys.private_key.into(),
         public_key: Some(subwallet_keys.public_key),
         derivation_path: Some(subwallet_keys.derivation_path),
         label: Some(args.name.clone()),
-        source_type: "mnemonic".to_string(),
+        source_type: master_account.wallet_source_type().to_string(),
         explorer_url: Some(blockchain.get_explorer_url(&subwallet_keys.address)),
         notes: None,
         created_at: Utc::now(),
@@ -1,6 +1,6 @@
 use anyhow::{Result, Context};
 use clap::Args;
-use crate::database::Database;
+use crate::database::{Database, WATCH_ONLY_KEY_NOTICE};
 
 #[derive(Args)]
 pub struct ShowSubwalletArgs {
@@ -19,6 +19,10 @@ pub struct ShowSubwalletArgs {
 }
 
 pub fn execute(args: ShowSubwalletArgs, db: &Database) -> Result<()> {
+    if args.include_sensitive {
+        db.require_unlocked()?;
+    }
+
     println!("📱 Subwallet Details");
     println!("Account: {}", args.account);
     println!("Wallet Group: {}", args.wallet_group);
@@ -195,11 +199,19 @@ pub fn execute(args: ShowSubwalletArgs, db: &Database) -> Result<()> {
     if args.include_sensitive {
         println!("\n🔒 Sensitive Information");
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-        println!("   🔑 Private Key: {}", subwallet.private_key);
+        if subwallet.is_watch_only() {
+            println!("   🔑 Private Key: {}", WATCH_ONLY_KEY_NOTICE);
+        } else {
+            println!("   🔑 Private Key: {}", subwallet.private_key.expose()?);
+        }
     } else {
         println!("\n🔒 Sensitive Information");
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-        println!("   🔑 Private Key: (use --include-sensitive to view)");
+        if subwallet.is_watch_only() {
+            println!("   🔑 Private Key: {}", WATCH_ONLY_KEY_NOTICE);
+        } else {
+            println!("   🔑 Private Key: (use --include-sensitive to view)");
+        }
     }
 
     println!("\n💡 Next steps:");
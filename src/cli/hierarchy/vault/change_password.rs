@@ -0,0 +1,70 @@
+use anyhow::{Result, Context};
+use clap::Args;
+use crate::database::Database;
+use crate::crypto::session;
+use crate::crypto::vault::KdfParams;
+use crate::utils::{prompt_password, prompt_new_password};
+
+#[derive(Args)]
+pub struct ChangePasswordArgs {
+    #[arg(long, help = "New Argon2id memory cost in KiB (must not be lower than the current value)")]
+    pub kdf_memory: Option<u32>,
+    #[arg(long, help = "New Argon2id iterations (must not be lower than the current value)")]
+    pub kdf_iterations: Option<u32>,
+    #[arg(long, help = "New Argon2id parallelism")]
+    pub kdf_parallelism: Option<u32>,
+}
+
+pub fn execute(args: ChangePasswordArgs, db: &Database) -> Result<()> {
+    println!("🔑 Changing vault password");
+
+    let header = match db.get_vault_header()? {
+        Some(header) => header,
+        None => {
+            println!("\n❌ This vault is not encrypted.");
+            println!("💡 Use 'encrypt-vault' to protect it with a password first.");
+            return Ok(());
+        }
+    };
+
+    let current = header.kdf_params;
+    let kdf_params = if args.kdf_memory.is_some() || args.kdf_iterations.is_some() || args.kdf_parallelism.is_some() {
+        let params = KdfParams {
+            memory_kib: args.kdf_memory.unwrap_or(current.memory_kib),
+            iterations: args.kdf_iterations.unwrap_or(current.iterations),
+            parallelism: args.kdf_parallelism.unwrap_or(current.parallelism),
+        };
+        params.validate().context("Invalid KDF parameters")?;
+        Some(params)
+    } else {
+        None
+    };
+
+    let effective = kdf_params.unwrap_or(current);
+    println!("   Current KDF: argon2id (memory: {} KiB, iterations: {}, parallelism: {})",
+        current.memory_kib, current.iterations, current.parallelism);
+    if effective != current {
+        println!("   New KDF:     argon2id (memory: {} KiB, iterations: {}, parallelism: {})",
+            effective.memory_kib, effective.iterations, effective.parallelism);
+    }
+
+    let old_password = prompt_password("Current vault password")?;
+    db.derive_vault_key(&old_password)?;
+    let new_password = prompt_new_password("New vault password")?;
+
+    let summary = db.change_vault_password(&old_password, &new_password, kdf_params)
+        .context("Failed to change vault password")?;
+
+    // The cached session key belongs to the old password
+    session::clear_session()?;
+
+    println!("\n🎉 Vault password changed successfully!");
+    println!("   Master accounts re-keyed: {}", summary.master_accounts_sealed);
+    println!("   Wallets re-keyed: {}", summary.wallets_sealed);
+    println!("   Wallet seeds re-keyed: {}", summary.wallet_secrets_sealed);
+
+    println!("\n💡 Next steps:");
+    println!("   • Run 'unlock' with the new password to start a new session");
+
+    Ok(())
+}
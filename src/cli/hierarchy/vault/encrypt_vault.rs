@@ -0,0 +1,51 @@
+use anyhow::{Result, Context};
+use clap::Args;
+use crate::database::Database;
+use crate::crypto::vault::KdfParams;
+use crate::utils::prompt_new_password;
+
+#[derive(Args)]
+pub struct EncryptVaultArgs {
+    #[arg(long, help = "Argon2id memory cost in KiB (default: 65536 = 64 MiB)")]
+    pub kdf_memory: Option<u32>,
+    #[arg(long, help = "Argon2id iterations (default: 3)")]
+    pub kdf_iterations: Option<u32>,
+    #[arg(long, help = "Argon2id parallelism (default: 1)")]
+    pub kdf_parallelism: Option<u32>,
+}
+
+pub fn execute(args: EncryptVaultArgs, db: &Database) -> Result<()> {
+    println!("🔐 Encrypting vault secrets at rest");
+
+    if db.is_vault_encrypted()? {
+        println!("\n❌ This vault is already encrypted.");
+        return Ok(());
+    }
+
+    let defaults = KdfParams::default();
+    let kdf_params = KdfParams {
+        memory_kib: args.kdf_memory.unwrap_or(defaults.memory_kib),
+        iterations: args.kdf_iterations.unwrap_or(defaults.iterations),
+        parallelism: args.kdf_parallelism.unwrap_or(defaults.parallelism),
+    };
+    kdf_params.validate().context("Invalid KDF parameters")?;
+
+    println!("   KDF: argon2id (memory: {} KiB, iterations: {}, parallelism: {})",
+        kdf_params.memory_kib, kdf_params.iterations, kdf_params.parallelism);
+
+    let password = prompt_new_password("New vault password")?;
+
+    let summary = db.encrypt_vault(&password, kdf_params)
+        .context("Failed to encrypt vault")?;
+
+    println!("\n🎉 Vault encrypted successfully!");
+    println!("   Master accounts sealed: {}", summary.master_accounts_sealed);
+    println!("   Wallets sealed: {}", summary.wallets_sealed);
+    println!("   Wallet seeds sealed: {}", summary.wallet_secrets_sealed);
+
+    println!("\n⚠️  IMPORTANT:");
+    println!("   • The vault password cannot be recovered - if you lose it, the stored secrets are lost");
+    println!("   • Keep an offline backup of your mnemonic phrases");
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use anyhow::{Result, Context};
+use clap::Args;
+use crate::database::Database;
+use crate::crypto::session::{self, DEFAULT_SESSION_TTL_MINUTES};
+use crate::crypto::vault::KdfParams;
+use crate::utils::prompt_new_password;
+
+#[derive(Args)]
+pub struct InitArgs {
+    #[arg(long, help = "Argon2id memory cost in KiB (default: 65536 = 64 MiB)")]
+    pub kdf_memory: Option<u32>,
+    #[arg(long, help = "Argon2id iterations (default: 3)")]
+    pub kdf_iterations: Option<u32>,
+    #[arg(long, help = "Argon2id parallelism (default: 1)")]
+    pub kdf_parallelism: Option<u32>,
+    #[arg(long, help = "Minutes the new vault stays unlocked (default: 15, max: 1440)")]
+    pub ttl: Option<i64>,
+}
+
+pub fn execute(args: InitArgs, db: &Database) -> Result<()> {
+    println!("🔐 Initializing protected vault");
+
+    if db.is_vault_encrypted()? {
+        println!("\n❌ A vault is already initialized in this database.");
+        println!("   Use 'unlock' to start a session.");
+        return Ok(());
+    }
+
+    if !db.list_master_accounts()?.is_empty() || !db.get_standalone_wallets()?.is_empty() {
+        println!("\n❌ This database already contains unencrypted secrets.");
+        println!("💡 Use 'encrypt-vault' to upgrade it in place.");
+        return Ok(());
+    }
+
+    let ttl = args.ttl.unwrap_or(DEFAULT_SESSION_TTL_MINUTES);
+    session::validate_ttl(ttl)?;
+
+    let defaults = KdfParams::default();
+    let kdf_params = KdfParams {
+        memory_kib: args.kdf_memory.unwrap_or(defaults.memory_kib),
+        iterations: args.kdf_iterations.unwrap_or(defaults.iterations),
+        parallelism: args.kdf_parallelism.unwrap_or(defaults.parallelism),
+    };
+    kdf_params.validate().context("Invalid KDF parameters")?;
+
+    println!("   KDF: argon2id (memory: {} KiB, iterations: {}, parallelism: {})",
+        kdf_params.memory_kib, kdf_params.iterations, kdf_params.parallelism);
+
+    let password = prompt_new_password("New vault password")?;
+
+    db.encrypt_vault(&password, kdf_params)
+        .context("Failed to initialize vault")?;
+
+    let vault_id = db.get_vault_id()?
+        .ok_or_else(|| anyhow::anyhow!("Vault header missing after initialization"))?;
+    let key = db.derive_vault_key(&password)?;
+    let expires_at = session::save_session(&vault_id, &key, ttl)?;
+
+    println!("\n🎉 Vault initialized successfully!");
+    println!("   Unlocked until: {}", expires_at.format("%Y-%m-%d %H:%M:%S UTC"));
+
+    println!("\n⚠️  IMPORTANT:");
+    println!("   • The vault password cannot be recovered - if you lose it, the stored secrets are lost");
+    println!("   • Run 'lock' when you are done to wipe the cached session key");
+
+    println!("\n💡 Next steps:");
+    println!("   • Create an account: wallet-backup add-account --name <name> --mnemonic \"<phrase>\"");
+
+    Ok(())
+}
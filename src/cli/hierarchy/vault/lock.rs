@@ -0,0 +1,19 @@
+use anyhow::Result;
+use clap::Args;
+use crate::database::Database;
+use crate::crypto::session;
+
+#[derive(Args)]
+pub struct LockArgs {}
+
+pub fn execute(_args: LockArgs, db: &Database) -> Result<()> {
+    db.lock();
+
+    if session::clear_session()? {
+        println!("🔒 Vault locked. Cached session key wiped.");
+    } else {
+        println!("🔒 Vault is already locked (no active session).");
+    }
+
+    Ok(())
+}
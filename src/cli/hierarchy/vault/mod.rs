@@ -0,0 +1,11 @@
+pub mod encrypt_vault;
+pub mod init;
+pub mod unlock;
+pub mod lock;
+pub mod change_password;
+
+pub use encrypt_vault::EncryptVaultArgs;
+pub use init::InitArgs;
+pub use unlock::UnlockArgs;
+pub use lock::LockArgs;
+pub use change_password::ChangePasswordArgs;
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use clap::Args;
+use crate::database::Database;
+use crate::crypto::session::{self, DEFAULT_SESSION_TTL_MINUTES};
+use crate::utils::prompt_password;
+
+#[derive(Args)]
+pub struct UnlockArgs {
+    #[arg(long, help = "Minutes the vault stays unlocked (default: 15, max: 1440)")]
+    pub ttl: Option<i64>,
+}
+
+pub fn execute(args: UnlockArgs, db: &Database) -> Result<()> {
+    println!("🔓 Unlocking vault");
+
+    let vault_id = match db.get_vault_id()? {
+        Some(vault_id) => vault_id,
+        None => {
+            println!("\n❌ This database has no protected vault.");
+            println!("💡 Use 'init' for a new vault or 'encrypt-vault' to protect existing secrets.");
+            return Ok(());
+        }
+    };
+
+    let ttl = args.ttl.unwrap_or(DEFAULT_SESSION_TTL_MINUTES);
+    session::validate_ttl(ttl)?;
+
+    let password = prompt_password("Vault password")?;
+    let key = db.derive_vault_key(&password)?;
+    let expires_at = session::save_session(&vault_id, &key, ttl)?;
+
+    println!("\n✓ Vault unlocked");
+    println!("   Expires: {}", expires_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    println!("   Session file: {}", session::session_file_path()?.display());
+    println!("\n💡 Run 'lock' to wipe the session before it expires.");
+
+    Ok(())
+}
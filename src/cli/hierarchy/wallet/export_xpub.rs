@@ -0,0 +1,133 @@
+use anyhow::{Result, Context, bail};
+use clap::Args;
+use bitcoin::bip32::Xpub;
+use bitcoin::secp256k1::Secp256k1;
+
+use crate::database::Database;
+use crate::blockchain::{SupportedBlockchain, BipStandard};
+use crate::crypto::derive_path_secp256k1;
+use crate::crypto::descriptor::account_descriptor;
+use crate::crypto::slip132::{encode_extended_public_key, Slip132Prefix};
+
+#[derive(Args)]
+pub struct ExportXpubArgs {
+    #[arg(long, help = "Name of the master account")]
+    pub account: String,
+    #[arg(long, help = "Name of the wallet group")]
+    pub wallet_group: String,
+    #[arg(long, help = "Name of the Bitcoin or Litecoin base wallet")]
+    pub wallet: String,
+}
+
+pub fn execute(args: ExportXpubArgs, db: &Database) -> Result<()> {
+    db.require_unlocked()?;
+
+    println!("🔑 Exporting account xpub for wallet '{}'", args.wallet);
+
+    let master_account = match db.get_master_account_by_name(&args.account)? {
+        Some(account) => account,
+        None => {
+            println!("❌ Master account '{}' not found.", args.account);
+            println!("   Use 'wallet-backup list-accounts' to see available accounts.");
+            return Ok(());
+        }
+    };
+
+    let wallet_group = match db.get_wallet_group_by_name(master_account.id.unwrap(), &args.wallet_group)? {
+        Some(group) => group,
+        None => {
+            println!("❌ Wallet group '{}' not found in account '{}'.", args.wallet_group, args.account);
+            println!("   Use 'wallet-backup list-wallet-groups --account \"{}\"' to see available groups.", args.account);
+            return Ok(());
+        }
+    };
+
+    let wallet = match db.get_wallet_by_name_in_group(wallet_group.id.unwrap(), &args.wallet)? {
+        Some(wallet) => wallet,
+        None => {
+            println!("❌ Base wallet '{}' not found in wallet group '{}'.", args.wallet, args.wallet_group);
+            println!("   Use 'wallet-backup list-wallets --account \"{}\" --wallet-group \"{}\"' to see available wallets.", args.account, args.wallet_group);
+            return Ok(());
+        }
+    };
+
+    let blockchain = SupportedBlockchain::from_str(&wallet.blockchain)?;
+    if !matches!(blockchain, SupportedBlockchain::Bitcoin | SupportedBlockchain::Litecoin) {
+        bail!("export-xpub supports Bitcoin and Litecoin wallets, not {}", blockchain);
+    }
+
+    let derivation_path = wallet.derivation_path.as_deref()
+        .context("Wallet has no derivation path")?;
+    let account_path = account_path_from(derivation_path)?;
+    let purpose = BipStandard::from_derivation_path(derivation_path)
+        .with_context(|| format!("Unsupported derivation path for descriptors: {}", derivation_path))?
+        .get_purpose();
+
+    // Key origin needs the master fingerprint, which a watch-only account does not have
+    let (xpub, origin) = if master_account.is_watch_only() {
+        let key = master_account.watch_only_key()?;
+        if key.account_path() != account_path {
+            bail!("Wallet path {} does not belong to the stored xpub ({})", account_path, key.account_path());
+        }
+        (key.xpub, None)
+    } else {
+        let secp = Secp256k1::new();
+        let root = master_account.bip32_root()?;
+        let account_xpriv = derive_path_secp256k1(&root, &account_path)?;
+        let xpub = Xpub::from_priv(&secp, &account_xpriv);
+        let origin = format!("{}/{}", root.fingerprint(&secp), account_path.trim_start_matches("m/"));
+        (xpub, Some(origin))
+    };
+
+    let plain_xpub = xpub.to_string();
+    let receive = account_descriptor(purpose, origin.as_deref(), &plain_xpub, 0)?;
+    let change = account_descriptor(purpose, origin.as_deref(), &plain_xpub, 1)?;
+
+    println!("\n📊 Account Key:");
+    println!("   Blockchain: {}", blockchain);
+    println!("   Address Type: BIP-{}", purpose);
+    println!("   Account Path: {}", account_path);
+    match &origin {
+        Some(origin) => println!("   Master Fingerprint: {}", &origin[..8]),
+        None => println!("   Master Fingerprint: (unknown - watch-only account, key origin omitted)"),
+    }
+    println!("   xpub: {}", plain_xpub);
+    if let Some(prefix) = slip132_prefix(&blockchain, purpose) {
+        if prefix != Slip132Prefix::Xpub {
+            println!("   {}: {}", prefix.as_str(), encode_extended_public_key(&xpub, prefix));
+        }
+    }
+
+    println!("\n📜 Output Descriptors (BIP-380):");
+    println!("   Receive: {}", receive);
+    println!("   Change:  {}", change);
+
+    println!("\n💡 Next steps:");
+    println!("   • Sparrow: File → New Wallet → Edit → paste the receive descriptor");
+    println!("   • Bitcoin Core: importdescriptors '[{{\"desc\": \"<receive>\", \"timestamp\": \"now\", \"active\": true, \"internal\": false}}]'");
+    println!("   • The exported keys are public: they reveal balances and history, but cannot spend");
+
+    Ok(())
+}
+
+// Truncates a wallet path like m/84'/0'/3'/0/0 to its hardened account prefix m/84'/0'/3'
+fn account_path_from(derivation_path: &str) -> Result<String> {
+    let components: Vec<&str> = derivation_path.split('/').collect();
+    if components.len() < 4 || components[0] != "m" || !components[1..4].iter().all(|c| c.ends_with('\'')) {
+        bail!("Expected a BIP-44 style path with a hardened account level, got {}", derivation_path);
+    }
+    Ok(components[..4].join("/"))
+}
+
+fn slip132_prefix(blockchain: &SupportedBlockchain, purpose: u32) -> Option<Slip132Prefix> {
+    match (blockchain, purpose) {
+        (SupportedBlockchain::Bitcoin, 44) => Some(Slip132Prefix::Xpub),
+        (SupportedBlockchain::Bitcoin, 49) => Some(Slip132Prefix::Ypub),
+        (SupportedBlockchain::Bitcoin, 84) => Some(Slip132Prefix::Zpub),
+        // SLIP-132 defines no Taproot prefix; descriptors carry the script type instead
+        (SupportedBlockchain::Bitcoin, 86) => Some(Slip132Prefix::Xpub),
+        (SupportedBlockchain::Litecoin, 44) => Some(Slip132Prefix::Ltub),
+        (SupportedBlockchain::Litecoin, 49) => Some(Slip132Prefix::Mtub),
+        _ => None,
+    }
+}
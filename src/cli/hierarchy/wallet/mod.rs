@@ -1,5 +1,7 @@
 pub mod modify_wallet;
 pub mod show_wallet;
+pub mod export_xpub;
 
 pub use modify_wallet::*;
-pub use show_wallet::*;
\ No newline at end of file
+pub use show_wallet::*;
+pub use export_xpub::*;
\ No newline at end of file
@@ -1,7 +1,9 @@
 use anyhow::{Result, Context};
 use clap::Args;
-use crate::database::Database;
+use crate::database::{Database, WATCH_ONLY_KEY_NOTICE};
+use crate::blockchain::algorand::export_mnemonic;
 use crate::blockchain::stellar::StellarHandler;
+use crate::blockchain::sui::export_private_key as export_sui_private_key;
 
 #[derive(Args)]
 pub struct ShowWalletArgs {
@@ -16,6 +18,10 @@ pub struct ShowWalletArgs {
 }
 
 pub fn execute(args: ShowWalletArgs, db: &Database) -> Result<()> {
+    if args.include_sensitive {
+        db.require_unlocked()?;
+    }
+
     println!("📱 Wallet Details");
     println!("Account: {}", args.account);
     println!("Wallet Group: {}", args.wallet_group);
@@ -161,14 +167,17 @@ pub fn execute(args: ShowWalletArgs, db: &Database) -> Result<()> {
     if args.include_sensitive {
         println!("\n🔒 Sensitive Information");
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+        let private_key = wallet.private_key.expose()?;
 
-        // For Stellar wallets, show both hex and Stellar secret formats
-        if wallet.blockchain == "stellar" {
-            println!("   🔑 Private Key (hex): {}", wallet.private_key);
+        if wallet.is_watch_only() {
+            println!("   🔑 Private Key: {}", WATCH_ONLY_KEY_NOTICE);
+        } else if wallet.blockchain == "stellar" {
+            // For Stellar wallets, show both hex and Stellar secret formats
+            println!("   🔑 Private Key (hex): {}", private_key);
 
             // Convert to Stellar secret format
             let stellar_handler = StellarHandler::new();
-            match stellar_handler.hex_private_key_to_stellar_secret(&wallet.private_key) {
+            match stellar_handler.hex_private_key_to_stellar_secret(private_key) {
                 Ok(stellar_secret) => {
                     println!("   🌟 Stellar Secret Key: {}", stellar_secret);
                     println!("   ℹ️  Note: Both formats represent the same private key");
@@ -177,13 +186,31 @@ pub fn execute(args: ShowWalletArgs, db: &Database) -> Result<()> {
                     println!("   🌟 Stellar Secret Key: (conversion error: {})", e);
                 }
             }
+        } else if wallet.blockchain == "algorand" {
+            // The 25-word mnemonic encodes the key itself, the form Algorand wallets import
+            println!("   🔑 Private Key: {}", private_key);
+            match export_mnemonic(private_key) {
+                Ok(mnemonic) => println!("   🌱 Algorand Mnemonic: {}", mnemonic),
+                Err(e) => println!("   🌱 Algorand Mnemonic: (conversion error: {})", e),
+            }
+        } else if wallet.blockchain == "sui" {
+            // Sui wallets and `sui keytool import` expect the Bech32 suiprivkey form
+            println!("   🔑 Private Key (hex): {}", private_key);
+            match export_sui_private_key(private_key) {
+                Ok(sui_key) => println!("   🔑 Sui Private Key: {}", sui_key),
+                Err(e) => println!("   🔑 Sui Private Key: (conversion error: {})", e),
+            }
         } else {
-            println!("   🔑 Private Key: {}", wallet.private_key);
+            println!("   🔑 Private Key: {}", private_key);
         }
     } else {
         println!("\n🔒 Sensitive Information");
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-        println!("   🔑 Private Key: (use --include-sensitive to view)");
+        if wallet.is_watch_only() {
+            println!("   🔑 Private Key: {}", WATCH_ONLY_KEY_NOTICE);
+        } else {
+            println!("   🔑 Private Key: (use --include-sensitive to view)");
+        }
     }
 
     // Show address groups count
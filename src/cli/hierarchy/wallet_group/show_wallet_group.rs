@@ -1,6 +1,6 @@
 use anyhow::{Result, Context};
 use clap::Args;
-use crate::database::Database;
+use crate::database::{Database, WATCH_ONLY_KEY_NOTICE};
 
 #[derive(Args)]
 pub struct ShowWalletGroupArgs {
@@ -13,6 +13,10 @@ pub struct ShowWalletGroupArgs {
 }
 
 pub fn execute(args: ShowWalletGroupArgs, db: &Database) -> Result<()> {
+    if args.include_sensitive {
+        db.require_unlocked()?;
+    }
+
     println!("🔍 Wallet Group Details");
     println!("Master Account: {}", args.account);
     println!("Group Name: {}", args.group_name);
@@ -70,8 +74,10 @@ pub fn execute(args: ShowWalletGroupArgs, db: &Database) -> Result<()> {
         println!("      📍 Address: {}", wallet.address);
         println!("      🛤️  Path: {}", derivation_path);
 
-        if args.include_sensitive {
-            println!("      🔒 Private Key: {}", wallet.private_key);
+        if wallet.is_watch_only() {
+            println!("      🔒 Private Key: {}", WATCH_ONLY_KEY_NOTICE);
+        } else if args.include_sensitive {
+            println!("      🔒 Private Key: {}", wallet.private_key.expose()?);
         } else {
             println!("      🔒 Private Key: (use --include-sensitive to view)");
         }
@@ -398,6 +398,86 @@ pub fn handle_modify_standalone_wallet(args: ModifyStandaloneWalletArgs, db: &Da
     hierarchy::standalone::modify_standalone_wallet::execute(args, db)
 }
 
+pub fn handle_encrypt_vault(args: EncryptVaultArgs, db: &Database) -> Result<()> {
+    hierarchy::vault::encrypt_vault::execute(args, db)
+}
+
+pub fn handle_init(args: InitArgs, db: &Database) -> Result<()> {
+    hierarchy::vault::init::execute(args, db)
+}
+
+pub fn handle_unlock(args: UnlockArgs, db: &Database) -> Result<()> {
+    hierarchy::vault::unlock::execute(args, db)
+}
+
+pub fn handle_lock(args: LockArgs, db: &Database) -> Result<()> {
+    hierarchy::vault::lock::execute(args, db)
+}
+
+pub fn handle_change_password(args: ChangePasswordArgs, db: &Database) -> Result<()> {
+    hierarchy::vault::change_password::execute(args, db)
+}
+
+pub fn handle_export_account(args: ExportAccountArgs, db: &Database) -> Result<()> {
+    hierarchy::backup::export_account::execute(args, db)
+}
+
+pub fn handle_import_account(args: ImportAccountArgs, db: &Database) -> Result<()> {
+    hierarchy::backup::import_account::execute(args, db)
+}
+
+pub fn handle_export_manifest(args: ExportManifestArgs, db: &Database) -> Result<()> {
+    hierarchy::backup::export_manifest::execute(args, db)
+}
+
+pub fn handle_restore(args: RestoreArgs, db: &Database) -> Result<()> {
+    hierarchy::backup::restore::execute(args, db)
+}
+
+pub fn handle_split_shares(args: SplitSharesArgs, db: &Database) -> Result<()> {
+    hierarchy::shares::split_shares::execute(args, db)
+}
+
+pub fn handle_recover_account(args: RecoverAccountArgs, db: &Database) -> Result<()> {
+    hierarchy::shares::recover_account::execute(args, db)
+}
+
+pub fn handle_verify_shares(args: VerifySharesArgs, db: &Database) -> Result<()> {
+    hierarchy::shares::verify_shares::execute(args, db)
+}
+
+pub fn handle_derive_child_seed(args: DeriveChildSeedArgs, db: &Database) -> Result<()> {
+    hierarchy::master_account::derive_child_seed::execute(args, db)
+}
+
+pub fn handle_export_xpub(args: ExportXpubArgs, db: &Database) -> Result<()> {
+    hierarchy::wallet::export_xpub::execute(args, db)
+}
+
+pub fn handle_sign_psbt(args: SignPsbtArgs, db: &Database) -> Result<()> {
+    hierarchy::signing::sign_psbt::execute(args, db)
+}
+
+pub fn handle_sign_evm_tx(args: SignEvmTxArgs, db: &Database) -> Result<()> {
+    hierarchy::signing::sign_evm_tx::execute(args, db)
+}
+
+pub fn handle_sign_cardano_tx(args: SignCardanoTxArgs, db: &Database) -> Result<()> {
+    hierarchy::signing::sign_cardano_tx::execute(args, db)
+}
+
+pub fn handle_sign_message(args: SignMessageArgs, db: &Database) -> Result<()> {
+    hierarchy::signing::sign_message::execute(args, db)
+}
+
+pub fn handle_verify_message(args: VerifyMessageArgs, db: &Database) -> Result<()> {
+    hierarchy::signing::verify_message::execute(args, db)
+}
+
+pub fn handle_sign(args: SignArgs, db: &Database) -> Result<()> {
+    hierarchy::signing::sign::execute(args, db)
+}
+
 // Legacy command handlers (TEMPORARILY DISABLED)
 // pub fn handle_import(args: ImportArgs, db: &Database) -> Result<()> {
 //     import::execute(args, db)
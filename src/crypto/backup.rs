@@ -0,0 +1,116 @@
+use anyhow::{Result, Context, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::crypto::vault::{self, KdfParams};
+
+/// Identifies an encrypted account backup file
+pub const BACKUP_FORMAT: &str = "wallet-backup/account-backup";
+
+/// Current backup file version (bump when the payload layout changes)
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Password-encrypted backup file: KDF parameters in the clear, payload sealed with the derived key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupEnvelope {
+    pub format: String,
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    pub kdf: BackupKdf,
+    pub payload: String, // enc:v1:<nonce hex>:<ciphertext hex>
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupKdf {
+    pub algorithm: String,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    pub salt: String, // Hex-encoded
+}
+
+/// Encrypts a serialized payload under a key derived from the backup password
+pub fn seal_backup(password: &str, payload: &str, kdf_params: &KdfParams) -> Result<BackupEnvelope> {
+    kdf_params.validate()?;
+
+    let salt = vault::generate_salt();
+    let key = vault::derive_key(password, &salt, kdf_params)?;
+
+    Ok(BackupEnvelope {
+        format: BACKUP_FORMAT.to_string(),
+        version: BACKUP_VERSION,
+        created_at: Utc::now(),
+        kdf: BackupKdf {
+            algorithm: vault::KDF_ALGORITHM.to_string(),
+            memory_kib: kdf_params.memory_kib,
+            iterations: kdf_params.iterations,
+            parallelism: kdf_params.parallelism,
+            salt: hex::encode(salt),
+        },
+        payload: vault::seal(&key, payload)?,
+    })
+}
+
+/// Checks the envelope format/version and decrypts the payload
+pub fn open_backup(password: &str, envelope: &BackupEnvelope) -> Result<String> {
+    if envelope.format != BACKUP_FORMAT {
+        bail!("Not an account backup file (format: {})", envelope.format);
+    }
+    if envelope.version > BACKUP_VERSION {
+        bail!("Unsupported backup version {} (this build supports up to {})", envelope.version, BACKUP_VERSION);
+    }
+    if envelope.kdf.algorithm != vault::KDF_ALGORITHM {
+        bail!("Unsupported backup KDF algorithm: {}", envelope.kdf.algorithm);
+    }
+
+    let kdf_params = KdfParams {
+        memory_kib: envelope.kdf.memory_kib,
+        iterations: envelope.kdf.iterations,
+        parallelism: envelope.kdf.parallelism,
+    };
+    kdf_params.validate().context("Backup file has out-of-range KDF parameters")?;
+    let salt = hex::decode(&envelope.kdf.salt).context("Invalid salt in backup file")?;
+    let key = vault::derive_key(password, &salt, &kdf_params)?;
+
+    vault::open(&key, &envelope.payload)
+        .map_err(|_| anyhow::anyhow!("Incorrect backup password or corrupted backup file."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_params() -> KdfParams {
+        KdfParams { memory_kib: 8 * 1024, iterations: 1, parallelism: 1 }
+    }
+
+    #[test]
+    fn test_backup_round_trip() {
+        let envelope = seal_backup("backup password", "{\"name\":\"Main\"}", &test_params()).unwrap();
+        assert_eq!(envelope.version, BACKUP_VERSION);
+        assert_eq!(open_backup("backup password", &envelope).unwrap(), "{\"name\":\"Main\"}");
+    }
+
+    #[test]
+    fn test_backup_wrong_password() {
+        let envelope = seal_backup("backup password", "payload", &test_params()).unwrap();
+        assert!(open_backup("other password", &envelope).is_err());
+    }
+
+    #[test]
+    fn test_backup_rejects_newer_version() {
+        let mut envelope = seal_backup("backup password", "payload", &test_params()).unwrap();
+        envelope.version = BACKUP_VERSION + 1;
+        assert!(open_backup("backup password", &envelope).is_err());
+    }
+
+    #[test]
+    fn test_backup_rejects_excessive_kdf_params() {
+        let mut envelope = seal_backup("backup password", "payload", &test_params()).unwrap();
+        envelope.kdf.memory_kib = u32::MAX;
+        assert!(open_backup("backup password", &envelope).is_err());
+
+        let mut envelope = seal_backup("backup password", "payload", &test_params()).unwrap();
+        envelope.kdf.iterations = u32::MAX;
+        assert!(open_backup("backup password", &envelope).is_err());
+    }
+}
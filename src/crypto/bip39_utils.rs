@@ -1,5 +1,7 @@
-use anyhow::{Result, Context};
+use anyhow::{Result, Context, bail};
 use bip39::Mnemonic;
+use rand::TryRngCore;
+use sha2::{Digest, Sha256};
 use std::str::FromStr;
 
 pub fn validate_mnemonic_phrase(mnemonic: &str) -> Result<()> {
@@ -44,6 +46,52 @@ pub fn normalize_mnemonic(mnemonic: &str) -> String {
         .join(" ")
 }
 
+/// Entropy length in bytes for a BIP-39 word count
+pub fn entropy_length_for_words(word_count: usize) -> Result<usize> {
+    if !is_valid_mnemonic_length(word_count) {
+        bail!("Mnemonic length must be 12, 15, 18, 21 or 24 words");
+    }
+    Ok(word_count * 4 / 3)
+}
+
+/// Fills a buffer of the given length from the operating system's CSPRNG
+pub fn generate_os_entropy(length: usize) -> Result<Vec<u8>> {
+    let mut entropy = vec![0u8; length];
+    rand::rngs::OsRng.try_fill_bytes(&mut entropy)
+        .map_err(|e| anyhow::anyhow!("Failed to read OS randomness: {}", e))?;
+    Ok(entropy)
+}
+
+/// Validates dice rolls (digits 1-6, separators ignored) and returns them as a compact string
+pub fn normalize_dice_rolls(rolls: &str) -> Result<String> {
+    let normalized: String = rolls.chars().filter(|c| !c.is_whitespace() && *c != ',').collect();
+    if let Some(bad) = normalized.chars().find(|c| !('1'..='6').contains(c)) {
+        bail!("Invalid dice roll '{}'. Use digits 1-6", bad);
+    }
+    Ok(normalized)
+}
+
+/// Validates coin flips (H/T or 1/0, separators ignored) and returns them as a string of 1s and 0s
+pub fn normalize_coin_flips(flips: &str) -> Result<String> {
+    flips.chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .map(|c| match c.to_ascii_lowercase() {
+            'h' | '1' => Ok('1'),
+            't' | '0' => Ok('0'),
+            other => bail!("Invalid coin flip '{}'. Use H/T or 1/0", other),
+        })
+        .collect()
+}
+
+/// Mixes OS entropy with user-supplied input: SHA-256(os_entropy || user_input), truncated to the OS entropy length
+pub fn mix_entropy(os_entropy: &[u8], user_input: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(os_entropy);
+    hasher.update(user_input.as_bytes());
+    let digest = hasher.finalize();
+    digest[..os_entropy.len().min(digest.len())].to_vec()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,4 +133,29 @@ mod tests {
         assert_eq!(seed_with_passphrase.len(), 64);
         assert_ne!(seed_no_passphrase, seed_with_passphrase);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_entropy_length_for_words() {
+        assert_eq!(entropy_length_for_words(12).unwrap(), 16);
+        assert_eq!(entropy_length_for_words(24).unwrap(), 32);
+        assert!(entropy_length_for_words(13).is_err());
+    }
+
+    #[test]
+    fn test_user_entropy_normalization() {
+        assert_eq!(normalize_dice_rolls("1 6, 3 4").unwrap(), "1634");
+        assert!(normalize_dice_rolls("127").is_err());
+        assert_eq!(normalize_coin_flips("H T h 0 1").unwrap(), "10101");
+        assert!(normalize_coin_flips("HX").is_err());
+    }
+
+    #[test]
+    fn test_mix_entropy_is_deterministic() {
+        let os_entropy = [1u8; 16];
+        let mixed = mix_entropy(&os_entropy, "123456");
+        assert_eq!(mixed.len(), 16);
+        assert_eq!(mixed, mix_entropy(&os_entropy, "123456"));
+        assert_ne!(mixed, mix_entropy(&os_entropy, "123455"));
+        assert!(entropy_to_mnemonic(&mixed).is_ok());
+    }
+}
@@ -0,0 +1,220 @@
+use anyhow::{Result, Context, bail};
+use base64::Engine;
+use bip39::{Language, Mnemonic};
+use bitcoin::bip32::{ChainCode, ChildNumber, DerivationPath, Fingerprint, Xpriv};
+use bitcoin::secp256k1::{Secp256k1, SecretKey};
+use bitcoin::{Network, NetworkKind, PrivateKey};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use std::str::FromStr;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// BIP-85 purpose level: m/83696968'/...
+pub const BIP85_PURPOSE: u32 = 83696968;
+
+const ENTROPY_HMAC_KEY: &[u8] = b"bip-entropy-from-k";
+
+// RFC 1924 alphabet, as used by BIP-85 PWD BASE85
+const BASE85_ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// Derives the 64 bytes of BIP-85 entropy for a full derivation path
+pub fn derive_entropy(master: &Xpriv, path: &str) -> Result<[u8; 64]> {
+    let derivation_path = DerivationPath::from_str(path)
+        .context("Invalid BIP-85 derivation path")?;
+    let child = master.derive_priv(&Secp256k1::new(), &derivation_path)
+        .context("Failed to derive BIP-85 child key")?;
+
+    let mut mac = HmacSha512::new_from_slice(ENTROPY_HMAC_KEY)
+        .map_err(|e| anyhow::anyhow!("HMAC error: {}", e))?;
+    mac.update(&child.private_key.secret_bytes());
+
+    let mut entropy = [0u8; 64];
+    entropy.copy_from_slice(&mac.finalize().into_bytes());
+    Ok(entropy)
+}
+
+/// Maps a BIP-85 language index to a BIP-39 wordlist
+pub fn language_from_index(index: u32) -> Result<Language> {
+    Ok(match index {
+        0 => Language::English,
+        1 => Language::Japanese,
+        2 => Language::Korean,
+        3 => Language::Spanish,
+        4 => Language::SimplifiedChinese,
+        5 => Language::TraditionalChinese,
+        6 => Language::French,
+        7 => Language::Italian,
+        8 => Language::Czech,
+        9 => Language::Portuguese,
+        _ => bail!("Unsupported BIP-85 language index: {} (0-9)", index),
+    })
+}
+
+/// BIP39 application: m/83696968'/39'/{language}'/{words}'/{index}'
+pub fn derive_mnemonic(master: &Xpriv, language: u32, words: u32, index: u32) -> Result<String> {
+    let entropy_len = match words {
+        12 => 16,
+        18 => 24,
+        24 => 32,
+        _ => bail!("BIP-85 mnemonics must be 12, 18 or 24 words"),
+    };
+    let path = format!("m/{}'/39'/{}'/{}'/{}'", BIP85_PURPOSE, language, words, index);
+    let entropy = derive_entropy(master, &path)?;
+
+    let mnemonic = Mnemonic::from_entropy_in(language_from_index(language)?, &entropy[..entropy_len])
+        .context("Failed to build child mnemonic")?;
+    Ok(mnemonic.to_string())
+}
+
+/// HD-Seed WIF application: m/83696968'/2'/{index}'
+pub fn derive_wif(master: &Xpriv, index: u32) -> Result<String> {
+    let path = format!("m/{}'/2'/{}'", BIP85_PURPOSE, index);
+    let entropy = derive_entropy(master, &path)?;
+
+    let private_key = PrivateKey::from_slice(&entropy[..32], Network::Bitcoin)
+        .context("Derived entropy is not a valid private key")?;
+    Ok(private_key.to_wif())
+}
+
+/// XPRV application: m/83696968'/32'/{index}' (chain code first, then private key)
+pub fn derive_xprv(master: &Xpriv, index: u32) -> Result<String> {
+    let path = format!("m/{}'/32'/{}'", BIP85_PURPOSE, index);
+    let entropy = derive_entropy(master, &path)?;
+
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&entropy[..32]);
+    let private_key = SecretKey::from_slice(&entropy[32..64])
+        .context("Derived entropy is not a valid private key")?;
+
+    let xprv = Xpriv {
+        network: NetworkKind::Main,
+        depth: 0,
+        parent_fingerprint: Fingerprint::default(),
+        child_number: ChildNumber::from_normal_idx(0)?,
+        private_key,
+        chain_code: ChainCode::from(chain_code),
+    };
+    Ok(xprv.to_string())
+}
+
+/// HEX application: m/83696968'/128169'/{num_bytes}'/{index}'
+pub fn derive_hex(master: &Xpriv, num_bytes: u32, index: u32) -> Result<String> {
+    if !(16..=64).contains(&num_bytes) {
+        bail!("BIP-85 hex entropy length must be between 16 and 64 bytes");
+    }
+    let path = format!("m/{}'/128169'/{}'/{}'", BIP85_PURPOSE, num_bytes, index);
+    let entropy = derive_entropy(master, &path)?;
+    Ok(hex::encode(&entropy[..num_bytes as usize]))
+}
+
+/// PWD BASE64 application: m/83696968'/707764'/{pwd_len}'/{index}'
+pub fn derive_password_base64(master: &Xpriv, length: u32, index: u32) -> Result<String> {
+    if !(20..=86).contains(&length) {
+        bail!("BIP-85 base64 password length must be between 20 and 86");
+    }
+    let path = format!("m/{}'/707764'/{}'/{}'", BIP85_PURPOSE, length, index);
+    let entropy = derive_entropy(master, &path)?;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(entropy);
+    Ok(encoded[..length as usize].to_string())
+}
+
+/// PWD BASE85 application: m/83696968'/707785'/{pwd_len}'/{index}'
+pub fn derive_password_base85(master: &Xpriv, length: u32, index: u32) -> Result<String> {
+    if !(10..=80).contains(&length) {
+        bail!("BIP-85 base85 password length must be between 10 and 80");
+    }
+    let path = format!("m/{}'/707785'/{}'/{}'", BIP85_PURPOSE, length, index);
+    let entropy = derive_entropy(master, &path)?;
+
+    let encoded = base85_encode(&entropy);
+    Ok(encoded[..length as usize].to_string())
+}
+
+// Base85 (RFC 1924 alphabet) for input whose length is a multiple of 4
+fn base85_encode(data: &[u8]) -> String {
+    let mut output = String::with_capacity(data.len() / 4 * 5);
+    for chunk in data.chunks(4) {
+        let mut value = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let mut digits = [0u8; 5];
+        for digit in digits.iter_mut().rev() {
+            *digit = BASE85_ALPHABET[(value % 85) as usize];
+            value /= 85;
+        }
+        output.extend(digits.iter().map(|&d| d as char));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Master key from the BIP-85 test vectors
+    const TEST_XPRV: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+    fn test_master() -> Xpriv {
+        Xpriv::from_str(TEST_XPRV).unwrap()
+    }
+
+    #[test]
+    fn test_entropy_vector() {
+        let entropy = derive_entropy(&test_master(), "m/83696968'/0'/0'").unwrap();
+        assert_eq!(
+            hex::encode(entropy),
+            "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f00b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7"
+        );
+    }
+
+    #[test]
+    fn test_bip39_12_words_vector() {
+        let mnemonic = derive_mnemonic(&test_master(), 0, 12, 0).unwrap();
+        assert_eq!(mnemonic, "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose");
+    }
+
+    #[test]
+    fn test_wif_vector() {
+        assert_eq!(derive_wif(&test_master(), 0).unwrap(), "Kzyv4uF39d4Jrw2W7UryTHwZr1zQVNk4dAFyqE6BuMrMh1Za7uhp");
+    }
+
+    #[test]
+    fn test_xprv_vector() {
+        assert_eq!(
+            derive_xprv(&test_master(), 0).unwrap(),
+            "xprv9s21ZrQH143K2srSbCSg4m4kLvPMzcWydgmKEnMmoZUurYuBuYG46c6P71UGXMzmriLzCCBvKQWBUv3vPB3m1SATMhp3uEjXHJ42jFg7myX"
+        );
+    }
+
+    #[test]
+    fn test_hex_vector() {
+        assert_eq!(
+            derive_hex(&test_master(), 64, 0).unwrap(),
+            "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c"
+        );
+    }
+
+    #[test]
+    fn test_password_base64_vector() {
+        assert_eq!(derive_password_base64(&test_master(), 21, 0).unwrap(), "dKLoepugzdVJvdL56ogNV");
+    }
+
+    #[test]
+    fn test_password_base85_vector() {
+        assert_eq!(derive_password_base85(&test_master(), 12, 0).unwrap(), "_s`{TW89)i4`");
+    }
+
+    #[test]
+    fn test_parameter_validation() {
+        assert!(derive_mnemonic(&test_master(), 0, 15, 0).is_err());
+        assert!(derive_hex(&test_master(), 8, 0).is_err());
+        assert!(derive_password_base64(&test_master(), 10, 0).is_err());
+        assert!(language_from_index(10).is_err());
+    }
+
+    #[test]
+    fn test_base85_encoding() {
+        assert_eq!(base85_encode(&[0, 0, 0, 0]), "00000");
+        assert_eq!(base85_encode(&[0xff, 0xff, 0xff, 0xff]).len(), 5);
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::{Result, bail};
+
+// BIP-380 descriptor checksum
+// Reference: https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki
+const INPUT_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u64; 5] = [0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd];
+
+fn polymod(symbols: &[u64]) -> u64 {
+    let mut chk: u64 = 1;
+    for &value in symbols {
+        let top = chk >> 35;
+        chk = ((chk & 0x7ffffffff) << 5) ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn expand(descriptor: &str) -> Result<Vec<u64>> {
+    let mut symbols = Vec::new();
+    let mut groups = Vec::new();
+
+    for c in descriptor.chars() {
+        let value = match INPUT_CHARSET.find(c) {
+            Some(position) => position as u64,
+            None => bail!("Invalid character '{}' in descriptor", c),
+        };
+        symbols.push(value & 31);
+        groups.push(value >> 5);
+        if groups.len() == 3 {
+            symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+            groups.clear();
+        }
+    }
+
+    match groups.len() {
+        1 => symbols.push(groups[0]),
+        2 => symbols.push(groups[0] * 3 + groups[1]),
+        _ => {}
+    }
+
+    Ok(symbols)
+}
+
+/// Computes the 8-character checksum of a descriptor (without the `#` suffix)
+pub fn descriptor_checksum(descriptor: &str) -> Result<String> {
+    let mut symbols = expand(descriptor)?;
+    symbols.extend_from_slice(&[0; 8]);
+    let checksum = polymod(&symbols) ^ 1;
+
+    Ok((0..8)
+        .map(|i| CHECKSUM_CHARSET[((checksum >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+/// Appends `#checksum` to a descriptor
+pub fn with_checksum(descriptor: &str) -> Result<String> {
+    Ok(format!("{}#{}", descriptor, descriptor_checksum(descriptor)?))
+}
+
+/// Single-key output descriptor for an account key and BIP purpose.
+/// `origin` is the key origin without brackets (e.g. `73c5da0a/84'/0'/0'`); `change` is 0 for
+/// receive and 1 for change addresses.
+pub fn account_descriptor(purpose: u32, origin: Option<&str>, xpub: &str, change: u32) -> Result<String> {
+    let key = match origin {
+        Some(origin) => format!("[{}]{}/{}/*", origin, xpub, change),
+        None => format!("{}/{}/*", xpub, change),
+    };
+
+    let descriptor = match purpose {
+        44 => format!("pkh({})", key),
+        49 => format!("sh(wpkh({}))", key),
+        84 => format!("wpkh({})", key),
+        86 => format!("tr({})", key),
+        _ => bail!("No output descriptor template for BIP-{}", purpose),
+    };
+
+    with_checksum(&descriptor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bip380_checksum_vector() {
+        assert_eq!(with_checksum("raw(deadbeef)").unwrap(), "raw(deadbeef)#89f8spxm");
+    }
+
+    #[test]
+    fn test_account_descriptor() {
+        let xpub = "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+        let descriptor = account_descriptor(84, Some("73c5da0a/84'/0'/0'"), xpub, 0).unwrap();
+        assert_eq!(
+            descriptor,
+            format!("wpkh([73c5da0a/84'/0'/0']{}/0/*)#wc3n3van", xpub)
+        );
+        assert!(account_descriptor(85, None, xpub, 0).is_err());
+    }
+}
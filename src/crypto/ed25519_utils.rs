@@ -32,12 +32,20 @@ pub fn derive_ed25519_key_from_mnemonic(
 /// SLIP-0010 ed25519 key derivation implementation
 /// Reference: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
 pub fn derive_slip0010_ed25519(seed: &[u8], path: &str) -> Result<(Vec<u8>, Vec<u8>)> {
+    // Start with master key from seed
+    let master_key = derive_master_key_ed25519(seed)?;
+
+    derive_slip0010_ed25519_from_master(&master_key, path)
+}
+
+/// Same derivation as `derive_slip0010_ed25519`, starting from an already derived master key
+/// instead of a seed (used by accounts backed by a SLIP-10 master secret)
+pub fn derive_slip0010_ed25519_from_master(master_key: &[u8; 32], path: &str) -> Result<(Vec<u8>, Vec<u8>)> {
     // Parse derivation path (e.g., "m/44'/501'/0'/0'")
     let path_components = parse_derivation_path(path)?;
-    
-    // Start with master key from seed
-    let mut key = derive_master_key_ed25519(seed)?;
-    
+
+    let mut key = *master_key;
+
     // Derive each level in the path
     for &index in &path_components {
         key = derive_child_key_ed25519(&key, index)?;
@@ -217,6 +225,58 @@ pub fn derive_slip10_ed25519_key_from_mnemonic(
     Ok((private_key.to_vec(), verifying_key.to_bytes().to_vec()))
 }
 
+/// Computes the SLIP-0010 ed25519 master key and chain code for a seed
+pub fn slip10_ed25519_master_from_seed(seed: &[u8]) -> Result<([u8; 32], [u8; 32])> {
+    type HmacSha512 = Hmac<sha2::Sha512>;
+
+    let mut mac = HmacSha512::new_from_slice(b"ed25519 seed")
+        .context("Failed to create HMAC")?;
+    mac.update(seed);
+    let result = mac.finalize().into_bytes();
+
+    let mut key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    key.copy_from_slice(&result[0..32]);
+    chain_code.copy_from_slice(&result[32..64]);
+
+    Ok((key, chain_code))
+}
+
+/// Standard SLIP-0010 ed25519 derivation from a master key and chain code
+/// Produces the same keys as `derive_slip10_ed25519_key_from_mnemonic` when given the
+/// master secret of the mnemonic's seed
+pub fn derive_slip10_ed25519_from_master(
+    master_key: &[u8; 32],
+    chain_code: &[u8; 32],
+    derivation_path: &str,
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    type HmacSha512 = Hmac<sha2::Sha512>;
+
+    let path_components = parse_derivation_path(derivation_path)?;
+
+    let mut key = *master_key;
+    let mut chain = *chain_code;
+    for &index in &path_components {
+        // SLIP-0010 only defines hardened derivation for ed25519
+        let hardened_index = index | 0x80000000;
+
+        let mut mac = HmacSha512::new_from_slice(&chain)
+            .context("Failed to create HMAC for child derivation")?;
+        mac.update(&[0x00]);
+        mac.update(&key);
+        mac.update(&hardened_index.to_be_bytes());
+        let result = mac.finalize().into_bytes();
+
+        key.copy_from_slice(&result[0..32]);
+        chain.copy_from_slice(&result[32..64]);
+    }
+
+    let signing_key = SigningKey::from_bytes(&key);
+    let verifying_key = signing_key.verifying_key();
+
+    Ok((key.to_vec(), verifying_key.to_bytes().to_vec()))
+}
+
 /// Cardano-specific key derivation using the official cardano-serialization-lib
 /// This uses the official Cardano BIP32 implementation
 pub fn derive_cardano_key_from_mnemonic(
@@ -336,4 +396,21 @@ mod tests {
         println!("Private key (hex): {}", hex::encode(&private_key2));
         println!("Public key (hex): {}", hex::encode(&public_key2));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_slip10_from_master_matches_seed_derivation() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let seed = Mnemonic::from_str(mnemonic).unwrap().to_seed("");
+        let (master_key, chain_code) = slip10_ed25519_master_from_seed(&seed).unwrap();
+
+        for path in ["m/44'/148'/0'", "m/44'/501'/3'/0'"] {
+            let expected = derive_slip10_ed25519_key_from_mnemonic(mnemonic, None, path).unwrap();
+            let actual = derive_slip10_ed25519_from_master(&master_key, &chain_code, path).unwrap();
+            assert_eq!(expected, actual);
+
+            let expected = derive_ed25519_key_from_mnemonic(mnemonic, None, path).unwrap();
+            let actual = derive_slip0010_ed25519_from_master(&master_key, path).unwrap();
+            assert_eq!(expected, actual);
+        }
+    }
+}
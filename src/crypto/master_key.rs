@@ -0,0 +1,165 @@
+use anyhow::{Result, Context, bail};
+use bitcoin::bip32::Xpriv;
+use std::str::FromStr;
+
+use crate::crypto::derive_path_secp256k1;
+use crate::crypto::ed25519_utils::{derive_slip0010_ed25519_from_master, derive_slip10_ed25519_from_master};
+
+/// Stored in place of the mnemonic for accounts created from a master key
+pub const MASTER_KEY_MNEMONIC_PLACEHOLDER: &str = "[MASTER_PRIVATE_KEY_SOURCE]";
+
+/// Prefix used when storing a SLIP-10 ed25519 master secret
+pub const SLIP10_ED25519_PREFIX: &str = "slip10-ed25519:";
+
+/// Root secret of an account that was created without its mnemonic
+///
+/// * `Bip32` - a depth-0 xprv/tprv; derives every secp256k1 chain
+/// * `Slip10Ed25519` - the SLIP-10 ed25519 master key and chain code
+///   (HMAC-SHA512("ed25519 seed", seed)); derives every SLIP-10 ed25519 chain
+#[derive(Clone)]
+pub enum MasterKey {
+    Bip32(Xpriv),
+    Slip10Ed25519 { key: [u8; 32], chain_code: [u8; 32] },
+}
+
+impl MasterKey {
+    /// Parses an `xprv...`/`tprv...` string or a 64-byte hex SLIP-10 master secret
+    /// (`key || chain_code`, optionally prefixed with `slip10-ed25519:`)
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+
+        if value.starts_with("xprv") || value.starts_with("tprv") {
+            let xpriv = Xpriv::from_str(value)
+                .context("Invalid BIP-32 extended private key")?;
+            if xpriv.depth != 0 {
+                bail!(
+                    "Extended private key has depth {}; a master (depth 0) xprv is required to derive full BIP-44 paths",
+                    xpriv.depth
+                );
+            }
+            return Ok(Self::Bip32(xpriv));
+        }
+
+        let hex_value = value.strip_prefix(SLIP10_ED25519_PREFIX).unwrap_or(value);
+        let hex_value = hex_value.strip_prefix("0x").unwrap_or(hex_value);
+        let bytes = hex::decode(hex_value)
+            .context("Master key must be an xprv/tprv or a hex SLIP-10 ed25519 master secret")?;
+        if bytes.len() != 64 {
+            bail!(
+                "SLIP-10 ed25519 master secret must be 64 bytes (32-byte key followed by 32-byte chain code), got {}",
+                bytes.len()
+            );
+        }
+
+        let mut key = [0u8; 32];
+        let mut chain_code = [0u8; 32];
+        key.copy_from_slice(&bytes[0..32]);
+        chain_code.copy_from_slice(&bytes[32..64]);
+
+        Ok(Self::Slip10Ed25519 { key, chain_code })
+    }
+
+    /// Canonical string stored in `master_accounts.master_private_key`
+    pub fn to_storage_string(&self) -> String {
+        match self {
+            Self::Bip32(xpriv) => xpriv.to_string(),
+            Self::Slip10Ed25519 { key, chain_code } => {
+                format!("{}{}{}", SLIP10_ED25519_PREFIX, hex::encode(key), hex::encode(chain_code))
+            }
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::Bip32(_) => "BIP-32 extended private key (secp256k1 chains)",
+            Self::Slip10Ed25519 { .. } => "SLIP-10 ed25519 master secret (ed25519 chains)",
+        }
+    }
+
+    /// Derives a secp256k1 private key at `path` using BIP-32
+    pub fn derive_secp256k1(&self, path: &str) -> Result<Vec<u8>> {
+        match self {
+            Self::Bip32(xpriv) => {
+                let derived = derive_path_secp256k1(xpriv, path)?;
+                Ok(derived.private_key.secret_bytes().to_vec())
+            }
+            Self::Slip10Ed25519 { .. } => {
+                bail!("secp256k1 chains need a BIP-32 xprv master key; this account holds a SLIP-10 ed25519 master secret")
+            }
+        }
+    }
+
+    /// Derives an ed25519 private key at `path` using standard SLIP-10 (Stellar, Solana)
+    pub fn derive_slip10_ed25519(&self, path: &str) -> Result<Vec<u8>> {
+        match self {
+            Self::Slip10Ed25519 { key, chain_code } => {
+                let (private_key, _) = derive_slip10_ed25519_from_master(key, chain_code, path)?;
+                Ok(private_key)
+            }
+            Self::Bip32(_) => bail!("ed25519 chains need a SLIP-10 ed25519 master secret; this account holds a BIP-32 xprv"),
+        }
+    }
+
+    /// Derives an ed25519 private key at `path` the way `derive_ed25519_key_from_mnemonic`
+    /// does (Hedera, Algorand, Polkadot, Sui, IOTA, TON)
+    pub fn derive_ed25519(&self, path: &str) -> Result<Vec<u8>> {
+        match self {
+            Self::Slip10Ed25519 { key, .. } => {
+                let (private_key, _) = derive_slip0010_ed25519_from_master(key, path)?;
+                Ok(private_key)
+            }
+            Self::Bip32(_) => bail!("ed25519 chains need a SLIP-10 ed25519 master secret; this account holds a BIP-32 xprv"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::bip32::derive_secp256k1_key_from_mnemonic;
+    use crate::crypto::ed25519_utils::{slip10_ed25519_master_from_seed, derive_slip10_ed25519_key_from_mnemonic};
+    use crate::crypto::{derive_master_key_secp256k1, mnemonic_to_seed};
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_xprv_matches_mnemonic_derivation() {
+        let seed = mnemonic_to_seed(MNEMONIC, None).unwrap();
+        let xprv = derive_master_key_secp256k1(&seed).unwrap().to_string();
+
+        let master_key = MasterKey::parse(&xprv).unwrap();
+        assert_eq!(master_key.to_storage_string(), xprv);
+
+        let path = "m/44'/60'/0'/0/0";
+        let (expected, _) = derive_secp256k1_key_from_mnemonic(MNEMONIC, None, path).unwrap();
+        assert_eq!(master_key.derive_secp256k1(path).unwrap(), expected);
+        assert!(master_key.derive_slip10_ed25519("m/44'/148'/0'").is_err());
+    }
+
+    #[test]
+    fn test_slip10_master_matches_mnemonic_derivation() {
+        let seed = mnemonic_to_seed(MNEMONIC, None).unwrap();
+        let (key, chain_code) = slip10_ed25519_master_from_seed(&seed).unwrap();
+        let stored = format!("{}{}", hex::encode(key), hex::encode(chain_code));
+
+        let master_key = MasterKey::parse(&stored).unwrap();
+        let round_trip = MasterKey::parse(&master_key.to_storage_string()).unwrap();
+        assert_eq!(round_trip.to_storage_string(), master_key.to_storage_string());
+
+        let path = "m/44'/148'/0'";
+        let (expected, _) = derive_slip10_ed25519_key_from_mnemonic(MNEMONIC, None, path).unwrap();
+        assert_eq!(master_key.derive_slip10_ed25519(path).unwrap(), expected);
+        assert!(master_key.derive_secp256k1("m/44'/0'/0'/0/0").is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_master_xprv_and_bad_lengths() {
+        let seed = mnemonic_to_seed(MNEMONIC, None).unwrap();
+        let master = derive_master_key_secp256k1(&seed).unwrap();
+        let account_level = derive_path_secp256k1(&master, "m/44'/0'/0'").unwrap();
+
+        assert!(MasterKey::parse(&account_level.to_string()).is_err());
+        assert!(MasterKey::parse("abcd").is_err());
+        assert!(MasterKey::parse(&"00".repeat(32)).is_err());
+    }
+}
@@ -7,6 +7,16 @@ use std::str::FromStr;
 pub mod bip32;
 pub mod bip39_utils;
 pub mod ed25519_utils;
+pub mod vault;
+pub mod session;
+pub mod backup;
+pub mod slip39;
+pub mod bip85;
+pub mod master_key;
+pub mod slip132;
+pub mod watch_only;
+pub mod descriptor;
+pub mod psbt;
 
 
 pub fn validate_mnemonic(mnemonic: &str) -> Result<Mnemonic> {
@@ -57,6 +67,11 @@ pub fn validate_mnemonic_with_account(
 ) -> Result<bool> {
     use crate::crypto::bip39_utils::{normalize_mnemonic, generate_seed_from_mnemonic};
 
+    // Key-backed accounts are verified with their master key instead
+    if master_account.master_key_matches(mnemonic) {
+        return Ok(true);
+    }
+
     // Normalize the mnemonic
     let normalized_mnemonic = normalize_mnemonic(mnemonic);
 
@@ -74,11 +89,11 @@ pub fn validate_mnemonic_with_account(
     // Compare with stored mnemonic or derive master private key to compare
     if !master_account.mnemonic.is_empty() {
         // Direct mnemonic comparison
-        Ok(normalized_mnemonic == master_account.mnemonic)
+        Ok(normalized_mnemonic == master_account.mnemonic.expose()?)
     } else {
         // Compare derived master private key
         let derived_master_private = hex::encode(&seed[0..32]);
-        Ok(derived_master_private == master_account.master_private_key)
+        Ok(derived_master_private == master_account.master_private_key.expose()?)
     }
 }
 
@@ -0,0 +1,629 @@
+use anyhow::{Result, Context, bail};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use bitcoin::bip32::{Fingerprint, KeySource, Xpriv};
+use bitcoin::psbt::{GetKey, Input, KeyRequest, Psbt};
+use bitcoin::secp256k1::{Secp256k1, Signing};
+use bitcoin::{Address, CompressedPublicKey, Network, NetworkKind, PrivateKey, PublicKey, Script, ScriptBuf};
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
+
+use crate::blockchain::{SignedTransaction, SupportedBlockchain};
+use crate::crypto::bip32::private_key_to_public_key_secp256k1;
+
+/// BIP-174 magic bytes ("psbt" followed by 0xff)
+const PSBT_MAGIC: &[u8] = b"psbt\xff";
+
+/// On-disk form of a PSBT; the signed PSBT is written back in the same form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PsbtEncoding {
+    Base64,
+    Binary,
+}
+
+/// Reads a binary PSBT or its base64 text form
+pub fn decode_psbt(data: &[u8]) -> Result<(Psbt, PsbtEncoding)> {
+    if data.starts_with(PSBT_MAGIC) {
+        let psbt = Psbt::deserialize(data).context("Invalid binary PSBT")?;
+        return Ok((psbt, PsbtEncoding::Binary));
+    }
+
+    let text = std::str::from_utf8(data)
+        .context("PSBT file is neither a binary PSBT nor base64 text")?;
+    let compact: String = text.split_whitespace().collect();
+    let bytes = BASE64.decode(compact.as_bytes()).context("Invalid base64 PSBT")?;
+    if !bytes.starts_with(PSBT_MAGIC) {
+        bail!("Not a PSBT (missing 'psbt' magic bytes)");
+    }
+
+    let psbt = Psbt::deserialize(&bytes).context("Invalid PSBT")?;
+    Ok((psbt, PsbtEncoding::Base64))
+}
+
+pub fn encode_psbt(psbt: &Psbt, encoding: PsbtEncoding) -> Vec<u8> {
+    match encoding {
+        PsbtEncoding::Binary => psbt.serialize(),
+        PsbtEncoding::Base64 => format!("{}\n", BASE64.encode(psbt.serialize())).into_bytes(),
+    }
+}
+
+/// A BIP-32 key origin listed by a PSBT input
+///
+/// `public_key` is the 33-byte compressed key for ECDSA inputs and the 32-byte
+/// x-only key for Taproot key-path inputs.
+#[derive(Debug, Clone)]
+pub struct InputKeyOrigin {
+    pub input: usize,
+    pub public_key: Vec<u8>,
+    pub source: KeySource,
+    pub taproot: bool,
+}
+
+/// Collects the key origins of every input (BIP-174 `bip32_derivation` and BIP-371
+/// `tap_key_origins`). Taproot script-path keys are skipped: only key-path spends are signed.
+pub fn input_key_origins(psbt: &Psbt) -> Vec<InputKeyOrigin> {
+    let mut origins = Vec::new();
+
+    for (index, input) in psbt.inputs.iter().enumerate() {
+        for (public_key, source) in &input.bip32_derivation {
+            origins.push(InputKeyOrigin {
+                input: index,
+                public_key: public_key.serialize().to_vec(),
+                source: source.clone(),
+                taproot: false,
+            });
+        }
+        for (public_key, (leaf_hashes, source)) in &input.tap_key_origins {
+            if leaf_hashes.is_empty() {
+                origins.push(InputKeyOrigin {
+                    input: index,
+                    public_key: public_key.serialize().to_vec(),
+                    source: source.clone(),
+                    taproot: true,
+                });
+            }
+        }
+    }
+
+    origins
+}
+
+impl InputKeyOrigin {
+    /// Whether a stored compressed public key is the key this origin refers to
+    pub fn matches_public_key(&self, compressed: &[u8]) -> bool {
+        if self.taproot {
+            compressed.len() == 33 && compressed[1..] == self.public_key[..]
+        } else {
+            compressed == &self.public_key[..]
+        }
+    }
+}
+
+/// Private keys offered to the PSBT signer, indexed by key origin
+#[derive(Default)]
+pub struct PsbtSigningKeys {
+    keys: BTreeMap<KeySource, PrivateKey>,
+}
+
+impl PsbtSigningKeys {
+    pub fn insert(&mut self, source: KeySource, secret: &[u8]) -> Result<()> {
+        let private_key = PrivateKey::from_slice(secret, NetworkKind::Main)
+            .context("Invalid secp256k1 private key")?;
+        self.keys.insert(source, private_key);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl GetKey for PsbtSigningKeys {
+    type Error = Infallible;
+
+    fn get_key<C: Signing>(&self, key_request: KeyRequest, _: &Secp256k1<C>) -> Result<Option<PrivateKey>, Self::Error> {
+        match key_request {
+            KeyRequest::Bip32(source) => Ok(self.keys.get(&source).copied()),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Signs every input that has a key in `keys`: P2PKH, P2SH-P2WPKH and P2WPKH with ECDSA,
+/// P2TR key-path spends with a BIP-341 tweaked Schnorr signature.
+/// Returns the signing errors by input index; inputs without a matching key are left untouched.
+pub fn sign_psbt(psbt: &mut Psbt, keys: &PsbtSigningKeys) -> BTreeMap<usize, String> {
+    let secp = Secp256k1::new();
+    match psbt.sign(keys, &secp) {
+        Ok(_) => BTreeMap::new(),
+        Err((_, errors)) => errors.into_iter()
+            .map(|(index, error)| (index, error.to_string()))
+            .collect(),
+    }
+}
+
+/// Number of signatures an input carries (ECDSA partial signatures and Taproot signatures)
+pub fn input_signature_count(input: &Input) -> usize {
+    input.partial_sigs.len() + input.tap_script_sigs.len() + usize::from(input.tap_key_sig.is_some())
+}
+
+/// Amounts of a PSBT, in satoshis (litoshis for Litecoin)
+pub struct PsbtSummary {
+    /// Value of each spent output, `None` when the PSBT omits the UTXO
+    pub inputs: Vec<Option<u64>>,
+    /// Inputs whose amount comes only from `witness_utxo` of a non-Taproot script: a segwit v0
+    /// signature does not commit to the other inputs' amounts, so the value may be a lie (CVE-2020-14199)
+    pub unverified_inputs: BTreeSet<usize>,
+    pub outputs: Vec<(ScriptBuf, u64)>,
+}
+
+impl PsbtSummary {
+    /// `None` when an input amount is unknown; fails if the amounts overflow a u64
+    pub fn total_in(&self) -> Result<Option<u64>> {
+        let mut total: u64 = 0;
+        for value in &self.inputs {
+            let Some(value) = value else { return Ok(None) };
+            total = total.checked_add(*value)
+                .ok_or_else(|| anyhow::anyhow!("PSBT input amounts overflow"))?;
+        }
+        Ok(Some(total))
+    }
+
+    pub fn total_out(&self) -> Result<u64> {
+        self.outputs.iter().try_fold(0u64, |total, (_, value)| {
+            total.checked_add(*value).ok_or_else(|| anyhow::anyhow!("PSBT output amounts overflow"))
+        })
+    }
+
+    /// `None` when an input amount is unknown or the outputs exceed the inputs
+    pub fn fee(&self) -> Result<Option<u64>> {
+        let total_out = self.total_out()?;
+        Ok(self.total_in()?.and_then(|total_in| total_in.checked_sub(total_out)))
+    }
+}
+
+/// Reads the input amounts, preferring the full previous transaction (`non_witness_utxo`), whose txid
+/// must match the outpoint being spent. Fails when the two UTXO fields of an input disagree.
+pub fn summarize_psbt(psbt: &Psbt) -> Result<PsbtSummary> {
+    let mut inputs = Vec::new();
+    let mut unverified_inputs = BTreeSet::new();
+
+    for (index, (input, tx_input)) in psbt.inputs.iter().zip(&psbt.unsigned_tx.input).enumerate() {
+        let outpoint = tx_input.previous_output;
+
+        let previous_output = match &input.non_witness_utxo {
+            Some(previous_tx) => {
+                if previous_tx.compute_txid() != outpoint.txid {
+                    bail!("Input #{}: non_witness_utxo is transaction {}, not {} being spent", index, previous_tx.compute_txid(), outpoint.txid);
+                }
+                let output = previous_tx.output.get(outpoint.vout as usize)
+                    .ok_or_else(|| anyhow::anyhow!("Input #{}: non_witness_utxo has no output {}", index, outpoint.vout))?;
+                Some(output)
+            }
+            None => None,
+        };
+
+        let value = match (previous_output, &input.witness_utxo) {
+            (Some(output), Some(witness_utxo)) if output != witness_utxo => {
+                bail!("Input #{}: witness_utxo disagrees with the output in non_witness_utxo", index);
+            }
+            (Some(output), _) => Some(output.value.to_sat()),
+            (None, Some(witness_utxo)) => {
+                if !witness_utxo.script_pubkey.is_p2tr() {
+                    unverified_inputs.insert(index);
+                }
+                Some(witness_utxo.value.to_sat())
+            }
+            (None, None) => None,
+        };
+        inputs.push(value);
+    }
+
+    let outputs = psbt.unsigned_tx.output.iter()
+        .map(|output| (output.script_pubkey.clone(), output.value.to_sat()))
+        .collect();
+
+    Ok(PsbtSummary { inputs, unverified_inputs, outputs })
+}
+
+/// Whether output `index` pays back to a key under one of `roots`. Key origins in the PSBT are only
+/// claims: the key is re-derived from the matching root at the claimed path and the output script
+/// must be that key's P2PKH, P2SH-P2WPKH, P2WPKH or Taproot key-path script.
+pub fn is_change_output(psbt: &Psbt, index: usize, roots: &BTreeMap<Fingerprint, Xpriv>) -> bool {
+    let (Some(output), Some(tx_output)) = (psbt.outputs.get(index), psbt.unsigned_tx.output.get(index)) else {
+        return false;
+    };
+    let script = &tx_output.script_pubkey;
+    let secp = Secp256k1::new();
+
+    let derive = |(fingerprint, path): &KeySource| {
+        let root = roots.get(fingerprint)?;
+        root.derive_priv(&secp, path).ok().map(|child| child.private_key.public_key(&secp))
+    };
+
+    let ecdsa_change = output.bip32_derivation.iter().any(|(public_key, source)| {
+        if derive(source) != Some(*public_key) {
+            return false;
+        }
+        let wpkh = ScriptBuf::new_p2wpkh(&CompressedPublicKey(*public_key).wpubkey_hash());
+        *script == wpkh
+            || *script == ScriptBuf::new_p2sh(&wpkh.script_hash())
+            || *script == ScriptBuf::new_p2pkh(&PublicKey::new(*public_key).pubkey_hash())
+    });
+
+    let taproot_change = output.tap_key_origins.iter().any(|(x_only, (leaf_hashes, source))| {
+        leaf_hashes.is_empty()
+            && derive(source).map(|public_key| public_key.x_only_public_key().0) == Some(*x_only)
+            && *script == ScriptBuf::new_p2tr(&secp, *x_only, None)
+    });
+
+    ecdsa_change || taproot_change
+}
+
+/// Satoshi amount as a decimal coin amount, e.g. `0.00150000 BTC`
+pub fn format_amount(value: u64, unit: &str) -> String {
+    format!("{}.{:08} {}", value / 100_000_000, value % 100_000_000, unit)
+}
+
+// ========== HANDLER INTEGRATION ==========
+// `BlockchainHandler::describe_transaction` / `sign_transaction` for Bitcoin and Litecoin,
+// with a single wallet key instead of the vault-wide key search done by `sign-psbt`.
+
+pub fn describe_psbt(blockchain: &SupportedBlockchain, data: &[u8]) -> Result<Vec<(String, String)>> {
+    let (psbt, _) = decode_psbt(data)?;
+    let summary = summarize_psbt(&psbt)?;
+    let unit = if *blockchain == SupportedBlockchain::Litecoin { "LTC" } else { "BTC" };
+
+    let mut details = Vec::new();
+    for (index, tx_input) in psbt.unsigned_tx.input.iter().enumerate() {
+        let amount = match summary.inputs[index] {
+            Some(value) if summary.unverified_inputs.contains(&index) => format!("{}, amount unverified", format_amount(value, unit)),
+            Some(value) => format_amount(value, unit),
+            None => "amount unknown".to_string(),
+        };
+        details.push((format!("Input #{}", index), format!("{} ({})", tx_input.previous_output, amount)));
+    }
+    for (index, (script, value)) in summary.outputs.iter().enumerate() {
+        let destination = script_address(script, blockchain)
+            .unwrap_or_else(|| format!("script {}", hex::encode(script.as_bytes())));
+        details.push((format!("Output #{}", index), format!("{} {}", destination, format_amount(*value, unit))));
+    }
+    details.push(("Fee".to_string(), match summary.fee()? {
+        Some(fee) if !summary.unverified_inputs.is_empty() => format!("{} (unverified input amounts)", format_amount(fee, unit)),
+        Some(fee) => format_amount(fee, unit),
+        None => "unknown".to_string(),
+    }));
+    Ok(details)
+}
+
+/// Signs every input whose key origins list the public key of `secret` and returns the
+/// updated PSBT as base64. The PSBT still has to be finalized, so no txid is reported.
+pub fn sign_psbt_with_key(secret: &[u8], data: &[u8]) -> Result<SignedTransaction> {
+    let (mut psbt, _) = decode_psbt(data)?;
+    let public_key = private_key_to_public_key_secp256k1(secret)?;
+
+    let mut keys = PsbtSigningKeys::default();
+    let mut inputs = BTreeSet::new();
+    for origin in input_key_origins(&psbt) {
+        if origin.matches_public_key(&public_key) {
+            keys.insert(origin.source.clone(), secret)?;
+            inputs.insert(origin.input);
+        }
+    }
+    if keys.is_empty() {
+        bail!("No PSBT input lists this wallet's key in its BIP-32 derivations");
+    }
+
+    let before: Vec<usize> = psbt.inputs.iter().map(input_signature_count).collect();
+    let errors = sign_psbt(&mut psbt, &keys);
+    for index in &inputs {
+        if input_signature_count(&psbt.inputs[*index]) <= before[*index] {
+            let reason = errors.get(index).map(String::as_str).unwrap_or("no signature was produced");
+            bail!("Input #{} could not be signed: {}", index, reason);
+        }
+    }
+
+    Ok(SignedTransaction {
+        encoded: BASE64.encode(psbt.serialize()),
+        hash: None,
+    })
+}
+
+/// Renders an output script as a Bitcoin or Litecoin address
+pub fn script_address(script: &Script, blockchain: &SupportedBlockchain) -> Option<String> {
+    match blockchain {
+        SupportedBlockchain::Bitcoin => Address::from_script(script, Network::Bitcoin)
+            .ok()
+            .map(|address| address.to_string()),
+        SupportedBlockchain::Litecoin => litecoin_script_address(script),
+        _ => None,
+    }
+}
+
+fn litecoin_script_address(script: &Script) -> Option<String> {
+    let bytes = script.as_bytes();
+
+    if script.is_p2pkh() {
+        let mut payload = vec![0x30u8]; // Litecoin mainnet P2PKH version byte
+        payload.extend_from_slice(&bytes[3..23]);
+        return Some(bitcoin::base58::encode_check(&payload));
+    }
+    if script.is_p2sh() {
+        let mut payload = vec![0x32u8]; // Litecoin mainnet P2SH version byte ('M' addresses)
+        payload.extend_from_slice(&bytes[2..22]);
+        return Some(bitcoin::base58::encode_check(&payload));
+    }
+
+    let version = script.witness_version()?;
+    let hrp = bech32::Hrp::parse("ltc").ok()?;
+    let version = bech32::Fe32::try_from(version.to_num()).ok()?;
+    bech32::segwit::encode(hrp, version, &bytes[2..]).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bitcoin::bip32::{DerivationPath, Fingerprint};
+    use bitcoin::hashes::Hash;
+    use bitcoin::secp256k1::XOnlyPublicKey;
+    use bitcoin::{absolute, transaction, Amount, CompressedPublicKey, OutPoint, PublicKey, Transaction, TxIn, TxOut, Txid};
+    use std::str::FromStr;
+    use crate::crypto::bip32::derive_secp256k1_key_from_mnemonic;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    const FINGERPRINT: &str = "73c5da0a";
+
+    fn spend(vout: u32) -> TxIn {
+        TxIn {
+            previous_output: OutPoint { txid: Txid::all_zeros(), vout },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sign_segwit_and_taproot_inputs() {
+        let secp = Secp256k1::new();
+        let fingerprint = Fingerprint::from_str(FINGERPRINT).unwrap();
+        let wpkh_path = "m/84'/0'/0'/0/0";
+        let tr_path = "m/86'/0'/0'/0/0";
+        let (wpkh_secret, wpkh_public) = derive_secp256k1_key_from_mnemonic(MNEMONIC, None, wpkh_path).unwrap();
+        let (tr_secret, tr_public) = derive_secp256k1_key_from_mnemonic(MNEMONIC, None, tr_path).unwrap();
+
+        let wpkh_key = CompressedPublicKey::from_slice(&wpkh_public).unwrap();
+        let tr_key = XOnlyPublicKey::from_slice(&tr_public[1..]).unwrap();
+
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![spend(0), spend(1)],
+            output: vec![TxOut {
+                value: Amount::from_sat(190_000),
+                script_pubkey: ScriptBuf::new_p2wpkh(&wpkh_key.wpubkey_hash()),
+            }],
+        };
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+
+        psbt.inputs[0].witness_utxo = Some(TxOut {
+            value: Amount::from_sat(100_000),
+            script_pubkey: ScriptBuf::new_p2wpkh(&wpkh_key.wpubkey_hash()),
+        });
+        psbt.inputs[0].bip32_derivation.insert(
+            wpkh_key.0,
+            (fingerprint, DerivationPath::from_str(wpkh_path).unwrap()),
+        );
+
+        psbt.inputs[1].witness_utxo = Some(TxOut {
+            value: Amount::from_sat(100_000),
+            script_pubkey: ScriptBuf::new_p2tr(&secp, tr_key, None),
+        });
+        psbt.inputs[1].tap_internal_key = Some(tr_key);
+        psbt.inputs[1].tap_key_origins.insert(
+            tr_key,
+            (vec![], (fingerprint, DerivationPath::from_str(tr_path).unwrap())),
+        );
+
+        let origins = input_key_origins(&psbt);
+        assert_eq!(origins.len(), 2);
+        assert!(origins[0].matches_public_key(&wpkh_public));
+        assert!(origins[1].taproot && origins[1].matches_public_key(&tr_public));
+
+        let summary = summarize_psbt(&psbt).unwrap();
+        assert_eq!(summary.total_in().unwrap(), Some(200_000));
+        assert_eq!(summary.unverified_inputs, BTreeSet::from([0]));
+        assert_eq!(summary.fee().unwrap(), Some(10_000));
+
+        let mut keys = PsbtSigningKeys::default();
+        for origin in &origins {
+            let secret = if origin.taproot { &tr_secret } else { &wpkh_secret };
+            keys.insert(origin.source.clone(), secret).unwrap();
+        }
+
+        let errors = sign_psbt(&mut psbt, &keys);
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert!(psbt.inputs[0].partial_sigs.contains_key(&PublicKey::new(wpkh_key.0)));
+        assert!(psbt.inputs[1].tap_key_sig.is_some());
+
+        let encoded = encode_psbt(&psbt, PsbtEncoding::Base64);
+        let (decoded, encoding) = decode_psbt(&encoded).unwrap();
+        assert_eq!(encoding, PsbtEncoding::Base64);
+        assert_eq!(decoded.serialize(), psbt.serialize());
+    }
+
+    #[test]
+    fn test_sign_psbt_with_single_wallet_key() {
+        let fingerprint = Fingerprint::from_str(FINGERPRINT).unwrap();
+        let path = "m/84'/0'/0'/0/0";
+        let (secret, public) = derive_secp256k1_key_from_mnemonic(MNEMONIC, None, path).unwrap();
+        let (other_secret, _) = derive_secp256k1_key_from_mnemonic(MNEMONIC, None, "m/84'/0'/0'/0/1").unwrap();
+        let key = CompressedPublicKey::from_slice(&public).unwrap();
+
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![spend(0)],
+            output: vec![TxOut {
+                value: Amount::from_sat(99_000),
+                script_pubkey: ScriptBuf::new_p2wpkh(&key.wpubkey_hash()),
+            }],
+        };
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        psbt.inputs[0].witness_utxo = Some(TxOut {
+            value: Amount::from_sat(100_000),
+            script_pubkey: ScriptBuf::new_p2wpkh(&key.wpubkey_hash()),
+        });
+        psbt.inputs[0].bip32_derivation.insert(key.0, (fingerprint, DerivationPath::from_str(path).unwrap()));
+        let encoded = encode_psbt(&psbt, PsbtEncoding::Base64);
+
+        let details = describe_psbt(&SupportedBlockchain::Bitcoin, &encoded).unwrap();
+        assert_eq!(details.last().unwrap(), &("Fee".to_string(), "0.00001000 BTC (unverified input amounts)".to_string()));
+
+        let signed = sign_psbt_with_key(&secret, &encoded).unwrap();
+        let (signed_psbt, _) = decode_psbt(signed.encoded.as_bytes()).unwrap();
+        assert!(signed_psbt.inputs[0].partial_sigs.contains_key(&PublicKey::new(key.0)));
+        assert!(signed.hash.is_none());
+
+        assert!(sign_psbt_with_key(&other_secret, &encoded).is_err());
+    }
+
+    #[test]
+    fn test_unknown_keys_leave_psbt_unsigned() {
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![spend(0)],
+            output: vec![],
+        };
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        let keys = PsbtSigningKeys::default();
+
+        sign_psbt(&mut psbt, &keys);
+        assert_eq!(input_signature_count(&psbt.inputs[0]), 0);
+        assert_eq!(summarize_psbt(&psbt).unwrap().fee().unwrap(), None);
+    }
+
+    #[test]
+    fn test_summary_checks_non_witness_utxo() {
+        let (_, public) = derive_secp256k1_key_from_mnemonic(MNEMONIC, None, "m/84'/0'/0'/0/0").unwrap();
+        let script_pubkey = ScriptBuf::new_p2wpkh(&CompressedPublicKey::from_slice(&public).unwrap().wpubkey_hash());
+        let previous_tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![spend(7)],
+            output: vec![TxOut { value: Amount::from_sat(50_000), script_pubkey: script_pubkey.clone() }],
+        };
+
+        let unsigned = |txid: Txid| Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![TxIn { previous_output: OutPoint { txid, vout: 0 }, ..Default::default() }],
+            output: vec![TxOut { value: Amount::from_sat(40_000), script_pubkey: script_pubkey.clone() }],
+        };
+
+        // Full previous transaction: amount is verified
+        let mut psbt = Psbt::from_unsigned_tx(unsigned(previous_tx.compute_txid())).unwrap();
+        psbt.inputs[0].non_witness_utxo = Some(previous_tx.clone());
+        psbt.inputs[0].witness_utxo = Some(previous_tx.output[0].clone());
+        let summary = summarize_psbt(&psbt).unwrap();
+        assert_eq!(summary.fee().unwrap(), Some(10_000));
+        assert!(summary.unverified_inputs.is_empty());
+
+        // witness_utxo that disagrees with the previous transaction
+        psbt.inputs[0].witness_utxo = Some(TxOut { value: Amount::from_sat(500_000), script_pubkey: script_pubkey.clone() });
+        assert!(summarize_psbt(&psbt).is_err());
+
+        // Previous transaction that is not the one being spent
+        let mut psbt = Psbt::from_unsigned_tx(unsigned(Txid::all_zeros())).unwrap();
+        psbt.inputs[0].non_witness_utxo = Some(previous_tx.clone());
+        assert!(summarize_psbt(&psbt).is_err());
+
+        // witness_utxo alone on a segwit v0 input is reported as unverified
+        psbt.inputs[0].non_witness_utxo = None;
+        psbt.inputs[0].witness_utxo = Some(previous_tx.output[0].clone());
+        assert_eq!(summarize_psbt(&psbt).unwrap().unverified_inputs, BTreeSet::from([0]));
+    }
+
+    #[test]
+    fn test_change_output_requires_derived_key_and_matching_script() {
+        let secp = Secp256k1::new();
+        let seed = crate::crypto::mnemonic_to_seed(MNEMONIC, None).unwrap();
+        let root = crate::crypto::derive_master_key_secp256k1(&seed).unwrap();
+        let fingerprint = root.fingerprint(&secp);
+        assert_eq!(fingerprint, Fingerprint::from_str(FINGERPRINT).unwrap());
+        let roots = BTreeMap::from([(fingerprint, root)]);
+
+        let change_path = "m/84'/0'/0'/1/0";
+        let tr_path = "m/86'/0'/0'/1/0";
+        let (_, change_public) = derive_secp256k1_key_from_mnemonic(MNEMONIC, None, change_path).unwrap();
+        let (_, tr_public) = derive_secp256k1_key_from_mnemonic(MNEMONIC, None, tr_path).unwrap();
+        let (_, attacker_public) = derive_secp256k1_key_from_mnemonic(MNEMONIC, Some("attacker"), change_path).unwrap();
+        let change_key = CompressedPublicKey::from_slice(&change_public).unwrap();
+        let attacker_key = CompressedPublicKey::from_slice(&attacker_public).unwrap();
+        let tr_key = XOnlyPublicKey::from_slice(&tr_public[1..]).unwrap();
+
+        let output = |script_pubkey: ScriptBuf| TxOut { value: Amount::from_sat(1_000), script_pubkey };
+        let tx = Transaction {
+            version: transaction::Version::TWO,
+            lock_time: absolute::LockTime::ZERO,
+            input: vec![spend(0)],
+            output: vec![
+                output(ScriptBuf::new_p2wpkh(&change_key.wpubkey_hash())),
+                output(ScriptBuf::new_p2wpkh(&attacker_key.wpubkey_hash())),
+                output(ScriptBuf::new_p2wpkh(&attacker_key.wpubkey_hash())),
+                output(ScriptBuf::new_p2tr(&secp, tr_key, None)),
+            ],
+        };
+        let mut psbt = Psbt::from_unsigned_tx(tx).unwrap();
+        let source = (fingerprint, DerivationPath::from_str(change_path).unwrap());
+
+        // Genuine change output
+        psbt.outputs[0].bip32_derivation.insert(change_key.0, source.clone());
+        // Our real key and origin attached to an output that pays someone else
+        psbt.outputs[1].bip32_derivation.insert(change_key.0, source.clone());
+        // Attacker key claimed to sit at our fingerprint and path
+        psbt.outputs[2].bip32_derivation.insert(attacker_key.0, source);
+        // Genuine Taproot change output
+        psbt.outputs[3].tap_key_origins.insert(
+            tr_key,
+            (vec![], (fingerprint, DerivationPath::from_str(tr_path).unwrap())),
+        );
+
+        assert!(is_change_output(&psbt, 0, &roots));
+        assert!(!is_change_output(&psbt, 1, &roots));
+        assert!(!is_change_output(&psbt, 2, &roots));
+        assert!(is_change_output(&psbt, 3, &roots));
+        assert!(!is_change_output(&psbt, 0, &BTreeMap::new()));
+    }
+
+    #[test]
+    fn test_summary_rejects_overflowing_amounts() {
+        let summary = PsbtSummary {
+            inputs: vec![Some(u64::MAX), Some(1)],
+            unverified_inputs: BTreeSet::new(),
+            outputs: vec![(ScriptBuf::new(), u64::MAX), (ScriptBuf::new(), 1)],
+        };
+        assert!(summary.total_in().is_err());
+        assert!(summary.total_out().is_err());
+        assert!(summary.fee().is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_non_psbt() {
+        assert!(decode_psbt(b"not a psbt").is_err());
+        assert!(decode_psbt(BASE64.encode(b"hello world").as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_litecoin_script_address() {
+        let (_, public_key) = derive_secp256k1_key_from_mnemonic(MNEMONIC, None, "m/84'/2'/0'/0/0").unwrap();
+        let key = CompressedPublicKey::from_slice(&public_key).unwrap();
+        let script = ScriptBuf::new_p2wpkh(&key.wpubkey_hash());
+
+        let address = script_address(&script, &SupportedBlockchain::Litecoin).unwrap();
+        assert!(address.starts_with("ltc1q"));
+        assert!(script_address(&script, &SupportedBlockchain::Bitcoin).unwrap().starts_with("bc1q"));
+    }
+}
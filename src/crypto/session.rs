@@ -0,0 +1,293 @@
+use anyhow::{Result, Context};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use crate::crypto::vault::VaultKey;
+
+/// Default lifetime of an unlocked session in minutes
+pub const DEFAULT_SESSION_TTL_MINUTES: i64 = 15;
+
+/// Longest session the CLI will cache a vault key for (24 hours)
+pub const MAX_SESSION_TTL_MINUTES: i64 = 24 * 60;
+
+const SESSION_DIR_NAME: &str = "wallet-backup";
+const SESSION_FILE_NAME: &str = "session.json";
+
+/// Cached vault key written by `unlock` and wiped by `lock`
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionFile {
+    vault_id: String, // Hex salt of the vault this key belongs to
+    key: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Per-user session directory: `$XDG_RUNTIME_DIR/wallet-backup`, otherwise `~/.wallet-backup`
+pub fn session_dir() -> Result<PathBuf> {
+    if let Some(runtime_dir) = std::env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        return Ok(PathBuf::from(runtime_dir).join(SESSION_DIR_NAME));
+    }
+
+    let home = std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .filter(|d| !d.is_empty())
+        .ok_or_else(|| anyhow::anyhow!("Cannot locate a per-user session directory (set XDG_RUNTIME_DIR or HOME)"))?;
+
+    Ok(PathBuf::from(home).join(format!(".{}", SESSION_DIR_NAME)))
+}
+
+/// Location of the per-user session file
+pub fn session_file_path() -> Result<PathBuf> {
+    Ok(session_dir()?.join(SESSION_FILE_NAME))
+}
+
+/// Checks a user-supplied session lifetime against the 1 minute .. 24 hour range
+pub fn validate_ttl(ttl_minutes: i64) -> Result<()> {
+    if ttl_minutes <= 0 {
+        anyhow::bail!("Session TTL must be at least 1 minute");
+    }
+    if ttl_minutes > MAX_SESSION_TTL_MINUTES {
+        anyhow::bail!("Session TTL must be at most {} minutes (24 hours)", MAX_SESSION_TTL_MINUTES);
+    }
+    Ok(())
+}
+
+/// Writes the unlocked vault key to the session file with owner-only permissions
+pub fn save_session(vault_id: &str, key: &VaultKey, ttl_minutes: i64) -> Result<DateTime<Utc>> {
+    save_session_at(&session_file_path()?, vault_id, key, ttl_minutes)
+}
+
+/// Loads the cached key for the given vault if the session exists and has not expired
+pub fn load_session(vault_id: &str) -> Result<Option<VaultKey>> {
+    load_session_at(&session_file_path()?, vault_id)
+}
+
+/// Overwrites and removes the session file; returns true if a session existed
+pub fn clear_session() -> Result<bool> {
+    clear_session_at(&session_file_path()?)
+}
+
+fn save_session_at(path: &Path, vault_id: &str, key: &VaultKey, ttl_minutes: i64) -> Result<DateTime<Utc>> {
+    let expires_at = TimeDelta::try_minutes(ttl_minutes)
+        .and_then(|ttl| Utc::now().checked_add_signed(ttl))
+        .ok_or_else(|| anyhow::anyhow!("Session TTL of {} minutes is out of range", ttl_minutes))?;
+    let session = SessionFile {
+        vault_id: vault_id.to_string(),
+        key: hex::encode(key.as_bytes()),
+        expires_at,
+    };
+    let contents = serde_json::to_string(&session)?;
+
+    let dir = path.parent()
+        .ok_or_else(|| anyhow::anyhow!("Session file {} has no parent directory", path.display()))?;
+    ensure_private_dir(dir)?;
+    ensure_not_symlink(path)?;
+
+    // Write to a fresh temp file (O_EXCL, never follows an existing path) and rename it into place
+    let tmp_path = dir.join(format!(".{}.{}.tmp", SESSION_FILE_NAME, uuid::Uuid::new_v4().simple()));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600).custom_flags(libc::O_NOFOLLOW);
+    }
+
+    let write_result = (|| -> Result<()> {
+        let mut file = options.open(&tmp_path)
+            .with_context(|| format!("Failed to create session file {}", tmp_path.display()))?;
+        set_owner_only(&tmp_path, 0o600)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+            .with_context(|| format!("Failed to write session file {}", path.display()))?;
+        Ok(())
+    })();
+
+    if write_result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    write_result?;
+
+    Ok(expires_at)
+}
+
+fn load_session_at(path: &Path, vault_id: &str) -> Result<Option<VaultKey>> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).context("Failed to read session file"),
+    };
+    if !metadata.file_type().is_file() {
+        anyhow::bail!("Refusing to read session file {}: not a regular file", path.display());
+    }
+    set_owner_only(path, 0o600)?;
+
+    let contents = fs::read_to_string(path).context("Failed to read session file")?;
+    let session: SessionFile = match serde_json::from_str(&contents) {
+        Ok(session) => session,
+        Err(_) => {
+            clear_session_at(path)?;
+            return Ok(None);
+        }
+    };
+
+    if session.expires_at <= Utc::now() {
+        clear_session_at(path)?;
+        return Ok(None);
+    }
+
+    if session.vault_id != vault_id {
+        return Ok(None);
+    }
+
+    let key_bytes: [u8; 32] = hex::decode(&session.key)
+        .context("Invalid key in session file")?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Invalid key length in session file"))?;
+
+    Ok(Some(VaultKey::from_bytes(key_bytes)))
+}
+
+fn clear_session_at(path: &Path) -> Result<bool> {
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e).context("Failed to read session file"),
+    };
+
+    // Only scrub regular files; a symlink is unlinked without touching its target
+    if metadata.file_type().is_file() {
+        let mut options = fs::OpenOptions::new();
+        options.write(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.custom_flags(libc::O_NOFOLLOW);
+        }
+        if let Ok(mut file) = options.open(path) {
+            let _ = file.write_all(&vec![0u8; metadata.len() as usize]);
+            let _ = file.sync_all();
+        }
+    }
+
+    fs::remove_file(path).context("Failed to remove session file")?;
+    Ok(true)
+}
+
+/// Creates the session directory with 0700, or tightens an existing one; symlinks are refused
+fn ensure_private_dir(dir: &Path) -> Result<()> {
+    match fs::symlink_metadata(dir) {
+        Ok(metadata) => {
+            if !metadata.file_type().is_dir() {
+                anyhow::bail!("Refusing to use session directory {}: not a directory", dir.display());
+            }
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let mut builder = fs::DirBuilder::new();
+            builder.recursive(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::DirBuilderExt;
+                builder.mode(0o700);
+            }
+            builder.create(dir)
+                .with_context(|| format!("Failed to create session directory {}", dir.display()))?;
+        }
+        Err(e) => return Err(e).with_context(|| format!("Failed to inspect session directory {}", dir.display())),
+    }
+
+    set_owner_only(dir, 0o700)
+}
+
+fn ensure_not_symlink(path: &Path) -> Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if !metadata.file_type().is_file() => {
+            anyhow::bail!("Refusing to overwrite session file {}: not a regular file", path.display())
+        }
+        Ok(_) => set_owner_only(path, 0o600),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("Failed to inspect session file {}", path.display())),
+    }
+}
+
+#[cfg(unix)]
+fn set_owner_only(path: &Path, mode: u32) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))
+        .with_context(|| format!("Failed to restrict permissions on {}", path.display()))
+}
+
+#[cfg(not(unix))]
+fn set_owner_only(_path: &Path, _mode: u32) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_round_trip_and_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wallet-backup").join(SESSION_FILE_NAME);
+
+        let key = VaultKey::from_bytes([7u8; 32]);
+        save_session_at(&path, "vault-a", &key, 5).unwrap();
+
+        let loaded = load_session_at(&path, "vault-a").unwrap().expect("session should be live");
+        assert_eq!(loaded.as_bytes(), key.as_bytes());
+        assert!(load_session_at(&path, "vault-b").unwrap().is_none());
+
+        assert!(clear_session_at(&path).unwrap());
+        assert!(load_session_at(&path, "vault-a").unwrap().is_none());
+
+        // Expired sessions are discarded on load
+        save_session_at(&path, "vault-a", &key, -1).unwrap();
+        assert!(load_session_at(&path, "vault-a").unwrap().is_none());
+        assert!(!path.exists());
+
+        // Absurd lifetimes are an error instead of a chrono overflow panic
+        assert!(save_session_at(&path, "vault-a", &key, i64::MAX).is_err());
+    }
+
+    #[test]
+    fn test_validate_ttl() {
+        assert!(validate_ttl(DEFAULT_SESSION_TTL_MINUTES).is_ok());
+        assert!(validate_ttl(MAX_SESSION_TTL_MINUTES).is_ok());
+        assert!(validate_ttl(0).is_err());
+        assert!(validate_ttl(MAX_SESSION_TTL_MINUTES + 1).is_err());
+        assert!(validate_ttl(i64::MAX).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_session_permissions_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let session_dir = dir.path().join("wallet-backup");
+        let path = session_dir.join(SESSION_FILE_NAME);
+        let key = VaultKey::from_bytes([9u8; 32]);
+
+        // A pre-existing, world-readable file is replaced and tightened
+        fs::create_dir(&session_dir).unwrap();
+        fs::set_permissions(&session_dir, fs::Permissions::from_mode(0o755)).unwrap();
+        fs::write(&path, "stale").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        save_session_at(&path, "vault-a", &key, 5).unwrap();
+        assert_eq!(fs::metadata(&session_dir).unwrap().permissions().mode() & 0o777, 0o700);
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+
+        // A planted symlink is never followed
+        let target = dir.path().join("target.json");
+        fs::write(&target, "untouched").unwrap();
+        fs::remove_file(&path).unwrap();
+        std::os::unix::fs::symlink(&target, &path).unwrap();
+        assert!(save_session_at(&path, "vault-a", &key, 5).is_err());
+        assert!(load_session_at(&path, "vault-a").is_err());
+        assert!(clear_session_at(&path).unwrap());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "untouched");
+    }
+}
@@ -0,0 +1,118 @@
+use anyhow::{Result, Context, bail};
+use bitcoin::base58;
+use bitcoin::bip32::Xpub;
+
+/// Standard BIP-32 mainnet public version bytes (`xpub`)
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
+
+/// SLIP-132 prefixes for single-signature mainnet extended public keys
+/// Reference: https://github.com/satoshilabs/slips/blob/master/slip-0132.md
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Slip132Prefix {
+    /// BIP-44 P2PKH (also used by non-Bitcoin secp256k1 chains)
+    Xpub,
+    /// BIP-49 P2WPKH nested in P2SH
+    Ypub,
+    /// BIP-84 native P2WPKH
+    Zpub,
+    /// Litecoin BIP-44 P2PKH
+    Ltub,
+    /// Litecoin BIP-49 P2WPKH nested in P2SH
+    Mtub,
+}
+
+impl Slip132Prefix {
+    pub fn version_bytes(&self) -> [u8; 4] {
+        match self {
+            Self::Xpub => XPUB_VERSION,
+            Self::Ypub => [0x04, 0x9D, 0x7C, 0xB2],
+            Self::Zpub => [0x04, 0xB2, 0x47, 0x46],
+            Self::Ltub => [0x01, 0x9D, 0xA4, 0x62],
+            Self::Mtub => [0x01, 0xB2, 0x6E, 0xF6],
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Xpub => "xpub",
+            Self::Ypub => "ypub",
+            Self::Zpub => "zpub",
+            Self::Ltub => "Ltub",
+            Self::Mtub => "Mtub",
+        }
+    }
+
+    /// BIP purpose implied by the prefix
+    pub fn purpose(&self) -> u32 {
+        match self {
+            Self::Xpub | Self::Ltub => 44,
+            Self::Ypub | Self::Mtub => 49,
+            Self::Zpub => 84,
+        }
+    }
+
+    pub fn from_version_bytes(version: &[u8]) -> Option<Self> {
+        [Self::Xpub, Self::Ypub, Self::Zpub, Self::Ltub, Self::Mtub]
+            .into_iter()
+            .find(|prefix| prefix.version_bytes() == version)
+    }
+}
+
+/// Decodes an xpub/ypub/zpub/Ltub/Mtub string into a BIP-32 public key and its prefix
+pub fn decode_extended_public_key(value: &str) -> Result<(Xpub, Slip132Prefix)> {
+    let mut data = base58::decode_check(value.trim())
+        .context("Invalid extended public key (base58check decoding failed)")?;
+    if data.len() != 78 {
+        bail!("Invalid extended public key length: {} bytes", data.len());
+    }
+
+    let prefix = match Slip132Prefix::from_version_bytes(&data[0..4]) {
+        Some(prefix) => prefix,
+        None => bail!(
+            "Unsupported extended public key version {}. Supported: xpub, ypub, zpub, Ltub, Mtub (mainnet only)",
+            hex::encode(&data[0..4])
+        ),
+    };
+
+    // Re-label as a plain xpub so the BIP-32 decoder accepts it
+    data[0..4].copy_from_slice(&XPUB_VERSION);
+    let xpub = Xpub::decode(&data).context("Invalid extended public key")?;
+
+    Ok((xpub, prefix))
+}
+
+/// Encodes a BIP-32 public key with the given SLIP-132 prefix
+pub fn encode_extended_public_key(xpub: &Xpub, prefix: Slip132Prefix) -> String {
+    let mut data = xpub.encode();
+    data[0..4].copy_from_slice(&prefix.version_bytes());
+    base58::encode_check(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // BIP-84 test vector account key for "abandon ... about"
+    const ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    #[test]
+    fn test_slip132_round_trip() {
+        let (xpub, prefix) = decode_extended_public_key(ZPUB).unwrap();
+        assert_eq!(prefix, Slip132Prefix::Zpub);
+        assert_eq!(prefix.purpose(), 84);
+        assert_eq!(xpub.depth, 3);
+        assert_eq!(encode_extended_public_key(&xpub, Slip132Prefix::Zpub), ZPUB);
+
+        let plain = encode_extended_public_key(&xpub, Slip132Prefix::Xpub);
+        assert!(plain.starts_with("xpub"));
+        let (decoded, prefix) = decode_extended_public_key(&plain).unwrap();
+        assert_eq!(prefix, Slip132Prefix::Xpub);
+        assert_eq!(decoded, xpub);
+    }
+
+    #[test]
+    fn test_rejects_private_keys() {
+        let xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+        assert!(decode_extended_public_key(xprv).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use anyhow::{Result, bail};
+
+/// PBKDF2 iteration exponent used when encrypting the master secret into shares (10000 * 2^e rounds).
+/// sssmc39 0.0.3 always writes exponent 0 into the share words, so any other value yields shares
+/// that decrypt to the wrong secret. Recovery reads the exponent from the shares, so shares made by
+/// other tools with a non-zero exponent still combine correctly.
+pub const ITERATION_EXPONENT: u8 = 0;
+
+/// Member threshold and share count for one SLIP-39 group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShareGroup {
+    pub threshold: u8,
+    pub count: u8,
+}
+
+impl ShareGroup {
+    /// Parses a group spec like "2-of-3" or "2/3"
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.trim().to_lowercase();
+        let (threshold, count) = spec.split_once("-of-")
+            .or_else(|| spec.split_once('/'))
+            .ok_or_else(|| anyhow::anyhow!("Invalid group '{}'. Use the form M-of-N (e.g. 2-of-3)", spec))?;
+
+        let group = Self {
+            threshold: threshold.trim().parse().map_err(|_| anyhow::anyhow!("Invalid group threshold in '{}'", spec))?,
+            count: count.trim().parse().map_err(|_| anyhow::anyhow!("Invalid share count in '{}'", spec))?,
+        };
+        group.validate()?;
+        Ok(group)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.count == 0 || self.count > 16 {
+            bail!("A group must have between 1 and 16 shares");
+        }
+        if self.threshold == 0 || self.threshold > self.count {
+            bail!("Group threshold must be between 1 and the number of shares ({})", self.count);
+        }
+        if self.threshold == 1 && self.count > 1 {
+            bail!("1-of-N groups are not allowed by SLIP-39; use 1-of-1 or a higher threshold");
+        }
+        Ok(())
+    }
+}
+
+/// Splits a master secret (BIP-39 entropy) into SLIP-39 share mnemonics, returned per group
+pub fn split_secret(master_secret: &[u8], group_threshold: u8, groups: &[ShareGroup], passphrase: &str) -> Result<Vec<Vec<String>>> {
+    if master_secret.len() < 16 || master_secret.len() % 2 != 0 {
+        bail!("SLIP-39 master secret must be at least 128 bits and a multiple of 16 bits");
+    }
+    if groups.is_empty() || groups.len() > 16 {
+        bail!("Between 1 and 16 groups are required");
+    }
+    if group_threshold == 0 || group_threshold as usize > groups.len() {
+        bail!("Group threshold must be between 1 and the number of groups ({})", groups.len());
+    }
+    for group in groups {
+        group.validate()?;
+    }
+
+    let group_specs: Vec<(u8, u8)> = groups.iter().map(|g| (g.threshold, g.count)).collect();
+    let group_shares = sssmc39::generate_mnemonics(group_threshold, &group_specs, master_secret, passphrase, ITERATION_EXPONENT)
+        .map_err(|e| anyhow::anyhow!("Failed to generate SLIP-39 shares: {}", e))?;
+
+    let mut result = Vec::new();
+    for group in group_shares {
+        let mnemonics = group.mnemonic_list()
+            .map_err(|e| anyhow::anyhow!("Failed to encode SLIP-39 shares: {}", e))?;
+        result.push(mnemonics.into_iter().map(|words| words.join(" ")).collect());
+    }
+
+    Ok(result)
+}
+
+/// Recombines SLIP-39 share mnemonics into the master secret
+pub fn combine_shares(shares: &[String], passphrase: &str) -> Result<Vec<u8>> {
+    if shares.is_empty() {
+        bail!("No shares provided");
+    }
+
+    let share_words: Vec<Vec<String>> = shares.iter()
+        .map(|share| share.split_whitespace().map(|w| w.to_lowercase()).collect())
+        .collect();
+
+    sssmc39::combine_mnemonics(&share_words, passphrase)
+        .map_err(|e| anyhow::anyhow!("Failed to recombine SLIP-39 shares: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_spec_parsing() {
+        assert_eq!(ShareGroup::parse("2-of-3").unwrap(), ShareGroup { threshold: 2, count: 3 });
+        assert_eq!(ShareGroup::parse("3/5").unwrap(), ShareGroup { threshold: 3, count: 5 });
+        assert!(ShareGroup::parse("1-of-3").is_err());
+        assert!(ShareGroup::parse("4-of-3").is_err());
+        assert!(ShareGroup::parse("two").is_err());
+    }
+
+    #[test]
+    fn test_split_and_combine_round_trip() {
+        let secret = [7u8; 16];
+        let groups = [ShareGroup { threshold: 2, count: 3 }];
+        let shares = split_secret(&secret, 1, &groups, "").unwrap();
+        assert_eq!(shares.len(), 1);
+        assert_eq!(shares[0].len(), 3);
+
+        let recovered = combine_shares(&shares[0][1..3].to_vec(), "").unwrap();
+        assert_eq!(recovered, secret.to_vec());
+    }
+
+    #[test]
+    fn test_insufficient_shares_fail() {
+        let secret = [9u8; 32];
+        let groups = [ShareGroup { threshold: 3, count: 5 }];
+        let shares = split_secret(&secret, 1, &groups, "").unwrap();
+        assert!(combine_shares(&shares[0][0..2].to_vec(), "").is_err());
+    }
+
+    // Vectors from trezor/python-shamir-mnemonic vectors.json (passphrase "TREZOR")
+    #[test]
+    fn test_trezor_vector_without_sharing() {
+        let shares = vec![
+            "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard".to_string(),
+        ];
+        assert_eq!(hex::encode(combine_shares(&shares, "TREZOR").unwrap()), "bb54aac4b89dc868ba37d9cc21b2cece");
+    }
+
+    #[test]
+    fn test_trezor_vectors_with_iteration_exponent() {
+        // Vector 4: 2-of-3, 128 bits, iteration exponent 2
+        let shares = vec![
+            "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed".to_string(),
+            "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking".to_string(),
+        ];
+        assert_eq!(hex::encode(combine_shares(&shares, "TREZOR").unwrap()), "b43ceb7e57a0ea8766221624d01b0864");
+
+        // Vector 23: 2-of-3, 256 bits, iteration exponent 2
+        let shares = vec![
+            "humidity disease academic always aluminum jewelry energy woman receiver strategy amuse duckling lying evidence network walnut tactics forget hairy rebound impulse brother survive clothes stadium mailman rival ocean reward venture always armed unwrap".to_string(),
+            "humidity disease academic agency actress jacket gross physics cylinder solution fake mortgage benefit public busy prepare sharp friar change work slow purchase ruler again tricycle involve viral wireless mixture anatomy desert cargo upgrade".to_string(),
+        ];
+        assert_eq!(
+            hex::encode(combine_shares(&shares, "TREZOR").unwrap()),
+            "c938b319067687e990e05e0da0ecce1278f75ff58d9853f19dcaeed5de104aae"
+        );
+    }
+
+    #[test]
+    fn test_trezor_vector_invalid_checksum_rejected() {
+        // Vector 2: one word changed, RS1024 checksum no longer matches
+        let shares = vec![
+            "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney".to_string(),
+        ];
+        assert!(combine_shares(&shares, "TREZOR").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use anyhow::{Result, Context, bail};
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use zeroize::Zeroize;
+
+/// Prefix marking a column value as sealed by the vault (format: enc:v1:<nonce hex>:<ciphertext hex>)
+pub const SEALED_PREFIX: &str = "enc:v1:";
+
+/// Known plaintext sealed into the vault header so a password can be checked before use
+pub const KEY_CHECK_PLAINTEXT: &str = "wallet-backup-vault-key-check";
+
+pub const KDF_ALGORITHM: &str = "argon2id";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+/// Argon2id cost parameters stored in the vault header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        // 64 MiB, 3 passes, single lane
+        Self {
+            memory_kib: 64 * 1024,
+            iterations: 3,
+            parallelism: 1,
+        }
+    }
+}
+
+/// Upper bounds for Argon2id costs (4 GiB, 64 passes) so a crafted header or backup file cannot exhaust the machine
+pub const MAX_KDF_MEMORY_KIB: u32 = 4 * 1024 * 1024;
+pub const MAX_KDF_ITERATIONS: u32 = 64;
+
+impl KdfParams {
+    pub fn validate(&self) -> Result<()> {
+        if self.memory_kib < 8 * 1024 {
+            bail!("KDF memory cost must be at least 8192 KiB (8 MiB)");
+        }
+        if self.memory_kib > MAX_KDF_MEMORY_KIB {
+            bail!("KDF memory cost must be at most {} KiB (4 GiB)", MAX_KDF_MEMORY_KIB);
+        }
+        if self.iterations == 0 {
+            bail!("KDF iterations must be at least 1");
+        }
+        if self.iterations > MAX_KDF_ITERATIONS {
+            bail!("KDF iterations must be at most {}", MAX_KDF_ITERATIONS);
+        }
+        if self.parallelism == 0 || self.parallelism > 16 {
+            bail!("KDF parallelism must be between 1 and 16");
+        }
+        Ok(())
+    }
+}
+
+/// 256-bit symmetric key derived from the vault password (wiped on drop)
+#[derive(Clone)]
+pub struct VaultKey([u8; 32]);
+
+impl VaultKey {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl Drop for VaultKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Generates a fresh random salt for key derivation
+pub fn generate_salt() -> Vec<u8> {
+    rand::random::<[u8; SALT_LEN]>().to_vec()
+}
+
+/// Derives the vault key from a password using Argon2id
+pub fn derive_key(password: &str, salt: &[u8], params: &KdfParams) -> Result<VaultKey> {
+    let argon2_params = Params::new(params.memory_kib, params.iterations, params.parallelism, Some(32))
+        .map_err(|e| anyhow::anyhow!("Invalid KDF parameters: {}", e))?;
+    let argon2 = Argon2::new(Algorithm::Argon2id, Version::V0x13, argon2_params);
+
+    let mut key = [0u8; 32];
+    argon2.hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow::anyhow!("Failed to derive vault key: {}", e))?;
+
+    Ok(VaultKey(key))
+}
+
+/// Returns true if the value was produced by `seal`
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(SEALED_PREFIX)
+}
+
+/// Encrypts a secret with XChaCha20-Poly1305 under the vault key
+pub fn seal(key: &VaultKey, plaintext: &str) -> Result<String> {
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(key.as_bytes()));
+    let nonce_bytes = rand::random::<[u8; NONCE_LEN]>();
+    let nonce = XNonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher.encrypt(nonce, plaintext.as_bytes())
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt secret"))?;
+
+    Ok(format!("{}{}:{}", SEALED_PREFIX, hex::encode(nonce_bytes), hex::encode(ciphertext)))
+}
+
+/// Decrypts a value produced by `seal`; fails if the key is wrong or the data was tampered with
+pub fn open(key: &VaultKey, sealed: &str) -> Result<String> {
+    let body = sealed.strip_prefix(SEALED_PREFIX)
+        .ok_or_else(|| anyhow::anyhow!("Value is not a sealed vault secret"))?;
+
+    let (nonce_hex, ciphertext_hex) = body.split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("Malformed sealed vault secret"))?;
+
+    let nonce_bytes = hex::decode(nonce_hex).context("Invalid nonce in sealed secret")?;
+    if nonce_bytes.len() != NONCE_LEN {
+        bail!("Invalid nonce length in sealed secret");
+    }
+    let ciphertext = hex::decode(ciphertext_hex).context("Invalid ciphertext in sealed secret")?;
+
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(key.as_bytes()));
+    let plaintext = cipher.decrypt(XNonce::from_slice(&nonce_bytes), ciphertext.as_ref())
+        .map_err(|_| anyhow::anyhow!("Failed to decrypt secret (wrong password or corrupted data)"))?;
+
+    String::from_utf8(plaintext).context("Decrypted secret is not valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_params() -> KdfParams {
+        KdfParams { memory_kib: 8 * 1024, iterations: 1, parallelism: 1 }
+    }
+
+    #[test]
+    fn test_seal_and_open_round_trip() {
+        let salt = generate_salt();
+        let key = derive_key("correct horse battery staple", &salt, &test_params()).unwrap();
+
+        let sealed = seal(&key, "abandon abandon about").unwrap();
+        assert!(is_sealed(&sealed));
+        assert_ne!(sealed, "abandon abandon about");
+        assert_eq!(open(&key, &sealed).unwrap(), "abandon abandon about");
+    }
+
+    #[test]
+    fn test_wrong_password_fails() {
+        let salt = generate_salt();
+        let key = derive_key("right password", &salt, &test_params()).unwrap();
+        let wrong_key = derive_key("wrong password", &salt, &test_params()).unwrap();
+
+        let sealed = seal(&key, "secret").unwrap();
+        assert!(open(&wrong_key, &sealed).is_err());
+    }
+
+    #[test]
+    fn test_key_derivation_is_deterministic() {
+        let salt = generate_salt();
+        let key1 = derive_key("password", &salt, &test_params()).unwrap();
+        let key2 = derive_key("password", &salt, &test_params()).unwrap();
+        assert_eq!(key1.as_bytes(), key2.as_bytes());
+    }
+
+    #[test]
+    fn test_kdf_params_validation() {
+        assert!(KdfParams::default().validate().is_ok());
+        assert!(KdfParams { memory_kib: 1024, iterations: 3, parallelism: 1 }.validate().is_err());
+        assert!(KdfParams { memory_kib: 65536, iterations: 0, parallelism: 1 }.validate().is_err());
+        assert!(KdfParams { memory_kib: MAX_KDF_MEMORY_KIB + 1, iterations: 3, parallelism: 1 }.validate().is_err());
+        assert!(KdfParams { memory_kib: 65536, iterations: MAX_KDF_ITERATIONS + 1, parallelism: 1 }.validate().is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::{Result, Context, bail};
+use bitcoin::bip32::{ChildNumber, Xpub};
+use bitcoin::secp256k1::Secp256k1;
+
+use crate::blockchain::{SupportedBlockchain, BipStandard, KeyScheme};
+use crate::crypto::slip132::{decode_extended_public_key, encode_extended_public_key, Slip132Prefix};
+
+/// Stored in place of the mnemonic for watch-only accounts
+pub const WATCH_ONLY_MNEMONIC_PLACEHOLDER: &str = "[WATCH_ONLY_XPUB]";
+
+/// Prefix of the storage string kept in `master_accounts.master_private_key`
+const STORAGE_PREFIX: &str = "watch-only";
+
+/// Account-level extended public key (m/purpose'/coin'/account') of a watch-only account
+///
+/// Only non-hardened children (`change/address_index`) can be derived, so every wallet
+/// of the account shares the purpose, coin type and account index of the key.
+#[derive(Debug, Clone)]
+pub struct WatchOnlyKey {
+    pub blockchain: SupportedBlockchain,
+    pub purpose: u32,
+    pub xpub: Xpub,
+    pub prefix: Slip132Prefix,
+}
+
+impl WatchOnlyKey {
+    /// Parses a user-supplied xpub/ypub/zpub/Ltub/Mtub for `blockchain`.
+    /// The BIP purpose comes from the SLIP-132 prefix unless `bip` overrides it.
+    pub fn new(blockchain: SupportedBlockchain, value: &str, bip: Option<BipStandard>) -> Result<Self> {
+        if blockchain.key_scheme() != KeyScheme::Secp256k1 {
+            bail!("Watch-only accounts need a BIP-32 xpub; {} does not use secp256k1 extended keys", blockchain);
+        }
+
+        let (xpub, prefix) = decode_extended_public_key(value)?;
+        if xpub.depth != 3 {
+            bail!(
+                "Expected an account-level extended public key (depth 3, m/purpose'/coin'/account'), got depth {}",
+                xpub.depth
+            );
+        }
+        if !matches!(xpub.child_number, ChildNumber::Hardened { .. }) {
+            bail!("Account-level extended public keys must come from a hardened account index");
+        }
+
+        let purpose = match bip {
+            Some(bip) => bip.get_purpose(),
+            None => prefix.purpose(),
+        };
+        let purpose_bip = BipStandard::from_str(&purpose.to_string())?;
+        if !blockchain.supports_bip(purpose_bip) {
+            bail!("{} does not support {} (implied by the '{}' prefix)", blockchain, purpose_bip, prefix.as_str());
+        }
+
+        Ok(Self { blockchain, purpose, xpub, prefix })
+    }
+
+    /// Parses the value written by `to_storage_string`
+    pub fn from_storage_string(value: &str) -> Result<Self> {
+        let mut parts = value.splitn(4, ':');
+        let (tag, blockchain, purpose, key) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(tag), Some(blockchain), Some(purpose), Some(key)) => (tag, blockchain, purpose, key),
+            _ => bail!("Invalid watch-only key record"),
+        };
+        if tag != STORAGE_PREFIX {
+            bail!("Invalid watch-only key record");
+        }
+
+        let blockchain = SupportedBlockchain::from_str(blockchain)?;
+        let purpose = BipStandard::from_str(purpose).context("Invalid watch-only key purpose")?;
+        Self::new(blockchain, key, Some(purpose))
+    }
+
+    pub fn to_storage_string(&self) -> String {
+        format!("{}:{}:{}:{}", STORAGE_PREFIX, self.blockchain, self.purpose, self.encoded())
+    }
+
+    /// The key in its original SLIP-132 encoding
+    pub fn encoded(&self) -> String {
+        encode_extended_public_key(&self.xpub, self.prefix)
+    }
+
+    pub fn account_index(&self) -> u32 {
+        match self.xpub.child_number {
+            ChildNumber::Hardened { index } | ChildNumber::Normal { index } => index,
+        }
+    }
+
+    /// Path of the key itself, m/purpose'/coin'/account'
+    pub fn account_path(&self) -> String {
+        format!(
+            "m/{}'/{}'/{}'",
+            self.purpose,
+            self.blockchain.get_coin_type().unwrap_or(0),
+            self.account_index()
+        )
+    }
+
+    pub fn derivation_path(&self, change: u32, address_index: u32) -> String {
+        format!("{}/{}/{}", self.account_path(), change, address_index)
+    }
+
+    /// Derives the compressed public key at `change/address_index` below the account key
+    pub fn derive_public_key(&self, change: u32, address_index: u32) -> Result<Vec<u8>> {
+        let path = [
+            ChildNumber::from_normal_idx(change).context("Invalid change index")?,
+            ChildNumber::from_normal_idx(address_index).context("Invalid address index")?,
+        ];
+        let child = self.xpub.derive_pub(&Secp256k1::verification_only(), &path)
+            .context("Failed to derive child public key")?;
+        Ok(child.public_key.serialize().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ZPUB: &str = "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+
+    #[test]
+    fn test_watch_only_key_round_trip() {
+        let key = WatchOnlyKey::new(SupportedBlockchain::Bitcoin, ZPUB, None).unwrap();
+        assert_eq!(key.purpose, 84);
+        assert_eq!(key.account_index(), 0);
+        assert_eq!(key.derivation_path(0, 5), "m/84'/0'/0'/0/5");
+
+        let restored = WatchOnlyKey::from_storage_string(&key.to_storage_string()).unwrap();
+        assert_eq!(restored.encoded(), ZPUB);
+        assert_eq!(restored.derive_public_key(0, 0).unwrap(), key.derive_public_key(0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_watch_only_matches_mnemonic_derivation() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let key = WatchOnlyKey::new(SupportedBlockchain::Bitcoin, ZPUB, None).unwrap();
+
+        let (_, expected) = crate::crypto::bip32::derive_secp256k1_key_from_mnemonic(mnemonic, None, "m/84'/0'/0'/0/1").unwrap();
+        assert_eq!(key.derive_public_key(0, 1).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_rejects_ed25519_chains() {
+        assert!(WatchOnlyKey::new(SupportedBlockchain::Solana, ZPUB, None).is_err());
+        assert!(WatchOnlyKey::new(SupportedBlockchain::Ethereum, ZPUB, None).is_err());
+    }
+}
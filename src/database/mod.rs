@@ -3,7 +3,79 @@ use anyhow::{Result, Context, bail};
 use chrono::{DateTime, Utc, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::cell::RefCell;
 use sha2::Digest;
+use crate::crypto::vault::{self, KdfParams, VaultKey};
+use crate::crypto::master_key::{MasterKey, MASTER_KEY_MNEMONIC_PLACEHOLDER};
+use crate::crypto::watch_only::{WatchOnlyKey, WATCH_ONLY_MNEMONIC_PLACEHOLDER};
+use bitcoin::bip32::Xpriv;
+
+/// Error message returned when a secret is needed but the vault has not been unlocked
+pub const VAULT_LOCKED_MESSAGE: &str = "Vault is locked. Run `wallet-backup unlock` first.";
+
+// ========== SECRET COLUMNS ==========
+
+/// A secret column as loaded from the database: plaintext once the vault is unlocked (or when it is not
+/// encrypted), otherwise the sealed value so rows can still be listed and shown without it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Secret {
+    Plain(String),
+    Sealed(String),
+}
+
+impl Secret {
+    /// Plaintext value; fails with the vault-locked error while the column is still sealed
+    pub fn expose(&self) -> Result<&str> {
+        match self {
+            Secret::Plain(value) => Ok(value),
+            Secret::Sealed(_) => bail!(VAULT_LOCKED_MESSAGE),
+        }
+    }
+
+    /// True for an empty plaintext value (empty values are never sealed)
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Secret::Plain(value) if value.is_empty())
+    }
+
+    // Value as written back to the database (sealed values pass through `seal_secret` unchanged)
+    fn stored(&self) -> &str {
+        match self {
+            Secret::Plain(value) | Secret::Sealed(value) => value,
+        }
+    }
+
+    // True only for a plaintext value equal to `other` (used for the non-secret mnemonic placeholders)
+    fn is_plain(&self, other: &str) -> bool {
+        matches!(self, Secret::Plain(value) if value == other)
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Secret::Plain(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Secret::Plain(value.to_string())
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Secret::Plain(value) => serializer.serialize_str(value),
+            Secret::Sealed(_) => Err(serde::ser::Error::custom(VAULT_LOCKED_MESSAGE)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Secret::Plain)
+    }
+}
 
 // ========== 4-LEVEL HIERARCHICAL STRUCTURES ==========
 
@@ -11,14 +83,79 @@ use sha2::Digest;
 pub struct MasterAccount {
     pub id: Option<i64>,
     pub name: String,
-    pub mnemonic: String,
-    pub master_private_key: String,
-    pub passphrase: Option<String>,
+    pub mnemonic: Secret,
+    pub master_private_key: Secret,
+    pub passphrase: Option<Secret>,
     pub next_account_index: u32,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl MasterAccount {
+    /// True when the account was created from an xprv/SLIP-10 master key instead of a mnemonic
+    pub fn uses_master_key(&self) -> bool {
+        self.mnemonic.is_plain(MASTER_KEY_MNEMONIC_PLACEHOLDER)
+    }
+
+    /// True for accounts that only hold an account-level xpub
+    pub fn is_watch_only(&self) -> bool {
+        self.mnemonic.is_plain(WATCH_ONLY_MNEMONIC_PLACEHOLDER)
+    }
+
+    /// `source_type` recorded on wallets derived from this account
+    pub fn wallet_source_type(&self) -> &'static str {
+        if self.is_watch_only() {
+            "watch_only"
+        } else if self.uses_master_key() {
+            "master_private_key"
+        } else {
+            "mnemonic"
+        }
+    }
+
+    /// BIP-32 root key of a mnemonic or xprv-backed account
+    pub fn bip32_root(&self) -> Result<Xpriv> {
+        if self.is_watch_only() {
+            bail!("Watch-only account '{}' has no private root key", self.name);
+        }
+        if self.uses_master_key() {
+            return match self.master_key()? {
+                MasterKey::Bip32(xpriv) => Ok(xpriv),
+                MasterKey::Slip10Ed25519 { .. } => {
+                    bail!("Account '{}' holds a SLIP-10 ed25519 master secret, not a BIP-32 root key", self.name)
+                }
+            };
+        }
+
+        let seed = crate::crypto::mnemonic_to_seed(self.mnemonic.expose()?, self.passphrase_str()?)
+            .context("This account has no BIP-39 mnemonic to derive from")?;
+        crate::crypto::derive_master_key_secp256k1(&seed)
+    }
+
+    pub fn watch_only_key(&self) -> Result<WatchOnlyKey> {
+        WatchOnlyKey::from_storage_string(self.master_private_key.expose()?)
+            .with_context(|| format!("Account '{}' does not hold a valid watch-only xpub", self.name))
+    }
+
+    /// Checks a user-supplied xprv/SLIP-10 secret against the stored master key
+    pub fn master_key_matches(&self, value: &str) -> bool {
+        self.uses_master_key()
+            && MasterKey::parse(value)
+                .map(|key| self.master_private_key.is_plain(&key.to_storage_string()))
+                .unwrap_or(false)
+    }
+
+    pub fn master_key(&self) -> Result<MasterKey> {
+        MasterKey::parse(self.master_private_key.expose()?)
+            .with_context(|| format!("Account '{}' does not hold a usable xprv or SLIP-10 master key", self.name))
+    }
+
+    /// BIP-39 passphrase, if any (fails while the vault is locked)
+    pub fn passphrase_str(&self) -> Result<Option<&str>> {
+        self.passphrase.as_ref().map(Secret::expose).transpose()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletGroup {
     pub id: Option<i64>,
@@ -51,11 +188,11 @@ pub struct Wallet {
     pub blockchain: String,
     pub address: String,
     pub address_with_checksum: Option<String>,
-    pub private_key: String,
+    pub private_key: Secret,
     pub public_key: Option<String>,
     pub derivation_path: Option<String>, // Contains full path like "m/0" or "m/0/5", NULL for standalone wallets
     pub label: Option<String>, // Individual wallet label (empty by default)
-    pub source_type: String, // "mnemonic", "master_private_key", or "private_key"
+    pub source_type: String, // "mnemonic", "master_private_key", "watch_only", or "private_key"
     pub explorer_url: Option<String>,
     pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
@@ -63,6 +200,18 @@ pub struct Wallet {
     pub secondary_addresses: HashMap<String, String>, // Alternative address formats
 }
 
+/// `wallet_secrets` type for a chain-native seed the wallet key was derived from (XRP family seed)
+pub const WALLET_SECRET_SEED: &str = "seed";
+
+/// Shown instead of a private key for wallets that have no secret
+pub const WATCH_ONLY_KEY_NOTICE: &str = "(none - watch-only wallet, no secret is stored)";
+
+impl Wallet {
+    pub fn is_watch_only(&self) -> bool {
+        self.source_type == "watch_only"
+    }
+}
+
 
 // ========== SUMMARY STRUCTURES ==========
 
@@ -96,6 +245,119 @@ pub struct AddressGroupSummary {
     pub created_at: DateTime<Utc>,
 }
 
+/// Records that an account was derived from another one (e.g. a BIP-85 child seed)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountLink {
+    pub child_account_id: i64,
+    pub parent_account_id: i64,
+    pub parent_account_name: String,
+    pub link_type: String, // "bip85"
+    pub derivation_index: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+// ========== VAULT STRUCTURES ==========
+
+#[derive(Debug, Clone)]
+pub struct VaultHeader {
+    pub kdf_algorithm: String,
+    pub kdf_params: KdfParams,
+    pub salt: Vec<u8>,
+    pub key_check: String, // Known plaintext sealed under the vault key
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VaultMigrationSummary {
+    pub master_accounts_sealed: usize,
+    pub wallets_sealed: usize,
+    pub wallet_secrets_sealed: usize,
+}
+
+// ========== BACKUP STRUCTURES ==========
+
+/// Full account tree with plaintext secrets (only ever written to disk inside an encrypted backup envelope)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountBackup {
+    pub name: String,
+    pub mnemonic: String,
+    pub master_private_key: String,
+    pub passphrase: Option<String>,
+    pub next_account_index: u32,
+    pub created_at: DateTime<Utc>,
+    pub wallet_groups: Vec<WalletGroupBackup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletGroupBackup {
+    pub name: String,
+    pub description: Option<String>,
+    pub account_index: u32,
+    pub created_at: DateTime<Utc>,
+    pub wallets: Vec<BaseWalletBackup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaseWalletBackup {
+    pub wallet: Wallet,
+    pub address_groups: Vec<AddressGroupBackup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressGroupBackup {
+    pub blockchain: String,
+    pub name: String,
+    pub address_group_index: u32,
+    pub next_address_index: u32,
+    pub created_at: DateTime<Utc>,
+    pub subwallets: Vec<Wallet>,
+}
+
+/// Secret-free view of an account tree, loaded without reading any secret column (works while the vault is locked)
+#[derive(Debug, Clone)]
+pub struct AccountOutline {
+    pub name: String,
+    pub uses_passphrase: bool,
+    pub next_account_index: u32,
+    pub wallet_groups: Vec<WalletGroupOutline>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WalletGroupOutline {
+    pub name: String,
+    pub description: Option<String>,
+    pub account_index: u32,
+    pub wallets: Vec<WalletOutline>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WalletOutline {
+    pub blockchain: String,
+    pub address: String,
+    pub derivation_path: Option<String>,
+    pub label: Option<String>,
+    pub notes: Option<String>,
+    pub address_groups: Vec<AddressGroupOutline>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddressGroupOutline {
+    pub blockchain: String,
+    pub name: String,
+    pub address_group_index: u32,
+    pub next_address_index: u32,
+    pub subwallets: Vec<WalletOutline>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AccountImportSummary {
+    pub wallet_groups: usize,
+    pub wallets: usize,
+    pub address_groups: usize,
+    pub subwallets: usize,
+}
+
 // ========== BULK OPERATION RESULTS ==========
 
 #[derive(Debug, Clone)]
@@ -108,6 +370,7 @@ pub struct HierarchyResult {
 
 pub struct Database {
     conn: Connection,
+    vault_key: RefCell<Option<VaultKey>>, // Present only after a successful unlock
 }
 
 impl Database {
@@ -115,7 +378,7 @@ impl Database {
         let conn = Connection::open(db_path)
             .context("Failed to open database connection")?;
 
-        let db = Database { conn };
+        let db = Database { conn, vault_key: RefCell::new(None) };
         db.create_tables()?;
         Ok(db)
     }
@@ -236,6 +499,56 @@ impl Database {
             [],
         ).context("Failed to create wallet_secondary_addresses table")?;
 
+        // Secrets a wallet key was derived from (e.g. XRP family seeds), sealed like private keys
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS wallet_secrets (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                wallet_id INTEGER NOT NULL,
+                secret_type TEXT NOT NULL,
+                secret_value TEXT NOT NULL,
+                FOREIGN KEY (wallet_id) REFERENCES wallets(id) ON DELETE CASCADE,
+                UNIQUE(wallet_id, secret_type)
+            );
+            "#,
+            [],
+        ).context("Failed to create wallet_secrets table")?;
+
+        // Parent/child links between master accounts (BIP-85 child seeds)
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS account_links (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                child_account_id INTEGER UNIQUE NOT NULL,
+                parent_account_id INTEGER NOT NULL,
+                link_type TEXT NOT NULL,
+                derivation_index INTEGER NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                FOREIGN KEY (child_account_id) REFERENCES master_accounts(id) ON DELETE CASCADE,
+                FOREIGN KEY (parent_account_id) REFERENCES master_accounts(id) ON DELETE CASCADE
+            );
+            "#,
+            [],
+        ).context("Failed to create account_links table")?;
+
+        // Vault header: KDF parameters, salt and key check for encryption at rest (single row)
+        self.conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS vault_header (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                kdf_algorithm TEXT NOT NULL,
+                kdf_memory_kib INTEGER NOT NULL,
+                kdf_iterations INTEGER NOT NULL,
+                kdf_parallelism INTEGER NOT NULL,
+                salt TEXT NOT NULL,
+                key_check TEXT NOT NULL,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+            );
+            "#,
+            [],
+        ).context("Failed to create vault_header table")?;
+
         // Create indexes for performance - Hierarchical structure optimized
 
         // Master Accounts indexes
@@ -326,6 +639,334 @@ impl Database {
         Ok(())
     }
 
+    // ========== VAULT OPERATIONS ==========
+
+    /// Gets the vault header (None for a plaintext database)
+    pub fn get_vault_header(&self) -> Result<Option<VaultHeader>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT kdf_algorithm, kdf_memory_kib, kdf_iterations, kdf_parallelism, salt, key_check, created_at, updated_at FROM vault_header WHERE id = 1"
+        ).context("Failed to prepare vault header query")?;
+
+        let header_result = stmt.query_row([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                KdfParams {
+                    memory_kib: row.get(1)?,
+                    iterations: row.get(2)?,
+                    parallelism: row.get(3)?,
+                },
+                row.get::<_, String>(4)?,
+                row.get::<_, String>(5)?,
+                self.parse_datetime(&row.get::<_, String>(6)?)?,
+                self.parse_datetime(&row.get::<_, String>(7)?)?,
+            ))
+        });
+
+        match header_result {
+            Ok((kdf_algorithm, kdf_params, salt_hex, key_check, created_at, updated_at)) => {
+                let salt = hex::decode(&salt_hex).context("Invalid salt in vault header")?;
+                Ok(Some(VaultHeader { kdf_algorithm, kdf_params, salt, key_check, created_at, updated_at }))
+            },
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(anyhow::Error::from(e).context("Failed to query vault header")),
+        }
+    }
+
+    /// Returns true if secrets in this database are encrypted at rest
+    pub fn is_vault_encrypted(&self) -> Result<bool> {
+        Ok(self.get_vault_header()?.is_some())
+    }
+
+    /// Returns true if the vault key is available in this process
+    pub fn is_unlocked(&self) -> bool {
+        self.vault_key.borrow().is_some()
+    }
+
+    /// Derives the vault key from the password and keeps it for this process
+    pub fn unlock(&self, password: &str) -> Result<()> {
+        let key = self.derive_vault_key(password)?;
+        *self.vault_key.borrow_mut() = Some(key);
+        Ok(())
+    }
+
+    /// Derives and verifies the vault key without keeping it
+    pub fn derive_vault_key(&self, password: &str) -> Result<VaultKey> {
+        let header = match self.get_vault_header()? {
+            Some(header) => header,
+            None => bail!("Vault is not encrypted. Nothing to unlock."),
+        };
+
+        if header.kdf_algorithm != vault::KDF_ALGORITHM {
+            bail!("Unsupported vault KDF algorithm: {}", header.kdf_algorithm);
+        }
+
+        let key = vault::derive_key(password, &header.salt, &header.kdf_params)?;
+        if !Self::key_matches(&header, &key) {
+            bail!("Incorrect vault password.");
+        }
+
+        Ok(key)
+    }
+
+    /// Unlocks with an already-derived key (e.g. from a cached session); returns false if the key does not fit this vault
+    pub fn unlock_with_key(&self, key: VaultKey) -> Result<bool> {
+        let header = match self.get_vault_header()? {
+            Some(header) => header,
+            None => return Ok(false),
+        };
+
+        if !Self::key_matches(&header, &key) {
+            return Ok(false);
+        }
+
+        *self.vault_key.borrow_mut() = Some(key);
+        Ok(true)
+    }
+
+    /// Hex-encoded salt used to tie cached sessions to this vault
+    pub fn get_vault_id(&self) -> Result<Option<String>> {
+        Ok(self.get_vault_header()?.map(|header| hex::encode(&header.salt)))
+    }
+
+    // Helper method to check a key against the sealed key-check value in the header
+    fn key_matches(header: &VaultHeader, key: &VaultKey) -> bool {
+        matches!(vault::open(key, &header.key_check), Ok(check) if check == vault::KEY_CHECK_PLAINTEXT)
+    }
+
+    /// Forgets the vault key held by this process
+    pub fn lock(&self) {
+        *self.vault_key.borrow_mut() = None;
+    }
+
+    /// Fails with a clear message if the vault is encrypted but not unlocked
+    pub fn require_unlocked(&self) -> Result<()> {
+        if self.is_vault_encrypted()? && !self.is_unlocked() {
+            bail!(VAULT_LOCKED_MESSAGE);
+        }
+        Ok(())
+    }
+
+    /// Upgrades a plaintext database in place: writes the vault header and seals every stored secret
+    pub fn encrypt_vault(&self, password: &str, kdf_params: KdfParams) -> Result<VaultMigrationSummary> {
+        if self.is_vault_encrypted()? {
+            bail!("Vault is already encrypted.");
+        }
+        kdf_params.validate()?;
+
+        let salt = vault::generate_salt();
+        let key = vault::derive_key(password, &salt, &kdf_params)?;
+        let key_check = vault::seal(&key, vault::KEY_CHECK_PLAINTEXT)?;
+
+        let tx = self.conn.unchecked_transaction()?;
+
+        tx.execute(
+            "INSERT INTO vault_header (id, kdf_algorithm, kdf_memory_kib, kdf_iterations, kdf_parallelism, salt, key_check) VALUES (1, ?1, ?2, ?3, ?4, ?5, ?6)",
+            params![vault::KDF_ALGORITHM, kdf_params.memory_kib, kdf_params.iterations, kdf_params.parallelism, hex::encode(&salt), key_check],
+        ).context("Failed to write vault header")?;
+
+        let mut summary = VaultMigrationSummary::default();
+
+        // Seal master account secrets
+        let accounts: Vec<(i64, String, String, Option<String>)> = {
+            let mut stmt = tx.prepare("SELECT id, mnemonic, master_private_key, passphrase FROM master_accounts")
+                .context("Failed to prepare master accounts query")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+                .context("Failed to query master accounts")?;
+            rows.collect::<SqlResult<Vec<_>>>().context("Failed to read master accounts")?
+        };
+
+        for (id, mnemonic, master_private_key, passphrase) in accounts {
+            let passphrase = match passphrase {
+                Some(p) => Some(seal_if_plaintext(&key, &p)?),
+                None => None,
+            };
+            tx.execute(
+                "UPDATE master_accounts SET mnemonic = ?1, master_private_key = ?2, passphrase = ?3 WHERE id = ?4",
+                params![seal_if_plaintext(&key, &mnemonic)?, seal_if_plaintext(&key, &master_private_key)?, passphrase, id],
+            ).context("Failed to seal master account secrets")?;
+            summary.master_accounts_sealed += 1;
+        }
+
+        // Seal wallet private keys
+        let wallets: Vec<(i64, String)> = {
+            let mut stmt = tx.prepare("SELECT id, private_key FROM wallets")
+                .context("Failed to prepare wallets query")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .context("Failed to query wallets")?;
+            rows.collect::<SqlResult<Vec<_>>>().context("Failed to read wallets")?
+        };
+
+        for (id, private_key) in wallets {
+            tx.execute(
+                "UPDATE wallets SET private_key = ?1 WHERE id = ?2",
+                params![seal_if_plaintext(&key, &private_key)?, id],
+            ).context("Failed to seal wallet private key")?;
+            summary.wallets_sealed += 1;
+        }
+
+        // Seal wallet seeds
+        let secrets: Vec<(i64, String)> = {
+            let mut stmt = tx.prepare("SELECT id, secret_value FROM wallet_secrets")
+                .context("Failed to prepare wallet secrets query")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .context("Failed to query wallet secrets")?;
+            rows.collect::<SqlResult<Vec<_>>>().context("Failed to read wallet secrets")?
+        };
+
+        for (id, secret_value) in secrets {
+            tx.execute(
+                "UPDATE wallet_secrets SET secret_value = ?1 WHERE id = ?2",
+                params![seal_if_plaintext(&key, &secret_value)?, id],
+            ).context("Failed to seal wallet secret")?;
+            summary.wallet_secrets_sealed += 1;
+        }
+
+        tx.commit().context("Failed to commit vault encryption")?;
+
+        *self.vault_key.borrow_mut() = Some(key);
+        Ok(summary)
+    }
+
+    /// Re-encrypts every stored secret under a key derived from the new password (single transaction, all or nothing)
+    pub fn change_vault_password(&self, old_password: &str, new_password: &str, new_kdf_params: Option<KdfParams>) -> Result<VaultMigrationSummary> {
+        let header = match self.get_vault_header()? {
+            Some(header) => header,
+            None => bail!("Vault is not encrypted. Use encrypt-vault first."),
+        };
+
+        let old_key = self.derive_vault_key(old_password)?;
+
+        let kdf_params = new_kdf_params.unwrap_or(header.kdf_params);
+        kdf_params.validate()?;
+        if kdf_params.memory_kib < header.kdf_params.memory_kib || kdf_params.iterations < header.kdf_params.iterations {
+            bail!("New KDF parameters must not be weaker than the current ones (memory: {} KiB, iterations: {})",
+                header.kdf_params.memory_kib, header.kdf_params.iterations);
+        }
+
+        let salt = vault::generate_salt();
+        let new_key = vault::derive_key(new_password, &salt, &kdf_params)?;
+        let key_check = vault::seal(&new_key, vault::KEY_CHECK_PLAINTEXT)?;
+
+        // Any error below drops the transaction, which rolls back every change
+        let tx = self.conn.unchecked_transaction()?;
+
+        tx.execute(
+            "UPDATE vault_header SET kdf_algorithm = ?1, kdf_memory_kib = ?2, kdf_iterations = ?3, kdf_parallelism = ?4, salt = ?5, key_check = ?6, updated_at = CURRENT_TIMESTAMP WHERE id = 1",
+            params![vault::KDF_ALGORITHM, kdf_params.memory_kib, kdf_params.iterations, kdf_params.parallelism, hex::encode(&salt), key_check],
+        ).context("Failed to update vault header")?;
+
+        let mut summary = VaultMigrationSummary::default();
+
+        // Re-key master account secrets
+        let accounts: Vec<(i64, String, String, String, Option<String>)> = {
+            let mut stmt = tx.prepare("SELECT id, name, mnemonic, master_private_key, passphrase FROM master_accounts")
+                .context("Failed to prepare master accounts query")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)))
+                .context("Failed to query master accounts")?;
+            rows.collect::<SqlResult<Vec<_>>>().context("Failed to read master accounts")?
+        };
+
+        for (id, name, mnemonic, master_private_key, passphrase) in accounts {
+            let context = || format!("Failed to re-key master account '{}'; no changes were made", name);
+            let mnemonic = rekey_value(&old_key, &new_key, &mnemonic).with_context(context)?;
+            let master_private_key = rekey_value(&old_key, &new_key, &master_private_key).with_context(context)?;
+            let passphrase = match passphrase {
+                Some(p) => Some(rekey_value(&old_key, &new_key, &p).with_context(context)?),
+                None => None,
+            };
+
+            tx.execute(
+                "UPDATE master_accounts SET mnemonic = ?1, master_private_key = ?2, passphrase = ?3, updated_at = CURRENT_TIMESTAMP WHERE id = ?4",
+                params![mnemonic, master_private_key, passphrase, id],
+            ).context("Failed to update master account secrets")?;
+            summary.master_accounts_sealed += 1;
+        }
+
+        // Re-key wallet private keys
+        let wallets: Vec<(i64, String, String)> = {
+            let mut stmt = tx.prepare("SELECT id, address, private_key FROM wallets")
+                .context("Failed to prepare wallets query")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+                .context("Failed to query wallets")?;
+            rows.collect::<SqlResult<Vec<_>>>().context("Failed to read wallets")?
+        };
+
+        for (id, address, private_key) in wallets {
+            let rekeyed = rekey_value(&old_key, &new_key, &private_key)
+                .with_context(|| format!("Failed to re-key wallet {}; no changes were made", address))?;
+
+            tx.execute(
+                "UPDATE wallets SET private_key = ?1 WHERE id = ?2",
+                params![rekeyed, id],
+            ).context("Failed to update wallet private key")?;
+            summary.wallets_sealed += 1;
+        }
+
+        // Re-key wallet seeds
+        let secrets: Vec<(i64, String)> = {
+            let mut stmt = tx.prepare("SELECT id, secret_value FROM wallet_secrets")
+                .context("Failed to prepare wallet secrets query")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .context("Failed to query wallet secrets")?;
+            rows.collect::<SqlResult<Vec<_>>>().context("Failed to read wallet secrets")?
+        };
+
+        for (id, secret_value) in secrets {
+            let rekeyed = rekey_value(&old_key, &new_key, &secret_value)
+                .with_context(|| format!("Failed to re-key wallet secret {}; no changes were made", id))?;
+
+            tx.execute(
+                "UPDATE wallet_secrets SET secret_value = ?1 WHERE id = ?2",
+                params![rekeyed, id],
+            ).context("Failed to update wallet secret")?;
+            summary.wallet_secrets_sealed += 1;
+        }
+
+        tx.commit().context("Failed to commit vault re-key")?;
+
+        *self.vault_key.borrow_mut() = Some(new_key);
+        Ok(summary)
+    }
+
+    // Helper method to seal a secret before writing (plaintext passthrough when the vault is not encrypted)
+    fn seal_secret(&self, value: &str) -> Result<String> {
+        if !self.is_vault_encrypted()? {
+            return Ok(value.to_string());
+        }
+
+        match self.vault_key.borrow().as_ref() {
+            Some(key) => seal_if_plaintext(key, value),
+            None => bail!(VAULT_LOCKED_MESSAGE),
+        }
+    }
+
+    // Helper method to decrypt a stored secret; errors if the vault is locked
+    fn open_secret(&self, value: &str) -> Result<String> {
+        if !vault::is_sealed(value) {
+            return Ok(value.to_string());
+        }
+
+        match self.vault_key.borrow().as_ref() {
+            Some(key) => vault::open(key, value),
+            None => bail!(VAULT_LOCKED_MESSAGE),
+        }
+    }
+
+    // Helper method for row builders: decrypts a sealed column when the vault is unlocked and otherwise keeps it
+    // sealed, so rows load while locked and only `Secret::expose` fails
+    fn reveal_secret(&self, column: usize, value: String) -> SqlResult<Secret> {
+        if !vault::is_sealed(&value) {
+            return Ok(Secret::Plain(value));
+        }
+        if !self.is_unlocked() {
+            return Ok(Secret::Sealed(value));
+        }
+
+        self.open_secret(&value)
+            .map(Secret::Plain)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(column, rusqlite::types::Type::Text, e.into()))
+    }
+
     // ========== MASTER ACCOUNT OPERATIONS ==========
 
     /// Creates a new master account with mnemonic storage
@@ -334,7 +975,12 @@ impl Database {
             "INSERT INTO master_accounts (name, mnemonic, master_private_key, passphrase) VALUES (?1, ?2, ?3, ?4)"
         ).context("Failed to prepare master account insert")?;
 
-        stmt.execute(params![name, mnemonic, master_private_key, passphrase])
+        let sealed_passphrase = match passphrase {
+            Some(p) => Some(self.seal_secret(p)?),
+            None => None,
+        };
+
+        stmt.execute(params![name, self.seal_secret(mnemonic)?, self.seal_secret(master_private_key)?, sealed_passphrase])
             .context("Failed to insert master account")?;
 
         Ok(self.conn.last_insert_rowid())
@@ -350,9 +996,9 @@ impl Database {
             Ok(MasterAccount {
                 id: Some(row.get(0)?),
                 name: row.get(1)?,
-                mnemonic: row.get(2)?,
-                master_private_key: row.get(3)?,
-                passphrase: row.get(4)?,
+                mnemonic: self.reveal_secret(2, row.get(2)?)?,
+                master_private_key: self.reveal_secret(3, row.get(3)?)?,
+                passphrase: row.get::<_, Option<String>>(4)?.map(|p| self.reveal_secret(4, p)).transpose()?,
                 next_account_index: row.get(5)?,
                 created_at: self.parse_datetime(&row.get::<_, String>(6)?)?,
                 updated_at: self.parse_datetime(&row.get::<_, String>(7)?)?,
@@ -366,6 +1012,27 @@ impl Database {
         }
     }
 
+    /// Gets the master account a hierarchical wallet was derived from (None for standalone wallets)
+    pub fn get_master_account_for_wallet(&self, wallet: &Wallet) -> Result<Option<MasterAccount>> {
+        let name_result = self.conn.query_row(
+            r#"
+            SELECT m.name FROM master_accounts m
+            JOIN wallet_groups g ON m.id = g.master_account_id
+            LEFT JOIN address_groups ag ON g.id = ag.wallet_group_id
+            WHERE g.id = ?1 OR ag.id = ?2
+            LIMIT 1
+            "#,
+            params![wallet.wallet_group_id, wallet.address_group_id],
+            |row| row.get::<_, String>(0),
+        );
+
+        match name_result {
+            Ok(name) => self.get_master_account_by_name(&name),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(anyhow::Error::from(e).context("Failed to query the wallet's master account")),
+        }
+    }
+
     /// Lists all master accounts with summary information
     pub fn list_master_accounts(&self) -> Result<Vec<MasterAccountSummary>> {
         let mut stmt = self.conn.prepare(
@@ -405,7 +1072,7 @@ impl Database {
     pub fn delete_master_account(&self, name: &str, mnemonic_verification: &str) -> Result<bool> {
         // Verify mnemonic first
         if let Some(account) = self.get_master_account_by_name(name)? {
-            if account.mnemonic != mnemonic_verification {
+            if account.mnemonic.expose()? != mnemonic_verification && !account.master_key_matches(mnemonic_verification) {
                 bail!("Mnemonic verification failed. Cannot delete master account.");
             }
         } else {
@@ -420,6 +1087,66 @@ impl Database {
         Ok(rows_affected > 0)
     }
 
+    /// Links a child account to the parent it was derived from
+    pub fn create_account_link(&self, child_account_id: i64, parent_account_id: i64, link_type: &str, derivation_index: u32) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO account_links (child_account_id, parent_account_id, link_type, derivation_index) VALUES (?1, ?2, ?3, ?4)",
+            params![child_account_id, parent_account_id, link_type, derivation_index],
+        ).context("Failed to insert account link")?;
+        Ok(())
+    }
+
+    /// Gets the parent link of an account, if it was derived from another account
+    pub fn get_account_link(&self, child_account_id: i64) -> Result<Option<AccountLink>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT l.child_account_id, l.parent_account_id, m.name, l.link_type, l.derivation_index, l.created_at
+            FROM account_links l
+            JOIN master_accounts m ON m.id = l.parent_account_id
+            WHERE l.child_account_id = ?1
+            "#
+        ).context("Failed to prepare account link query")?;
+
+        let link_result = stmt.query_row([child_account_id], |row| {
+            Ok(AccountLink {
+                child_account_id: row.get(0)?,
+                parent_account_id: row.get(1)?,
+                parent_account_name: row.get(2)?,
+                link_type: row.get(3)?,
+                derivation_index: row.get(4)?,
+                created_at: self.parse_datetime(&row.get::<_, String>(5)?)?,
+            })
+        });
+
+        match link_result {
+            Ok(link) => Ok(Some(link)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(anyhow::Error::from(e).context("Failed to query account link")),
+        }
+    }
+
+    /// Lists child accounts derived from a parent as (child name, link type, derivation index)
+    pub fn list_child_accounts(&self, parent_account_id: i64) -> Result<Vec<(String, String, u32)>> {
+        let mut stmt = self.conn.prepare(
+            r#"
+            SELECT m.name, l.link_type, l.derivation_index
+            FROM account_links l
+            JOIN master_accounts m ON m.id = l.child_account_id
+            WHERE l.parent_account_id = ?1
+            ORDER BY l.derivation_index
+            "#
+        ).context("Failed to prepare child accounts query")?;
+
+        let rows = stmt.query_map([parent_account_id], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .context("Failed to query child accounts")?;
+
+        let mut children = Vec::new();
+        for row in rows {
+            children.push(row.context("Failed to parse child account")?);
+        }
+        Ok(children)
+    }
+
     // ========== WALLET GROUP OPERATIONS ==========
 
     /// Creates a new wallet group with auto-assigned account index
@@ -568,6 +1295,30 @@ impl Database {
         Ok(addresses)
     }
 
+    /// Stores a secret the wallet key was derived from (sealed when the vault is encrypted)
+    pub fn set_wallet_secret(&self, wallet_id: i64, secret_type: &str, secret_value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO wallet_secrets (wallet_id, secret_type, secret_value) VALUES (?1, ?2, ?3)",
+            params![wallet_id, secret_type, self.seal_secret(secret_value)?],
+        ).context("Failed to store wallet secret")?;
+        Ok(())
+    }
+
+    /// Loads and decrypts a wallet secret; errors if the vault is locked
+    pub fn get_wallet_secret(&self, wallet_id: i64, secret_type: &str) -> Result<Option<String>> {
+        let secret_result = self.conn.query_row(
+            "SELECT secret_value FROM wallet_secrets WHERE wallet_id = ?1 AND secret_type = ?2",
+            params![wallet_id, secret_type],
+            |row| row.get::<_, String>(0),
+        );
+
+        match secret_result {
+            Ok(value) => Ok(Some(self.open_secret(&value)?)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(anyhow::Error::from(e).context("Failed to query wallet secret")),
+        }
+    }
+
     // ========== ADDRESS GROUP OPERATIONS ==========
 
     /// Creates or gets default address group for a blockchain (e.g., "bitcoin-0", "ethereum-0")
@@ -801,7 +1552,14 @@ impl Database {
         self.validate_wallet_label_uniqueness(wallet)?;
 
         let tx = self.conn.unchecked_transaction()?;
+        let wallet_id = self.insert_wallet_record(&tx, wallet)?;
 
+        tx.commit().context("Failed to commit wallet creation")?;
+        Ok(wallet_id)
+    }
+
+    // Helper method to insert a wallet row with its additional data and secondary addresses (caller owns the transaction)
+    fn insert_wallet_record(&self, tx: &Connection, wallet: &Wallet) -> Result<i64> {
         // Insert wallet - no address_index management needed (handled by derivation_path)
         let wallet_id = {
             let mut stmt = tx.prepare(
@@ -820,7 +1578,7 @@ impl Database {
                 wallet.blockchain,
                 wallet.address,
                 wallet.address_with_checksum,
-                wallet.private_key,
+                self.seal_secret(wallet.private_key.stored())?,
                 wallet.public_key,
                 wallet.derivation_path,
                 wallet.label,
@@ -856,7 +1614,6 @@ impl Database {
             }
         }
 
-        tx.commit().context("Failed to commit wallet creation")?;
         Ok(wallet_id)
     }
 
@@ -963,7 +1720,7 @@ impl Database {
             blockchain: row.get(3)?,
             address: row.get(4)?,
             address_with_checksum: row.get(5)?,
-            private_key: row.get(6)?,
+            private_key: self.reveal_secret(6, row.get(6)?)?,
             public_key: row.get(7)?,
             derivation_path: row.get(8)?,
             label: row.get(9)?,
@@ -1059,6 +1816,21 @@ impl Database {
         }
     }
 
+    /// Finds a wallet, subwallet or standalone wallet by address or label.
+    /// Addresses also match case-insensitively, so EVM addresses work without their checksum casing.
+    pub fn find_wallet(&self, identifier: &str) -> Result<Option<Wallet>> {
+        if let Some(wallet) = self.get_wallet_by_address(identifier)? {
+            return Ok(Some(wallet));
+        }
+        if let Some(wallet) = self.get_wallet_by_label(identifier)? {
+            return Ok(Some(wallet));
+        }
+
+        Ok(self.search_wallets(identifier, None)?
+            .into_iter()
+            .find(|wallet| wallet.address.eq_ignore_ascii_case(identifier)))
+    }
+
     // ========== BULK OPERATIONS ==========
 
     /// Creates complete hierarchy from mnemonic for import-multi command
@@ -1077,7 +1849,7 @@ impl Database {
         // Create or get master account
         let master_account_id = match self.get_master_account_by_name(account_name)? {
             Some(existing_account) => {
-                if existing_account.mnemonic != mnemonic {
+                if existing_account.mnemonic.expose()? != mnemonic {
                     bail!("Master account '{}' already exists with a different mnemonic.", account_name);
                 }
                 existing_account.id.unwrap()
@@ -1224,6 +1996,7 @@ impl Database {
             [base_wallet_id],
             |row| Ok(row.get(0)?)
         ).context("Failed to get master account mnemonic")?;
+        let master_account_mnemonic = self.open_secret(&master_account_mnemonic)?;
 
         if master_account_mnemonic != mnemonic_verification {
             bail!("Mnemonic verification failed. Cannot delete address group.");
@@ -1256,6 +2029,7 @@ impl Database {
                     [wallet_group_id],
                     |row| Ok(row.get(0)?)
                 ).context("Failed to get master account mnemonic")?;
+                let master_account_mnemonic = self.open_secret(&master_account_mnemonic)?;
 
                 if master_account_mnemonic != mnemonic_verify {
                     bail!("Mnemonic verification failed. Cannot delete wallet address.");
@@ -1265,6 +2039,12 @@ impl Database {
             }
         }
 
+        // Seeds must not outlive their wallet, whether or not foreign keys are enforced
+        self.conn.execute(
+            "DELETE FROM wallet_secrets WHERE wallet_id = (SELECT id FROM wallets WHERE address = ?1)",
+            params![address],
+        ).context("Failed to delete wallet secrets")?;
+
         let rows_affected = self.conn.execute(
             "DELETE FROM wallets WHERE address = ?1",
             params![address],
@@ -1414,4 +2194,297 @@ impl Database {
         Ok(wallets)
     }
 
-}
\ No newline at end of file
+
+    // ========== BACKUP OPERATIONS ==========
+
+    /// Collects a master account with its full hierarchy and plaintext secrets (vault must be unlocked)
+    pub fn export_account_tree(&self, name: &str) -> Result<Option<AccountBackup>> {
+        self.require_unlocked()?;
+        self.get_account_tree(name)
+    }
+
+    /// Collects a master account with its full hierarchy and plaintext account secrets (fails while the vault is locked)
+    pub fn get_account_tree(&self, name: &str) -> Result<Option<AccountBackup>> {
+        let master_account = match self.get_master_account_by_name(name)? {
+            Some(account) => account,
+            None => return Ok(None),
+        };
+        let master_account_id = master_account.id.unwrap();
+
+        let mut wallet_groups = Vec::new();
+        for group_summary in self.list_wallet_groups(master_account_id)? {
+            let mut wallets = Vec::new();
+            for base_wallet in self.get_wallets_by_wallet_group(group_summary.id)? {
+                let base_wallet_id = base_wallet.id.unwrap();
+
+                let mut address_groups = Vec::new();
+                for ag_summary in self.list_address_groups_for_wallet(base_wallet_id)? {
+                    let address_group = self.get_address_group_by_name_for_wallet(base_wallet_id, &ag_summary.name)?
+                        .ok_or_else(|| anyhow::anyhow!("Address group '{}' disappeared during export", ag_summary.name))?;
+
+                    address_groups.push(AddressGroupBackup {
+                        blockchain: address_group.blockchain,
+                        name: address_group.name,
+                        address_group_index: address_group.address_group_index,
+                        next_address_index: address_group.next_address_index,
+                        created_at: address_group.created_at,
+                        subwallets: self.get_wallets_by_address_group(ag_summary.id)?,
+                    });
+                }
+
+                wallets.push(BaseWalletBackup { wallet: base_wallet, address_groups });
+            }
+
+            wallet_groups.push(WalletGroupBackup {
+                name: group_summary.name,
+                description: group_summary.description,
+                account_index: group_summary.account_index,
+                created_at: group_summary.created_at,
+                wallets,
+            });
+        }
+
+        let mnemonic = master_account.mnemonic.expose()?.to_string();
+        let master_private_key = master_account.master_private_key.expose()?.to_string();
+        let passphrase = master_account.passphrase_str()?.map(str::to_string);
+
+        Ok(Some(AccountBackup {
+            name: master_account.name,
+            mnemonic,
+            master_private_key,
+            passphrase,
+            next_account_index: master_account.next_account_index,
+            created_at: master_account.created_at,
+            wallet_groups,
+        }))
+    }
+
+    /// Collects the public shape of an account tree (names, indices, addresses, paths) for manifests
+    pub fn get_account_outline(&self, name: &str) -> Result<Option<AccountOutline>> {
+        let account_result = self.conn.query_row(
+            "SELECT id, name, passphrase IS NOT NULL AND passphrase != '', next_account_index FROM master_accounts WHERE name = ?1",
+            [name],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, bool>(2)?, row.get::<_, u32>(3)?)),
+        );
+        let (master_account_id, name, uses_passphrase, next_account_index) = match account_result {
+            Ok(account) => account,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+            Err(e) => return Err(anyhow::Error::from(e).context("Failed to query master account")),
+        };
+
+        let mut wallet_groups = Vec::new();
+        for group_summary in self.list_wallet_groups(master_account_id)? {
+            let mut wallets = Vec::new();
+            for (base_wallet_id, mut base_wallet) in self.list_wallet_outlines("wallet_group_id = ?1 AND address_group_id IS NULL", group_summary.id)? {
+                for ag_summary in self.list_address_groups_for_wallet(base_wallet_id)? {
+                    let address_group = self.get_address_group_by_name_for_wallet(base_wallet_id, &ag_summary.name)?
+                        .ok_or_else(|| anyhow::anyhow!("Address group '{}' disappeared during export", ag_summary.name))?;
+
+                    base_wallet.address_groups.push(AddressGroupOutline {
+                        blockchain: address_group.blockchain,
+                        name: address_group.name,
+                        address_group_index: address_group.address_group_index,
+                        next_address_index: address_group.next_address_index,
+                        subwallets: self.list_wallet_outlines("address_group_id = ?1", ag_summary.id)?
+                            .into_iter()
+                            .map(|(_, subwallet)| subwallet)
+                            .collect(),
+                    });
+                }
+                wallets.push(base_wallet);
+            }
+
+            wallet_groups.push(WalletGroupOutline {
+                name: group_summary.name,
+                description: group_summary.description,
+                account_index: group_summary.account_index,
+                wallets,
+            });
+        }
+
+        Ok(Some(AccountOutline { name, uses_passphrase, next_account_index, wallet_groups }))
+    }
+
+    // Helper method to load (id, outline) pairs for wallets matching a filter; never selects the private_key column
+    fn list_wallet_outlines(&self, filter: &str, id: i64) -> Result<Vec<(i64, WalletOutline)>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT id, blockchain, address, derivation_path, label, notes FROM wallets WHERE {} ORDER BY created_at DESC",
+            filter
+        )).context("Failed to prepare wallet outline query")?;
+
+        let rows = stmt.query_map([id], |row| {
+            Ok((row.get(0)?, WalletOutline {
+                blockchain: row.get(1)?,
+                address: row.get(2)?,
+                derivation_path: row.get(3)?,
+                label: row.get(4)?,
+                notes: row.get(5)?,
+                address_groups: Vec::new(),
+            }))
+        }).context("Failed to query wallet outlines")?;
+
+        rows.collect::<SqlResult<Vec<_>>>().context("Failed to read wallet outlines")
+    }
+
+    /// Recreates an exported account tree in a single transaction, preserving names, paths and index counters
+    pub fn import_account_tree(&self, backup: &AccountBackup, name: &str) -> Result<AccountImportSummary> {
+        self.require_unlocked()?;
+
+        if self.get_master_account_by_name(name)?.is_some() {
+            bail!("An account named '{}' already exists", name);
+        }
+
+        let sealed_passphrase = match &backup.passphrase {
+            Some(p) => Some(self.seal_secret(p)?),
+            None => None,
+        };
+
+        let tx = self.conn.unchecked_transaction()?;
+        let mut summary = AccountImportSummary::default();
+
+        tx.execute(
+            "INSERT INTO master_accounts (name, mnemonic, master_private_key, passphrase, next_account_index) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![name, self.seal_secret(&backup.mnemonic)?, self.seal_secret(&backup.master_private_key)?, sealed_passphrase, backup.next_account_index],
+        ).context("Failed to insert master account")?;
+        let master_account_id = tx.last_insert_rowid();
+
+        for group in &backup.wallet_groups {
+            tx.execute(
+                "INSERT INTO wallet_groups (master_account_id, name, description, account_index) VALUES (?1, ?2, ?3, ?4)",
+                params![master_account_id, group.name, group.description, group.account_index],
+            ).with_context(|| format!("Failed to insert wallet group '{}'", group.name))?;
+            let wallet_group_id = tx.last_insert_rowid();
+            summary.wallet_groups += 1;
+
+            for base in &group.wallets {
+                let mut base_wallet = base.wallet.clone();
+                base_wallet.id = None;
+                base_wallet.wallet_group_id = Some(wallet_group_id);
+                base_wallet.address_group_id = None;
+
+                let base_wallet_id = self.insert_wallet_record(&tx, &base_wallet)
+                    .with_context(|| format!("Failed to insert wallet {}", base_wallet.address))?;
+                summary.wallets += 1;
+
+                for address_group in &base.address_groups {
+                    tx.execute(
+                        "INSERT INTO address_groups (wallet_group_id, base_wallet_id, blockchain, name, address_group_index, next_address_index) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                        params![wallet_group_id, base_wallet_id, address_group.blockchain, address_group.name, address_group.address_group_index, address_group.next_address_index],
+                    ).with_context(|| format!("Failed to insert address group '{}'", address_group.name))?;
+                    let address_group_id = tx.last_insert_rowid();
+                    summary.address_groups += 1;
+
+                    for subwallet in &address_group.subwallets {
+                        let mut subwallet = subwallet.clone();
+                        subwallet.id = None;
+                        subwallet.wallet_group_id = Some(wallet_group_id);
+                        subwallet.address_group_id = Some(address_group_id);
+
+                        self.insert_wallet_record(&tx, &subwallet)
+                            .with_context(|| format!("Failed to insert subwallet {}", subwallet.address))?;
+                        summary.subwallets += 1;
+                    }
+                }
+            }
+        }
+
+        tx.commit().context("Failed to commit account import")?;
+        Ok(summary)
+    }
+}
+
+// Seals a value unless it is empty, already sealed, or one of the non-secret mnemonic placeholders (kept in
+// plaintext so the account type is known while the vault is locked)
+fn seal_if_plaintext(key: &VaultKey, value: &str) -> Result<String> {
+    if value.is_empty()
+        || vault::is_sealed(value)
+        || value == MASTER_KEY_MNEMONIC_PLACEHOLDER
+        || value == WATCH_ONLY_MNEMONIC_PLACEHOLDER
+    {
+        return Ok(value.to_string());
+    }
+    vault::seal(key, value)
+}
+
+// Decrypts a value with the old key and seals it under the new one (plaintext leftovers are simply sealed)
+fn rekey_value(old_key: &VaultKey, new_key: &VaultKey, value: &str) -> Result<String> {
+    if !vault::is_sealed(value) {
+        return seal_if_plaintext(new_key, value);
+    }
+    let plaintext = vault::open(old_key, value)?;
+    seal_if_plaintext(new_key, &plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standalone_wallet(address: &str, private_key: &str) -> Wallet {
+        Wallet {
+            id: None,
+            wallet_group_id: None,
+            address_group_id: None,
+            blockchain: "ethereum".to_string(),
+            address: address.to_string(),
+            address_with_checksum: None,
+            private_key: private_key.into(),
+            public_key: None,
+            derivation_path: None,
+            label: Some("cold".to_string()),
+            source_type: "private_key".to_string(),
+            explorer_url: None,
+            notes: None,
+            created_at: Utc::now(),
+            additional_data: HashMap::new(),
+            secondary_addresses: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_locked_vault_lists_and_shows_without_secrets() {
+        let db = Database::new(":memory:").unwrap();
+        db.create_master_account("main", "abandon abandon about", "00ff", Some("pass")).unwrap();
+        db.create_master_account("xprv", MASTER_KEY_MNEMONIC_PLACEHOLDER, "xprv-secret", None).unwrap();
+        db.create_standalone_wallet(&standalone_wallet("0xabc", "deadbeef")).unwrap();
+        db.encrypt_vault("password", KdfParams { memory_kib: 8 * 1024, iterations: 1, parallelism: 1 }).unwrap();
+        db.lock();
+
+        let wallets = db.get_standalone_wallets().unwrap();
+        assert_eq!(wallets.len(), 1);
+        assert!(matches!(wallets[0].private_key, Secret::Sealed(_)));
+        assert!(wallets[0].private_key.expose().is_err());
+
+        let wallet = db.get_wallet_by_label("cold").unwrap().unwrap();
+        assert_eq!(wallet.address, "0xabc");
+
+        let account = db.get_master_account_by_name("main").unwrap().unwrap();
+        assert!(!account.uses_master_key());
+        assert!(account.mnemonic.expose().is_err());
+        assert!(account.passphrase_str().is_err());
+        assert!(db.get_account_tree("main").is_err());
+        assert!(db.get_master_account_by_name("xprv").unwrap().unwrap().uses_master_key());
+
+        db.unlock("password").unwrap();
+        let wallet = db.get_wallet_by_label("cold").unwrap().unwrap();
+        assert_eq!(wallet.private_key.expose().unwrap(), "deadbeef");
+        let account = db.get_master_account_by_name("main").unwrap().unwrap();
+        assert_eq!(account.mnemonic.expose().unwrap(), "abandon abandon about");
+        assert_eq!(account.passphrase_str().unwrap(), Some("pass"));
+    }
+
+    #[test]
+    fn test_account_outline_on_locked_vault() {
+        let db = Database::new(":memory:").unwrap();
+        db.create_master_account("main", "abandon abandon about", "00ff", Some("pass")).unwrap();
+        db.create_master_account("plain", "abandon abandon about", "00ff", None).unwrap();
+        db.encrypt_vault("password", KdfParams { memory_kib: 8 * 1024, iterations: 1, parallelism: 1 }).unwrap();
+        db.lock();
+
+        let outline = db.get_account_outline("main").unwrap().unwrap();
+        assert_eq!(outline.name, "main");
+        assert!(outline.uses_passphrase);
+        assert!(outline.wallet_groups.is_empty());
+        assert!(!db.get_account_outline("plain").unwrap().unwrap().uses_passphrase);
+        assert!(db.get_account_outline("missing").unwrap().is_none());
+    }
+}
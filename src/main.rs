@@ -87,6 +87,46 @@ enum Commands {
     ModifySubwallet(ModifySubwalletArgs),
     /// Modify standalone wallet properties (label, notes, additional data, secondary addresses)
     ModifyStandaloneWallet(ModifyStandaloneWalletArgs),
+    /// Encrypt all stored secrets with a password-derived key (upgrades a plaintext vault in place)
+    EncryptVault(EncryptVaultArgs),
+    /// Create a new password-protected vault
+    Init(InitArgs),
+    /// Unlock the vault for a limited time
+    Unlock(UnlockArgs),
+    /// Lock the vault and wipe the cached session key
+    Lock(LockArgs),
+    /// Change the vault password and re-key all stored secrets
+    ChangePassword(ChangePasswordArgs),
+    /// Export an account with its full hierarchy to a password-encrypted backup file
+    ExportAccount(ExportAccountArgs),
+    /// Import an account from an encrypted backup file
+    ImportAccount(ImportAccountArgs),
+    /// Export a public, secret-free manifest of an account for restore-by-rederivation
+    ExportManifest(ExportManifestArgs),
+    /// Rebuild an account from its mnemonic and a public manifest, verifying every address
+    Restore(RestoreArgs),
+    /// Split an account seed into SLIP-39 Shamir share mnemonics
+    SplitShares(SplitSharesArgs),
+    /// Create an account by recombining SLIP-39 shares
+    RecoverAccount(RecoverAccountArgs),
+    /// Check that a set of SLIP-39 shares recombines to a stored account
+    VerifyShares(VerifySharesArgs),
+    /// Derive a BIP-85 child mnemonic, key or password from an account
+    DeriveChildSeed(DeriveChildSeedArgs),
+    /// Export the account xpub (SLIP-132) and output descriptors of a Bitcoin/Litecoin wallet
+    ExportXpub(ExportXpubArgs),
+    /// Sign the inputs of a Bitcoin/Litecoin PSBT that belong to wallets in the vault
+    SignPsbt(SignPsbtArgs),
+    /// Sign an EVM transaction (legacy, EIP-2930, EIP-1559) offline and print the raw transaction
+    SignEvmTx(SignEvmTxArgs),
+    /// Witness a Cardano transaction with a wallet's payment key and, when needed, its stake key
+    SignCardanoTx(SignCardanoTxArgs),
+    /// Sign a message with a wallet (EIP-191 personal_sign or EIP-712 typed data)
+    SignMessage(SignMessageArgs),
+    /// Verify a signed message, recover its signer and find the matching stored wallet
+    VerifyMessage(VerifyMessageArgs),
+    /// Sign a message or transaction with any wallet using its chain's native scheme
+    Sign(SignArgs),
     // TEMPORARILY DISABLED - TO BE REPLACED
     // /// Import a wallet from mnemonic or private key
     // Import(ImportArgs),
@@ -129,7 +169,14 @@ fn main() -> Result<()> {
     
     // Initialize database
     let db = Database::new("wallets.db")?;
-    
+
+    // Pick up the key cached by `unlock`; without a live session the vault stays locked
+    if let Some(vault_id) = db.get_vault_id()? {
+        if let Some(key) = crypto::session::load_session(&vault_id)? {
+            db.unlock_with_key(key)?;
+        }
+    }
+
     // Execute command
     match cli.command {
         Commands::AddAccount(args) => handle_create_master(args, &db),
@@ -165,6 +212,26 @@ fn main() -> Result<()> {
         Commands::ShowStandaloneWallet(args) => handle_show_standalone_wallet(args, &db),
         Commands::ModifySubwallet(args) => handle_modify_subwallet(args, &db),
         Commands::ModifyStandaloneWallet(args) => handle_modify_standalone_wallet(args, &db),
+        Commands::EncryptVault(args) => handle_encrypt_vault(args, &db),
+        Commands::Init(args) => handle_init(args, &db),
+        Commands::Unlock(args) => handle_unlock(args, &db),
+        Commands::Lock(args) => handle_lock(args, &db),
+        Commands::ChangePassword(args) => handle_change_password(args, &db),
+        Commands::ExportAccount(args) => handle_export_account(args, &db),
+        Commands::ImportAccount(args) => handle_import_account(args, &db),
+        Commands::ExportManifest(args) => handle_export_manifest(args, &db),
+        Commands::Restore(args) => handle_restore(args, &db),
+        Commands::SplitShares(args) => handle_split_shares(args, &db),
+        Commands::RecoverAccount(args) => handle_recover_account(args, &db),
+        Commands::VerifyShares(args) => handle_verify_shares(args, &db),
+        Commands::DeriveChildSeed(args) => handle_derive_child_seed(args, &db),
+        Commands::ExportXpub(args) => handle_export_xpub(args, &db),
+        Commands::SignPsbt(args) => handle_sign_psbt(args, &db),
+        Commands::SignEvmTx(args) => handle_sign_evm_tx(args, &db),
+        Commands::SignCardanoTx(args) => handle_sign_cardano_tx(args, &db),
+        Commands::SignMessage(args) => handle_sign_message(args, &db),
+        Commands::VerifyMessage(args) => handle_verify_message(args, &db),
+        Commands::Sign(args) => handle_sign(args, &db),
         // TEMPORARILY DISABLED
         // Commands::Import(args) => handle_import(args, &db),
         // Commands::ImportMulti(args) => handle_import_multi(args, &db),
@@ -12,6 +12,47 @@ pub fn confirm_action(message: &str) -> Result<bool> {
     Ok(confirmed == "y" || confirmed == "yes")
 }
 
+pub fn prompt_input(message: &str) -> Result<String> {
+    print!("{}: ", message);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input.trim().to_string())
+}
+
+pub fn prompt_password(message: &str) -> Result<String> {
+    let password = rpassword::prompt_password(format!("{}: ", message))?;
+    Ok(password)
+}
+
+pub fn prompt_new_password(message: &str) -> Result<String> {
+    let password = prompt_password(message)?;
+    if password.len() < 8 {
+        anyhow::bail!("Password must be at least 8 characters long");
+    }
+
+    let confirmation = prompt_password("Confirm password")?;
+    if password != confirmation {
+        anyhow::bail!("Passwords do not match");
+    }
+
+    Ok(password)
+}
+
+pub fn prompt_secret_list(message: &str) -> Result<Vec<String>> {
+    println!("{} (one per prompt, empty line to finish)", message);
+    let mut values = Vec::new();
+    loop {
+        let value = prompt_password(&format!("  #{}", values.len() + 1))?;
+        if value.trim().is_empty() {
+            break;
+        }
+        values.push(value.trim().to_string());
+    }
+    Ok(values)
+}
+
 pub fn truncate_address(address: &str, start_chars: usize, end_chars: usize) -> String {
     if address.len() <= start_chars + end_chars + 3 {
         address.to_string()